@@ -81,6 +81,7 @@ class GoalPlannerAgent(BaseAgent):
         - target_returns_percent: float
         - duration_years: float
         - risk_profile: str (LOW, MEDIUM, HIGH, AGGRESSIVE_PLUS)
+        - seed: int, optional (reproducible Monte Carlo)
         """
         start_time = time.time()
         
@@ -239,7 +240,8 @@ class GoalPlannerAgent(BaseAgent):
 
             # 4. Monte Carlo Simulation
             mc_result = self._monte_carlo_simulation(
-                initial_capital, expected_ret, expected_vol, duration, target_returns
+                initial_capital, expected_ret, expected_vol, duration, target_returns,
+                seed=context.get("seed")
             )
             
             # 5. Generate Growth Path for Charting
@@ -451,15 +453,16 @@ class GoalPlannerAgent(BaseAgent):
             "sharpe_ratio": sharpe
         }
 
-    def _monte_carlo_simulation(self, s0, mu, sigma, t, target_mu):
+    def _monte_carlo_simulation(self, s0, mu, sigma, t, target_mu, seed=None):
         """
         Run 10,000 simulations to calculate probability of success.
-        Uses Geometric Brownian Motion.
+        Uses Geometric Brownian Motion. Draws come from NumPy's PCG64
+        generator, so a given `seed` reproduces the result on any platform.
         """
         num_sims = 10000
         
         # Formula: S_T = S_0 * exp((mu - 0.5*sigma^2)T + sigma*sqrt(T)*Z)
-        z = np.random.standard_normal(num_sims)
+        z = np.random.default_rng(seed).standard_normal(num_sims)
         final_values = s0 * np.exp((mu - 0.5 * sigma**2) * t + sigma * np.sqrt(t) * z)
         
         # Target value calculation
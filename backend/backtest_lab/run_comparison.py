@@ -100,7 +100,9 @@ def forecast_ml(df: pd.DataFrame, periods: int = 7, model_type="xgboost") -> pd.
     return predictions
 
 # --- DATA FETCHING ---
-def get_test_data(ticker="1HOUR_INTRADAY", days=20):
+def get_test_data(ticker="1HOUR_INTRADAY", days=20, seed=42):
+    """Synthetic hourly bars; the same `seed` gives the same prices (None draws fresh ones)."""
+    rng = np.random.default_rng(seed)
     points = days * 24 # Hourly 20 days
     dates = [datetime.now() - timedelta(hours=x) for x in range(points)]
     dates.reverse()
@@ -109,7 +111,7 @@ def get_test_data(ticker="1HOUR_INTRADAY", days=20):
     price = [100.0]
     for i in range(1, points):
         # Revert to 100
-        change = (100 - price[-1]) * 0.05 + np.random.normal(0, 0.5)
+        change = (100 - price[-1]) * 0.05 + rng.normal(0, 0.5)
         price.append(price[-1] + change)
     
     price = np.array(price)
@@ -0,0 +1,493 @@
+//! Price alerts evaluated incrementally, one bar per ticker per tick.
+//!
+//! Each ticker keeps just the state its alerts need (a bounded close/high/low
+//! history and one streaming RSI per period), so a tick costs time
+//! proportional to the alerts on the tickers that moved, not to the history
+//! length. Alerts are edge-triggered: they fire on the bar where their
+//! condition becomes true, and can fire again only after it has been false.
+
+use std::collections::{HashMap, VecDeque};
+
+use crate::chunked::{RsiState, Streaming};
+
+/// Which side of a level an alert waits for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Above,
+    Below,
+}
+
+impl std::str::FromStr for Direction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "above" | "up" => Ok(Direction::Above),
+            "below" | "down" => Ok(Direction::Below),
+            other => Err(format!("Unknown direction '{}', expected 'above' or 'below'", other)),
+        }
+    }
+}
+
+impl Direction {
+    fn holds(self, value: f64, level: f64) -> bool {
+        match self {
+            Direction::Above => value >= level,
+            Direction::Below => value <= level,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum AlertCondition {
+    /// The close crosses `level`.
+    PriceCross { level: f64, direction: Direction },
+    /// RSI(`period`) of the closes crosses `level`.
+    Rsi { period: usize, level: f64, direction: Direction },
+    /// The close moved at least `pct` percent (either way) from the close
+    /// `lookback` bars earlier.
+    PctMove { pct: f64, lookback: usize },
+    /// The bar's high exceeds every high of the previous `window` bars.
+    NewHigh { window: usize },
+    /// The bar's low undercuts every low of the previous `window` bars.
+    NewLow { window: usize },
+}
+
+impl AlertCondition {
+    /// Name used when reporting fired alerts.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AlertCondition::PriceCross { .. } => "price_cross",
+            AlertCondition::Rsi { .. } => "rsi",
+            AlertCondition::PctMove { .. } => "pct_move",
+            AlertCondition::NewHigh { .. } => "new_high",
+            AlertCondition::NewLow { .. } => "new_low",
+        }
+    }
+
+    /// Bars of history needed besides the current one.
+    fn history(&self) -> usize {
+        match *self {
+            AlertCondition::PriceCross { .. } | AlertCondition::Rsi { .. } => 0,
+            AlertCondition::PctMove { lookback, .. } => lookback,
+            AlertCondition::NewHigh { window } | AlertCondition::NewLow { window } => window,
+        }
+    }
+
+    /// Crosses need to have seen the other side first; the others fire on
+    /// the first bar where they hold.
+    fn is_cross(&self) -> bool {
+        matches!(self, AlertCondition::PriceCross { .. } | AlertCondition::Rsi { .. })
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let positive = |name: &str, v: usize| if v == 0 { Err(format!("{} must be positive", name)) } else { Ok(()) };
+        match *self {
+            AlertCondition::PriceCross { level, .. } | AlertCondition::Rsi { level, .. } if !level.is_finite() => {
+                Err("level must be finite".to_string())
+            }
+            AlertCondition::PriceCross { .. } => Ok(()),
+            AlertCondition::Rsi { period, .. } => positive("period", period),
+            AlertCondition::PctMove { pct, lookback } => {
+                if pct.is_nan() || pct <= 0.0 {
+                    return Err("pct must be positive".to_string());
+                }
+                positive("lookback", lookback)
+            }
+            AlertCondition::NewHigh { window } | AlertCondition::NewLow { window } => positive("window", window),
+        }
+    }
+}
+
+/// The latest bar of a ticker. For a plain price, pass it as all three.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Bar {
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+}
+
+impl Bar {
+    pub fn from_price(price: f64) -> Self {
+        Bar { high: price, low: price, close: price }
+    }
+}
+
+/// An alert that fired on the latest tick.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FiredAlert {
+    pub id: u64,
+    pub ticker: String,
+    pub kind: &'static str,
+    /// The value tested: close, RSI, percent move, or the new high/low.
+    pub value: f64,
+}
+
+#[derive(Clone, Debug)]
+struct Alert {
+    id: u64,
+    condition: AlertCondition,
+    once: bool,
+    /// Whether the condition held on the previous bar (None before any).
+    last: Option<bool>,
+}
+
+/// Streaming RSI plus the number of closes it has seen.
+struct Rsi {
+    period: usize,
+    state: RsiState,
+    seen: usize,
+    value: f64,
+}
+
+#[derive(Default)]
+struct TickerState {
+    /// Previous bars, newest last, capped at the longest history needed.
+    bars: VecDeque<Bar>,
+    rsi: Vec<Rsi>,
+    alerts: Vec<Alert>,
+}
+
+impl TickerState {
+    fn capacity(&self) -> usize {
+        self.alerts.iter().map(|a| a.condition.history()).max().unwrap_or(0)
+    }
+
+    fn rsi_value(&self, period: usize) -> f64 {
+        self.rsi.iter().find(|r| r.period == period).map_or(f64::NAN, |r| r.value)
+    }
+
+    /// Folds `close` into the RSIs, which include the current bar.
+    fn update_rsi(&mut self, close: f64) {
+        for rsi in &mut self.rsi {
+            let value = rsi.state.push(close);
+            rsi.seen += 1;
+            // The first `period` values are a neutral placeholder.
+            rsi.value = if rsi.seen > rsi.period { value } else { f64::NAN };
+        }
+    }
+
+    /// Stores `bar` as history for the next one.
+    fn remember(&mut self, bar: Bar) {
+        self.bars.push_back(bar);
+        self.trim();
+    }
+
+    fn trim(&mut self) {
+        let capacity = self.capacity();
+        while self.bars.len() > capacity {
+            self.bars.pop_front();
+        }
+    }
+
+    /// Drops the RSIs and history that no remaining alert reads.
+    fn prune(&mut self) {
+        let alerts = &self.alerts;
+        self.rsi.retain(|r| alerts.iter().any(|a| matches!(a.condition, AlertCondition::Rsi { period, .. } if period == r.period)));
+        self.trim();
+    }
+
+    /// Advances every alert's state over `bar`; returns each alert's index,
+    /// tested value and whether it fired.
+    fn step(&mut self, bar: Bar) -> Vec<(usize, f64, bool)> {
+        self.update_rsi(bar.close);
+        let results: Vec<(f64, bool)> = self.alerts.iter().map(|a| self.test(&a.condition, bar)).collect();
+        self.remember(bar);
+        self.alerts
+            .iter_mut()
+            .zip(results)
+            .enumerate()
+            .map(|(i, (alert, (value, holds)))| {
+                // Warm-up bars say nothing about which side the value is on.
+                if value.is_nan() {
+                    return (i, value, false);
+                }
+                let was = alert.last.replace(holds);
+                let armed = if alert.condition.is_cross() { was == Some(false) } else { was != Some(true) };
+                (i, value, holds && armed)
+            })
+            .collect()
+    }
+
+    /// Tested value and whether the condition holds on `bar`, given the
+    /// bars before it. NaN (not enough history) never holds.
+    fn test(&self, condition: &AlertCondition, bar: Bar) -> (f64, bool) {
+        match *condition {
+            AlertCondition::PriceCross { level, direction } => (bar.close, direction.holds(bar.close, level)),
+            AlertCondition::Rsi { period, level, direction } => {
+                let v = self.rsi_value(period);
+                (v, !v.is_nan() && direction.holds(v, level))
+            }
+            AlertCondition::PctMove { pct, lookback } => {
+                let v = match self.bars.len().checked_sub(lookback) {
+                    Some(i) => (bar.close / self.bars[i].close - 1.0) * 100.0,
+                    None => f64::NAN,
+                };
+                (v, v.abs() >= pct)
+            }
+            AlertCondition::NewHigh { window } => {
+                let prior = self.bars.iter().rev().take(window);
+                let full = self.bars.len() >= window;
+                let max = prior.map(|b| b.high).fold(f64::NEG_INFINITY, f64::max);
+                (bar.high, full && bar.high > max)
+            }
+            AlertCondition::NewLow { window } => {
+                let prior = self.bars.iter().rev().take(window);
+                let full = self.bars.len() >= window;
+                let min = prior.map(|b| b.low).fold(f64::INFINITY, f64::min);
+                (bar.low, full && bar.low < min)
+            }
+        }
+    }
+}
+
+/// Registry of alerts across tickers.
+#[derive(Default)]
+pub struct AlertEngine {
+    tickers: HashMap<String, TickerState>,
+    next_id: u64,
+}
+
+impl AlertEngine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers an alert and returns its id.
+    pub fn add(&mut self, ticker: &str, condition: AlertCondition, once: bool) -> Result<u64, String> {
+        condition.validate()?;
+        let state = self.tickers.entry(ticker.to_string()).or_default();
+        if let AlertCondition::Rsi { period, .. } = condition {
+            if !state.rsi.iter().any(|r| r.period == period) {
+                // Started now, so it warms up on the bars that follow.
+                state.rsi.push(Rsi { period, state: RsiState::new(period), seen: 0, value: f64::NAN });
+            }
+        }
+        self.next_id += 1;
+        state.alerts.push(Alert { id: self.next_id, condition, once, last: None });
+        Ok(self.next_id)
+    }
+
+    /// Removes an alert. Returns whether it existed.
+    pub fn remove(&mut self, id: u64) -> bool {
+        let found = self.tickers.iter_mut().find_map(|(ticker, state)| {
+            let i = state.alerts.iter().position(|a| a.id == id)?;
+            state.alerts.remove(i);
+            Some(ticker.clone())
+        });
+        match found {
+            Some(ticker) => {
+                self.prune(&ticker);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Drops the state `ticker`'s remaining alerts no longer need, and the
+    /// ticker itself once it has none.
+    fn prune(&mut self, ticker: &str) {
+        let Some(state) = self.tickers.get_mut(ticker) else { return };
+        if state.alerts.is_empty() {
+            self.tickers.remove(ticker);
+        } else {
+            state.prune();
+        }
+    }
+
+    /// Feeds history without firing, e.g. to warm up RSI and new-high
+    /// windows from a backfill. Alerts take their "previous bar" state from
+    /// the last bar fed.
+    pub fn prime(&mut self, ticker: &str, bars: &[Bar]) {
+        let Some(state) = self.tickers.get_mut(ticker) else { return };
+        for &bar in bars {
+            state.step(bar);
+        }
+    }
+
+    /// Evaluates one new bar per ticker and returns the alerts that fired,
+    /// ordered by id. Tickers without alerts are ignored; `once` alerts are
+    /// removed after firing.
+    pub fn evaluate(&mut self, latest: &[(String, Bar)]) -> Vec<FiredAlert> {
+        let mut fired = Vec::new();
+        let mut spent_tickers = Vec::new();
+        for (ticker, bar) in latest {
+            let Some(state) = self.tickers.get_mut(ticker) else { continue };
+            let mut spent = Vec::new();
+            for (i, value, fires) in state.step(*bar) {
+                if !fires {
+                    continue;
+                }
+                let alert = &state.alerts[i];
+                fired.push(FiredAlert { id: alert.id, ticker: ticker.clone(), kind: alert.condition.kind(), value });
+                if alert.once {
+                    spent.push(i);
+                }
+            }
+            if !spent.is_empty() {
+                spent_tickers.push(ticker);
+            }
+            for i in spent.into_iter().rev() {
+                state.alerts.remove(i);
+            }
+        }
+        for ticker in spent_tickers {
+            self.prune(ticker);
+        }
+        fired.sort_by_key(|f| f.id);
+        fired
+    }
+
+    /// Number of registered alerts.
+    pub fn len(&self) -> usize {
+        self.tickers.values().map(|s| s.alerts.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tick(engine: &mut AlertEngine, ticker: &str, price: f64) -> Vec<u64> {
+        engine.evaluate(&[(ticker.to_string(), Bar::from_price(price))]).iter().map(|f| f.id).collect()
+    }
+
+    fn cross(level: f64, direction: Direction) -> AlertCondition {
+        AlertCondition::PriceCross { level, direction }
+    }
+
+    fn rsi(period: usize, level: f64, direction: Direction) -> AlertCondition {
+        AlertCondition::Rsi { period, level, direction }
+    }
+
+    #[test]
+    fn crosses_fire_only_after_seeing_the_other_side() {
+        let mut engine = AlertEngine::new();
+        let id = engine.add("AAPL", cross(100.0, Direction::Above), false).unwrap();
+        assert!(tick(&mut engine, "AAPL", 105.0).is_empty());
+        assert!(tick(&mut engine, "AAPL", 95.0).is_empty());
+        assert_eq!(tick(&mut engine, "AAPL", 105.0), [id]);
+        assert!(tick(&mut engine, "AAPL", 106.0).is_empty());
+        assert!(tick(&mut engine, "AAPL", 99.0).is_empty());
+        assert_eq!(tick(&mut engine, "AAPL", 100.0), [id]);
+    }
+
+    #[test]
+    fn level_conditions_fire_on_the_first_bar_they_hold() {
+        let mut engine = AlertEngine::new();
+        let id = engine.add("AAPL", AlertCondition::PctMove { pct: 5.0, lookback: 1 }, false).unwrap();
+        assert!(tick(&mut engine, "AAPL", 100.0).is_empty());
+        assert_eq!(tick(&mut engine, "AAPL", 110.0), [id]);
+        assert!(tick(&mut engine, "AAPL", 120.0).is_empty());
+        assert!(tick(&mut engine, "AAPL", 121.0).is_empty());
+        assert_eq!(tick(&mut engine, "AAPL", 110.0), [id]);
+    }
+
+    #[test]
+    fn new_highs_wait_for_a_full_window() {
+        let mut engine = AlertEngine::new();
+        let id = engine.add("AAPL", AlertCondition::NewHigh { window: 2 }, false).unwrap();
+        assert!(tick(&mut engine, "AAPL", 10.0).is_empty());
+        assert!(tick(&mut engine, "AAPL", 11.0).is_empty());
+        assert_eq!(tick(&mut engine, "AAPL", 12.0), [id]);
+    }
+
+    #[test]
+    fn rsi_alerts_ignore_the_warm_up() {
+        let mut engine = AlertEngine::new();
+        let id = engine.add("AAPL", rsi(3, 50.0, Direction::Above), false).unwrap();
+        // Rising closes hold the condition as soon as RSI is valid, but a
+        // cross needs a valid bar below the level first.
+        for close in [10.0, 11.0, 12.0, 13.0, 14.0] {
+            assert!(tick(&mut engine, "AAPL", close).is_empty());
+        }
+        for close in [10.0, 9.0, 8.0] {
+            assert!(tick(&mut engine, "AAPL", close).is_empty());
+        }
+        let fired = engine.evaluate(&[("AAPL".to_string(), Bar::from_price(20.0))]);
+        assert_eq!(fired.len(), 1);
+        assert_eq!((fired[0].id, fired[0].kind), (id, "rsi"));
+        assert!(fired[0].value > 50.0);
+    }
+
+    #[test]
+    fn once_alerts_are_removed_after_firing() {
+        let mut engine = AlertEngine::new();
+        let once = engine.add("AAPL", cross(100.0, Direction::Below), true).unwrap();
+        let repeat = engine.add("AAPL", cross(100.0, Direction::Below), false).unwrap();
+        tick(&mut engine, "AAPL", 105.0);
+        assert_eq!(tick(&mut engine, "AAPL", 95.0), [once, repeat]);
+        assert_eq!(engine.len(), 1);
+        tick(&mut engine, "AAPL", 105.0);
+        assert_eq!(tick(&mut engine, "AAPL", 95.0), [repeat]);
+    }
+
+    #[test]
+    fn prime_sets_the_previous_bar_without_firing() {
+        let mut engine = AlertEngine::new();
+        let id = engine.add("AAPL", cross(100.0, Direction::Above), false).unwrap();
+        engine.prime("AAPL", &[Bar::from_price(105.0), Bar::from_price(95.0)]);
+        assert_eq!(tick(&mut engine, "AAPL", 101.0), [id]);
+
+        // Priming a ticker without alerts keeps no state for it.
+        engine.prime("MSFT", &[Bar::from_price(1.0)]);
+        assert!(!engine.tickers.contains_key("MSFT"));
+    }
+
+    #[test]
+    fn prime_warms_up_rsi() {
+        let mut engine = AlertEngine::new();
+        let id = engine.add("AAPL", rsi(3, 50.0, Direction::Above), false).unwrap();
+        engine.prime("AAPL", &[10.0, 9.0, 8.0, 7.0].map(Bar::from_price));
+        assert_eq!(tick(&mut engine, "AAPL", 20.0), [id]);
+    }
+
+    #[test]
+    fn removing_the_last_rsi_alert_drops_its_state() {
+        let mut engine = AlertEngine::new();
+        let a = engine.add("AAPL", rsi(14, 30.0, Direction::Below), false).unwrap();
+        let b = engine.add("AAPL", rsi(14, 70.0, Direction::Above), false).unwrap();
+        let c = engine.add("AAPL", cross(100.0, Direction::Above), false).unwrap();
+        assert!(engine.remove(a));
+        assert_eq!(engine.tickers["AAPL"].rsi.len(), 1);
+        assert!(engine.remove(b));
+        assert!(engine.tickers["AAPL"].rsi.is_empty());
+        assert!(!engine.remove(b));
+        assert!(engine.remove(c));
+        assert!(engine.tickers.is_empty());
+        assert!(engine.is_empty());
+    }
+
+    #[test]
+    fn spent_once_alerts_drop_their_state() {
+        let mut engine = AlertEngine::new();
+        engine.add("AAPL", rsi(3, 50.0, Direction::Above), true).unwrap();
+        let high = engine.add("AAPL", AlertCondition::NewHigh { window: 50 }, true).unwrap();
+        engine.add("AAPL", cross(1000.0, Direction::Above), false).unwrap();
+        engine.prime("AAPL", &[10.0, 9.0, 8.0, 7.0].map(Bar::from_price));
+        assert_eq!(tick(&mut engine, "AAPL", 20.0).len(), 1);
+        let state = &engine.tickers["AAPL"];
+        assert!(state.rsi.is_empty());
+        assert_eq!(state.bars.len(), 5);
+        engine.remove(high);
+        assert!(engine.tickers["AAPL"].bars.is_empty());
+
+        engine.add("MSFT", cross(1.0, Direction::Above), true).unwrap();
+        engine.prime("MSFT", &[Bar::from_price(0.5)]);
+        tick(&mut engine, "MSFT", 2.0);
+        assert!(!engine.tickers.contains_key("MSFT"));
+    }
+
+    #[test]
+    fn invalid_conditions_are_rejected() {
+        let mut engine = AlertEngine::new();
+        assert!(engine.add("AAPL", rsi(0, 30.0, Direction::Below), false).is_err());
+        assert!(engine.add("AAPL", cross(f64::NAN, Direction::Above), false).is_err());
+        assert!(engine.add("AAPL", AlertCondition::PctMove { pct: 0.0, lookback: 1 }, false).is_err());
+        assert!(engine.is_empty());
+    }
+}
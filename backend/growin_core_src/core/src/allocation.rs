@@ -0,0 +1,438 @@
+//! Splitting cash across target weights into share quantities.
+//!
+//! Trading212 pies hold fractional shares, so an "invest into pie" deposit
+//! becomes one order per slice. Quantities are rounded down to the broker's
+//! precision, so the orders never cost more than the cash, and the leftover
+//! is then spent one quantity step at a time on the slice furthest below its
+//! target. [`optimize_allocation`] instead trades leftover cash for a closer
+//! match, reporting each position's weight error. [`exposure`] breaks the
+//! resulting portfolio down by currency, sector, region and instrument type.
+
+use rust_decimal::{Decimal, RoundingStrategy};
+use std::collections::BTreeMap;
+
+/// One slice of a pie.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PieSlice {
+    pub ticker: String,
+    /// Target weight; weights need not sum to 1.
+    pub weight: Decimal,
+    pub price: Decimal,
+}
+
+/// Order for one slice. `quantity` is zero for slices whose share of the
+/// cash is below the minimum order value.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PieOrder {
+    pub ticker: String,
+    pub quantity: Decimal,
+    /// `quantity * price`.
+    pub value: Decimal,
+    /// The slice's share of the cash before rounding.
+    pub target_value: Decimal,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PieAllocation {
+    /// One order per slice, in input order.
+    pub orders: Vec<PieOrder>,
+    pub invested: Decimal,
+    pub remaining_cash: Decimal,
+}
+
+/// Allocates `cash` across `slices`, with quantities in multiples of
+/// `10^-quantity_dp` shares (0 for whole shares).
+///
+/// Slices whose target is below `min_order_value` are dropped and their
+/// weight spread over the rest, smallest first, since the broker would
+/// reject the order.
+pub fn allocate_pie(slices: &[PieSlice], cash: Decimal, min_order_value: Decimal, quantity_dp: u32) -> Result<PieAllocation, String> {
+    if cash.is_sign_negative() {
+        return Err(format!("cash must be non-negative, got {}", cash));
+    }
+    if quantity_dp > Decimal::MAX_SCALE {
+        return Err(format!("quantity_dp must be at most {}, got {}", Decimal::MAX_SCALE, quantity_dp));
+    }
+    if min_order_value.is_sign_negative() {
+        return Err(format!("min_order_value must be non-negative, got {}", min_order_value));
+    }
+    for slice in slices {
+        if slice.weight.is_sign_negative() {
+            return Err(format!("weight of '{}' must be non-negative, got {}", slice.ticker, slice.weight));
+        }
+        if slice.price <= Decimal::ZERO {
+            return Err(format!("price of '{}' must be positive, got {}", slice.ticker, slice.price));
+        }
+    }
+    if slices.iter().all(|s| s.weight.is_zero()) {
+        return Err("target weights must not all be zero".to_string());
+    }
+
+    let mut active: Vec<bool> = slices.iter().map(|s| !s.weight.is_zero()).collect();
+    let mut targets = vec![Decimal::ZERO; slices.len()];
+    loop {
+        let total: Decimal = slices.iter().zip(&active).filter(|(_, &on)| on).map(|(s, _)| s.weight).sum();
+        for (i, slice) in slices.iter().enumerate() {
+            targets[i] = if active[i] { cash * slice.weight / total } else { Decimal::ZERO };
+        }
+        let smallest = (0..slices.len()).filter(|&i| active[i]).min_by_key(|&i| targets[i]);
+        match smallest {
+            Some(i) if targets[i] < min_order_value => active[i] = false,
+            _ => break,
+        }
+        if !active.contains(&true) {
+            break;
+        }
+    }
+    for (target, _) in targets.iter_mut().zip(&active).filter(|(_, &on)| !on) {
+        *target = Decimal::ZERO;
+    }
+
+    let step = Decimal::new(1, quantity_dp);
+    let mut quantities: Vec<Decimal> = slices
+        .iter()
+        .zip(&targets)
+        .map(|(slice, &target)| {
+            let quantity = (target / slice.price).round_dp_with_strategy(quantity_dp, RoundingStrategy::ToZero);
+            if quantity * slice.price < min_order_value {
+                Decimal::ZERO
+            } else {
+                quantity
+            }
+        })
+        .collect();
+    let mut remaining = cash - slices.iter().zip(&quantities).map(|(s, &q)| q * s.price).sum::<Decimal>();
+
+    // Top up with the leftover cash. A slice with no order yet can only be
+    // bought up to the minimum order value.
+    loop {
+        let increment = |i: usize| {
+            let price = slices[i].price;
+            if quantities[i].is_zero() {
+                let first = (min_order_value / price).round_dp_with_strategy(quantity_dp, RoundingStrategy::AwayFromZero);
+                first.max(step)
+            } else {
+                step
+            }
+        };
+        let best = (0..slices.len())
+            .filter(|&i| active[i] && increment(i) * slices[i].price <= remaining)
+            .max_by_key(|&i| (targets[i] - quantities[i] * slices[i].price, std::cmp::Reverse(i)));
+        let Some(i) = best else { break };
+        let add = increment(i);
+        quantities[i] += add;
+        remaining -= add * slices[i].price;
+    }
+
+    let orders: Vec<PieOrder> = slices
+        .iter()
+        .zip(quantities.iter().zip(&targets))
+        .map(|(slice, (&quantity, &target_value))| PieOrder {
+            ticker: slice.ticker.clone(),
+            quantity,
+            value: quantity * slice.price,
+            target_value,
+        })
+        .collect();
+    Ok(PieAllocation { invested: cash - remaining, orders, remaining_cash: remaining })
+}
+
+/// Result of [`optimize_allocation`], one entry per position in input order.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Allocation {
+    pub quantities: Vec<Decimal>,
+    /// `quantity * price / cash - target_weight`; uninvested cash makes the
+    /// errors sum to a negative value.
+    pub weight_errors: Vec<Decimal>,
+    pub invested: Decimal,
+    pub remaining_cash: Decimal,
+}
+
+impl Allocation {
+    pub fn max_abs_weight_error(&self) -> Decimal {
+        self.weight_errors.iter().map(|e| e.abs()).max().unwrap_or_default()
+    }
+}
+
+/// Buys quantities in multiples of `10^-quantity_dp` shares whose values best
+/// match `cash * weight` (weights normalized to sum to 1), never spending
+/// more than `cash`.
+///
+/// Quantities start rounded down, then the leftover buys one step at a time
+/// wherever it most reduces the squared value error. While the steps of
+/// different positions cost about the same, that hands them out by largest
+/// remainder; it stops once no affordable step improves on the error, so,
+/// unlike [`allocate_pie`], cash may be left over to stay closer to target.
+pub fn optimize_allocation(prices: &[Decimal], weights: &[Decimal], cash: Decimal, quantity_dp: u32) -> Result<Allocation, String> {
+    if prices.len() != weights.len() {
+        return Err(format!("prices has length {} but weights has length {}", prices.len(), weights.len()));
+    }
+    if cash <= Decimal::ZERO {
+        return Err(format!("cash must be positive, got {}", cash));
+    }
+    if quantity_dp > Decimal::MAX_SCALE {
+        return Err(format!("quantity_dp must be at most {}, got {}", Decimal::MAX_SCALE, quantity_dp));
+    }
+    if let Some(i) = prices.iter().position(|&p| p <= Decimal::ZERO) {
+        return Err(format!("price at index {} must be positive, got {}", i, prices[i]));
+    }
+    if let Some(i) = weights.iter().position(|w| w.is_sign_negative()) {
+        return Err(format!("weight at index {} must be non-negative, got {}", i, weights[i]));
+    }
+    let total: Decimal = weights.iter().sum();
+    if total.is_zero() {
+        return Err("target weights must not all be zero".to_string());
+    }
+
+    let step = Decimal::new(1, quantity_dp);
+    let targets: Vec<Decimal> = weights.iter().map(|&w| cash * w / total).collect();
+    let mut quantities: Vec<Decimal> = targets
+        .iter()
+        .zip(prices)
+        .map(|(&t, &p)| (t / p).round_dp_with_strategy(quantity_dp, RoundingStrategy::ToZero))
+        .collect();
+    let mut remaining = cash - quantities.iter().zip(prices).map(|(&q, &p)| q * p).sum::<Decimal>();
+
+    loop {
+        // Buying `cost` more of i changes (target - value)^2 by
+        // cost * (cost - 2 * shortfall).
+        let gain = |i: usize| {
+            let cost = step * prices[i];
+            let shortfall = targets[i] - quantities[i] * prices[i];
+            cost * (shortfall * Decimal::TWO - cost)
+        };
+        let best = (0..prices.len())
+            .filter(|&i| step * prices[i] <= remaining && gain(i) > Decimal::ZERO)
+            .max_by_key(|&i| (gain(i), std::cmp::Reverse(i)));
+        let Some(i) = best else { break };
+        quantities[i] += step;
+        remaining -= step * prices[i];
+    }
+
+    let weight_errors = quantities
+        .iter()
+        .zip(prices)
+        .zip(weights)
+        .map(|((&q, &p), &w)| q * p / cash - w / total)
+        .collect();
+    Ok(Allocation { quantities, weight_errors, invested: cash - remaining, remaining_cash: remaining })
+}
+
+/// A position with the classification [`exposure`] groups it by.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Holding {
+    pub ticker: String,
+    /// Market value in the account currency.
+    pub value: Decimal,
+    pub currency: String,
+    pub sector: String,
+    pub region: String,
+    pub instrument_type: String,
+    /// Exposure per unit of value: 1 for ordinary shares and ETFs, the
+    /// daily leverage for leveraged ETPs (e.g. 3, or -3 for a short ETP).
+    pub leverage: Decimal,
+}
+
+impl Holding {
+    /// `value * leverage`.
+    pub fn effective_value(&self) -> Decimal {
+        self.value * self.leverage
+    }
+}
+
+/// Effective exposure to one group.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ExposureBucket {
+    pub value: Decimal,
+    /// `value` as a fraction of the portfolio's market value; leverage can
+    /// push a group past 1 or below 0.
+    pub weight: Decimal,
+}
+
+/// Portfolio exposure by each classification, groups sorted by name.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Exposure {
+    /// Sum of the market values.
+    pub total_value: Decimal,
+    /// Sum of the effective values.
+    pub net_exposure: Decimal,
+    /// Sum of the absolute effective values.
+    pub gross_exposure: Decimal,
+    pub by_currency: BTreeMap<String, ExposureBucket>,
+    pub by_sector: BTreeMap<String, ExposureBucket>,
+    pub by_region: BTreeMap<String, ExposureBucket>,
+    pub by_instrument_type: BTreeMap<String, ExposureBucket>,
+}
+
+/// Aggregates holdings' effective values by currency, sector, region and
+/// instrument type, weighting each group by the portfolio's market value.
+pub fn exposure(holdings: &[Holding]) -> Result<Exposure, String> {
+    if let Some(h) = holdings.iter().find(|h| h.value < Decimal::ZERO) {
+        return Err(format!("value of '{}' must be non-negative, got {}", h.ticker, h.value));
+    }
+    let total_value: Decimal = holdings.iter().map(|h| h.value).sum();
+    if total_value.is_zero() {
+        return Err("the portfolio has no market value".to_string());
+    }
+    let mut out = Exposure { total_value, ..Exposure::default() };
+    for h in holdings {
+        let effective = h.effective_value();
+        out.net_exposure += effective;
+        out.gross_exposure += effective.abs();
+        for (groups, key) in [
+            (&mut out.by_currency, &h.currency),
+            (&mut out.by_sector, &h.sector),
+            (&mut out.by_region, &h.region),
+            (&mut out.by_instrument_type, &h.instrument_type),
+        ] {
+            groups.entry(key.clone()).or_default().value += effective;
+        }
+    }
+    for groups in [&mut out.by_currency, &mut out.by_sector, &mut out.by_region, &mut out.by_instrument_type] {
+        for bucket in groups.values_mut() {
+            bucket.weight = bucket.value / total_value;
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(v: &str) -> Decimal {
+        v.parse().unwrap()
+    }
+
+    fn slice(ticker: &str, weight: &str, price: &str) -> PieSlice {
+        PieSlice { ticker: ticker.to_string(), weight: d(weight), price: d(price) }
+    }
+
+    fn quantities(allocation: &PieAllocation) -> Vec<Decimal> {
+        allocation.orders.iter().map(|o| o.quantity).collect()
+    }
+
+    #[test]
+    fn slices_below_the_minimum_order_are_dropped_and_reweighted() {
+        let slices = [slice("A", "0.6", "10"), slice("B", "0.38", "20"), slice("C", "0.02", "5")];
+        let allocation = allocate_pie(&slices, d("100"), d("5"), 8).unwrap();
+        let [a, b, c] = [0, 1, 2].map(|i| &allocation.orders[i]);
+        assert_eq!((c.quantity, c.target_value), (Decimal::ZERO, Decimal::ZERO));
+        assert!((a.target_value + b.target_value - d("100")).abs() < d("1e-20"));
+        assert!((a.target_value / b.target_value - d("0.6") / d("0.38")).abs() < d("1e-20"));
+        assert!(allocation.remaining_cash < d("0.0000002"));
+    }
+
+    #[test]
+    fn dropping_reweights_before_checking_the_next_smallest() {
+        // B's 4.9 alone is below 5, but reaches it once C's weight is spread.
+        let slices = [slice("A", "0.5", "1"), slice("B", "0.049", "1"), slice("C", "0.02", "1"), slice("D", "0.431", "1")];
+        let allocation = allocate_pie(&slices, d("100"), d("5"), 0).unwrap();
+        assert_eq!(allocation.orders[1].target_value, d("5"));
+        assert_eq!(quantities(&allocation), [d("51"), d("5"), Decimal::ZERO, d("44")]);
+        assert_eq!(allocation.invested, d("100"));
+    }
+
+    #[test]
+    fn leftover_whole_shares_go_to_the_slice_furthest_below_target() {
+        let slices = [slice("A", "1", "10"), slice("B", "1", "10"), slice("C", "1", "10")];
+        let allocation = allocate_pie(&slices, d("100"), d("1"), 0).unwrap();
+        assert_eq!(quantities(&allocation), [d("4"), d("3"), d("3")]);
+        assert_eq!((allocation.invested, allocation.remaining_cash), (d("100"), Decimal::ZERO));
+    }
+
+    #[test]
+    fn whole_shares_leave_cash_no_share_fits() {
+        let slices = [slice("A", "1", "30"), slice("B", "1", "45")];
+        let allocation = allocate_pie(&slices, d("100"), d("1"), 0).unwrap();
+        assert_eq!(quantities(&allocation), [d("1"), d("1")]);
+        assert_eq!(allocation.remaining_cash, d("25"));
+    }
+
+    #[test]
+    fn top_up_keeps_buying_while_cash_allows() {
+        // B is too dear for a whole share, so A takes the leftover.
+        let slices = [slice("A", "0.5", "3"), slice("B", "0.5", "100")];
+        let allocation = allocate_pie(&slices, d("10"), d("1"), 0).unwrap();
+        assert_eq!(quantities(&allocation), [d("3"), Decimal::ZERO]);
+        assert_eq!(allocation.remaining_cash, d("1"));
+    }
+
+    #[test]
+    fn fractional_quantities_round_down_to_quantity_dp() {
+        let slices = [slice("A", "1", "3"), slice("B", "2", "7")];
+        let allocation = allocate_pie(&slices, d("100"), d("1"), 2).unwrap();
+        for order in &allocation.orders {
+            assert!(order.quantity.scale() <= 2, "{}", order.quantity);
+        }
+        assert_eq!(quantities(&allocation), [d("11.12"), d("9.52")]);
+        assert_eq!(allocation.remaining_cash, d("0.00"));
+    }
+
+    #[test]
+    fn never_spends_more_than_the_cash() {
+        let mut seed = 7u64;
+        let mut next = |m: u64| {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (seed >> 33) % m
+        };
+        for _ in 0..200 {
+            let slices: Vec<PieSlice> = (0..1 + next(6))
+                .map(|i| PieSlice {
+                    ticker: i.to_string(),
+                    weight: Decimal::new(next(100) as i64, 2),
+                    price: Decimal::new(1 + next(50_000) as i64, 2),
+                })
+                .collect();
+            if slices.iter().all(|s| s.weight.is_zero()) {
+                continue;
+            }
+            let cash = Decimal::new(next(100_000) as i64, 2);
+            let min_order = Decimal::new(next(500) as i64, 2);
+            let dp = next(4) as u32 * 2;
+            let allocation = allocate_pie(&slices, cash, min_order, dp).unwrap();
+            let spent: Decimal = allocation.orders.iter().map(|o| o.value).sum();
+            assert_eq!(spent, allocation.invested);
+            assert!(spent <= cash, "{} > {}", spent, cash);
+            assert_eq!(allocation.remaining_cash, cash - spent);
+            assert!(allocation.orders.iter().all(|o| o.value.is_zero() || o.value >= min_order));
+        }
+    }
+
+    #[test]
+    fn pie_inputs_are_validated() {
+        let slices = [slice("A", "1", "10")];
+        assert!(allocate_pie(&slices, d("-1"), d("1"), 0).is_err());
+        assert!(allocate_pie(&[slice("A", "0", "10")], d("10"), d("1"), 0).is_err());
+        assert!(allocate_pie(&[slice("A", "1", "0")], d("10"), d("1"), 0).is_err());
+        assert!(allocate_pie(&slices, d("10"), d("1"), 29).is_err());
+    }
+
+    #[test]
+    fn optimize_buys_the_step_that_most_reduces_the_error() {
+        let prices = [d("10"), d("10")];
+        let allocation = optimize_allocation(&prices, &[d("0.57"), d("0.43")], d("100"), 0).unwrap();
+        assert_eq!(allocation.quantities, [d("6"), d("4")]);
+        assert_eq!(allocation.weight_errors, [d("0.03"), d("-0.03")]);
+        assert_eq!(allocation.remaining_cash, Decimal::ZERO);
+    }
+
+    #[test]
+    fn optimize_leaves_cash_rather_than_overshoot() {
+        let prices = [d("10"), d("10"), d("10")];
+        let allocation = optimize_allocation(&prices, &[Decimal::ONE; 3], d("100"), 0).unwrap();
+        assert_eq!(allocation.quantities, [d("3"); 3]);
+        assert_eq!((allocation.invested, allocation.remaining_cash), (d("90"), d("10")));
+
+        let fractional = optimize_allocation(&prices, &[Decimal::ONE; 3], d("100"), 4).unwrap();
+        assert!(fractional.max_abs_weight_error() < d("0.0001"));
+        assert!(fractional.invested <= d("100"));
+    }
+
+    #[test]
+    fn optimize_inputs_are_validated() {
+        assert!(optimize_allocation(&[d("10")], &[], d("100"), 0).is_err());
+        assert!(optimize_allocation(&[d("10")], &[Decimal::ZERO], d("100"), 0).is_err());
+        assert!(optimize_allocation(&[d("10")], &[Decimal::ONE], Decimal::ZERO, 0).is_err());
+    }
+}
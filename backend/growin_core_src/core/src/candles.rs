@@ -0,0 +1,194 @@
+use crate::lanes::{lit, Real};
+
+/// OHLCV bars as parallel columns.
+///
+/// `open`/`high`/`low` are as long as `close` (line series repeat the close),
+/// while `volume` and `timestamps` (epoch seconds) may be empty when the
+/// source has no such column.
+#[derive(Clone, Debug, Default)]
+pub struct Candles {
+    pub open: Vec<f64>,
+    pub high: Vec<f64>,
+    pub low: Vec<f64>,
+    pub close: Vec<f64>,
+    pub volume: Vec<f64>,
+    pub timestamps: Vec<i64>,
+}
+
+impl Candles {
+    pub fn len(&self) -> usize {
+        self.close.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.close.is_empty()
+    }
+
+    pub fn has_volume(&self) -> bool {
+        !self.volume.is_empty()
+    }
+
+    /// Warns about bars that silently degrade indicator output.
+    pub fn log_quality_issues(&self) {
+        log_quality_issues(&self.high, &self.low, &self.close, &self.timestamps);
+    }
+}
+
+/// [`Candles::log_quality_issues`] for columns held outside a `Candles`.
+pub fn log_quality_issues(high: &[f64], low: &[f64], close: &[f64], timestamps: &[i64]) {
+    if !log::log_enabled!(log::Level::Warn) {
+        return;
+    }
+    let nan_closes = close.iter().filter(|c| c.is_nan()).count();
+    if nan_closes > 0 {
+        log::warn!("candles: {} of {} closes are NaN", nan_closes, close.len());
+    }
+    let inverted = high.iter().zip(low).filter(|(h, l)| h < l).count();
+    if inverted > 0 {
+        log::warn!("candles: {} bars have high below low", inverted);
+    }
+    if timestamps.windows(2).any(|w| w[1] <= w[0]) {
+        log::warn!("candles: timestamps are not strictly ascending");
+    }
+}
+
+/// Heikin-Ashi candles as `[open, high, low, close]`.
+///
+/// The close is the bar's OHLC mean and the open the midpoint of the
+/// previous Heikin-Ashi candle's body (the first bar's own open/close
+/// midpoint), so each open depends on the one before it. The high and low
+/// stretch to cover the new body.
+pub fn heikin_ashi<T: Real>(open: &[T], high: &[T], low: &[T], close: &[T]) -> [Vec<T>; 4] {
+    let n = close.len().min(open.len()).min(high.len()).min(low.len());
+    let mut ha: [Vec<T>; 4] = std::array::from_fn(|_| Vec::with_capacity(n));
+    let (two, four) = (lit::<T>(2.0), lit::<T>(4.0));
+    let mut prev: Option<(T, T)> = None;
+    for i in 0..n {
+        let ha_close = (open[i] + high[i] + low[i] + close[i]) / four;
+        let ha_open = match prev {
+            Some((o, c)) => (o + c) / two,
+            None => (open[i] + close[i]) / two,
+        };
+        ha[0].push(ha_open);
+        ha[1].push(high[i].max(ha_open).max(ha_close));
+        ha[2].push(low[i].min(ha_open).min(ha_close));
+        ha[3].push(ha_close);
+        prev = Some((ha_open, ha_close));
+    }
+    ha
+}
+
+/// One Renko brick.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RenkoBrick {
+    pub open: f64,
+    pub close: f64,
+    /// +1 for a rising brick, -1 for a falling one.
+    pub direction: i8,
+    /// Index of the price that completed the brick.
+    pub index: usize,
+}
+
+/// Most bricks [`renko`] lays before giving up on a brick size that is
+/// far too small for the series.
+pub const MAX_RENKO_BRICKS: usize = 1_000_000;
+
+/// Renko bricks of `brick_size` from a price series.
+///
+/// Anchored at the first finite price, a brick is laid each time the price
+/// closes a full brick beyond the last one; a reversal needs two bricks
+/// (the new brick starts from the far side of the last). One price that
+/// moves several bricks lays them all; non-finite prices are skipped.
+///
+/// Errors when `brick_size` is not positive and finite, is too small to
+/// move the price it is added to, or would lay more than
+/// [`MAX_RENKO_BRICKS`].
+pub fn renko(prices: &[f64], brick_size: f64) -> Result<Vec<RenkoBrick>, String> {
+    if !brick_size.is_finite() || brick_size <= 0.0 {
+        return Err(format!("brick_size must be positive, got {}", brick_size));
+    }
+    let mut bricks = Vec::new();
+    let Some(anchor) = prices.iter().copied().find(|p| p.is_finite()) else {
+        return Ok(bricks);
+    };
+    // Bottom and top of the last brick.
+    let (mut bottom, mut top) = (anchor, anchor);
+    for (index, &price) in prices.iter().enumerate() {
+        if !price.is_finite() {
+            continue;
+        }
+        let (base, direction, count) = if price >= top + brick_size {
+            (top, 1.0, ((price - top) / brick_size).floor().max(1.0))
+        } else if price <= bottom - brick_size {
+            (bottom, -1.0, ((bottom - price) / brick_size).floor().max(1.0))
+        } else {
+            continue;
+        };
+        if base + direction * brick_size == base {
+            return Err(format!("brick_size {} is too small for prices around {}", brick_size, base));
+        }
+        if bricks.len() as f64 + count > MAX_RENKO_BRICKS as f64 {
+            return Err(format!("brick_size {} lays more than {} bricks", brick_size, MAX_RENKO_BRICKS));
+        }
+        // Each edge is measured from the base, so error does not accumulate.
+        let edge = |k: usize| base + direction * k as f64 * brick_size;
+        for k in 0..count as usize {
+            bricks.push(RenkoBrick { open: edge(k), close: edge(k + 1), direction: direction as i8, index });
+        }
+        let (inner, outer) = (edge(count as usize - 1), edge(count as usize));
+        (bottom, top) = if direction > 0.0 { (inner, outer) } else { (outer, inner) };
+    }
+    Ok(bricks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edges(bricks: &[RenkoBrick]) -> Vec<(f64, f64, i8, usize)> {
+        bricks.iter().map(|b| (b.open, b.close, b.direction, b.index)).collect()
+    }
+
+    #[test]
+    fn renko_lays_every_brick_a_move_covers() {
+        let bricks = renko(&[f64::NAN, 10.0, 11.5, 13.2, 12.5, 11.0, 9.0], 1.0).unwrap();
+        assert_eq!(
+            edges(&bricks),
+            vec![
+                (10.0, 11.0, 1, 2),
+                (11.0, 12.0, 1, 3),
+                (12.0, 13.0, 1, 3),
+                (12.0, 11.0, -1, 5),
+                (11.0, 10.0, -1, 6),
+                (10.0, 9.0, -1, 6),
+            ]
+        );
+    }
+
+    #[test]
+    fn renko_skips_infinite_prices() {
+        let prices = [f64::INFINITY, 10.0, f64::INFINITY, 12.0, f64::NEG_INFINITY, 9.0];
+        let bricks = renko(&prices, 1.0).unwrap();
+        assert_eq!(edges(&bricks), vec![(10.0, 11.0, 1, 3), (11.0, 12.0, 1, 3), (11.0, 10.0, -1, 5), (10.0, 9.0, -1, 5)]);
+        assert!(renko(&[f64::INFINITY, f64::NEG_INFINITY], 1.0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn renko_rejects_brick_sizes_too_small_for_the_prices() {
+        // 5e-15 does not change 100.0, so stepping a brick at a time never ends.
+        assert_eq!(100.0 + 5e-15, 100.0);
+        let err = renko(&[100.0, 101.0], 5e-15).unwrap_err();
+        assert!(err.contains("too small"), "{err}");
+        // Representable, but a million bricks for a one-point move.
+        let err = renko(&[100.0, 101.0], 1e-9).unwrap_err();
+        assert!(err.contains("more than 1000000 bricks"), "{err}");
+        assert_eq!(renko(&[100.0, 101.0], 1e-6).unwrap().len(), MAX_RENKO_BRICKS);
+    }
+
+    #[test]
+    fn renko_rejects_bad_brick_sizes() {
+        for size in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert!(renko(&[1.0, 2.0], size).is_err());
+        }
+    }
+}
@@ -0,0 +1,292 @@
+//! Chunk-at-a-time indicators for histories too long to hold in memory.
+//!
+//! Each state consumes values in order and carries everything the next value
+//! depends on, so feeding a series block by block produces exactly the
+//! output the batch function computes over the whole series.
+
+use std::collections::VecDeque;
+
+use crate::lanes;
+use crate::moments::RollingMoments;
+use crate::stats;
+
+/// An indicator that can be advanced one value at a time.
+pub trait Streaming: Send + 'static {
+    type Output;
+
+    fn push(&mut self, x: f64) -> Self::Output;
+}
+
+/// How the EMA seed (the mean of the first `period` values) is summed; the
+/// two batch EMAs differ here, and states must match them bit for bit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EmaSeed {
+    /// Lane-chunked sum, as in [`crate::indicators::ema`].
+    Lanes,
+    /// Left-to-right sum, as in the EMAs inside [`crate::indicators::macd`].
+    Fold,
+}
+
+/// [`crate::indicators::ema`], zero during warm-up. Unlike the batch
+/// function it cannot look ahead, so a series shorter than `period` stays
+/// zero instead of being seeded from its first value.
+pub struct EmaState {
+    period: usize,
+    k: f64,
+    seed: EmaSeed,
+    warmup: Vec<f64>,
+    current: Option<f64>,
+}
+
+impl EmaState {
+    pub fn new(period: usize, seed: EmaSeed) -> Self {
+        EmaState { period, k: 2.0 / (period as f64 + 1.0), seed, warmup: Vec::with_capacity(period), current: None }
+    }
+}
+
+impl Streaming for EmaState {
+    type Output = f64;
+
+    fn push(&mut self, x: f64) -> f64 {
+        if let Some(current) = &mut self.current {
+            *current = (x * self.k) + (*current * (1.0 - self.k));
+            return *current;
+        }
+        self.warmup.push(x);
+        if self.warmup.len() < self.period {
+            return 0.0;
+        }
+        let sum = match self.seed {
+            EmaSeed::Lanes => lanes::sum(&self.warmup),
+            EmaSeed::Fold => self.warmup.iter().fold(0.0, |a, &x| a + x),
+        };
+        let seed = sum / self.period as f64;
+        self.current = Some(seed);
+        self.warmup = Vec::new();
+        seed
+    }
+}
+
+/// [`crate::indicators::sma`]: zero until the first full window.
+pub struct SmaState {
+    period: usize,
+    window: VecDeque<f64>,
+    sum: f64,
+}
+
+impl SmaState {
+    pub fn new(period: usize) -> Self {
+        SmaState { period, window: VecDeque::with_capacity(period + 1), sum: 0.0 }
+    }
+}
+
+impl Streaming for SmaState {
+    type Output = f64;
+
+    fn push(&mut self, x: f64) -> f64 {
+        self.window.push_back(x);
+        if self.window.len() < self.period {
+            return 0.0;
+        }
+        if self.window.len() == self.period {
+            self.sum = lanes::sum(self.window.make_contiguous());
+        } else {
+            let old = self.window.pop_front().unwrap_or(0.0);
+            self.sum += x;
+            self.sum -= old;
+        }
+        self.sum / self.period as f64
+    }
+}
+
+/// [`crate::indicators::rsi`] with Wilder's smoothing: 50.0 for the first
+/// `period` values.
+pub struct RsiState {
+    period: usize,
+    seen: usize,
+    prev: f64,
+    gains: Vec<f64>,
+    losses: Vec<f64>,
+    avg_gain: f64,
+    avg_loss: f64,
+}
+
+impl RsiState {
+    pub fn new(period: usize) -> Self {
+        RsiState {
+            period,
+            seen: 0,
+            prev: 0.0,
+            gains: Vec::with_capacity(period),
+            losses: Vec::with_capacity(period),
+            avg_gain: 0.0,
+            avg_loss: 0.0,
+        }
+    }
+}
+
+impl Streaming for RsiState {
+    type Output = f64;
+
+    fn push(&mut self, x: f64) -> f64 {
+        let i = self.seen;
+        self.seen += 1;
+        let change = x - self.prev;
+        self.prev = x;
+        if i == 0 {
+            return 50.0;
+        }
+        // Same branchless split as `lanes::gains_losses`.
+        let (gain, loss) = if change > 0.0 { (change, 0.0) } else { (0.0, -change) };
+        let p = self.period as f64;
+        if i <= self.period {
+            self.gains.push(gain);
+            self.losses.push(loss);
+            if i < self.period {
+                return 50.0;
+            }
+            self.avg_gain = lanes::sum(&self.gains) / p;
+            self.avg_loss = lanes::sum(&self.losses) / p;
+            (self.gains, self.losses) = (Vec::new(), Vec::new());
+        } else {
+            self.avg_gain = (self.avg_gain * (p - 1.0) + gain) / p;
+            self.avg_loss = (self.avg_loss * (p - 1.0) + loss) / p;
+        }
+        let rs = if self.avg_loss == 0.0 { 100.0 } else { self.avg_gain / self.avg_loss };
+        100.0 - (100.0 / (1.0 + rs))
+    }
+}
+
+/// [`crate::indicators::macd`]: (macd, signal, histogram).
+pub struct MacdState {
+    fast: EmaState,
+    slow: EmaState,
+    signal: EmaState,
+}
+
+impl MacdState {
+    pub fn new(fast: usize, slow: usize, signal: usize) -> Self {
+        MacdState {
+            fast: EmaState::new(fast, EmaSeed::Fold),
+            slow: EmaState::new(slow, EmaSeed::Fold),
+            signal: EmaState::new(signal, EmaSeed::Fold),
+        }
+    }
+}
+
+impl Streaming for MacdState {
+    type Output = (f64, f64, f64);
+
+    fn push(&mut self, x: f64) -> (f64, f64, f64) {
+        let m = self.fast.push(x) - self.slow.push(x);
+        let s = self.signal.push(m);
+        (m, s, m - s)
+    }
+}
+
+/// [`crate::indicators::bbands`]: (upper, middle, lower), zero until the
+/// first full window.
+pub struct BbandsState {
+    std_dev: f64,
+    moments: RollingMoments<f64>,
+}
+
+impl BbandsState {
+    pub fn new(period: usize, std_dev: f64) -> Self {
+        Self::with_ddof(period, std_dev, 0)
+    }
+
+    /// See [`crate::indicators::bbands_ddof_into`].
+    pub fn with_ddof(period: usize, std_dev: f64, ddof: usize) -> Self {
+        BbandsState { std_dev, moments: RollingMoments::with_ddof(period, ddof) }
+    }
+}
+
+impl Streaming for BbandsState {
+    type Output = (f64, f64, f64);
+
+    fn push(&mut self, x: f64) -> (f64, f64, f64) {
+        let (mean, std) = match self.moments.push(x) {
+            Some((mean, variance)) => (mean, variance.sqrt()),
+            None if self.moments.is_full() => (f64::NAN, f64::NAN),
+            None => return (0.0, 0.0, 0.0),
+        };
+        (mean + (self.std_dev * std), mean, mean - (self.std_dev * std))
+    }
+}
+
+/// [`crate::indicators::atr`] over bars pushed one at a time with
+/// [`AtrState::push_bar`]: zero until `period` true ranges have been seen,
+/// then Wilder-smoothed from their mean.
+pub struct AtrState {
+    period: usize,
+    prev_close: Option<f64>,
+    warmup: Vec<f64>,
+    current: Option<f64>,
+}
+
+impl AtrState {
+    pub fn new(period: usize) -> Self {
+        AtrState { period, prev_close: None, warmup: Vec::with_capacity(period), current: None }
+    }
+
+    pub fn push_bar(&mut self, high: f64, low: f64, close: f64) -> f64 {
+        let hl = high - low;
+        let tr = match self.prev_close.replace(close) {
+            Some(prev) => hl.max((high - prev).abs()).max((low - prev).abs()),
+            None => hl,
+        };
+        let p = self.period as f64;
+        if let Some(current) = &mut self.current {
+            *current = (*current * (p - 1.0) + tr) / p;
+            return *current;
+        }
+        self.warmup.push(tr);
+        if self.warmup.len() < self.period {
+            return 0.0;
+        }
+        let seed = lanes::sum(&self.warmup) / p;
+        self.current = Some(seed);
+        self.warmup = Vec::new();
+        seed
+    }
+}
+
+/// [`crate::stats::rolling_mean`], [`crate::stats::rolling_std`] and
+/// [`crate::stats::rolling_zscore`] in one pass: (mean, std, zscore), NaN
+/// until the first full window and for windows containing a NaN or ±Inf.
+pub struct RollingStatsState {
+    moments: RollingMoments<f64>,
+}
+
+impl RollingStatsState {
+    pub fn new(period: usize) -> Self {
+        Self::with_ddof(period, 0)
+    }
+
+    /// Like [`RollingStatsState::new`] with the std over `period - ddof`
+    /// degrees of freedom, as [`crate::stats::rolling_std_ddof`].
+    pub fn with_ddof(period: usize, ddof: usize) -> Self {
+        RollingStatsState { moments: RollingMoments::with_ddof(period, ddof) }
+    }
+}
+
+impl Streaming for RollingStatsState {
+    type Output = (f64, f64, f64);
+
+    fn push(&mut self, x: f64) -> (f64, f64, f64) {
+        match self.moments.push(x) {
+            Some((mu, variance)) => {
+                let sd = variance.sqrt();
+                (mu, sd, stats::zscore(x, mu, sd))
+            }
+            None => (f64::NAN, f64::NAN, f64::NAN),
+        }
+    }
+}
+
+/// Runs a state over one block.
+pub fn process_block<S: Streaming>(state: &mut S, block: &[f64]) -> Vec<S::Output> {
+    block.iter().map(|&x| state.push(x)).collect()
+}
+
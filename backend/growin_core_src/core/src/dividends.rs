@@ -0,0 +1,160 @@
+//! Forward dividend income from each holding's payment history.
+//!
+//! A holding's payment frequency is inferred from how often it paid in the
+//! latest year, and future payments repeat the last year's dates and
+//! amounts: a company paying a small interim and a larger final dividend is
+//! projected to keep doing so, rather than paying the latest amount every
+//! six months.
+
+use rust_decimal::Decimal;
+use std::collections::BTreeMap;
+
+use crate::calendar::Date;
+
+/// A past payment per share.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Dividend {
+    pub date: Date,
+    pub per_share: Decimal,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Frequency {
+    Monthly,
+    Quarterly,
+    SemiAnnual,
+    Annual,
+}
+
+impl Frequency {
+    /// From how many payments fall in the year (11 months, allowing for
+    /// dates that drift) up to the latest one. Counting, rather than
+    /// averaging gaps, copes with uneven interim/final schedules.
+    pub fn infer(dates: &[Date]) -> Self {
+        let Some(last) = dates.iter().max() else {
+            return Frequency::Annual;
+        };
+        let recent = dates.iter().filter(|d| last.days() - d.days() < 335).count();
+        match recent {
+            0 | 1 => Frequency::Annual,
+            2 => Frequency::SemiAnnual,
+            3..=6 => Frequency::Quarterly,
+            _ => Frequency::Monthly,
+        }
+    }
+
+    pub fn months(self) -> i32 {
+        match self {
+            Frequency::Monthly => 1,
+            Frequency::Quarterly => 3,
+            Frequency::SemiAnnual => 6,
+            Frequency::Annual => 12,
+        }
+    }
+
+    pub fn per_year(self) -> usize {
+        (12 / self.months()) as usize
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Frequency::Monthly => "monthly",
+            Frequency::Quarterly => "quarterly",
+            Frequency::SemiAnnual => "semi_annual",
+            Frequency::Annual => "annual",
+        }
+    }
+}
+
+/// A projected payment.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProjectedPayment {
+    pub ticker: String,
+    pub date: Date,
+    pub per_share: Decimal,
+    /// `per_share * quantity`.
+    pub amount: Decimal,
+}
+
+/// One holding's inferred dividend profile.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DividendProfile {
+    pub ticker: String,
+    pub frequency: Frequency,
+    /// Sum of the last year's worth of payments.
+    pub annual_per_share: Decimal,
+    pub annual_income: Decimal,
+    pub next_payment: Option<Date>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DividendProjection {
+    /// Holdings with a payment history, sorted by ticker.
+    pub profiles: Vec<DividendProfile>,
+    /// Payments after `as_of` and within the horizon, by date.
+    pub payments: Vec<ProjectedPayment>,
+}
+
+impl DividendProjection {
+    /// Projected payments grouped by "YYYY-MM".
+    pub fn by_month(&self) -> BTreeMap<String, Vec<&ProjectedPayment>> {
+        let mut out: BTreeMap<String, Vec<&ProjectedPayment>> = BTreeMap::new();
+        for p in &self.payments {
+            out.entry(p.date.month_label()).or_default().push(p);
+        }
+        out
+    }
+}
+
+/// Projects each holding's payments from `as_of` (exclusive) over the next
+/// `horizon_months`. `holdings` pairs a ticker and share quantity with its
+/// payment history, in any order; holdings without history are skipped.
+pub fn project_dividends(holdings: &[(String, Decimal, Vec<Dividend>)], as_of: Date, horizon_months: u32) -> DividendProjection {
+    let end = as_of.add_months(horizon_months as i32);
+    let mut out = DividendProjection::default();
+    for (ticker, quantity, history) in holdings {
+        let mut history = history.clone();
+        history.sort_by_key(|d| d.date);
+        let Some(last) = history.last().copied() else { continue };
+        let dates: Vec<Date> = history.iter().map(|d| d.date).collect();
+        let frequency = Frequency::infer(&dates);
+        // The last year of payments, oldest first; repeated going forward.
+        let pattern = &history[history.len().saturating_sub(frequency.per_year())..];
+        let full_year = pattern.len() == frequency.per_year();
+        let annual_per_share = if full_year {
+            pattern.iter().map(|d| d.per_share).sum()
+        } else {
+            last.per_share * Decimal::from(frequency.per_year())
+        };
+
+        let mut next_payment = None;
+        for k in 1.. {
+            // A full year's payments recur on the same dates a year later;
+            // a shorter history steps on from the latest payment.
+            let (date, per_share) = if full_year {
+                let p = pattern[(k - 1) % pattern.len()];
+                (p.date.add_months(12 * ((k - 1) / pattern.len() + 1) as i32), p.per_share)
+            } else {
+                (last.date.add_months(k as i32 * frequency.months()), last.per_share)
+            };
+            if date > end {
+                break;
+            }
+            if date <= as_of {
+                continue;
+            }
+            next_payment.get_or_insert(date);
+            out.payments.push(ProjectedPayment { ticker: ticker.clone(), date, per_share, amount: per_share * quantity });
+        }
+        out.profiles.push(DividendProfile {
+            ticker: ticker.clone(),
+            frequency,
+            annual_per_share,
+            annual_income: annual_per_share * quantity,
+            next_payment,
+        });
+    }
+    out.profiles.sort_by(|a, b| a.ticker.cmp(&b.ticker));
+    out.payments.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.ticker.cmp(&b.ticker)));
+    out
+}
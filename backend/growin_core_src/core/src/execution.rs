@@ -0,0 +1,156 @@
+//! Execution quality: what fills cost against market benchmarks.
+//!
+//! Each fill is compared with three prices from the bar it executed in: the
+//! arrival price (the bar's open unless the caller recorded one), the bar's
+//! VWAP and its close. Slippage is signed so that a positive cost means the
+//! fill was worse than the benchmark: paid more on a buy, got less on a sell.
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::calendar::Date;
+use crate::candles::Candles;
+use crate::risk::Side;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExecutionFill {
+    pub ticker: String,
+    /// Epoch seconds, on the same clock as the candle timestamps.
+    pub timestamp: i64,
+    /// `Long` for buys, `Short` for sells.
+    pub side: Side,
+    pub quantity: f64,
+    pub price: f64,
+    /// Price when the order was sent, if recorded.
+    pub arrival_price: Option<f64>,
+}
+
+/// Slippage against one benchmark; sums of these stay meaningful.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Slippage {
+    /// `side * (price - benchmark) * quantity`; positive is a cost.
+    pub cost: f64,
+    /// `benchmark * quantity`.
+    pub benchmark_value: f64,
+}
+
+impl Slippage {
+    fn new(side: Side, quantity: f64, price: f64, benchmark: f64) -> Self {
+        Slippage { cost: side.sign() * (price - benchmark) * quantity, benchmark_value: benchmark * quantity }
+    }
+
+    /// Cost in basis points of the benchmark value.
+    pub fn bps(&self) -> f64 {
+        if self.benchmark_value == 0.0 {
+            f64::NAN
+        } else {
+            self.cost / self.benchmark_value * 10_000.0
+        }
+    }
+
+    fn add(&mut self, other: Slippage) {
+        self.cost += other.cost;
+        self.benchmark_value += other.benchmark_value;
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FillQuality {
+    pub arrival_price: f64,
+    pub vwap: f64,
+    pub close: f64,
+    pub vs_arrival: Slippage,
+    pub vs_vwap: Slippage,
+    pub vs_close: Slippage,
+}
+
+/// Slippage summed over a group of fills.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct QualitySummary {
+    pub fills: usize,
+    /// `price * quantity` summed.
+    pub notional: f64,
+    pub vs_arrival: Slippage,
+    pub vs_vwap: Slippage,
+    pub vs_close: Slippage,
+}
+
+impl QualitySummary {
+    fn add(&mut self, fill: &ExecutionFill, quality: &FillQuality) {
+        self.fills += 1;
+        self.notional += fill.price * fill.quantity;
+        self.vs_arrival.add(quality.vs_arrival);
+        self.vs_vwap.add(quality.vs_vwap);
+        self.vs_close.add(quality.vs_close);
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ExecutionQuality {
+    /// One entry per fill, in input order.
+    pub fills: Vec<FillQuality>,
+    pub by_ticker: BTreeMap<String, QualitySummary>,
+    /// Keyed by UTC month, "YYYY-MM".
+    pub by_month: BTreeMap<String, QualitySummary>,
+    pub total: QualitySummary,
+}
+
+/// VWAP over the `bars` bars ending at `end`, from typical prices; the plain
+/// mean of typical prices without volume.
+fn interval_vwap(candles: &Candles, end: usize, bars: usize) -> f64 {
+    let start = (end + 1).saturating_sub(bars);
+    let typical = |i: usize| (candles.high[i] + candles.low[i] + candles.close[i]) / 3.0;
+    let volume: f64 = if candles.has_volume() { candles.volume[start..=end].iter().sum() } else { 0.0 };
+    if volume > 0.0 {
+        (start..=end).map(|i| typical(i) * candles.volume[i]).sum::<f64>() / volume
+    } else {
+        (start..=end).map(typical).sum::<f64>() / (end + 1 - start) as f64
+    }
+}
+
+fn fill_quality(fill: &ExecutionFill, candles: &Candles, vwap_bars: usize) -> Result<FillQuality, String> {
+    if candles.timestamps.len() != candles.len() || candles.is_empty() {
+        return Err(format!("candles for '{}' need timestamps", fill.ticker));
+    }
+    // The bar the fill executed in: the last one opening at or before it.
+    let Some(i) = candles.timestamps.partition_point(|&t| t <= fill.timestamp).checked_sub(1) else {
+        return Err(format!("fill of '{}' at {} is before its first candle", fill.ticker, fill.timestamp));
+    };
+    let arrival_price = fill.arrival_price.unwrap_or(candles.open[i]);
+    let vwap = interval_vwap(candles, i, vwap_bars);
+    let close = candles.close[i];
+    let slippage = |benchmark: f64| Slippage::new(fill.side, fill.quantity, fill.price, benchmark);
+    Ok(FillQuality {
+        arrival_price,
+        vwap,
+        close,
+        vs_arrival: slippage(arrival_price),
+        vs_vwap: slippage(vwap),
+        vs_close: slippage(close),
+    })
+}
+
+/// Compares every fill with its bar's arrival price, VWAP over the
+/// `vwap_bars` bars ending at the fill's bar, and close, and sums the
+/// slippage per ticker, per month and overall.
+pub fn execution_quality(
+    fills: &[ExecutionFill],
+    candles: &HashMap<String, Candles>,
+    vwap_bars: usize,
+) -> Result<ExecutionQuality, String> {
+    if vwap_bars == 0 {
+        return Err("vwap_bars must be positive".to_string());
+    }
+    let mut out = ExecutionQuality::default();
+    for fill in fills {
+        if !(fill.quantity.is_finite() && fill.price.is_finite()) || fill.quantity <= 0.0 || fill.price <= 0.0 {
+            return Err(format!("fill of '{}' at {} needs a positive quantity and price", fill.ticker, fill.timestamp));
+        }
+        let bars = candles.get(&fill.ticker).ok_or_else(|| format!("no candles for '{}'", fill.ticker))?;
+        let quality = fill_quality(fill, bars, vwap_bars)?;
+        out.by_ticker.entry(fill.ticker.clone()).or_default().add(fill, &quality);
+        out.by_month.entry(Date::from_timestamp(fill.timestamp).month_label()).or_default().add(fill, &quality);
+        out.total.add(fill, &quality);
+        out.fills.push(quality);
+    }
+    Ok(out)
+}
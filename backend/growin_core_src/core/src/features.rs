@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+
+use crate::candles::Candles;
+use crate::indicators;
+use crate::stats;
+
+/// A single feature column. All features are NaN during their warm-up so the
+/// ML layer can drop incomplete rows instead of training on padding.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FeatureKind {
+    /// Simple return over `lag` bars.
+    Return { lag: usize },
+    /// Log return over `lag` bars.
+    LogReturn { lag: usize },
+    Rsi { period: usize },
+    MacdHist { fast: usize, slow: usize, signal: usize },
+    /// Rolling std of one-bar returns.
+    Volatility { period: usize },
+    /// Short-window volatility divided by long-window volatility.
+    VolRatio { fast: usize, slow: usize },
+    /// close / SMA - 1.
+    DistSma { period: usize },
+    /// close / EMA - 1.
+    DistEma { period: usize },
+    /// volume / SMA(volume).
+    VolumeRatio { period: usize },
+    /// (high - low) / close.
+    Range,
+    /// open / previous close - 1.
+    Gap,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FeatureSpec {
+    pub kind: FeatureKind,
+    /// Number of bars the feature is lagged by (0 = same bar).
+    pub shift: usize,
+    pub name: String,
+}
+
+impl FeatureKind {
+    /// Default column name, e.g. `rsi_14` or `macd_hist_12_26_9`.
+    pub fn default_name(&self) -> String {
+        match self {
+            FeatureKind::Return { lag } => format!("return_{}", lag),
+            FeatureKind::LogReturn { lag } => format!("log_return_{}", lag),
+            FeatureKind::Rsi { period } => format!("rsi_{}", period),
+            FeatureKind::MacdHist { fast, slow, signal } => format!("macd_hist_{}_{}_{}", fast, slow, signal),
+            FeatureKind::Volatility { period } => format!("volatility_{}", period),
+            FeatureKind::VolRatio { fast, slow } => format!("vol_ratio_{}_{}", fast, slow),
+            FeatureKind::DistSma { period } => format!("dist_sma_{}", period),
+            FeatureKind::DistEma { period } => format!("dist_ema_{}", period),
+            FeatureKind::VolumeRatio { period } => format!("volume_ratio_{}", period),
+            FeatureKind::Range => "range".to_string(),
+            FeatureKind::Gap => "gap".to_string(),
+        }
+    }
+}
+
+/// Shares intermediate series (returns, moving averages, volatilities)
+/// between features so each is computed at most once per call.
+struct FeatureContext<'a> {
+    candles: &'a Candles,
+    one_bar_returns: Option<Vec<f64>>,
+    sma: HashMap<usize, Vec<f64>>,
+    ema: HashMap<usize, Vec<f64>>,
+    volatility: HashMap<usize, Vec<f64>>,
+    /// Delta degrees of freedom of the volatilities.
+    ddof: usize,
+}
+
+impl<'a> FeatureContext<'a> {
+    fn new(candles: &'a Candles, ddof: usize) -> Self {
+        FeatureContext {
+            candles,
+            ddof,
+            one_bar_returns: None,
+            sma: HashMap::new(),
+            ema: HashMap::new(),
+            volatility: HashMap::new(),
+        }
+    }
+
+    fn one_bar_returns(&mut self) -> &[f64] {
+        let close = &self.candles.close;
+        self.one_bar_returns.get_or_insert_with(|| lagged_returns(close, 1, false))
+    }
+
+    fn volatility(&mut self, period: usize) -> Vec<f64> {
+        if let Some(v) = self.volatility.get(&period) {
+            return v.clone();
+        }
+        let ddof = self.ddof;
+        let v = stats::rolling_std_ddof(self.one_bar_returns(), period, ddof);
+        self.volatility.insert(period, v.clone());
+        v
+    }
+
+    fn sma(&mut self, period: usize) -> &[f64] {
+        let close = &self.candles.close;
+        self.sma.entry(period).or_insert_with(|| {
+            let mut v = indicators::sma(close, period);
+            indicators::mask_warmup(&mut v, period.saturating_sub(1));
+            v
+        })
+    }
+
+    fn ema(&mut self, period: usize) -> &[f64] {
+        let close = &self.candles.close;
+        self.ema.entry(period).or_insert_with(|| {
+            let mut v = indicators::ema(close, period);
+            indicators::mask_warmup(&mut v, period.saturating_sub(1));
+            v
+        })
+    }
+
+    fn compute(&mut self, kind: &FeatureKind) -> Result<Vec<f64>, String> {
+        let candles = self.candles;
+        let close = &candles.close;
+        let n = close.len();
+        let values = match *kind {
+            FeatureKind::Return { lag } => lagged_returns(close, lag, false),
+            FeatureKind::LogReturn { lag } => lagged_returns(close, lag, true),
+            FeatureKind::Rsi { period } => {
+                let mut v = indicators::rsi(close, period);
+                indicators::mask_warmup(&mut v, period);
+                v
+            }
+            FeatureKind::MacdHist { fast, slow, signal } => {
+                let (_, _, mut hist) = indicators::macd(close, fast, slow, signal);
+                hist.resize(n, f64::NAN);
+                indicators::mask_warmup(&mut hist, fast.max(slow) + signal - 2);
+                hist
+            }
+            FeatureKind::Volatility { period } => self.volatility(period),
+            FeatureKind::VolRatio { fast, slow } => {
+                let short = self.volatility(fast);
+                let long = self.volatility(slow);
+                short.iter().zip(&long).map(|(s, l)| if *l == 0.0 { f64::NAN } else { s / l }).collect()
+            }
+            FeatureKind::DistSma { period } => distance(close, self.sma(period)),
+            FeatureKind::DistEma { period } => distance(close, self.ema(period)),
+            FeatureKind::VolumeRatio { period } => {
+                if !candles.has_volume() {
+                    return Err("volume_ratio requires a 'volume' column".to_string());
+                }
+                let volume = &candles.volume;
+                let mut avg = indicators::sma(volume, period);
+                indicators::mask_warmup(&mut avg, period.saturating_sub(1));
+                volume.iter().zip(&avg).map(|(v, a)| if *a == 0.0 { f64::NAN } else { v / a }).collect()
+            }
+            FeatureKind::Range => (0..n)
+                .map(|i| if close[i] == 0.0 { f64::NAN } else { (candles.high[i] - candles.low[i]) / close[i] })
+                .collect(),
+            FeatureKind::Gap => (0..n)
+                .map(|i| if i == 0 || close[i - 1] == 0.0 { f64::NAN } else { candles.open[i] / close[i - 1] - 1.0 })
+                .collect(),
+        };
+        Ok(values)
+    }
+}
+
+fn lagged_returns(close: &[f64], lag: usize, log: bool) -> Vec<f64> {
+    let mut out = vec![f64::NAN; close.len()];
+    if lag == 0 {
+        return out;
+    }
+    for i in lag..close.len() {
+        let prev = close[i - lag];
+        if prev != 0.0 {
+            out[i] = if log { (close[i] / prev).ln() } else { close[i] / prev - 1.0 };
+        }
+    }
+    out
+}
+
+fn distance(close: &[f64], average: &[f64]) -> Vec<f64> {
+    close
+        .iter()
+        .zip(average)
+        .map(|(c, a)| if *a == 0.0 { f64::NAN } else { c / a - 1.0 })
+        .collect()
+}
+
+fn shift(values: Vec<f64>, by: usize) -> Vec<f64> {
+    if by == 0 {
+        return values;
+    }
+    let n = values.len();
+    let mut out = vec![f64::NAN; n];
+    if by < n {
+        out[by..].copy_from_slice(&values[..n - by]);
+    }
+    out
+}
+
+/// Computes every feature in `specs` and returns the columns in order;
+/// volatilities use a std over `period - ddof` degrees of freedom.
+pub fn build_features(candles: &Candles, specs: &[FeatureSpec], ddof: usize) -> Result<Vec<Vec<f64>>, String> {
+    let mut ctx = FeatureContext::new(candles, ddof);
+    specs
+        .iter()
+        .map(|spec| ctx.compute(&spec.kind).map(|column| shift(column, spec.shift)))
+        .collect()
+}
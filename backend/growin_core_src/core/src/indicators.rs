@@ -0,0 +1,1614 @@
+use std::collections::VecDeque;
+
+use crate::lanes::{self, lit, Real};
+use crate::moments::RollingMoments;
+
+/// Overwrites the first `valid_from` values with NaN.
+pub fn mask_warmup<T: Real>(values: &mut [T], valid_from: usize) {
+    let end = valid_from.min(values.len());
+    values[..end].iter_mut().for_each(|v| *v = T::nan());
+}
+
+/// Relative Strength Index with Wilder's smoothing.
+/// The first `period` values are 50.0 (neutral warm-up).
+pub fn rsi<T: Real>(prices: &[T], period: usize) -> Vec<T> {
+    let mut out = vec![T::zero(); prices.len()];
+    rsi_into(prices, period, &mut out);
+    out
+}
+
+/// [`rsi`] writing into `out`, which must have the input's length.
+pub fn rsi_into<T: Real>(prices: &[T], period: usize, out: &mut [T]) {
+    let n = prices.len();
+    let neutral = lit::<T>(50.0);
+    out.fill(neutral);
+    if period == 0 || n <= period {
+        return;
+    }
+
+    // Diffs and the gain/loss split are element-wise and vectorize; only the
+    // Wilder recursion below is inherently serial.
+    let (gains, losses) = lanes::gains_losses(&lanes::diff(prices));
+    let p = lit::<T>(period as f64);
+    let (one, hundred) = (T::one(), lit::<T>(100.0));
+
+    // Standard seed: the first average covers the first `period` changes
+    // (indices 1..=period), so RSI(14) needs 15 prices for its first value.
+    let mut avg_gain = lanes::sum(&gains[1..=period]) / p;
+    let mut avg_loss = lanes::sum(&losses[1..=period]) / p;
+
+    for i in period..n {
+        if i > period {
+            // Wilder's smoothing: avg = (prev_avg * (period - 1) + current) / period
+            avg_gain = (avg_gain * (p - one) + gains[i]) / p;
+            avg_loss = (avg_loss * (p - one) + losses[i]) / p;
+        }
+        let rs = if avg_loss == T::zero() { hundred } else { avg_gain / avg_loss };
+        out[i] = hundred - (hundred / (one + rs));
+    }
+}
+
+/// Simple Moving Average, zero-padded until `period - 1`.
+pub fn sma<T: Real>(data: &[T], period: usize) -> Vec<T> {
+    let mut out = vec![T::zero(); data.len()];
+    sma_into(data, period, &mut out);
+    out
+}
+
+/// [`sma`] writing into `out`, which must have the input's length.
+pub fn sma_into<T: Real>(data: &[T], period: usize, sma: &mut [T]) {
+    sma.fill(T::zero());
+    if period == 0 || data.len() < period {
+        return;
+    }
+    let p = lit::<T>(period as f64);
+    let mut sum = lanes::sum(&data[..period]);
+    sma[period - 1] = sum / p;
+    for ((out, &x), &old) in sma[period..].iter_mut().zip(&data[period..]).zip(data) {
+        sum += x;
+        sum -= old;
+        *out = sum / p;
+    }
+}
+
+/// Exponential Moving Average seeded with the SMA of the first `period` values.
+pub fn ema<T: Real>(data: &[T], period: usize) -> Vec<T> {
+    let mut out = vec![T::zero(); data.len()];
+    ema_into(data, period, &mut out);
+    out
+}
+
+/// [`ema`] writing into `out`, which must have the input's length.
+pub fn ema_into<T: Real>(data: &[T], period: usize, ema: &mut [T]) {
+    if period == 0 || data.is_empty() {
+        ema.fill(T::zero());
+        return;
+    }
+    
+    let k = lit::<T>(2.0 / (period as f64 + 1.0));
+    
+    // First value is usually SMA of first 'period' elements, or just the first price if period=1?
+    // Commonly initialized with First Price or SMA.
+    // Let's use SMA of first `period` if enough data, else first price.
+    
+    let start_idx = if data.len() >= period { period - 1 } else { 0 };
+    
+    // Padding with NaNs or 0s until valid? 
+    // Pandas TA typically produces NaNs. We use 0.0 for simplicity in this context or handle in Python.
+    // To match previous SMA behavior (padding 0.0), we pad.
+    
+    ema[..start_idx].fill(T::zero());
+
+    let mut current_ema = if start_idx < data.len() {
+        // Calculate SMA for the first valid point
+        let sum = lanes::sum(&data[0..=start_idx]);
+        sum / lit((start_idx + 1) as f64)
+    } else {
+        data[0]
+    };
+    
+    if start_idx < data.len() {
+        ema[start_idx] = current_ema;
+        
+        for (out, &price) in ema.iter_mut().zip(data).skip(start_idx + 1) {
+            current_ema = (price * k) + (current_ema * (T::one() - k));
+            *out = current_ema;
+        }
+    }
+}
+
+/// EMA zero-padded until `period - 1` and seeded there with the SMA of the
+/// first `period` values; all zeros when `data` is shorter than `period`.
+/// Unlike [`ema`], which falls back to seeding from the first value, it
+/// never emits a value from a partial window.
+pub fn ema_seeded<T: Real>(data: &[T], period: usize) -> Vec<T> {
+    let mut out = vec![T::zero(); data.len()];
+    if period == 0 || data.len() < period {
+        return out;
+    }
+    let k = lit::<T>(2.0 / (period as f64 + 1.0));
+    let mut current = data[..period].iter().fold(T::zero(), |a, &x| a + x) / lit(period as f64);
+    out[period - 1] = current;
+    for (o, &x) in out.iter_mut().zip(data).skip(period) {
+        current = (x * k) + (current * (T::one() - k));
+        *o = current;
+    }
+    out
+}
+
+/// MACD line, signal line and histogram.
+pub fn macd<T: Real>(data: &[T], fast: usize, slow: usize, signal: usize) -> (Vec<T>, Vec<T>, Vec<T>) {
+    let n = data.len();
+    let (mut macd_line, mut signal_line, mut histogram) = (vec![T::zero(); n], vec![T::zero(); n], vec![T::zero(); n]);
+    macd_into(data, fast, slow, signal, &mut macd_line, &mut signal_line, &mut histogram);
+    (macd_line, signal_line, histogram)
+}
+
+/// [`macd`] writing into the three outputs, which must have the input's
+/// length. The intermediate EMAs are still allocated.
+pub fn macd_into<T: Real>(
+    data: &[T],
+    fast: usize,
+    slow: usize,
+    signal: usize,
+    macd_line: &mut [T],
+    signal_line: &mut [T],
+    histogram: &mut [T],
+) {
+    let ema_fast = ema_seeded(data, fast);
+    let ema_slow = ema_seeded(data, slow);
+    
+    for ((m, &f), &s) in macd_line.iter_mut().zip(&ema_fast).zip(&ema_slow) {
+        // Only valid if both are non-zero? Or simple subtraction
+        *m = f - s;
+    }
+    
+    // Signal line is EMA of MACD line
+    // BUT we need to ignore the initial zeros in calculation/padding
+    // Doing a "naive" EMA on the whole macd_line including leading zeros might skew it near start.
+    // However, for this SOTA implementation, let's keep it consistent.
+    let signal_ema = ema_seeded(macd_line, signal);
+    for (((h, sig), &m), &s) in histogram.iter_mut().zip(signal_line.iter_mut()).zip(macd_line.iter()).zip(&signal_ema) {
+        *sig = s;
+        *h = m - s;
+    }
+}
+
+/// Bollinger Bands as (upper, middle, lower); `middle` is the SMA.
+pub fn bbands<T: Real>(data: &[T], period: usize, std_dev: f64) -> (Vec<T>, Vec<T>, Vec<T>) {
+    bbands_ddof(data, period, std_dev, 0)
+}
+
+/// [`bbands`] over `period - ddof` degrees of freedom; see [`bbands_ddof_into`].
+pub fn bbands_ddof<T: Real>(data: &[T], period: usize, std_dev: f64, ddof: usize) -> (Vec<T>, Vec<T>, Vec<T>) {
+    let n = data.len();
+    let mut upper = vec![T::zero(); n];
+    let mut middle = vec![T::zero(); n]; // This is SMA
+    let mut lower = vec![T::zero(); n];
+    bbands_ddof_into(data, period, std_dev, ddof, &mut upper, &mut middle, &mut lower);
+    (upper, middle, lower)
+}
+
+/// [`bbands`] writing into the three outputs, which must have the input's
+/// length. Single pass over running moments; windows containing a NaN or ±Inf give
+/// NaN bands.
+pub fn bbands_into<T: Real>(data: &[T], period: usize, std_dev: f64, upper: &mut [T], middle: &mut [T], lower: &mut [T]) {
+    bbands_ddof_into(data, period, std_dev, 0, upper, middle, lower);
+}
+
+/// [`bbands_into`] with the standard deviation taken over `period - ddof`
+/// degrees of freedom; `ddof = 1` matches pandas' sample std.
+pub fn bbands_ddof_into<T: Real>(
+    data: &[T],
+    period: usize,
+    std_dev: f64,
+    ddof: usize,
+    upper: &mut [T],
+    middle: &mut [T],
+    lower: &mut [T],
+) {
+    for band in [&mut *upper, &mut *middle, &mut *lower] {
+        band.fill(T::zero());
+    }
+    if period == 0 {
+        return;
+    }
+
+    let std_dev = lit::<T>(std_dev);
+    let mut moments = RollingMoments::with_ddof(period, ddof);
+    for (i, &x) in data.iter().enumerate() {
+        let (mean, std) = match moments.push(x) {
+            Some((mean, variance)) => (mean, variance.sqrt()),
+            None if moments.is_full() => (T::nan(), T::nan()),
+            None => continue,
+        };
+        middle[i] = mean;
+        upper[i] = mean + (std_dev * std);
+        lower[i] = mean - (std_dev * std);
+    }
+}
+
+/// True range per bar. The first bar has no previous close, so it is high - low.
+pub fn true_range<T: Real>(high: &[T], low: &[T], close: &[T]) -> Vec<T> {
+    let n = close.len().min(high.len()).min(low.len());
+    let mut tr = Vec::with_capacity(n);
+    for i in 0..n {
+        let hl = high[i] - low[i];
+        if i == 0 {
+            tr.push(hl);
+        } else {
+            let prev_close = close[i - 1];
+            tr.push(hl.max((high[i] - prev_close).abs()).max((low[i] - prev_close).abs()));
+        }
+    }
+    tr
+}
+
+/// Average True Range with Wilder's smoothing, seeded with the SMA of the first
+/// `period` true ranges and zero-padded until `period - 1`.
+pub fn atr<T: Real>(high: &[T], low: &[T], close: &[T], period: usize) -> Vec<T> {
+    let tr = true_range(high, low, close);
+    let n = tr.len();
+    let mut out = vec![T::zero(); n];
+    if period == 0 || n < period {
+        return out;
+    }
+    let p = lit::<T>(period as f64);
+    let mut current = lanes::sum(&tr[..period]) / p;
+    out[period - 1] = current;
+    for i in period..n {
+        current = (current * (p - T::one()) + tr[i]) / p;
+        out[i] = current;
+    }
+    out
+}
+
+/// Highest value over the trailing `period` bars (inclusive), NaN until the
+/// first full window. NaNs are skipped; an all-NaN window gives NaN.
+pub fn rolling_max<T: Real>(data: &[T], period: usize) -> Vec<T> {
+    rolling_extreme(data, period, |kept, new| kept > new)
+}
+
+/// Lowest value over the trailing `period` bars (inclusive), NaN until the
+/// first full window. NaNs are skipped; an all-NaN window gives NaN.
+pub fn rolling_min<T: Real>(data: &[T], period: usize) -> Vec<T> {
+    rolling_extreme(data, period, |kept, new| kept < new)
+}
+
+fn rolling_extreme<T: Real>(data: &[T], period: usize, beats: fn(T, T) -> bool) -> Vec<T> {
+    rolling_extreme_index(data, period, beats)
+        .into_iter()
+        .map(|j| j.map_or(T::nan(), |j| data[j]))
+        .collect()
+}
+
+/// Index of the sliding-window extreme in O(n) for any `period`, using a
+/// monotonic deque; None until the first full window and for all-NaN ones.
+/// Ties go to the most recent value.
+///
+/// The deque holds the indices of the window's remaining candidates, each
+/// value strictly beating the one behind it, so the front is the window's
+/// extreme. A new value evicts every candidate from the back that does not
+/// beat it, and the front leaves once it falls out of the window. Each index
+/// is pushed and popped at most once.
+fn rolling_extreme_index<T: Real>(data: &[T], period: usize, beats: fn(T, T) -> bool) -> Vec<Option<usize>> {
+    let mut out = vec![None; data.len()];
+    if period == 0 {
+        return out;
+    }
+    let mut candidates: VecDeque<usize> = VecDeque::with_capacity(period.min(data.len()));
+    for (i, &x) in data.iter().enumerate() {
+        if candidates.front().is_some_and(|&j| j + period <= i) {
+            candidates.pop_front();
+        }
+        if !x.is_nan() {
+            while candidates.back().is_some_and(|&j| !beats(data[j], x)) {
+                candidates.pop_back();
+            }
+            candidates.push_back(i);
+        }
+        if i + 1 >= period {
+            out[i] = candidates.front().copied();
+        }
+    }
+    out
+}
+
+/// Average Directional Index with +DI/-DI (Wilder). Returns (adx, plus_di, minus_di).
+/// The DI lines are zero-padded until `period` and ADX until `2 * period - 1`.
+pub fn adx<T: Real>(high: &[T], low: &[T], close: &[T], period: usize) -> (Vec<T>, Vec<T>, Vec<T>) {
+    let n = close.len().min(high.len()).min(low.len());
+    let zero = T::zero();
+    let mut adx = vec![zero; n];
+    let mut plus_di = vec![zero; n];
+    let mut minus_di = vec![zero; n];
+    if period == 0 || n <= period {
+        return (adx, plus_di, minus_di);
+    }
+
+    let tr = true_range(high, low, close);
+    let (p, hundred) = (lit::<T>(period as f64), lit::<T>(100.0));
+    let (mut s_tr, mut s_plus, mut s_minus) = (zero, zero, zero);
+    let mut dx_sum = zero;
+    let mut current_adx = zero;
+
+    for i in 1..n {
+        let up = high[i] - high[i - 1];
+        let down = low[i - 1] - low[i];
+        let plus_dm = if up > down && up > zero { up } else { zero };
+        let minus_dm = if down > up && down > zero { down } else { zero };
+
+        if i <= period {
+            // Seed the Wilder sums with the first `period` movements.
+            s_tr += tr[i];
+            s_plus += plus_dm;
+            s_minus += minus_dm;
+            if i < period {
+                continue;
+            }
+        } else {
+            s_tr = s_tr - s_tr / p + tr[i];
+            s_plus = s_plus - s_plus / p + plus_dm;
+            s_minus = s_minus - s_minus / p + minus_dm;
+        }
+
+        let (pdi, mdi) = if s_tr == zero { (zero, zero) } else { (hundred * s_plus / s_tr, hundred * s_minus / s_tr) };
+        plus_di[i] = pdi;
+        minus_di[i] = mdi;
+        let dx = if pdi + mdi == zero { zero } else { hundred * (pdi - mdi).abs() / (pdi + mdi) };
+
+        if i < 2 * period - 1 {
+            dx_sum += dx;
+        } else if i == 2 * period - 1 {
+            current_adx = (dx_sum + dx) / p;
+            adx[i] = current_adx;
+        } else {
+            current_adx = (current_adx * (p - T::one()) + dx) / p;
+            adx[i] = current_adx;
+        }
+    }
+    (adx, plus_di, minus_di)
+}
+
+/// Simple moving average of `data[start..]` written to `out[start..]`, for
+/// smoothing a series that only becomes valid at `start`; earlier values
+/// and the new warm-up are left as they are.
+fn sma_from<T: Real>(data: &[T], start: usize, period: usize, out: &mut [T]) {
+    if period == 0 || start >= data.len() {
+        return;
+    }
+    let smoothed = sma(&data[start..], period);
+    for (o, &v) in out[start..].iter_mut().zip(&smoothed).skip(period - 1) {
+        *o = v;
+    }
+}
+
+/// Where each close sits in its `period`-bar high-low range, 0-100 (50 when
+/// the range is flat); zero-padded until `period - 1`, which must be
+/// within the data.
+fn raw_stochastic<T: Real>(high: &[T], low: &[T], close: &[T], period: usize) -> Vec<T> {
+    let (highest, lowest) = (rolling_max(high, period), rolling_min(low, period));
+    let (fifty, hundred) = (lit::<T>(50.0), lit::<T>(100.0));
+    let mut raw = vec![T::zero(); close.len()];
+    for i in period - 1..close.len() {
+        let range = highest[i] - lowest[i];
+        raw[i] = if range == T::zero() { fifty } else { hundred * (close[i] - lowest[i]) / range };
+    }
+    raw
+}
+
+/// Stochastic Oscillator as (%K, %D).
+///
+/// Raw %K is where the close sits in the `k_period` high-low range (0-100,
+/// 50 when the range is flat); %K is its `smooth_k`-bar SMA (1 for the fast
+/// stochastic) and %D the `d_period`-bar SMA of %K. %K is zero-padded until
+/// `k_period + smooth_k - 2` and %D for `d_period - 1` bars more.
+pub fn stochastic<T: Real>(
+    high: &[T],
+    low: &[T],
+    close: &[T],
+    k_period: usize,
+    d_period: usize,
+    smooth_k: usize,
+) -> (Vec<T>, Vec<T>) {
+    let n = close.len().min(high.len()).min(low.len());
+    let (mut k, mut d) = (vec![T::zero(); n], vec![T::zero(); n]);
+    if k_period == 0 || d_period == 0 || smooth_k == 0 || n < k_period {
+        return (k, d);
+    }
+    let raw = raw_stochastic(&high[..n], &low[..n], &close[..n], k_period);
+    sma_from(&raw, k_period - 1, smooth_k, &mut k);
+    sma_from(&k.clone(), k_period + smooth_k - 2, d_period, &mut d);
+    (k, d)
+}
+
+/// On-Balance Volume: running total of volume, added on up closes and
+/// subtracted on down closes, starting from 0 on the first bar.
+pub fn obv<T: Real>(close: &[T], volume: &[T]) -> Vec<T> {
+    let n = close.len().min(volume.len());
+    let mut out = vec![T::zero(); n];
+    let mut total = T::zero();
+    for i in 1..n {
+        if close[i] > close[i - 1] {
+            total += volume[i];
+        } else if close[i] < close[i - 1] {
+            total -= volume[i];
+        }
+        out[i] = total;
+    }
+    out
+}
+
+/// Typical price `(high + low + close) / 3` per bar.
+pub fn typical_price<T: Real>(high: &[T], low: &[T], close: &[T]) -> Vec<T> {
+    let three = lit::<T>(3.0);
+    high.iter().zip(low).zip(close).map(|((&h, &l), &c)| (h + l + c) / three).collect()
+}
+
+/// Volume-weighted average of the typical price since the start of each
+/// session. `sessions` labels each bar's session (e.g. its trading day) and
+/// the average restarts whenever the label changes; without it the whole
+/// series is one session. Before any volume trades it is the typical price.
+pub fn vwap<T: Real>(high: &[T], low: &[T], close: &[T], volume: &[T], sessions: Option<&[i64]>) -> Vec<T> {
+    let typical = typical_price(high, low, close);
+    let n = typical.len().min(volume.len());
+    let mut out = vec![T::zero(); n];
+    let (mut value, mut traded) = (T::zero(), T::zero());
+    for i in 0..n {
+        if i > 0 && sessions.is_some_and(|s| s[i] != s[i - 1]) {
+            (value, traded) = (T::zero(), T::zero());
+        }
+        value += typical[i] * volume[i];
+        traded += volume[i];
+        out[i] = if traded == T::zero() { typical[i] } else { value / traded };
+    }
+    out
+}
+
+/// VWAP of the typical price over the trailing `window` bars, zero-padded
+/// until `window - 1`; the typical price where the window traded nothing.
+pub fn rolling_vwap<T: Real>(high: &[T], low: &[T], close: &[T], volume: &[T], window: usize) -> Vec<T> {
+    vwma(&typical_price(high, low, close), volume, window)
+}
+
+/// Commodity Channel Index: the typical price's distance from its
+/// `period`-bar SMA in units of `constant` (0.015 by convention) times the
+/// mean absolute deviation, zero-padded until `period - 1`. A window with
+/// no deviation scores 0.
+pub fn cci<T: Real>(high: &[T], low: &[T], close: &[T], period: usize, constant: f64) -> Vec<T> {
+    let typical = typical_price(high, low, close);
+    let n = typical.len();
+    let mut out = vec![T::zero(); n];
+    if period == 0 || n < period {
+        return out;
+    }
+    let mean = sma(&typical, period);
+    let (p, constant) = (lit::<T>(period as f64), lit::<T>(constant));
+    for i in period - 1..n {
+        let window = &typical[i + 1 - period..=i];
+        let deviation = window.iter().fold(T::zero(), |acc, &x| acc + (x - mean[i]).abs()) / p;
+        out[i] = if deviation == T::zero() { T::zero() } else { (typical[i] - mean[i]) / (constant * deviation) };
+    }
+    out
+}
+
+/// Williams %R: where the close sits below the `period`-bar high, from 0
+/// (at the high) to -100 (at the low); -50 while the range is flat.
+/// Zero-padded until `period - 1`.
+pub fn williams_r<T: Real>(high: &[T], low: &[T], close: &[T], period: usize) -> Vec<T> {
+    let n = close.len().min(high.len()).min(low.len());
+    let mut out = vec![T::zero(); n];
+    if period == 0 || n < period {
+        return out;
+    }
+    let (highest, lowest) = (rolling_max(&high[..n], period), rolling_min(&low[..n], period));
+    let hundred = lit::<T>(100.0);
+    for i in period - 1..n {
+        let range = highest[i] - lowest[i];
+        out[i] = if range == T::zero() { -hundred / lit(2.0) } else { -hundred * (highest[i] - close[i]) / range };
+    }
+    out
+}
+
+/// Money Flow Index: RSI's gain/loss ratio applied to money flow (typical
+/// price times volume), summed over the last `period` bars as is standard
+/// for MFI rather than Wilder-smoothed. A bar's flow counts as positive when
+/// its typical price rose and negative when it fell. Values are 0-100, 100
+/// without negative flow and 50 without any; the first `period` are 0, as
+/// the first bar has no change.
+pub fn mfi<T: Real>(high: &[T], low: &[T], close: &[T], volume: &[T], period: usize) -> Vec<T> {
+    let typical = typical_price(high, low, close);
+    let n = typical.len().min(volume.len());
+    let mut out = vec![T::zero(); n];
+    if period == 0 || n <= period {
+        return out;
+    }
+    let (mut positive, mut negative) = (vec![T::zero(); n], vec![T::zero(); n]);
+    for i in 1..n {
+        let flow = typical[i] * volume[i];
+        if typical[i] > typical[i - 1] {
+            positive[i] = flow;
+        } else if typical[i] < typical[i - 1] {
+            negative[i] = flow;
+        }
+    }
+    let hundred = lit::<T>(100.0);
+    let (mut pos, mut neg) = (lanes::sum(&positive[1..period]), lanes::sum(&negative[1..period]));
+    for i in period..n {
+        pos += positive[i];
+        neg += negative[i];
+        if i > period {
+            pos -= positive[i - period];
+            neg -= negative[i - period];
+        }
+        out[i] = if neg == T::zero() {
+            if pos == T::zero() { hundred / lit(2.0) } else { hundred }
+        } else {
+            hundred - hundred / (T::one() + pos / neg)
+        };
+    }
+    out
+}
+
+/// SuperTrend as (line, direction).
+///
+/// Bands sit `multiplier` x ATR(`period`) above and below the bar midpoint
+/// `(high + low) / 2`. Each band only moves towards price (the lower band
+/// never falls, the upper never rises) until the close breaks through it.
+/// As in the original SuperTrend script on TradingView (KivancOzbilgic's),
+/// the trend turns down (-1) when the close falls below the previous bar's
+/// final lower band and up (+1) when it rises above the previous final upper
+/// band; the line is the lower band in an uptrend and the upper band in a
+/// downtrend. Pine's built-in `ta.supertrend` compares the close with the
+/// current bar's final bands instead, so the two disagree on bars where the
+/// band ratchets past the close. Both outputs are zero-padded until
+/// `period - 1`, where the trend starts up.
+pub fn supertrend<T: Real>(high: &[T], low: &[T], close: &[T], period: usize, multiplier: f64) -> (Vec<T>, Vec<T>) {
+    let n = close.len().min(high.len()).min(low.len());
+    let (mut line, mut direction) = (vec![T::zero(); n], vec![T::zero(); n]);
+    if period == 0 || n < period {
+        return (line, direction);
+    }
+    let atr = atr(&high[..n], &low[..n], &close[..n], period);
+    let (m, two) = (lit::<T>(multiplier), lit::<T>(2.0));
+    let start = period - 1;
+    let (mut upper, mut lower) = (T::zero(), T::zero());
+    let mut up = true;
+    for i in start..n {
+        let mid = (high[i] + low[i]) / two;
+        let (basic_upper, basic_lower) = (mid + m * atr[i], mid - m * atr[i]);
+        if i == start {
+            (upper, lower) = (basic_upper, basic_lower);
+        } else {
+            let (prev_close, prev_upper, prev_lower) = (close[i - 1], upper, lower);
+            if basic_upper < upper || prev_close >= upper {
+                upper = basic_upper;
+            }
+            if basic_lower > lower || prev_close <= lower {
+                lower = basic_lower;
+            }
+            if up && close[i] < prev_lower {
+                up = false;
+            } else if !up && close[i] > prev_upper {
+                up = true;
+            }
+        }
+        line[i] = if up { lower } else { upper };
+        direction[i] = if up { T::one() } else { -T::one() };
+    }
+    (line, direction)
+}
+
+/// Rate of Change: percent change from `period` bars earlier, zero-padded
+/// for the first `period` values; NaN where the earlier value is 0.
+pub fn roc<T: Real>(data: &[T], period: usize) -> Vec<T> {
+    let mut out = vec![T::zero(); data.len()];
+    let hundred = lit::<T>(100.0);
+    for i in period.max(1)..data.len() {
+        let base = data[i - period];
+        out[i] = if base == T::zero() { T::nan() } else { hundred * (data[i] / base - T::one()) };
+    }
+    out
+}
+
+/// Momentum: change from `period` bars earlier, zero-padded for the first
+/// `period` values.
+pub fn momentum<T: Real>(data: &[T], period: usize) -> Vec<T> {
+    let mut out = vec![T::zero(); data.len()];
+    for i in period.max(1)..data.len() {
+        out[i] = data[i] - data[i - period];
+    }
+    out
+}
+
+/// [`ema_seeded`] of `data[start..]` written to `out[start..]`; see
+/// [`sma_from`].
+fn ema_from<T: Real>(data: &[T], start: usize, period: usize, out: &mut [T]) {
+    if period == 0 || start >= data.len() {
+        return;
+    }
+    let smoothed = ema_seeded(&data[start..], period);
+    for (o, &v) in out[start..].iter_mut().zip(&smoothed).skip(period - 1) {
+        *o = v;
+    }
+}
+
+/// TRIX as (trix, signal): the one-bar percent change of a triple-smoothed
+/// `period` EMA, and its `signal`-bar EMA. Each EMA is seeded once the one
+/// before it is, so TRIX is zero-padded until `3 * (period - 1) + 1` and the
+/// signal for `signal - 1` bars more.
+pub fn trix<T: Real>(data: &[T], period: usize, signal: usize) -> (Vec<T>, Vec<T>) {
+    let n = data.len();
+    let (mut trix, mut signal_line) = (vec![T::zero(); n], vec![T::zero(); n]);
+    if period == 0 || signal == 0 {
+        return (trix, signal_line);
+    }
+    let lag = period - 1;
+    let triple = ema_chain(data, period, 3).pop().unwrap();
+
+    let hundred = lit::<T>(100.0);
+    for i in 3 * lag + 1..n {
+        let prev = triple[i - 1];
+        trix[i] = if prev == T::zero() { T::nan() } else { hundred * (triple[i] / prev - T::one()) };
+    }
+    ema_from(&trix, 3 * lag + 1, signal, &mut signal_line);
+    (trix, signal_line)
+}
+
+/// Aroon as (up, down, oscillator). Up is `100 * (period - d) / period`
+/// where `d` is the bars since the highest high of the last `period + 1`
+/// bars (the latest on ties), down the same for the lowest low, and the
+/// oscillator up minus down. Zero-padded for the first `period` values.
+pub fn aroon<T: Real>(high: &[T], low: &[T], period: usize) -> (Vec<T>, Vec<T>, Vec<T>) {
+    let n = high.len().min(low.len());
+    let (mut up, mut down, mut oscillator) = (vec![T::zero(); n], vec![T::zero(); n], vec![T::zero(); n]);
+    if period == 0 {
+        return (up, down, oscillator);
+    }
+    let highest = rolling_extreme_index(&high[..n], period + 1, |kept, new| kept > new);
+    let lowest = rolling_extreme_index(&low[..n], period + 1, |kept, new| kept < new);
+    let p = period as f64;
+    let score = |i: usize, j: Option<usize>| j.map_or(T::nan(), |j| lit::<T>(100.0 * (p - (i - j) as f64) / p));
+    for i in period..n {
+        up[i] = score(i, highest[i]);
+        down[i] = score(i, lowest[i]);
+        oscillator[i] = up[i] - down[i];
+    }
+    (up, down, oscillator)
+}
+
+/// Money flow volume per bar: volume times the close's position in the
+/// bar's range, from -1 (at the low) to 1 (at the high); 0 for a flat bar.
+fn money_flow_volume<T: Real>(high: &[T], low: &[T], close: &[T], volume: &[T]) -> Vec<T> {
+    let n = close.len().min(high.len()).min(low.len()).min(volume.len());
+    (0..n)
+        .map(|i| {
+            let range = high[i] - low[i];
+            if range == T::zero() {
+                T::zero()
+            } else {
+                ((close[i] - low[i]) - (high[i] - close[i])) / range * volume[i]
+            }
+        })
+        .collect()
+}
+
+/// Accumulation/Distribution line: the running total of money flow volume.
+pub fn ad_line<T: Real>(high: &[T], low: &[T], close: &[T], volume: &[T]) -> Vec<T> {
+    let mut total = T::zero();
+    money_flow_volume(high, low, close, volume)
+        .into_iter()
+        .map(|flow| {
+            total += flow;
+            total
+        })
+        .collect()
+}
+
+/// Chaikin Money Flow: money flow volume over volume across the last
+/// `period` bars, in [-1, 1]; 0 where nothing traded. Zero-padded until
+/// `period - 1`.
+pub fn cmf<T: Real>(high: &[T], low: &[T], close: &[T], volume: &[T], period: usize) -> Vec<T> {
+    let flow = money_flow_volume(high, low, close, volume);
+    let n = flow.len();
+    let mut out = vec![T::zero(); n];
+    if period == 0 || n < period {
+        return out;
+    }
+    let (mut flow_sum, mut volume_sum) = (T::zero(), T::zero());
+    for i in 0..n {
+        flow_sum += flow[i];
+        volume_sum += volume[i];
+        if i >= period {
+            flow_sum -= flow[i - period];
+            volume_sum -= volume[i - period];
+        }
+        if i + 1 >= period {
+            out[i] = if volume_sum == T::zero() { T::zero() } else { flow_sum / volume_sum };
+        }
+    }
+    out
+}
+
+/// Chaikin Oscillator: the `fast` EMA minus the `slow` EMA of the
+/// Accumulation/Distribution line, zero-padded until
+/// `max(fast, slow) - 1`.
+pub fn chaikin_oscillator<T: Real>(high: &[T], low: &[T], close: &[T], volume: &[T], fast: usize, slow: usize) -> Vec<T> {
+    let ad = ad_line(high, low, close, volume);
+    let (fast_ema, slow_ema) = (ema_seeded(&ad, fast), ema_seeded(&ad, slow));
+    let seeded = fast.max(slow).saturating_sub(1);
+    let mut out = vec![T::zero(); ad.len()];
+    for i in seeded..ad.len() {
+        out[i] = fast_ema[i] - slow_ema[i];
+    }
+    out
+}
+
+/// The `depth` successive seeded EMAs of `data` (EMA, EMA of EMA, ...),
+/// each seeded once the one before it is valid: level `k` (from 0) is
+/// zero-padded until `(k + 1) * (period - 1)`.
+fn ema_chain<T: Real>(data: &[T], period: usize, depth: usize) -> Vec<Vec<T>> {
+    let mut levels = vec![ema_seeded(data, period)];
+    for k in 1..depth {
+        let mut next = vec![T::zero(); data.len()];
+        ema_from(&levels[k - 1], k * (period - 1), period, &mut next);
+        levels.push(next);
+    }
+    levels
+}
+
+/// Double EMA, `2 * EMA - EMA(EMA)`, zero-padded until `2 * (period - 1)`.
+pub fn dema<T: Real>(data: &[T], period: usize) -> Vec<T> {
+    let mut out = vec![T::zero(); data.len()];
+    if period == 0 {
+        return out;
+    }
+    let levels = ema_chain(data, period, 2);
+    let two = lit::<T>(2.0);
+    for i in 2 * (period - 1)..data.len() {
+        out[i] = two * levels[0][i] - levels[1][i];
+    }
+    out
+}
+
+/// Triple EMA, `3 * EMA - 3 * EMA(EMA) + EMA(EMA(EMA))`, zero-padded until
+/// `3 * (period - 1)`.
+pub fn tema<T: Real>(data: &[T], period: usize) -> Vec<T> {
+    let mut out = vec![T::zero(); data.len()];
+    if period == 0 {
+        return out;
+    }
+    let levels = ema_chain(data, period, 3);
+    let three = lit::<T>(3.0);
+    for i in 3 * (period - 1)..data.len() {
+        out[i] = three * (levels[0][i] - levels[1][i]) + levels[2][i];
+    }
+    out
+}
+
+/// Weighted Moving Average with linear weights 1..=`period` (newest
+/// heaviest), zero-padded until `period - 1`. O(n) via running sums.
+pub fn wma<T: Real>(data: &[T], period: usize) -> Vec<T> {
+    let n = data.len();
+    let mut out = vec![T::zero(); n];
+    if period == 0 || n < period {
+        return out;
+    }
+    let p = lit::<T>(period as f64);
+    let divisor = lit::<T>((period * (period + 1)) as f64 / 2.0);
+    let (mut total, mut weighted) = (T::zero(), T::zero());
+    for (i, &x) in data.iter().enumerate() {
+        if i < period {
+            // Seed: the i-th value weighs i + 1.
+            weighted += lit::<T>((i + 1) as f64) * x;
+            total += x;
+        } else {
+            // Every older value loses one weight step, the oldest drops out.
+            weighted += p * x - total;
+            total += x - data[i - period];
+        }
+        if i + 1 >= period {
+            out[i] = weighted / divisor;
+        }
+    }
+    out
+}
+
+/// Volume-Weighted Moving Average of `data` over the trailing `period`
+/// bars, zero-padded until `period - 1`; the plain value where the window
+/// traded nothing.
+pub fn vwma<T: Real>(data: &[T], volume: &[T], period: usize) -> Vec<T> {
+    let n = data.len().min(volume.len());
+    let mut out = vec![T::zero(); n];
+    if period == 0 {
+        return out;
+    }
+    let (mut value, mut traded) = (T::zero(), T::zero());
+    for i in 0..n {
+        value += data[i] * volume[i];
+        traded += volume[i];
+        if i >= period {
+            value -= data[i - period] * volume[i - period];
+            traded -= volume[i - period];
+        }
+        if i + 1 >= period {
+            out[i] = if traded == T::zero() { data[i] } else { value / traded };
+        }
+    }
+    out
+}
+
+/// Zero-Lag EMA: the seeded EMA of `2 * x[i] - x[i - lag]`, with `lag =
+/// (period - 1) / 2`, which cancels most of the EMA's lag. Zero-padded
+/// until `lag + period - 1`.
+pub fn zlema<T: Real>(data: &[T], period: usize) -> Vec<T> {
+    let n = data.len();
+    let mut out = vec![T::zero(); n];
+    if period == 0 {
+        return out;
+    }
+    let lag = (period - 1) / 2;
+    let two = lit::<T>(2.0);
+    let mut delagged = vec![T::zero(); n];
+    for i in lag..n {
+        delagged[i] = two * data[i] - data[i - lag];
+    }
+    ema_from(&delagged, lag, period, &mut out);
+    out
+}
+
+/// How pivot levels are spaced around the pivot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PivotMethod {
+    /// Floor-trader pivots from the pivot and the range.
+    Classic,
+    /// The range's 38.2%, 61.8% and 100% either side of the pivot.
+    Fibonacci,
+    /// 1.1/12, 1.1/6 and 1.1/4 of the range either side of the close.
+    Camarilla,
+}
+
+impl std::str::FromStr for PivotMethod {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "classic" | "standard" | "floor" => Ok(PivotMethod::Classic),
+            "fibonacci" | "fib" => Ok(PivotMethod::Fibonacci),
+            "camarilla" => Ok(PivotMethod::Camarilla),
+            other => Err(format!("Unknown pivot method '{}', expected 'classic', 'fibonacci' or 'camarilla'", other)),
+        }
+    }
+}
+
+impl PivotMethod {
+    pub fn name(self) -> &'static str {
+        match self {
+            PivotMethod::Classic => "classic",
+            PivotMethod::Fibonacci => "fibonacci",
+            PivotMethod::Camarilla => "camarilla",
+        }
+    }
+}
+
+/// Pivot levels from each period's high, low and close, for trading the
+/// period after it: `[pp, r1, r2, r3, s1, s2, s3]`. The pivot is the
+/// typical price for every method.
+pub fn pivots<T: Real>(high: &[T], low: &[T], close: &[T], method: PivotMethod) -> [Vec<T>; 7] {
+    let n = close.len().min(high.len()).min(low.len());
+    let mut levels: [Vec<T>; 7] = std::array::from_fn(|_| Vec::with_capacity(n));
+    let typical = typical_price(high, low, close);
+    let two = lit::<T>(2.0);
+    for i in 0..n {
+        let (h, l, c, pp) = (high[i], low[i], close[i], typical[i]);
+        let range = h - l;
+        let (resistance, support) = match method {
+            PivotMethod::Classic => (
+                [two * pp - l, pp + range, h + two * (pp - l)],
+                [two * pp - h, pp - range, l - two * (h - pp)],
+            ),
+            PivotMethod::Fibonacci => {
+                let steps = [lit::<T>(0.382) * range, lit::<T>(0.618) * range, range];
+                (steps.map(|s| pp + s), steps.map(|s| pp - s))
+            }
+            PivotMethod::Camarilla => {
+                let steps = [lit::<T>(1.1 / 12.0) * range, lit::<T>(1.1 / 6.0) * range, lit::<T>(1.1 / 4.0) * range];
+                (steps.map(|s| c + s), steps.map(|s| c - s))
+            }
+        };
+        levels[0].push(pp);
+        for k in 0..3 {
+            levels[1 + k].push(resistance[k]);
+            levels[4 + k].push(support[k]);
+        }
+    }
+    levels
+}
+
+/// Standard Fibonacci retracement ratios.
+pub const FIB_RETRACEMENTS: [f64; 5] = [0.236, 0.382, 0.5, 0.618, 0.786];
+/// Standard Fibonacci extension ratios.
+pub const FIB_EXTENSIONS: [f64; 4] = [1.272, 1.618, 2.0, 2.618];
+
+/// A swing high and low, from arguments or detected by [`last_swing`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Swing {
+    pub high: f64,
+    pub low: f64,
+    /// Whether the move ran from the low up to the high.
+    pub up: bool,
+}
+
+/// Fibonacci levels of a swing as (ratio, price) pairs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FibLevels {
+    /// Pullback levels inside the swing, measured back from its end.
+    pub retracements: Vec<(f64, f64)>,
+    /// Targets beyond the swing's end, measured from its start.
+    pub extensions: Vec<(f64, f64)>,
+}
+
+/// Fibonacci retracement and extension levels of `swing`.
+pub fn fibonacci_levels(swing: Swing) -> FibLevels {
+    let range = swing.high - swing.low;
+    let level = |r: f64, from_high: bool| if from_high { swing.high - r * range } else { swing.low + r * range };
+    FibLevels {
+        retracements: FIB_RETRACEMENTS.iter().map(|&r| (r, level(r, swing.up))).collect(),
+        extensions: FIB_EXTENSIONS.iter().map(|&r| (r, level(r, !swing.up))).collect(),
+    }
+}
+
+/// Index of the last confirmed swing high and swing low: a bar whose high
+/// (low) is the highest (lowest) of the `lookback` bars either side of it.
+/// A swing needs `lookback` bars after it, so the last ones never qualify.
+pub fn last_swing_points(high: &[f64], low: &[f64], lookback: usize) -> (Option<usize>, Option<usize>) {
+    let n = high.len().min(low.len());
+    let window = 2 * lookback + 1;
+    let (highest, lowest) = (rolling_max(&high[..n], window), rolling_min(&low[..n], window));
+    // The window ending at `i + lookback` is centred on `i`.
+    let last = |values: &[f64], extreme: &[f64]| {
+        (lookback..n.saturating_sub(lookback)).rev().find(|&i| !values[i].is_nan() && values[i] == extreme[i + lookback])
+    };
+    (last(&high[..n], &highest), last(&low[..n], &lowest))
+}
+
+/// Rolling least-squares line over the last `period` values, as
+/// `[slope, intercept, midline, upper, lower]`.
+///
+/// Each window is fitted against bar offsets 0..`period`: `slope` is the
+/// change per bar, `intercept` the fitted value at the window's oldest bar
+/// and `midline` at its newest. The channel is `midline ± k` standard
+/// errors of the residuals (over `period - 2` degrees of freedom). All are
+/// zero-padded until `period - 1`; `period` must be at least 3. Windows are
+/// fitted from centred values, which keeps float32 input accurate.
+pub fn linreg<T: Real>(data: &[T], period: usize, k: f64) -> [Vec<T>; 5] {
+    let n = data.len();
+    let mut out: [Vec<T>; 5] = std::array::from_fn(|_| vec![T::zero(); n]);
+    if period < 3 || n < period {
+        return out;
+    }
+    let p = lit::<T>(period as f64);
+    let x_mean = lit::<T>((period - 1) as f64 / 2.0);
+    let sxx = lit::<T>((period * (period * period - 1)) as f64 / 12.0);
+    let (dof, k) = (lit::<T>((period - 2) as f64), lit::<T>(k));
+    for i in period - 1..n {
+        let window = &data[i + 1 - period..=i];
+        let y_mean = window.iter().fold(T::zero(), |a, &y| a + y) / p;
+        let (mut sxy, mut syy) = (T::zero(), T::zero());
+        for (j, &y) in window.iter().enumerate() {
+            let (dx, dy) = (lit::<T>(j as f64) - x_mean, y - y_mean);
+            sxy += dx * dy;
+            syy += dy * dy;
+        }
+        let slope = sxy / sxx;
+        let sse = (syy - slope * sxy).max(T::zero());
+        let error = (sse / dof).sqrt();
+        let intercept = y_mean - slope * x_mean;
+        let midline = y_mean + slope * x_mean;
+        out[0][i] = slope;
+        out[1][i] = intercept;
+        out[2][i] = midline;
+        out[3][i] = midline + k * error;
+        out[4][i] = midline - k * error;
+    }
+    out
+}
+
+/// Arnaud Legoux Moving Average: a window average with Gaussian weights
+/// centred `offset` of the way from the oldest value to the newest, with
+/// width `period / sigma`. Zero-padded until `period - 1`.
+pub fn alma<T: Real>(data: &[T], period: usize, offset: f64, sigma: f64) -> Vec<T> {
+    let n = data.len();
+    let mut out = vec![T::zero(); n];
+    if period == 0 || n < period {
+        return out;
+    }
+    let centre = offset * (period - 1) as f64;
+    let width = period as f64 / sigma;
+    let weights: Vec<f64> = (0..period).map(|i| (-(i as f64 - centre).powi(2) / (2.0 * width * width)).exp()).collect();
+    let norm = weights.iter().sum::<f64>();
+    let weights: Vec<T> = weights.iter().map(|&w| lit::<T>(w / norm)).collect();
+    for (i, window) in data.windows(period).enumerate() {
+        out[i + period - 1] = window.iter().zip(&weights).fold(T::zero(), |acc, (&x, &w)| acc + w * x);
+    }
+    out
+}
+
+/// Vortex Indicator as (VI+, VI-): the sums over `period` bars of `|high
+/// - previous low|` and `|low - previous high|`, each divided by the sum of
+/// the true ranges. Both are 1 over a window without range, and zero-padded
+/// until `period` (the first bar has no previous one).
+pub fn vortex<T: Real>(high: &[T], low: &[T], close: &[T], period: usize) -> (Vec<T>, Vec<T>) {
+    let tr = true_range(high, low, close);
+    let n = tr.len();
+    let mut plus = vec![T::zero(); n];
+    let mut minus = vec![T::zero(); n];
+    if period == 0 || n <= period {
+        return (plus, minus);
+    }
+    let movement = |i: usize| ((high[i] - low[i - 1]).abs(), (low[i] - high[i - 1]).abs());
+    let (mut sum_plus, mut sum_minus, mut sum_tr) = (T::zero(), T::zero(), T::zero());
+    for i in 1..n {
+        let (up, down) = movement(i);
+        sum_plus += up;
+        sum_minus += down;
+        sum_tr += tr[i];
+        if i > period {
+            let (up, down) = movement(i - period);
+            sum_plus -= up;
+            sum_minus -= down;
+            sum_tr -= tr[i - period];
+        }
+        if i >= period {
+            (plus[i], minus[i]) = if sum_tr > T::zero() { (sum_plus / sum_tr, sum_minus / sum_tr) } else { (T::one(), T::one()) };
+        }
+    }
+    (plus, minus)
+}
+
+/// Coppock Curve: the [`wma`] over `wma_period` bars of `roc(roc1) +
+/// roc(roc2)`, started once both ROCs exist so the warm-up zeros stay out
+/// of it. Zero-padded until `max(roc1, roc2) + wma_period - 1`.
+pub fn coppock<T: Real>(data: &[T], roc1: usize, roc2: usize, wma_period: usize) -> Vec<T> {
+    let n = data.len();
+    let mut out = vec![T::zero(); n];
+    let start = roc1.max(roc2);
+    if start >= n {
+        return out;
+    }
+    let (long, short) = (roc(data, roc1), roc(data, roc2));
+    let total: Vec<T> = long[start..].iter().zip(&short[start..]).map(|(&a, &b)| a + b).collect();
+    out[start..].copy_from_slice(&wma(&total, wma_period));
+    out
+}
+
+/// Chande Momentum Oscillator: `100 * (ups - downs) / (ups + downs)` over
+/// the last `period` price changes, without RSI's smoothing. In -100..=100,
+/// 0 over a flat window, and zero-padded for the first `period` values.
+pub fn cmo<T: Real>(data: &[T], period: usize) -> Vec<T> {
+    let n = data.len();
+    let mut out = vec![T::zero(); n];
+    if period == 0 || n <= period {
+        return out;
+    }
+    let hundred = lit::<T>(100.0);
+    let change = |i: usize| data[i] - data[i - 1];
+    let (mut ups, mut downs) = (T::zero(), T::zero());
+    for (i, o) in out.iter_mut().enumerate().skip(1) {
+        let c = change(i);
+        if c > T::zero() { ups += c } else { downs -= c }
+        if i > period {
+            let old = change(i - period);
+            if old > T::zero() { ups -= old } else { downs += old }
+        }
+        if i >= period {
+            let total = ups + downs;
+            *o = if total > T::zero() { hundred * (ups - downs) / total } else { T::zero() };
+        }
+    }
+    out
+}
+
+/// Median price `(high + low) / 2` per bar.
+pub fn median_price<T: Real>(high: &[T], low: &[T]) -> Vec<T> {
+    let two = lit::<T>(2.0);
+    high.iter().zip(low).map(|(&h, &l)| (h + l) / two).collect()
+}
+
+/// Awesome Oscillator: the `fast`-bar minus the `slow`-bar SMA of the
+/// median price, zero-padded until `max(fast, slow) - 1`.
+pub fn awesome_oscillator<T: Real>(high: &[T], low: &[T], fast: usize, slow: usize) -> Vec<T> {
+    let median = median_price(high, low);
+    let mut out: Vec<T> = sma(&median, fast).iter().zip(sma(&median, slow)).map(|(&f, s)| f - s).collect();
+    let warmup = fast.max(slow).saturating_sub(1).min(out.len());
+    out[..warmup].fill(T::zero());
+    out
+}
+
+/// Bill Williams' signals on an oscillator valid from `valid_from`, as
+/// (zero_cross, saucer), each 1 (bullish), -1 (bearish) or 0 per bar.
+///
+/// A zero cross is the bar the oscillator changes sign. A bullish saucer is
+/// two falling bars then a rising one, all above zero; a bearish saucer
+/// mirrors it below zero.
+pub fn ao_signals<T: Real>(ao: &[T], valid_from: usize) -> (Vec<T>, Vec<T>) {
+    let n = ao.len();
+    let mut zero_cross = vec![T::zero(); n];
+    let mut saucer = vec![T::zero(); n];
+    let zero = T::zero();
+    for i in valid_from + 1..n {
+        let (prev, cur) = (ao[i - 1], ao[i]);
+        if prev <= zero && cur > zero {
+            zero_cross[i] = T::one();
+        } else if prev >= zero && cur < zero {
+            zero_cross[i] = -T::one();
+        }
+        if i < valid_from + 3 {
+            continue;
+        }
+        let bars = &ao[i - 3..=i];
+        let (a, b, c, d) = (bars[0], bars[1], bars[2], bars[3]);
+        if bars[1..].iter().all(|&x| x > zero) && b < a && c < b && d > c {
+            saucer[i] = T::one();
+        } else if bars[1..].iter().all(|&x| x < zero) && b > a && c > b && d < c {
+            saucer[i] = -T::one();
+        }
+    }
+    (zero_cross, saucer)
+}
+
+/// Percentage Price Oscillator as (ppo, signal, histogram): the gap between
+/// the `fast` and `slow` seeded EMAs as a percent of the slow one, its
+/// `signal`-bar EMA and their difference. Being in percent it compares
+/// across price (or volume) scales, unlike MACD. 0 where the slow EMA is 0;
+/// zero-padded until `slow - 1`, and the signal and histogram for
+/// `signal - 1` bars more. Run on volume it is the PVO.
+pub fn ppo<T: Real>(data: &[T], fast: usize, slow: usize, signal: usize) -> (Vec<T>, Vec<T>, Vec<T>) {
+    let n = data.len();
+    let mut line = vec![T::zero(); n];
+    let mut signal_line = vec![T::zero(); n];
+    let mut histogram = vec![T::zero(); n];
+    if fast == 0 || slow == 0 || signal == 0 {
+        return (line, signal_line, histogram);
+    }
+    let start = fast.max(slow) - 1;
+    let (ema_fast, ema_slow) = (ema_seeded(data, fast), ema_seeded(data, slow));
+    let hundred = lit::<T>(100.0);
+    for i in start..n {
+        let base = ema_slow[i];
+        line[i] = if base == T::zero() { T::zero() } else { hundred * (ema_fast[i] - base) / base };
+    }
+    ema_from(&line, start, signal, &mut signal_line);
+    for i in start + signal - 1..n {
+        histogram[i] = line[i] - signal_line[i];
+    }
+    (line, signal_line, histogram)
+}
+
+/// Ease of Movement: the `period`-bar SMA of each bar's midpoint move
+/// times its range per `volume / divisor`, so large moves on light volume
+/// score high. A bar without range or volume scores 0; zero-padded until
+/// `period` (the first bar has no previous midpoint).
+pub fn eom<T: Real>(high: &[T], low: &[T], volume: &[T], period: usize, divisor: f64) -> Vec<T> {
+    let n = high.len().min(low.len()).min(volume.len());
+    let mut out = vec![T::zero(); n];
+    if period == 0 {
+        return out;
+    }
+    let median = median_price(&high[..n], &low[..n]);
+    let divisor = lit::<T>(divisor);
+    let mut raw = vec![T::zero(); n];
+    for i in 1..n {
+        let (range, v) = (high[i] - low[i], volume[i]);
+        if range > T::zero() && v > T::zero() {
+            raw[i] = (median[i] - median[i - 1]) * range * divisor / v;
+        }
+    }
+    sma_from(&raw, 1, period, &mut out);
+    out
+}
+
+/// [`vwap`] accumulated from bar `anchor` on (e.g. an earnings date or a
+/// swing low) and zero before it.
+pub fn anchored_vwap<T: Real>(high: &[T], low: &[T], close: &[T], volume: &[T], anchor: usize) -> Vec<T> {
+    let n = high.len().min(low.len()).min(close.len()).min(volume.len());
+    let mut out = vec![T::zero(); n];
+    if anchor < n {
+        out[anchor..].copy_from_slice(&vwap(&high[anchor..n], &low[anchor..n], &close[anchor..n], &volume[anchor..n], None));
+    }
+    out
+}
+
+/// McGinley Dynamic: `md += (x - md) / (period * (x / md)^4)`, a moving
+/// average whose speed adapts to how far price runs from it. Seeded with
+/// the SMA of the first `period` values (TradingView's form, without
+/// McGinley's 0.6 factor) and zero-padded until `period - 1`. A zero
+/// average restarts from the price.
+pub fn mcginley<T: Real>(data: &[T], period: usize) -> Vec<T> {
+    let n = data.len();
+    let mut out = vec![T::zero(); n];
+    if period == 0 || n < period {
+        return out;
+    }
+    let p = lit::<T>(period as f64);
+    let mut md = lanes::sum(&data[..period]) / p;
+    out[period - 1] = md;
+    for (o, &x) in out[period..].iter_mut().zip(&data[period..]) {
+        md = if md == T::zero() { x } else { md + (x - md) / (p * (x / md).powi(4)) };
+        *o = md;
+    }
+    out
+}
+
+/// KDJ as (K, D, J), the stochastic as quoted by Asian brokers. K smooths
+/// the raw `period`-bar %K as `k = ((k_smooth - 1) * k + raw) / k_smooth`,
+/// D smooths K likewise over `d_smooth`, both starting from 50, and `J = 3K
+/// - 2D` leads them (and leaves 0-100). Zero-padded until `period - 1`.
+pub fn kdj<T: Real>(high: &[T], low: &[T], close: &[T], period: usize, k_smooth: usize, d_smooth: usize) -> [Vec<T>; 3] {
+    let n = close.len().min(high.len()).min(low.len());
+    let mut out: [Vec<T>; 3] = std::array::from_fn(|_| vec![T::zero(); n]);
+    if period == 0 || k_smooth == 0 || d_smooth == 0 || n < period {
+        return out;
+    }
+    let raw = raw_stochastic(&high[..n], &low[..n], &close[..n], period);
+    let (km, dm) = (lit::<T>(k_smooth as f64), lit::<T>(d_smooth as f64));
+    let (mut k, mut d) = (lit::<T>(50.0), lit::<T>(50.0));
+    let three = lit::<T>(3.0);
+    for i in period - 1..n {
+        k = ((km - T::one()) * k + raw[i]) / km;
+        d = ((dm - T::one()) * d + k) / dm;
+        out[0][i] = k;
+        out[1][i] = d;
+        out[2][i] = three * k - (d + d);
+    }
+    out
+}
+
+/// Ulcer Index: the root mean square over `period` bars of the percent
+/// drawdown from the `period`-bar high, so it grows with both the depth and
+/// the duration of drawdowns and ignores upside volatility. Zero-padded
+/// until `2 * (period - 1)`.
+pub fn ulcer_index<T: Real>(close: &[T], period: usize) -> Vec<T> {
+    let n = close.len();
+    let mut out = vec![T::zero(); n];
+    if period == 0 || n < period {
+        return out;
+    }
+    let peaks = rolling_max(close, period);
+    let hundred = lit::<T>(100.0);
+    let mut squared = vec![T::zero(); n];
+    for i in period - 1..n {
+        let drawdown = hundred * (close[i] - peaks[i]) / peaks[i];
+        squared[i] = drawdown * drawdown;
+    }
+    sma_from(&squared, period - 1, period, &mut out);
+    for v in &mut out {
+        *v = v.sqrt();
+    }
+    out
+}
+
+/// Ulcer Performance Index (Martin ratio): the return over the last
+/// `period` bars, compounded to `annualization` periods per year, in excess
+/// of `risk_free_rate` (a decimal, 0.04 for 4%), per unit of the
+/// [`ulcer_index`], both in percent. NaN without drawdown; zero-padded
+/// until `max(2 * (period - 1), period)`.
+pub fn ulcer_performance_index<T: Real>(close: &[T], period: usize, risk_free_rate: f64, annualization: f64) -> Vec<T> {
+    let n = close.len();
+    let mut out = vec![T::zero(); n];
+    if period == 0 {
+        return out;
+    }
+    let ulcer = ulcer_index(close, period);
+    let (hundred, one) = (lit::<T>(100.0), T::one());
+    let (years, risk_free) = (lit::<T>(annualization / period as f64), lit::<T>(risk_free_rate * 100.0));
+    for i in (2 * (period - 1)).max(period)..n {
+        let annual = hundred * ((close[i] / close[i - period]).powf(years) - one);
+        out[i] = if ulcer[i] == T::zero() { T::nan() } else { (annual - risk_free) / ulcer[i] };
+    }
+    out
+}
+
+/// One stochastic-and-smooth stage of the Schaff Trend Cycle over
+/// `data[start..]`: where each value sits in its `cycle`-bar range (the
+/// previous result over a flat range, 0 at first), smoothed by `factor`
+/// from its first value. Written to `out` from `start + cycle - 1`.
+fn stc_stage<T: Real>(data: &[T], start: usize, cycle: usize, factor: T, out: &mut [T]) {
+    if start + cycle > data.len() {
+        return;
+    }
+    let (lowest, highest) = (rolling_min(&data[start..], cycle), rolling_max(&data[start..], cycle));
+    let hundred = lit::<T>(100.0);
+    let (mut stochastic, mut smoothed) = (T::zero(), None);
+    for j in cycle - 1..lowest.len() {
+        let range = highest[j] - lowest[j];
+        if range > T::zero() {
+            stochastic = hundred * (data[start + j] - lowest[j]) / range;
+        }
+        let value = smoothed.map_or(stochastic, |s: T| s + factor * (stochastic - s));
+        smoothed = Some(value);
+        out[start + j] = value;
+    }
+}
+
+/// Schaff Trend Cycle: MACD(`fast`, `slow`) put through two rounds of a
+/// `cycle`-bar stochastic, each smoothed by `factor` (0.5 by convention),
+/// giving a 0-100 oscillator that turns ahead of MACD. Zero-padded until
+/// `max(fast, slow) + 2 * cycle - 3`.
+pub fn stc<T: Real>(data: &[T], fast: usize, slow: usize, cycle: usize, factor: f64) -> Vec<T> {
+    let n = data.len();
+    let mut out = vec![T::zero(); n];
+    if fast == 0 || slow == 0 || cycle == 0 {
+        return out;
+    }
+    let start = fast.max(slow) - 1;
+    let macd: Vec<T> = ema_seeded(data, fast).iter().zip(ema_seeded(data, slow)).map(|(&f, s)| f - s).collect();
+    let factor = lit::<T>(factor);
+    let mut first = vec![T::zero(); n];
+    stc_stage(&macd, start, cycle, factor, &mut first);
+    stc_stage(&first, start + cycle - 1, cycle, factor, &mut out);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: &[f64], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len());
+        for (i, (a, e)) in actual.iter().zip(expected).enumerate() {
+            assert!((a - e).abs() < 1e-9 || (a.is_nan() && e.is_nan()), "index {}: {} != {}", i, a, e);
+        }
+    }
+
+    /// Bars on which a band ratchets past the close (bar 4).
+    fn ratchet_bars() -> ([f64; 20], [f64; 20], [f64; 20]) {
+        let close = [
+            10.0, 10.471, 9.773, 9.433, 9.618, 9.11, 9.074, 9.164, 8.748, 8.093, 8.19, 8.687, 8.364, 8.197, 9.02,
+            8.741, 8.484, 9.686, 8.92, 9.318,
+        ];
+        let high = [
+            10.03, 10.571, 10.278, 9.594, 9.691, 10.49, 9.8, 9.565, 10.132, 8.175, 8.342, 9.635, 9.114, 8.256,
+            9.171, 8.77, 8.827, 9.776, 8.948, 9.503,
+        ];
+        let low = [
+            9.968, 10.024, 9.708, 9.118, 8.787, 9.011, 7.867, 9.039, 8.701, 8.08, 7.79, 8.668, 8.266, 8.103, 7.625,
+            7.706, 7.687, 8.459, 8.82, 8.07,
+        ];
+        (high, low, close)
+    }
+
+    #[test]
+    fn supertrend_flips_against_the_previous_final_bands() {
+        let (high, low, close) = ratchet_bars();
+        // The original SuperTrend(3, 0.5) script on these bars. Bar 4 closes
+        // above its own upper band but below bar 3's, so the downtrend holds.
+        let (line, direction) = supertrend(&high, &low, &close, 3, 0.5);
+        assert_close(
+            &direction,
+            &[
+                0.0, 0.0, 1.0, -1.0, -1.0, -1.0, -1.0, -1.0, -1.0, -1.0, -1.0, 1.0, -1.0, -1.0, 1.0, 1.0, 1.0, 1.0,
+                1.0, 1.0,
+            ],
+        );
+        assert_close(
+            &line,
+            &[
+                0.0,
+                0.0,
+                9.760333333333334,
+                9.620277777777778,
+                9.565851851851852,
+                10.2149012345679,
+                9.465267489711936,
+                9.465267489711936,
+                9.465267489711936,
+                8.623986663618352,
+                8.488991109078901,
+                8.628672593947398,
+                9.179884937368401,
+                8.549589958245601,
+                7.893606694502933,
+                7.893606694502933,
+                7.893606694502933,
+                8.524550131704572,
+                8.524550131704572,
+                8.524550131704572,
+            ],
+        );
+    }
+
+    #[test]
+    fn supertrend_differs_from_ta_supertrend_where_the_band_ratchets() {
+        let (high, low, close) = ratchet_bars();
+        let (line, direction) = supertrend(&high, &low, &close, 3, 0.5);
+        // In a downtrend the line is the final upper band. Bar 4's close is
+        // above its own band, where `ta.supertrend` turns up, but not above
+        // bar 3's, so this variant stays down until bar 11.
+        assert_eq!(direction[3], -1.0);
+        assert!(close[4] > line[4] && close[4] <= line[3]);
+        assert_eq!(direction[4], -1.0);
+        assert!(direction[5..11].iter().all(|&d| d == -1.0));
+        assert_eq!(direction[11], 1.0);
+    }
+
+    #[test]
+    fn supertrend_bands_reset_when_the_close_touches_them() {
+        let close = [
+            10.0, 10.47, 10.22, 9.53, 9.23, 9.59, 9.05, 9.02, 7.94, 7.58, 7.28, 6.36, 6.63, 6.59, 7.06, 6.03, 5.66,
+            6.79, 6.15, 7.16,
+        ];
+        let high = [
+            10.41, 11.0, 10.51, 10.06, 9.44, 9.92, 9.59, 9.13, 8.22, 8.15, 7.58, 6.84, 7.01, 6.99, 7.25, 6.43,
+            6.02, 7.21, 6.5, 7.31,
+        ];
+        let low = [
+            9.59, 9.98, 9.71, 9.0, 8.71, 9.04, 8.76, 8.44, 7.43, 7.04, 6.86, 6.14, 6.11, 6.18, 6.63, 5.47, 5.32,
+            6.37, 5.62, 6.64,
+        ];
+        // Bar 4 closes exactly on the lower band (9.23). The script only
+        // keeps a band while the close is strictly beyond it
+        // (`close[1] > up1 ? max(up, up1) : up`), so bar 5 takes the lower
+        // basic band and the trend holds until bar 8.
+        let (_, direction) = supertrend(&high, &low, &close, 3, 1.0);
+        assert_close(
+            &direction,
+            &[
+                0.0, 0.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, -1.0, -1.0, -1.0, -1.0, -1.0, -1.0, -1.0, -1.0, -1.0, 1.0,
+                1.0, 1.0,
+            ],
+        );
+    }
+
+    #[test]
+    fn supertrend_is_zero_for_short_input() {
+        let (line, direction) = supertrend(&[1.0, 2.0], &[0.5, 1.5], &[0.8, 1.8], 3, 3.0);
+        assert_eq!((line, direction), (vec![0.0; 2], vec![0.0; 2]));
+    }
+
+    #[test]
+    fn stc_matches_the_tradingview_script() {
+        let data = [
+            102.39, 101.78, 102.22, 102.42, 103.31, 101.96, 101.6, 100.9, 99.88, 99.09, 98.63, 98.39, 97.53, 98.0,
+            97.5, 94.35, 95.59, 95.25, 94.56, 94.88, 95.16, 95.26, 94.46, 94.7, 93.21, 94.7, 93.48, 93.32, 93.39,
+            93.66, 93.46, 93.99, 90.41, 90.23, 89.99, 89.48, 90.93, 89.87, 89.71, 87.59, 87.78, 86.07, 84.41, 86.7,
+            87.33,
+        ];
+        // TradingView's published STC script (SMA-seeded `ta.ema`, `na`
+        // until each stage's first full window) with fast 5, slow 10, cycle
+        // 4 and factor 0.5. Its first value is at slow + 2 * cycle - 3.
+        let expected = [
+            50.0,
+            25.0,
+            17.262205282500865,
+            58.63110264125043,
+            79.31555132062522,
+            89.65777566031261,
+            94.8288878301563,
+            97.41444391507815,
+            98.70722195753908,
+            49.35361097876954,
+            50.211008896248735,
+            54.39685020941272,
+            77.19842510470636,
+            88.59921255235318,
+            94.29960627617659,
+            97.14980313808829,
+            98.57490156904414,
+            49.28745078452207,
+            24.643725392261036,
+            12.321862696130518,
+            6.160931348065259,
+            53.08046567403263,
+            76.54023283701632,
+            88.27011641850817,
+            44.13505820925408,
+            22.06752910462704,
+            11.03376455231352,
+            5.51688227615676,
+            52.75844113807838,
+            76.37922056903919,
+        ];
+        let mut out = stc(&data, 5, 10, 4, 0.5);
+        assert!(out[..15].iter().all(|&v| v == 0.0));
+        assert_close(&out[15..], &expected);
+
+        // The binding pads exactly the warm-up under `padding="nan"`.
+        mask_warmup(&mut out, 10 + 2 * 4 - 3);
+        assert!(out[..15].iter().all(|v| v.is_nan()));
+        assert_eq!(out[15], 50.0);
+    }
+
+    #[test]
+    fn stc_is_zero_without_a_full_warm_up() {
+        let data: Vec<f64> = (0..15).map(|i| 100.0 + i as f64).collect();
+        assert!(stc(&data, 5, 10, 4, 0.5).iter().all(|&v| v == 0.0));
+    }
+
+    const CLOSE: [f64; 16] = [
+        44.34, 44.09, 44.15, 43.61, 44.33, 44.83, 45.10, 45.42, 45.84, 46.08, 45.89, 46.03, 45.61, 46.28, 46.28, 46.00,
+    ];
+
+    #[test]
+    fn sma_is_zero_padded_then_the_window_mean() {
+        let expected = [
+            0.0, 0.0, 0.0, 44.0475, 44.045, 44.23, 44.4675, 44.92, 45.2975, 45.61, 45.8075, 45.96, 45.9025, 45.9525,
+            46.05, 46.0425,
+        ];
+        assert_close(&sma(&CLOSE, 4), &expected);
+        assert!(sma(&CLOSE[..3], 4).iter().all(|&v| v == 0.0));
+    }
+
+    #[test]
+    fn ema_is_seeded_with_the_first_window_mean() {
+        let expected = [
+            0.0, 0.0, 0.0, 0.0, 44.104, 44.346, 44.597333333333, 44.871555555556, 45.19437037037, 45.489580246914,
+            45.623053497942, 45.758702331962, 45.709134887974, 45.89942325865, 46.026282172433, 46.017521448289,
+        ];
+        assert_close(&ema(&CLOSE, 5), &expected);
+        assert_close(&ema_seeded(&CLOSE, 5), &expected);
+    }
+
+    #[test]
+    fn rsi_uses_wilder_smoothing_after_a_neutral_warm_up() {
+        let expected = [
+            50.0, 50.0, 50.0, 50.0, 50.0, 61.83574879227, 67.185877466251, 72.828890799656, 78.807947019868,
+            81.686467690524, 72.00755134174, 74.761893195368, 54.611201769556, 70.47803954687, 70.47803954687,
+            57.380019673966,
+        ];
+        assert_close(&rsi(&CLOSE, 5), &expected);
+        assert!(rsi(&CLOSE[..5], 5).iter().all(|&v| v == 50.0));
+    }
+
+    #[test]
+    fn macd_is_the_ema_gap_less_its_signal() {
+        let (line, signal, histogram) = macd(&CLOSE, 3, 6, 3);
+        let expected = [
+            0.247916666667, 0.311458333333, 0.358229166667, 0.413757440476, 0.425909332483, 0.328690817845,
+            0.277014088639, 0.128984672689, 0.201262070894, 0.198323702982, 0.108942328302,
+        ];
+        assert_close(&line[5..], &expected);
+        for i in 0..CLOSE.len() {
+            assert!((histogram[i] - (line[i] - signal[i])).abs() < 1e-12, "index {}", i);
+        }
+    }
+
+    #[test]
+    fn bbands_are_the_sma_plus_minus_population_std() {
+        let (upper, middle, lower) = bbands(&CLOSE, 5, 2.0);
+        let expected_upper = [
+            44.63550352774, 44.990152269552, 45.449493185057, 45.926536164246, 46.129951265899, 46.373443309835,
+            46.377460469738, 46.318373241085, 46.220575256182, 46.4229539302, 46.524185736662, 46.53136544445,
+        ];
+        let expected_lower = [
+            43.57249647226, 43.413847730448, 43.358506814943, 43.389463835754, 44.078048734101, 44.534556690165,
+            44.954539530262, 45.385626758915, 45.559424743818, 45.5330460698, 45.511814263338, 45.54863455555,
+        ];
+        assert_close(&upper[4..], &expected_upper);
+        assert_close(&middle, &sma(&CLOSE, 5));
+        assert_close(&lower[4..], &expected_lower);
+    }
+
+    #[test]
+    fn atr_is_wilder_smoothed_true_range() {
+        let high: Vec<f64> = CLOSE.iter().map(|c| c + 0.3).collect();
+        let low: Vec<f64> = CLOSE.iter().map(|c| c - 0.25).collect();
+        let expected = [
+            0.0, 0.0, 0.0, 0.61, 0.7125, 0.734375, 0.69328125, 0.6749609375, 0.686220703125, 0.652165527344,
+            0.626624145508, 0.607468109131, 0.623101081848, 0.709825811386, 0.66986935854, 0.639902018905,
+        ];
+        assert_close(&atr(&high, &low, &CLOSE, 4), &expected);
+    }
+
+    #[test]
+    fn zero_periods_leave_the_warm_up_value() {
+        assert!(sma(&CLOSE, 0).iter().all(|&v| v == 0.0));
+        assert!(ema(&CLOSE, 0).iter().all(|&v| v == 0.0));
+        assert!(rsi(&CLOSE, 0).iter().all(|&v| v == 50.0));
+
+        let mut out = vec![1.0; CLOSE.len()];
+        sma_from(&CLOSE, 2, 0, &mut out);
+        ema_from(&CLOSE, 2, 0, &mut out);
+        assert!(out.iter().all(|&v| v == 1.0));
+    }
+}
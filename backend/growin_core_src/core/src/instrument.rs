@@ -0,0 +1,71 @@
+//! Instrument reference data shared by normalization, order checks and
+//! trade costs.
+
+use rust_decimal::Decimal;
+
+use crate::money::InstrumentClass;
+use crate::orders::InstrumentRules;
+use crate::ticker::normalize_ticker;
+
+/// A tradable instrument as described by a broker or data provider.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Instrument {
+    /// Symbol as the source spells it (e.g. `AAPL_US_EQ` at Trading212).
+    pub symbol: String,
+    pub isin: Option<String>,
+    pub name: Option<String>,
+    /// ISO 4217 code of the quote currency (`GBX` for pence).
+    pub currency: String,
+    pub exchange: Option<String>,
+    /// Lower-case kind: "stock", "etf", "etp", "fund", "crypto", ...
+    pub kind: String,
+    /// Smallest quantity increment.
+    pub lot_size: Decimal,
+    /// Smallest price increment, when known.
+    pub tick_size: Option<Decimal>,
+    pub min_quantity: Decimal,
+    pub max_open_quantity: Option<Decimal>,
+}
+
+impl Instrument {
+    /// Canonical kind for the spellings brokers and providers use.
+    pub fn canonical_kind(kind: &str) -> String {
+        match kind.to_lowercase().as_str() {
+            "equity" | "share" | "stock" => "stock".to_string(),
+            "mutualfund" | "fund" => "fund".to_string(),
+            "cryptocurrency" | "crypto" => "crypto".to_string(),
+            other => other.to_string(),
+        }
+    }
+
+    /// The symbol in the form the rest of the crate keys on.
+    pub fn normalized_symbol(&self) -> String {
+        normalize_ticker(&self.symbol)
+    }
+
+    pub fn is_exchange_traded_product(&self) -> bool {
+        matches!(self.kind.as_str(), "etf" | "etp" | "etc" | "etn")
+    }
+
+    /// Class for UK stamp duty and the PTM levy.
+    pub fn trade_class(&self) -> InstrumentClass {
+        if self.is_exchange_traded_product() {
+            InstrumentClass::Etf
+        } else if self.exchange.as_deref().is_some_and(|e| e.to_ascii_uppercase().contains("AIM")) {
+            InstrumentClass::Aim
+        } else {
+            InstrumentClass::Share
+        }
+    }
+
+    /// Order limits for [`crate::orders::validate_order`].
+    pub fn order_rules(&self, market_open: bool) -> InstrumentRules {
+        InstrumentRules {
+            min_quantity: self.min_quantity,
+            lot_size: self.lot_size,
+            tick_size: self.tick_size,
+            max_open_quantity: self.max_open_quantity,
+            market_open,
+        }
+    }
+}
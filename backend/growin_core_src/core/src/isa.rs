@@ -0,0 +1,171 @@
+//! ISA subscription tracking against the annual allowance.
+//!
+//! UK tax years run from 6 April to 5 April. Deposits count against the
+//! year's allowance; in a flexible ISA, money withdrawn can be paid back in
+//! during the same tax year without using allowance again.
+
+use rust_decimal::Decimal;
+
+/// A deposit (positive `amount`) or withdrawal (negative) on a calendar date.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Contribution {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+    pub amount: Decimal,
+}
+
+impl Contribution {
+    /// Calendar year in which the contribution's tax year starts, e.g. 2024
+    /// for 2024/25.
+    pub fn tax_year(&self) -> i32 {
+        tax_year(self.year, self.month, self.day)
+    }
+}
+
+/// Start year of the tax year containing a date.
+pub fn tax_year(year: i32, month: u32, day: u32) -> i32 {
+    if (month, day) >= (4, 6) {
+        year
+    } else {
+        year - 1
+    }
+}
+
+/// "2024/25" for 2024.
+pub fn tax_year_label(start_year: i32) -> String {
+    format!("{}/{:02}", start_year, (start_year + 1).rem_euclid(100))
+}
+
+/// One tax year's allowance usage.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IsaYear {
+    pub tax_year: i32,
+    /// Deposits counted against the allowance.
+    pub subscribed: Decimal,
+    pub withdrawn: Decimal,
+    /// Deposits that paid back earlier withdrawals instead (flexible ISAs).
+    pub replaced: Decimal,
+    /// Withdrawals that could still be paid back without using allowance.
+    pub replaceable: Decimal,
+    /// `allowance - subscribed`; negative when over-subscribed.
+    pub remaining: Decimal,
+}
+
+/// Allowance usage per tax year with any contributions, oldest first.
+///
+/// Contributions are replayed in date order (input order within a day).
+/// For a flexible ISA each deposit first pays back the year's unreplaced
+/// withdrawals; unreplaced withdrawals lapse at the end of the tax year.
+pub fn isa_allowance(contributions: &[Contribution], allowance: Decimal, flexible: bool) -> Vec<IsaYear> {
+    let mut ordered: Vec<&Contribution> = contributions.iter().collect();
+    ordered.sort_by_key(|c| (c.year, c.month, c.day));
+
+    let mut years: Vec<IsaYear> = Vec::new();
+    for c in ordered {
+        let ty = c.tax_year();
+        if years.last().is_none_or(|y| y.tax_year != ty) {
+            years.push(IsaYear {
+                tax_year: ty,
+                subscribed: Decimal::ZERO,
+                withdrawn: Decimal::ZERO,
+                replaced: Decimal::ZERO,
+                replaceable: Decimal::ZERO,
+                remaining: allowance,
+            });
+        }
+        let year = years.last_mut().unwrap();
+        if c.amount.is_sign_negative() {
+            year.withdrawn -= c.amount;
+            if flexible {
+                year.replaceable -= c.amount;
+            }
+        } else {
+            let replaced = c.amount.min(year.replaceable);
+            year.replaced += replaced;
+            year.replaceable -= replaced;
+            year.subscribed += c.amount - replaced;
+        }
+        year.remaining = allowance - year.subscribed;
+    }
+    years
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn c(year: i32, month: u32, day: u32, amount: i64) -> Contribution {
+        Contribution { year, month, day, amount: Decimal::from(amount) }
+    }
+
+    fn allowance() -> Decimal {
+        Decimal::from(20_000)
+    }
+
+    #[test]
+    fn tax_years_start_on_the_sixth_of_april() {
+        assert_eq!(tax_year(2024, 4, 5), 2023);
+        assert_eq!(tax_year(2024, 4, 6), 2024);
+        assert_eq!(tax_year(2024, 1, 1), 2023);
+        assert_eq!(tax_year(2024, 12, 31), 2024);
+        assert_eq!(tax_year(2025, 3, 31), 2024);
+        assert_eq!(tax_year_label(2024), "2024/25");
+        assert_eq!(tax_year_label(1999), "1999/00");
+    }
+
+    #[test]
+    fn deposits_either_side_of_the_boundary_use_different_allowances() {
+        let years = isa_allowance(&[c(2024, 4, 6, 3000), c(2024, 4, 5, 20_000)], allowance(), false);
+        assert_eq!(years.len(), 2);
+        assert_eq!((years[0].tax_year, years[0].subscribed, years[0].remaining), (2023, allowance(), Decimal::ZERO));
+        assert_eq!((years[1].tax_year, years[1].subscribed), (2024, Decimal::from(3000)));
+        assert_eq!(years[1].remaining, Decimal::from(17_000));
+    }
+
+    #[test]
+    fn flexible_isas_let_withdrawals_be_paid_back() {
+        let contributions = [c(2024, 5, 1, 10_000), c(2024, 6, 1, -4000), c(2024, 7, 1, 6000)];
+        let year = &isa_allowance(&contributions, allowance(), true)[0];
+        assert_eq!(year.withdrawn, Decimal::from(4000));
+        assert_eq!(year.replaced, Decimal::from(4000));
+        assert_eq!(year.replaceable, Decimal::ZERO);
+        assert_eq!(year.subscribed, Decimal::from(12_000));
+        assert_eq!(year.remaining, Decimal::from(8000));
+    }
+
+    #[test]
+    fn non_flexible_withdrawals_do_not_restore_allowance() {
+        let contributions = [c(2024, 5, 1, 10_000), c(2024, 6, 1, -4000), c(2024, 7, 1, 6000)];
+        let year = &isa_allowance(&contributions, allowance(), false)[0];
+        assert_eq!((year.replaced, year.replaceable), (Decimal::ZERO, Decimal::ZERO));
+        assert_eq!(year.subscribed, Decimal::from(16_000));
+    }
+
+    #[test]
+    fn replaceable_withdrawals_lapse_at_the_end_of_the_tax_year() {
+        let contributions = [c(2024, 5, 1, 10_000), c(2025, 4, 5, -4000), c(2025, 4, 6, 4000)];
+        let years = isa_allowance(&contributions, allowance(), true);
+        assert_eq!(years[0].replaceable, Decimal::from(4000));
+        assert_eq!(years[0].subscribed, Decimal::from(10_000));
+        assert_eq!((years[1].tax_year, years[1].replaced), (2025, Decimal::ZERO));
+        assert_eq!(years[1].subscribed, Decimal::from(4000));
+    }
+
+    #[test]
+    fn over_subscription_leaves_a_negative_remainder() {
+        let years = isa_allowance(&[c(2024, 5, 1, 15_000), c(2024, 9, 1, 7500)], allowance(), true);
+        assert_eq!(years[0].subscribed, Decimal::from(22_500));
+        assert_eq!(years[0].remaining, Decimal::from(-2500));
+    }
+
+    #[test]
+    fn same_day_contributions_keep_input_order() {
+        // Withdrawing before depositing on the same day replaces; the other
+        // way round the deposit uses allowance.
+        let years = isa_allowance(&[c(2024, 5, 1, -1000), c(2024, 5, 1, 1000)], allowance(), true);
+        assert_eq!((years[0].replaced, years[0].subscribed), (Decimal::from(1000), Decimal::ZERO));
+        let years = isa_allowance(&[c(2024, 5, 1, 1000), c(2024, 5, 1, -1000)], allowance(), true);
+        assert_eq!((years[0].replaced, years[0].subscribed), (Decimal::ZERO, Decimal::from(1000)));
+    }
+}
@@ -0,0 +1,70 @@
+//! Chunked reductions for the hot indicator loops.
+//!
+//! `std::simd` is still nightly-only, so these helpers split work into
+//! fixed-width lanes with independent accumulators. That breaks the serial
+//! dependency chain of a naive `iter().sum()` and lets LLVM emit packed
+//! SIMD instructions on stable. Summation order differs from a scalar loop,
+//! so results can differ from it in the last few ULPs.
+
+use num_traits::{Float, NumAssign};
+
+/// Element type of the indicator kernels: `f64`, or `f32` for full-universe
+/// scans where halving memory matters more than precision.
+pub trait Real: Float + NumAssign + Send + Sync + 'static {}
+
+impl<T: Float + NumAssign + Send + Sync + 'static> Real for T {}
+
+/// Converts an f64 constant into the kernel's element type.
+#[inline]
+pub fn lit<T: Real>(x: f64) -> T {
+    T::from(x).unwrap()
+}
+
+/// Number of independent accumulators (one AVX register of f64).
+pub const LANES: usize = 4;
+
+/// Sum of a slice using `LANES` parallel accumulators.
+pub fn sum<T: Real>(data: &[T]) -> T {
+    let chunks = data.chunks_exact(LANES);
+    let tail = chunks.remainder().iter().fold(T::zero(), |a, &x| a + x);
+    let mut acc = [T::zero(); LANES];
+    for chunk in chunks {
+        for (a, &x) in acc.iter_mut().zip(chunk) {
+            *a += x;
+        }
+    }
+    acc.iter().fold(T::zero(), |a, &x| a + x) + tail
+}
+
+/// Sum of squared deviations from `mu`.
+pub fn sum_sq_dev<T: Real>(data: &[T], mu: T) -> T {
+    let chunks = data.chunks_exact(LANES);
+    let tail = chunks.remainder().iter().fold(T::zero(), |a, &x| a + (x - mu) * (x - mu));
+    let mut acc = [T::zero(); LANES];
+    for chunk in chunks {
+        for (a, &x) in acc.iter_mut().zip(chunk) {
+            let d = x - mu;
+            *a += d * d;
+        }
+    }
+    acc.iter().fold(T::zero(), |a, &x| a + x) + tail
+}
+
+/// First differences with a leading 0.0, so the output aligns with `data`.
+pub fn diff<T: Real>(data: &[T]) -> Vec<T> {
+    let mut out = Vec::with_capacity(data.len());
+    if !data.is_empty() {
+        out.push(T::zero());
+        out.extend(data.windows(2).map(|w| w[1] - w[0]));
+    }
+    out
+}
+
+/// Splits changes into (gains, losses) without branches. Losses are positive
+/// and a NaN change propagates into the loss side, as in the scalar RSI.
+pub fn gains_losses<T: Real>(changes: &[T]) -> (Vec<T>, Vec<T>) {
+    let zero = T::zero();
+    let gains = changes.iter().map(|&c| if c > zero { c } else { zero }).collect();
+    let losses = changes.iter().map(|&c| if c > zero { zero } else { -c }).collect();
+    (gains, losses)
+}
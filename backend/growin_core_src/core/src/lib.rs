@@ -0,0 +1,38 @@
+//! Pure-Rust analytics behind the `growin_core` Python module.
+//!
+//! Indicators, statistics, signals, screening, rules and exact money
+//! arithmetic with no Python dependency, so the same logic can back a CLI
+//! or a Rust service. The PyO3 bindings in the parent crate only convert
+//! arguments, validate them into Python exceptions and release the GIL.
+
+pub mod alerts;
+pub mod allocation;
+pub mod calendar;
+pub mod candles;
+pub mod chunked;
+pub mod dividends;
+pub mod execution;
+pub mod features;
+pub mod indicators;
+pub mod instrument;
+pub mod isa;
+pub mod lanes;
+pub mod microstructure;
+pub mod ml;
+pub mod moments;
+pub mod money;
+pub mod orders;
+pub mod portfolio;
+pub mod regime;
+pub mod risk;
+pub mod rules;
+pub mod scaling;
+pub mod scoring;
+pub mod screening;
+pub mod search;
+pub mod sectors;
+pub mod signals;
+pub mod stats;
+pub mod ticker;
+#[cfg(feature = "wasm")]
+pub mod wasm;
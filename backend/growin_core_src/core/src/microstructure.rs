@@ -0,0 +1,235 @@
+//! Market microstructure: quoted and effective spreads, and order book
+//! imbalance.
+//!
+//! Quote statistics are time-weighted: each quote counts for as long as it
+//! stood, until the next update, so a tight quote that flickers for a moment
+//! does not hide a wide one that stood all afternoon.
+
+use std::collections::BTreeMap;
+
+use crate::stats;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SpreadStats {
+    /// Quotes with a finite, positive bid at or below the ask.
+    pub valid_quotes: usize,
+    /// Crossed, empty or non-finite quotes, which are skipped.
+    pub invalid_quotes: usize,
+    /// Simple mean of `ask - bid`.
+    pub mean_spread: f64,
+    pub mean_spread_bps: f64,
+    /// Means weighted by how long each quote stood.
+    pub time_weighted_spread: f64,
+    pub time_weighted_spread_bps: f64,
+    pub min_spread_bps: f64,
+    pub max_spread_bps: f64,
+    /// Time-weighted spread in bps by time-of-day bucket, keyed by the
+    /// bucket's start in seconds after local midnight.
+    pub profile: BTreeMap<i64, f64>,
+}
+
+/// `(spread, spread in bps of the mid)`, or None for an unusable quote.
+fn quote_spread(bid: f64, ask: f64) -> Option<(f64, f64)> {
+    if !(bid.is_finite() && ask.is_finite()) || bid <= 0.0 || ask < bid {
+        return None;
+    }
+    let spread = ask - bid;
+    Some((spread, spread / ((bid + ask) / 2.0) * 10_000.0))
+}
+
+/// Quoted spread statistics of a quote stream.
+///
+/// A quote stands until the next timestamp, for at most `max_gap_secs`, so
+/// overnight and other gaps in the feed do not dominate the averages; the
+/// last quote gets no weight. The intraday profile buckets quotes by the
+/// local time (`utc_offset_secs` from UTC) they were posted.
+pub fn spread_stats(
+    bid: &[f64],
+    ask: &[f64],
+    timestamps: &[i64],
+    bucket_secs: i64,
+    utc_offset_secs: i64,
+    max_gap_secs: i64,
+) -> Result<SpreadStats, String> {
+    if bid.len() != ask.len() || bid.len() != timestamps.len() {
+        return Err(format!(
+            "bid, ask and timestamps must have equal lengths, got {}, {} and {}",
+            bid.len(),
+            ask.len(),
+            timestamps.len()
+        ));
+    }
+    if bucket_secs <= 0 || 86_400 % bucket_secs != 0 {
+        return Err(format!("the bucket length must divide a day, got {} seconds", bucket_secs));
+    }
+    if max_gap_secs <= 0 {
+        return Err(format!("max_gap must be positive, got {} seconds", max_gap_secs));
+    }
+    if let Some(i) = timestamps.windows(2).position(|w| w[1] < w[0]) {
+        return Err(format!("timestamps must be non-decreasing, but index {} is before index {}", i + 1, i));
+    }
+
+    let mut out = SpreadStats { min_spread_bps: f64::INFINITY, max_spread_bps: f64::NEG_INFINITY, ..SpreadStats::default() };
+    let (mut sum, mut sum_bps) = (0.0, 0.0);
+    let (mut weighted, mut weighted_bps, mut total_secs) = (0.0, 0.0, 0.0);
+    // Per bucket: (bps x seconds, seconds).
+    let mut buckets: BTreeMap<i64, (f64, f64)> = BTreeMap::new();
+    for i in 0..bid.len() {
+        let Some((spread, bps)) = quote_spread(bid[i], ask[i]) else {
+            out.invalid_quotes += 1;
+            continue;
+        };
+        out.valid_quotes += 1;
+        sum += spread;
+        sum_bps += bps;
+        out.min_spread_bps = out.min_spread_bps.min(bps);
+        out.max_spread_bps = out.max_spread_bps.max(bps);
+
+        let secs = timestamps.get(i + 1).map_or(0, |next| (next - timestamps[i]).min(max_gap_secs)) as f64;
+        weighted += spread * secs;
+        weighted_bps += bps * secs;
+        total_secs += secs;
+        let slot = (timestamps[i] + utc_offset_secs).rem_euclid(86_400) / bucket_secs * bucket_secs;
+        let bucket = buckets.entry(slot).or_insert((0.0, 0.0));
+        bucket.0 += bps * secs;
+        bucket.1 += secs;
+    }
+
+    if out.valid_quotes == 0 {
+        return Err("no valid quotes".to_string());
+    }
+    let n = out.valid_quotes as f64;
+    out.mean_spread = sum / n;
+    out.mean_spread_bps = sum_bps / n;
+    let per_sec = |total: f64, secs: f64| if secs > 0.0 { total / secs } else { f64::NAN };
+    out.time_weighted_spread = per_sec(weighted, total_secs);
+    out.time_weighted_spread_bps = per_sec(weighted_bps, total_secs);
+    out.profile = buckets
+        .into_iter()
+        .filter(|(_, (_, secs))| *secs > 0.0)
+        .map(|(slot, (bps_secs, secs))| (slot, bps_secs / secs))
+        .collect();
+    Ok(out)
+}
+
+/// Roll's (1984) effective spread estimate from trade prices alone.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RollSpread {
+    /// `2 * sqrt(-cov(dp[t], dp[t-1]))`; NaN when the serial covariance is
+    /// not negative, where the model does not apply.
+    pub spread: f64,
+    /// `spread` in bps of the mean price.
+    pub spread_bps: f64,
+    /// First-order autocovariance of the price changes.
+    pub serial_covariance: f64,
+}
+
+/// Bid-ask bounce makes consecutive trade-price changes negatively
+/// correlated; the effective spread follows from their autocovariance.
+/// Needs at least three finite prices.
+pub fn roll_spread(prices: &[f64]) -> Result<RollSpread, String> {
+    let prices: Vec<f64> = prices.iter().copied().filter(|p| p.is_finite()).collect();
+    if prices.len() < 3 {
+        return Err(format!("need at least 3 finite prices, got {}", prices.len()));
+    }
+    let changes: Vec<f64> = prices.windows(2).map(|w| w[1] - w[0]).collect();
+    let (a, b) = (&changes[1..], &changes[..changes.len() - 1]);
+    let n = a.len() as f64;
+    let (mean_a, mean_b) = (a.iter().sum::<f64>() / n, b.iter().sum::<f64>() / n);
+    let serial_covariance = a.iter().zip(b).map(|(x, y)| (x - mean_a) * (y - mean_b)).sum::<f64>() / n;
+    let spread = if serial_covariance < 0.0 { 2.0 * (-serial_covariance).sqrt() } else { f64::NAN };
+    let mean_price = prices.iter().sum::<f64>() / prices.len() as f64;
+    Ok(RollSpread { spread, spread_bps: spread / mean_price * 10_000.0, serial_covariance })
+}
+
+/// Order book imbalance over the best `levels` price levels (all when
+/// None): `(bid depth - ask depth) / (bid depth + ask depth)`, from -1 (only
+/// offers) to 1 (only bids). NaN for an empty book.
+pub fn book_imbalance(bid_sizes: &[f64], ask_sizes: &[f64], levels: Option<usize>) -> f64 {
+    let depth = |sizes: &[f64]| -> f64 {
+        let n = levels.map_or(sizes.len(), |l| l.min(sizes.len()));
+        sizes[..n].iter().filter(|s| s.is_finite()).sum()
+    };
+    let (bid, ask) = (depth(bid_sizes), depth(ask_sizes));
+    if bid + ask > 0.0 { (bid - ask) / (bid + ask) } else { f64::NAN }
+}
+
+/// Imbalance of each level-2 snapshot, as `(bid_sizes, ask_sizes)`, and its
+/// rolling mean over `window` snapshots (NaN until the window fills).
+pub fn rolling_book_imbalance(snapshots: &[(Vec<f64>, Vec<f64>)], levels: Option<usize>, window: usize) -> (Vec<f64>, Vec<f64>) {
+    let imbalance: Vec<f64> = snapshots.iter().map(|(bids, asks)| book_imbalance(bids, asks, levels)).collect();
+    let rolling = stats::rolling_mean(&imbalance, window);
+    (imbalance, rolling)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: &[f64], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len());
+        for (i, (a, e)) in actual.iter().zip(expected).enumerate() {
+            assert!((a - e).abs() < 1e-9 || (a.is_nan() && e.is_nan()), "index {}: {} != {}", i, a, e);
+        }
+    }
+
+    #[test]
+    fn spread_stats_weight_quotes_by_how_long_they_stood() {
+        // 200 bps for 60s, then 100 bps until the crossed quote, capped at
+        // 300s; the last quote carries no time.
+        let bid = [99.0, 99.5, 101.0, 99.9];
+        let ask = [101.0, 100.5, 100.0, 100.1];
+        let stats = spread_stats(&bid, &ask, &[-60, 0, 4_000, 7_260], 3_600, 0, 300).unwrap();
+        assert_eq!((stats.valid_quotes, stats.invalid_quotes), (3, 1));
+        assert_close(&[stats.mean_spread, stats.mean_spread_bps], &[3.2 / 3.0, 320.0 / 3.0]);
+        assert_close(&[stats.time_weighted_spread, stats.time_weighted_spread_bps], &[420.0 / 360.0, 42_000.0 / 360.0]);
+        assert_close(&[stats.min_spread_bps, stats.max_spread_bps], &[20.0, 200.0]);
+        // The first quote was posted the evening before; the last one has no time.
+        assert_eq!(stats.profile.keys().copied().collect::<Vec<_>>(), vec![0, 82_800]);
+        assert_close(&stats.profile.values().copied().collect::<Vec<_>>(), &[100.0, 200.0]);
+    }
+
+    #[test]
+    fn spread_stats_reject_bad_input() {
+        assert!(spread_stats(&[99.0], &[101.0, 102.0], &[0], 3_600, 0, 300).is_err());
+        assert!(spread_stats(&[99.0], &[101.0], &[0], 7_000, 0, 300).is_err());
+        assert!(spread_stats(&[99.0, 99.0], &[101.0, 101.0], &[60, 0], 3_600, 0, 300).is_err());
+        assert!(spread_stats(&[0.0, f64::NAN], &[101.0, 101.0], &[0, 60], 3_600, 0, 300).is_err());
+    }
+
+    #[test]
+    fn roll_spread_reads_the_bid_ask_bounce() {
+        // Changes alternate +1/-1: the serial covariance is -8/9.
+        let roll = roll_spread(&[100.0, 101.0, f64::NAN, 100.0, 101.0, 100.0]).unwrap();
+        let spread = 2.0 * (8.0f64 / 9.0).sqrt();
+        assert_close(&[roll.serial_covariance, roll.spread, roll.spread_bps], &[-8.0 / 9.0, spread, spread / 100.4 * 10_000.0]);
+
+        let trend = roll_spread(&[1.0, 2.0, 3.0, 4.0]).unwrap();
+        assert_eq!(trend.serial_covariance, 0.0);
+        assert!(trend.spread.is_nan());
+        assert!(roll_spread(&[100.0, f64::NAN, 101.0]).is_err());
+    }
+
+    #[test]
+    fn book_imbalance_sums_the_top_levels() {
+        let (bids, asks) = ([300.0, 100.0, f64::NAN], [100.0, 100.0, 500.0]);
+        assert_close(&[book_imbalance(&bids, &asks, Some(1)), book_imbalance(&bids, &asks, Some(2))], &[0.5, 1.0 / 3.0]);
+        // Every level, skipping the missing size, however many are asked for.
+        assert_close(&[book_imbalance(&bids, &asks, None), book_imbalance(&bids, &asks, Some(10))], &[-3.0 / 11.0, -3.0 / 11.0]);
+        assert!(book_imbalance(&[], &[0.0], None).is_nan());
+    }
+
+    #[test]
+    fn rolling_book_imbalance_gaps_on_an_empty_book() {
+        let snapshots = vec![
+            (vec![3.0], vec![1.0]),
+            (vec![1.0], vec![1.0]),
+            (vec![], vec![]),
+            (vec![1.0], vec![0.0]),
+            (vec![0.0], vec![2.0]),
+        ];
+        let (imbalance, rolling) = rolling_book_imbalance(&snapshots, None, 2);
+        assert_close(&imbalance, &[0.5, 0.0, f64::NAN, 1.0, -1.0]);
+        assert_close(&rolling, &[f64::NAN, 0.25, f64::NAN, f64::NAN, 0.0]);
+    }
+}
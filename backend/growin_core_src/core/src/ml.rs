@@ -0,0 +1,150 @@
+// --- Triple-Barrier Labeling ---
+
+/// Outcome of one labeled event.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BarrierLabel {
+    /// 1 = profit-taking barrier hit first, -1 = stop-loss hit first,
+    /// 0 = vertical (time) barrier unless `sign_at_vertical` is set.
+    pub label: i8,
+    /// Bar index at which the first barrier was touched.
+    pub exit_index: usize,
+    /// Return from the event bar to the exit: the barrier level for horizontal
+    /// exits, the close at `exit_index` for vertical ones.
+    pub ret: f64,
+}
+
+/// Triple-barrier labeling (López de Prado, AFML ch. 3) with barriers scaled
+/// by the ATR at the event bar.
+///
+/// For an event at `t0` with entry `p0 = close[t0]` the horizontal barriers are
+/// `p0 + pt_mult * atr[t0]` and `p0 - sl_mult * atr[t0]` (a multiplier of 0
+/// disables that barrier), and the vertical barrier is `t0 + max_holding`.
+/// Events inside the ATR warm-up or at the last bar cannot be labeled and get
+/// label 0 with a NaN return.
+#[allow(clippy::too_many_arguments)]
+pub fn triple_barrier_labels(
+    close: &[f64],
+    atr: &[f64],
+    events: &[usize],
+    pt_mult: f64,
+    sl_mult: f64,
+    max_holding: usize,
+    high: Option<&[f64]>,
+    low: Option<&[f64]>,
+    sign_at_vertical: bool,
+) -> Vec<BarrierLabel> {
+    let n = close.len();
+    events
+        .iter()
+        .map(|&t0| {
+            let unlabeled = BarrierLabel { label: 0, exit_index: t0, ret: f64::NAN };
+            if t0 + 1 >= n || atr[t0] <= 0.0 || close[t0] == 0.0 {
+                return unlabeled;
+            }
+            let p0 = close[t0];
+            let upper = (pt_mult > 0.0).then(|| p0 + pt_mult * atr[t0]);
+            let lower = (sl_mult > 0.0).then(|| p0 - sl_mult * atr[t0]);
+            let t1 = (t0 + max_holding.max(1)).min(n - 1);
+
+            for t in (t0 + 1)..=t1 {
+                let bar_high = high.map_or(close[t], |h| h[t]);
+                let bar_low = low.map_or(close[t], |l| l[t]);
+                let hit_upper = upper.is_some_and(|u| bar_high >= u);
+                let hit_lower = lower.is_some_and(|l| bar_low <= l);
+                // When both barriers fall inside one bar the order is unknown;
+                // assume the stop was hit first (the conservative choice).
+                if let (true, Some(level)) = (hit_lower, lower) {
+                    return BarrierLabel { label: -1, exit_index: t, ret: level / p0 - 1.0 };
+                }
+                if let (true, Some(level)) = (hit_upper, upper) {
+                    return BarrierLabel { label: 1, exit_index: t, ret: level / p0 - 1.0 };
+                }
+            }
+
+            let ret = close[t1] / p0 - 1.0;
+            let label = if sign_at_vertical && ret != 0.0 { ret.signum() as i8 } else { 0 };
+            BarrierLabel { label, exit_index: t1, ret }
+        })
+        .collect()
+}
+
+// --- Fractional Differentiation ---
+
+/// Binomial-series weights for fractional differencing of order `d`,
+/// truncated once |w_k| falls below `threshold` (or at `max_width` terms).
+/// `weights[k]` applies to the value `k` bars back.
+pub fn frac_diff_weights(d: f64, threshold: f64, max_width: usize) -> Vec<f64> {
+    let mut weights = vec![1.0];
+    let mut k = 1;
+    while weights.len() < max_width {
+        let next = -weights[k - 1] * (d - k as f64 + 1.0) / k as f64;
+        if next.abs() < threshold {
+            break;
+        }
+        weights.push(next);
+        k += 1;
+    }
+    weights
+}
+
+/// Fixed-width window fractional differencing (FFD, AFML ch. 5). Every output
+/// uses the same truncated weight vector, so the transformed series stays
+/// stationary-friendly without the drift of an expanding window. Values before
+/// the first full window, or whose window contains a NaN, are NaN.
+pub fn frac_diff(series: &[f64], d: f64, threshold: f64) -> Vec<f64> {
+    let n = series.len();
+    let mut out = vec![f64::NAN; n];
+    if n == 0 {
+        return out;
+    }
+    let weights = frac_diff_weights(d, threshold, n);
+    let width = weights.len();
+    for i in (width - 1)..n {
+        out[i] = weights.iter().enumerate().map(|(k, w)| w * series[i - k]).sum();
+    }
+    out
+}
+
+// --- Purged Cross-Validation ---
+
+/// Purged k-fold splits with embargo (AFML ch. 7).
+///
+/// Test folds are contiguous blocks (sized like `numpy.array_split`). Sample
+/// `i` carries a label spanning bars `[i, t1[i]]`; a training sample is purged
+/// when that span overlaps the test fold's span, and the `embargo` samples
+/// immediately after each test fold are dropped from training as well.
+pub fn purged_kfold_splits(
+    n_samples: usize,
+    n_splits: usize,
+    embargo: usize,
+    t1: Option<&[usize]>,
+) -> Vec<(Vec<usize>, Vec<usize>)> {
+    let label_end = |i: usize| t1.map_or(i, |t| t[i].max(i));
+    let base = n_samples / n_splits;
+    let extra = n_samples % n_splits;
+
+    let mut splits = Vec::with_capacity(n_splits);
+    let mut start = 0;
+    for fold in 0..n_splits {
+        let end = start + base + usize::from(fold < extra);
+        let test: Vec<usize> = (start..end).collect();
+        let test_span_end = test.iter().map(|&i| label_end(i)).max().unwrap_or(start);
+        let embargo_end = end + embargo;
+
+        let train = (0..n_samples)
+            .filter(|&i| {
+                if (start..end).contains(&i) {
+                    return false;
+                }
+                // Labels that overlap any part of the test span leak information.
+                let overlaps = i <= test_span_end && label_end(i) >= start;
+                let embargoed = i >= end && i < embargo_end;
+                !overlaps && !embargoed
+            })
+            .collect();
+
+        splits.push((train, test));
+        start = end;
+    }
+    splits
+}
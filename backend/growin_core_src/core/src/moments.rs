@@ -0,0 +1,145 @@
+//! Mean and variance over a sliding window, updated in O(1) per value.
+//!
+//! Each step folds in the value entering the window and removes the one
+//! leaving it with Welford's update, instead of re-reducing the whole window,
+//! and stays accurate where `sum_sq / n - mean^2` cancels catastrophically on
+//! prices far from zero. Bollinger Bands, the rolling mean/std/z-score and
+//! their chunked states all run on it, so they agree bit for bit.
+
+use std::collections::VecDeque;
+
+use crate::lanes::{lit, Real};
+
+/// Running moments of the last `period` values. Non-finite values (NaN and
+/// ±Inf) are tracked rather than folded in, and any window containing one
+/// has no moments.
+#[derive(Clone, Debug)]
+pub struct RollingMoments<T> {
+    period: usize,
+    window: VecDeque<T>,
+    /// Finite values in the window, i.e. those folded into `mean`/`m2`.
+    count: usize,
+    /// Non-finite values in the window.
+    gaps: usize,
+    mean: T,
+    /// Sum of squared deviations from `mean`.
+    m2: T,
+    /// Delta degrees of freedom: the variance divides `m2` by `count - ddof`.
+    ddof: usize,
+}
+
+impl<T: Real> RollingMoments<T> {
+    /// `period` must be positive.
+    pub fn new(period: usize) -> Self {
+        Self::with_ddof(period, 0)
+    }
+
+    /// Like [`RollingMoments::new`] with a sample variance for `ddof = 1`
+    /// (pandas' `rolling().std()` default). Windows of `ddof` values or fewer
+    /// have a NaN variance.
+    pub fn with_ddof(period: usize, ddof: usize) -> Self {
+        RollingMoments {
+            period,
+            window: VecDeque::with_capacity(period + 1),
+            count: 0,
+            gaps: 0,
+            mean: T::zero(),
+            m2: T::zero(),
+            ddof,
+        }
+    }
+
+    /// Whether `period` values have been pushed.
+    pub fn is_full(&self) -> bool {
+        self.window.len() == self.period
+    }
+
+    /// Pushes `x`, dropping the oldest value once the window is full.
+    ///
+    /// Returns the window's (mean, variance), population by default, or None
+    /// while the window is not yet full or contains a non-finite value.
+    pub fn push(&mut self, x: T) -> Option<(T, T)> {
+        self.window.push_back(x);
+        let old = if self.window.len() > self.period { self.window.pop_front() } else { None };
+        match old {
+            Some(old) if old.is_finite() && x.is_finite() => self.replace(old, x),
+            _ => {
+                match old {
+                    Some(old) if !old.is_finite() => self.gaps -= 1,
+                    Some(old) => self.remove(old),
+                    None => {}
+                }
+                if !x.is_finite() {
+                    self.gaps += 1;
+                } else {
+                    self.add(x);
+                }
+            }
+        }
+        (self.is_full() && self.gaps == 0).then(|| (self.mean, self.variance()))
+    }
+
+    /// (mean, variance) of the finite values in the window, skipping the
+    /// others instead of voiding the window. The mean of no values is zero.
+    pub fn finite_moments(&self) -> (T, T) {
+        (self.mean, self.variance())
+    }
+
+    fn variance(&self) -> T {
+        match self.count.checked_sub(self.ddof) {
+            Some(dof) if dof > 0 => (self.m2 / lit(dof as f64)).max(T::zero()),
+            _ => T::nan(),
+        }
+    }
+
+    fn add(&mut self, x: T) {
+        self.count += 1;
+        let delta = x - self.mean;
+        self.mean += delta / lit(self.count as f64);
+        self.m2 += delta * (x - self.mean);
+    }
+
+    fn remove(&mut self, x: T) {
+        self.count -= 1;
+        if self.count == 0 {
+            (self.mean, self.m2) = (T::zero(), T::zero());
+            return;
+        }
+        let delta = x - self.mean;
+        self.mean -= delta / lit(self.count as f64);
+        self.m2 -= delta * (x - self.mean);
+    }
+
+    /// Swaps `old` for `new` at a constant count, in one update.
+    fn replace(&mut self, old: T, new: T) {
+        let prev_mean = self.mean;
+        self.mean += (new - old) / lit(self.count as f64);
+        self.m2 += (new - old) * (new - self.mean + old - prev_mean);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn window_recovers_once_an_infinity_rolls_out() {
+        for gap in [f64::INFINITY, f64::NEG_INFINITY, f64::NAN] {
+            let mut moments = RollingMoments::new(3);
+            let out: Vec<_> = [1.0, 2.0, gap, 4.0, 5.0, 6.0, 8.0].into_iter().map(|x| moments.push(x)).collect();
+            assert_eq!(out[..5], [None; 5]);
+            assert_eq!(out[5], Some((5.0, 2.0 / 3.0)));
+            let (mean, variance) = out[6].unwrap();
+            assert!((mean - 19.0 / 3.0).abs() < 1e-12 && (variance - 14.0 / 9.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn finite_moments_skip_the_gaps() {
+        let mut moments = RollingMoments::with_ddof(4, 1);
+        for x in [2.0, f64::INFINITY, 4.0, 6.0] {
+            moments.push(x);
+        }
+        assert_eq!(moments.finite_moments(), (4.0, 4.0));
+    }
+}
@@ -0,0 +1,342 @@
+//! Exact decimal arithmetic for money.
+//!
+//! Indicators stay in f64, but anything reconciled against a broker
+//! statement (cost basis, fees, tax, P&L) is computed in `rust_decimal`
+//! (96-bit mantissa, 28 significant digits) so no binary rounding error is
+//! accumulated.
+
+use rust_decimal::{Decimal, RoundingStrategy};
+use std::collections::VecDeque;
+
+/// How closing fills are matched against open lots.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CostBasisMethod {
+    /// Close the oldest lots first.
+    Fifo,
+    /// Close at the running average cost of the position.
+    Average,
+}
+
+impl std::str::FromStr for CostBasisMethod {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "fifo" => Ok(CostBasisMethod::Fifo),
+            "average" | "avg" => Ok(CostBasisMethod::Average),
+            other => Err(format!("Unknown cost basis method '{}', expected 'fifo' or 'average'", other)),
+        }
+    }
+}
+
+/// One execution: `quantity` is positive for buys and negative for sells.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Fill {
+    pub quantity: Decimal,
+    pub price: Decimal,
+    pub fee: Decimal,
+}
+
+/// Open lot: signed quantity and the signed total cost paid for it
+/// (including its share of opening fees).
+#[derive(Clone, Copy, Debug)]
+struct Lot {
+    quantity: Decimal,
+    cost: Decimal,
+}
+
+/// Result of replaying a position's fills.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PositionPnl {
+    /// Net signed quantity still open.
+    pub position: Decimal,
+    /// Signed cost of the open quantity, opening fees included.
+    pub cost_basis: Decimal,
+    /// P&L of closed quantity, net of closing fees.
+    pub realized_pnl: Decimal,
+    /// Total fees paid.
+    pub fees: Decimal,
+}
+
+impl PositionPnl {
+    pub fn average_cost(&self) -> Option<Decimal> {
+        (!self.position.is_zero()).then(|| self.cost_basis / self.position)
+    }
+
+    pub fn unrealized_pnl(&self, mark_price: Decimal) -> Decimal {
+        self.position * mark_price - self.cost_basis
+    }
+}
+
+/// Replays fills into open lots, realizing P&L whenever a fill reduces the
+/// position. A fill that flips the position closes it and opens the
+/// remainder at the fill price; its fee is split pro rata.
+pub fn position_pnl(fills: &[Fill], method: CostBasisMethod) -> PositionPnl {
+    let mut lots: VecDeque<Lot> = VecDeque::new();
+    let mut realized = Decimal::ZERO;
+    let mut fees = Decimal::ZERO;
+
+    for fill in fills {
+        fees += fill.fee;
+        if fill.quantity.is_zero() {
+            realized -= fill.fee;
+            continue;
+        }
+        let mut remaining = fill.quantity;
+        // Close against lots of the opposite sign.
+        while !remaining.is_zero() {
+            let Some(lot) = lots.front_mut() else { break };
+            if lot.quantity.is_sign_positive() == remaining.is_sign_positive() {
+                break;
+            }
+            let closed = if remaining.abs() >= lot.quantity.abs() { lot.quantity } else { -remaining };
+            let closed_cost = lot.cost * closed / lot.quantity;
+            realized += closed * fill.price - closed_cost;
+            lot.quantity -= closed;
+            lot.cost -= closed_cost;
+            remaining += closed;
+            if lot.quantity.is_zero() {
+                lots.pop_front();
+            }
+        }
+        let opened_fee = fill.fee * remaining / fill.quantity;
+        realized -= fill.fee - opened_fee;
+        if remaining.is_zero() {
+            continue;
+        }
+        let lot = Lot { quantity: remaining, cost: remaining * fill.price + opened_fee };
+        match (method, lots.back_mut()) {
+            (CostBasisMethod::Average, Some(pooled)) => {
+                pooled.quantity += lot.quantity;
+                pooled.cost += lot.cost;
+            }
+            _ => lots.push_back(lot),
+        }
+    }
+
+    PositionPnl {
+        position: lots.iter().map(|l| l.quantity).sum(),
+        cost_basis: lots.iter().map(|l| l.cost).sum(),
+        realized_pnl: realized,
+        fees,
+    }
+}
+
+/// Fee on a trade: `|notional| * rate`, clamped to `[minimum, maximum]`.
+pub fn trade_fee(notional: Decimal, rate: Decimal, minimum: Decimal, maximum: Option<Decimal>) -> Decimal {
+    let fee = (notional.abs() * rate).max(minimum);
+    maximum.map_or(fee, |max| fee.min(max))
+}
+
+/// Whether converting between two ISO 4217 codes needs an FX trade. Pence
+/// quotes (`GBX`, `GBp`) count as sterling.
+pub fn needs_fx(from_ccy: &str, to_ccy: &str) -> bool {
+    let canonical = |ccy: &str| match ccy.to_ascii_uppercase().as_str() {
+        "GBX" => "GBP".to_string(),
+        other => other.to_string(),
+    };
+    canonical(from_ccy) != canonical(to_ccy)
+}
+
+/// FX fee on converting `amount` (sign ignored): `fee_bps` basis points when
+/// the currencies differ, zero otherwise.
+pub fn fx_cost(amount: Decimal, from_ccy: &str, to_ccy: &str, fee_bps: Decimal) -> Decimal {
+    if needs_fx(from_ccy, to_ccy) {
+        amount.abs() * fee_bps / Decimal::from(10_000)
+    } else {
+        Decimal::ZERO
+    }
+}
+
+/// Kind of a listed instrument, as far as UK transaction taxes care.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InstrumentClass {
+    /// Shares of a UK-incorporated company on the LSE main market,
+    /// investment trusts included.
+    Share,
+    /// ETFs and other exchange-traded products; exempt from stamp duty.
+    Etf,
+    /// AIM-listed shares; exempt from stamp duty since 2014.
+    Aim,
+}
+
+impl std::str::FromStr for InstrumentClass {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "share" | "equity" | "trust" => Ok(InstrumentClass::Share),
+            "etf" | "etp" | "etc" => Ok(InstrumentClass::Etf),
+            "aim" => Ok(InstrumentClass::Aim),
+            other => Err(format!("Unknown instrument class '{}', expected 'share', 'etf' or 'aim'", other)),
+        }
+    }
+}
+
+/// UK taxes and levies on one trade, in GBP.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct UkTradeCosts {
+    /// Stamp Duty Reserve Tax: 0.5% of a purchase, rounded to the penny.
+    pub stamp_duty: Decimal,
+    /// Panel on Takeovers and Mergers levy: a flat £1 on share trades over
+    /// £10,000.
+    pub ptm_levy: Decimal,
+}
+
+impl UkTradeCosts {
+    pub fn total(&self) -> Decimal {
+        self.stamp_duty + self.ptm_levy
+    }
+}
+
+/// Stamp duty and PTM levy on a trade of `notional` GBP (sign ignored) in
+/// `ticker`, a normalized symbol. Only LSE listings (`.L`) are liable; `.IL`
+/// international order book listings are foreign companies and are not.
+pub fn uk_trade_costs(ticker: &str, notional: Decimal, is_buy: bool, class: InstrumentClass) -> UkTradeCosts {
+    if !ticker.to_ascii_uppercase().ends_with(".L") {
+        return UkTradeCosts::default();
+    }
+    let notional = notional.abs();
+    let stamp_duty = if is_buy && class == InstrumentClass::Share {
+        (notional * Decimal::new(5, 3)).round_dp_with_strategy(2, RoundingStrategy::MidpointAwayFromZero)
+    } else {
+        Decimal::ZERO
+    };
+    // The levy funds the Takeover Panel, so it follows the Takeover Code:
+    // company shares (AIM included) on both sides, but not funds.
+    let ptm_levy = if class != InstrumentClass::Etf && notional > Decimal::from(10_000) {
+        Decimal::ONE
+    } else {
+        Decimal::ZERO
+    };
+    UkTradeCosts { stamp_duty, ptm_levy }
+}
+
+/// Tax on realized gains above a tax-free `allowance`. Losses owe nothing.
+pub fn capital_gains_tax(realized_gains: Decimal, rate: Decimal, allowance: Decimal) -> Decimal {
+    (realized_gains - allowance).max(Decimal::ZERO) * rate
+}
+
+/// How often interest is credited to a savings balance.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Compounding {
+    Monthly,
+    Quarterly,
+    Annually,
+}
+
+impl std::str::FromStr for Compounding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "monthly" => Ok(Compounding::Monthly),
+            "quarterly" => Ok(Compounding::Quarterly),
+            "annually" | "annual" | "yearly" => Ok(Compounding::Annually),
+            other => Err(format!("Unknown compounding '{}', expected 'monthly', 'quarterly' or 'annually'", other)),
+        }
+    }
+}
+
+impl Compounding {
+    fn months(self) -> u32 {
+        match self {
+            Compounding::Monthly => 1,
+            Compounding::Quarterly => 3,
+            Compounding::Annually => 12,
+        }
+    }
+}
+
+/// Balance of a savings plan at the end of a month.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SavingsPoint {
+    /// Months since the start; 0 is the opening balance.
+    pub month: u32,
+    pub balance: Decimal,
+    /// Principal plus every contribution so far.
+    pub contributions: Decimal,
+    /// `balance - contributions`: interest credited so far.
+    pub growth: Decimal,
+}
+
+/// Month-by-month projection of `principal` plus a contribution at the end
+/// of every month, growing at `annual_rate` (0.05 for 5%).
+///
+/// Interest accrues monthly at `annual_rate / 12` on the balance and is
+/// credited every compounding period, so money paid in mid-period earns
+/// simple interest until the next credit. Fails rather than panicking when
+/// the balance outgrows `Decimal` (about 7.9e28).
+pub fn project_savings(
+    principal: Decimal,
+    monthly_contribution: Decimal,
+    annual_rate: Decimal,
+    months: u32,
+    compounding: Compounding,
+) -> Result<Vec<SavingsPoint>, String> {
+    let overflow = |month: u32| format!("savings projection overflows the decimal range at month {}", month);
+    let monthly_rate = annual_rate / Decimal::from(12);
+    let period = compounding.months();
+    let (mut balance, mut contributions, mut accrued) = (principal, principal, Decimal::ZERO);
+    let mut out = Vec::with_capacity(months as usize + 1);
+    out.push(SavingsPoint { month: 0, balance, contributions, growth: Decimal::ZERO });
+    for month in 1..=months {
+        accrued = balance.checked_mul(monthly_rate).and_then(|i| accrued.checked_add(i)).ok_or_else(|| overflow(month))?;
+        if month % period == 0 {
+            balance = balance.checked_add(accrued).ok_or_else(|| overflow(month))?;
+            accrued = Decimal::ZERO;
+        }
+        balance = balance.checked_add(monthly_contribution).ok_or_else(|| overflow(month))?;
+        contributions = contributions.checked_add(monthly_contribution).ok_or_else(|| overflow(month))?;
+        let growth = balance.checked_sub(contributions).ok_or_else(|| overflow(month))?;
+        out.push(SavingsPoint { month, balance, contributions, growth });
+    }
+    Ok(out)
+}
+
+/// Rounds to exactly `dp` places, half away from zero as on broker
+/// statements (`dp=2` gives `Decimal("4.50")`, not `Decimal("4.5")`).
+pub fn round(value: Decimal, dp: Option<u32>) -> Decimal {
+    match dp {
+        Some(dp) => {
+            let mut rounded = value.round_dp_with_strategy(dp, RoundingStrategy::MidpointAwayFromZero);
+            rounded.rescale(dp);
+            rounded
+        }
+        None => value.normalize(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn monthly_compounding_credits_interest_every_month() {
+        // 1000 at 12% is 1% a month: 1000 -> 1110 -> 1221.1 -> 1333.311.
+        let points = project_savings(Decimal::from(1000), Decimal::from(100), Decimal::new(12, 2), 3, Compounding::Monthly).unwrap();
+        let balances: Vec<Decimal> = points.iter().map(|p| p.balance).collect();
+        assert_eq!(balances, [Decimal::from(1000), Decimal::from(1110), Decimal::new(12211, 1), Decimal::new(1333311, 3)]);
+        let last = points.last().unwrap();
+        assert_eq!(last.contributions, Decimal::from(1300));
+        assert_eq!(last.growth, Decimal::new(33311, 3));
+    }
+
+    #[test]
+    fn annual_compounding_pays_simple_interest_within_the_year() {
+        // 1200 at 12% accrues 12 x 1% = 144 on the opening balance plus
+        // 1% on each month's 100 for the months after it was paid in.
+        let points = project_savings(Decimal::from(1200), Decimal::from(100), Decimal::new(12, 2), 12, Compounding::Annually).unwrap();
+        assert_eq!(points[11].balance, Decimal::from(2300));
+        assert_eq!(points[11].growth, Decimal::ZERO);
+        assert_eq!(points[12].balance, Decimal::from(1200 + 144 + 66 + 1200));
+        assert_eq!(points[12].contributions, Decimal::from(2400));
+    }
+
+    #[test]
+    fn overflowing_projections_fail_instead_of_panicking() {
+        let err = project_savings(Decimal::from(1000), Decimal::ZERO, Decimal::new(1, 1), 12_000, Compounding::Monthly).unwrap_err();
+        assert!(err.contains("overflows"));
+        assert!(project_savings(Decimal::MAX, Decimal::ONE, Decimal::ZERO, 1, Compounding::Monthly).is_err());
+    }
+}
@@ -0,0 +1,227 @@
+//! Pre-trade order checks.
+//!
+//! Mirrors the rules Trading212 enforces on order placement, so an invalid
+//! order can be blocked, with every reason at once, before it reaches the
+//! API. Quantities and prices are exact decimals, since precision limits are
+//! themselves decimal.
+
+use rust_decimal::Decimal;
+
+use crate::risk::Side;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OrderType {
+    Market,
+    Limit,
+    Stop,
+    StopLimit,
+}
+
+impl std::str::FromStr for OrderType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace('-', "_").as_str() {
+            "market" => Ok(OrderType::Market),
+            "limit" => Ok(OrderType::Limit),
+            "stop" => Ok(OrderType::Stop),
+            "stop_limit" => Ok(OrderType::StopLimit),
+            other => Err(format!("Unknown order type '{}', expected 'market', 'limit', 'stop' or 'stop_limit'", other)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Order {
+    /// `Long` to buy, `Short` to sell.
+    pub side: Side,
+    pub order_type: OrderType,
+    pub quantity: Decimal,
+    /// Price the order is expected to fill at: the limit or stop price, or
+    /// the last price for market orders.
+    pub price: Decimal,
+}
+
+/// Per-instrument limits, as in Trading212's instrument metadata.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InstrumentRules {
+    pub min_quantity: Decimal,
+    /// Quantities must be a multiple of this (1 for whole shares only).
+    pub lot_size: Decimal,
+    /// Limit and stop prices must be a multiple of this, when known.
+    pub tick_size: Option<Decimal>,
+    /// Largest position the broker allows in the instrument, in shares.
+    pub max_open_quantity: Option<Decimal>,
+    pub market_open: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccountState {
+    /// Cash free to spend on buys.
+    pub buying_power: Decimal,
+    /// Shares already held in the instrument.
+    pub position_quantity: Decimal,
+    /// Largest value the account allows one position to reach.
+    pub max_position_value: Option<Decimal>,
+}
+
+/// A reason an order would be rejected.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Violation {
+    /// Stable identifier for the UI, e.g. "insufficient_funds".
+    pub code: &'static str,
+    /// The order field the violation is about.
+    pub field: &'static str,
+    pub message: String,
+}
+
+fn violation(code: &'static str, field: &'static str, message: String) -> Violation {
+    Violation { code, field, message }
+}
+
+/// Every rule `order` breaks; empty when it can be placed. Sells may not
+/// exceed the position, as Trading212 does not allow short selling; market
+/// orders need the market to be open, while pending orders may be queued.
+pub fn validate_order(order: &Order, rules: &InstrumentRules, account: &AccountState) -> Vec<Violation> {
+    let mut out = Vec::new();
+    let quantity = order.quantity;
+    if quantity <= Decimal::ZERO {
+        out.push(violation("invalid_quantity", "quantity", format!("quantity must be positive, got {}", quantity)));
+        return out;
+    }
+    if quantity < rules.min_quantity {
+        out.push(violation(
+            "min_quantity",
+            "quantity",
+            format!("quantity {} is below the minimum of {}", quantity, rules.min_quantity.normalize()),
+        ));
+    }
+    if rules.lot_size > Decimal::ZERO && !(quantity % rules.lot_size).is_zero() {
+        out.push(violation(
+            "quantity_precision",
+            "quantity",
+            format!("quantity {} is not a multiple of the lot size {}", quantity, rules.lot_size.normalize()),
+        ));
+    }
+    if order.price <= Decimal::ZERO {
+        out.push(violation("invalid_price", "price", format!("price must be positive, got {}", order.price)));
+    } else if let Some(tick) = rules.tick_size.filter(|t| *t > Decimal::ZERO && order.order_type != OrderType::Market) {
+        if !(order.price % tick).is_zero() {
+            out.push(violation(
+                "price_tick",
+                "price",
+                format!("price {} is not a multiple of the tick size {}", order.price, tick.normalize()),
+            ));
+        }
+    }
+    if order.order_type == OrderType::Market && !rules.market_open {
+        out.push(violation("market_closed", "type", "the market is closed; place a limit or stop order instead".to_string()));
+    }
+
+    let value = quantity * order.price;
+    match order.side {
+        Side::Long => {
+            if value > account.buying_power {
+                out.push(violation(
+                    "insufficient_funds",
+                    "quantity",
+                    format!("order value {} exceeds buying power {}", value.round_dp(2), account.buying_power.round_dp(2)),
+                ));
+            }
+            let resulting = account.position_quantity + quantity;
+            if let Some(max) = rules.max_open_quantity {
+                if resulting > max {
+                    out.push(violation(
+                        "max_open_quantity",
+                        "quantity",
+                        format!("the position would reach {} shares, above the instrument maximum of {}", resulting.normalize(), max.normalize()),
+                    ));
+                }
+            }
+            if let Some(max) = account.max_position_value {
+                let resulting_value = resulting * order.price;
+                if resulting_value > max {
+                    out.push(violation(
+                        "max_position_value",
+                        "quantity",
+                        format!("the position would be worth {}, above the limit of {}", resulting_value.round_dp(2), max.round_dp(2)),
+                    ));
+                }
+            }
+        }
+        Side::Short => {
+            if quantity > account.position_quantity {
+                out.push(violation(
+                    "insufficient_position",
+                    "quantity",
+                    format!("cannot sell {} shares of a {} share position", quantity.normalize(), account.position_quantity.normalize()),
+                ));
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(v: &str) -> Decimal {
+        v.parse().unwrap()
+    }
+
+    fn order(side: Side, order_type: OrderType, quantity: &str, price: &str) -> Order {
+        Order { side, order_type, quantity: d(quantity), price: d(price) }
+    }
+
+    fn rules() -> InstrumentRules {
+        InstrumentRules { min_quantity: d("0.2"), lot_size: d("0.1"), tick_size: Some(d("0.01")), max_open_quantity: Some(d("100")), market_open: false }
+    }
+
+    fn account(buying_power: &str, position_quantity: &str) -> AccountState {
+        AccountState { buying_power: d(buying_power), position_quantity: d(position_quantity), max_position_value: Some(d("1000")) }
+    }
+
+    fn codes(violations: &[Violation]) -> Vec<&'static str> {
+        violations.iter().map(|v| v.code).collect()
+    }
+
+    #[test]
+    fn a_valid_limit_order_passes_while_the_market_is_closed() {
+        assert!(validate_order(&order(Side::Long, OrderType::Limit, "2.5", "10.05"), &rules(), &account("500", "0")).is_empty());
+    }
+
+    #[test]
+    fn every_broken_rule_is_reported_at_once() {
+        let violations = validate_order(&order(Side::Long, OrderType::Market, "0.15", "10.005"), &rules(), &account("1", "0"));
+        // Market orders fill at the last price, so its tick does not matter.
+        assert_eq!(codes(&violations), vec!["min_quantity", "quantity_precision", "market_closed", "insufficient_funds"]);
+        assert_eq!(violations[0].message, "quantity 0.15 is below the minimum of 0.2");
+        assert_eq!(violations[2].field, "type");
+
+        let violations = validate_order(&order(Side::Long, OrderType::StopLimit, "1", "10.005"), &rules(), &account("500", "0"));
+        assert_eq!(codes(&violations), vec!["price_tick"]);
+    }
+
+    #[test]
+    fn buys_are_capped_by_the_resulting_position() {
+        // 95 + 10 shares, worth 1050 at 10.
+        let violations = validate_order(&order(Side::Long, OrderType::Limit, "10", "10"), &rules(), &account("500", "95"));
+        assert_eq!(codes(&violations), vec!["max_open_quantity", "max_position_value"]);
+        assert_eq!(violations[1].message, "the position would be worth 1050, above the limit of 1000");
+    }
+
+    #[test]
+    fn sells_cannot_exceed_the_position() {
+        let violations = validate_order(&order(Side::Short, OrderType::Limit, "5", "10"), &rules(), &account("0", "3"));
+        assert_eq!(codes(&violations), vec!["insufficient_position"]);
+        assert_eq!(violations[0].message, "cannot sell 5 shares of a 3 share position");
+        assert!(validate_order(&order(Side::Short, OrderType::Limit, "3", "10"), &rules(), &account("0", "3")).is_empty());
+    }
+
+    #[test]
+    fn non_positive_quantities_and_prices_are_rejected() {
+        assert_eq!(codes(&validate_order(&order(Side::Long, OrderType::Limit, "0", "0"), &rules(), &account("500", "0"))), vec!["invalid_quantity"]);
+        assert_eq!(codes(&validate_order(&order(Side::Long, OrderType::Limit, "1", "0"), &rules(), &account("500", "0"))), vec!["invalid_price"]);
+    }
+}
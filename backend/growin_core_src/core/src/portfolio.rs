@@ -0,0 +1,119 @@
+//! Portfolio risk from position values and a return covariance matrix.
+//!
+//! Positions are given as values, indexed like the rows of the covariance
+//! matrix of their per-period returns. [`what_if`] evaluates the portfolio
+//! before and after a trade so the two can be compared.
+
+use crate::stats;
+
+/// Risk and concentration of a long-only portfolio.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PortfolioMetrics {
+    pub value: f64,
+    pub weights: Vec<f64>,
+    /// Standard deviation of portfolio returns per period.
+    pub volatility: f64,
+    /// Parametric (normal, zero-mean) one-period value at risk, as a
+    /// positive amount.
+    pub var: f64,
+    /// Herfindahl index, the sum of squared weights.
+    pub hhi: f64,
+    /// `1 / hhi`: how many equal positions would be as concentrated.
+    pub effective_holdings: f64,
+    pub max_weight: f64,
+    /// Weighted average correlation between distinct positions, NaN with
+    /// fewer than two.
+    pub average_correlation: f64,
+}
+
+fn check_covariance(n: usize, covariance: &[Vec<f64>]) -> Result<(), String> {
+    if covariance.len() != n || covariance.iter().any(|row| row.len() != n) {
+        return Err(format!("covariance must be {0}x{0} to match the positions", n));
+    }
+    if let Some(i) = (0..n).find(|&i| covariance[i][i].is_nan() || covariance[i][i] < 0.0) {
+        return Err(format!("covariance has a negative or NaN variance at index {}", i));
+    }
+    Ok(())
+}
+
+/// Metrics of the portfolio holding `values`, with VaR at `confidence` (e.g.
+/// 0.95).
+pub fn portfolio_metrics(values: &[f64], covariance: &[Vec<f64>], confidence: f64) -> Result<PortfolioMetrics, String> {
+    let n = values.len();
+    check_covariance(n, covariance)?;
+    if let Some(i) = values.iter().position(|v| v.is_nan() || *v < 0.0) {
+        return Err(format!("position value at index {} must be non-negative, got {}", i, values[i]));
+    }
+    let value: f64 = values.iter().sum();
+    if value <= 0.0 {
+        return Err("the portfolio has no value".to_string());
+    }
+    let weights: Vec<f64> = values.iter().map(|v| v / value).collect();
+    let variance: f64 = (0..n).map(|i| (0..n).map(|j| weights[i] * weights[j] * covariance[i][j]).sum::<f64>()).sum();
+    let volatility = variance.max(0.0).sqrt();
+    let hhi: f64 = weights.iter().map(|w| w * w).sum();
+
+    let (mut corr_sum, mut pair_weight) = (0.0, 0.0);
+    for i in 0..n {
+        for j in 0..n {
+            let sd = (covariance[i][i] * covariance[j][j]).sqrt();
+            if i != j && sd > 0.0 {
+                let w = weights[i] * weights[j];
+                corr_sum += w * covariance[i][j] / sd;
+                pair_weight += w;
+            }
+        }
+    }
+    Ok(PortfolioMetrics {
+        value,
+        volatility,
+        var: stats::normal_quantile(confidence) * volatility * value,
+        hhi,
+        effective_holdings: 1.0 / hhi,
+        max_weight: weights.iter().copied().fold(0.0, f64::max),
+        average_correlation: if pair_weight > 0.0 { corr_sum / pair_weight } else { f64::NAN },
+        weights,
+    })
+}
+
+/// Correlation of asset `i`'s returns with the portfolio's; NaN when either
+/// has no variance.
+pub fn correlation_to_portfolio(weights: &[f64], covariance: &[Vec<f64>], i: usize) -> f64 {
+    let n = weights.len();
+    let cov_ip: f64 = (0..n).map(|j| weights[j] * covariance[i][j]).sum();
+    let var_p: f64 = (0..n).map(|a| weights[a] * (0..n).map(|b| weights[b] * covariance[a][b]).sum::<f64>()).sum();
+    let denom = (covariance[i][i] * var_p).sqrt();
+    if denom > 0.0 { cov_ip / denom } else { f64::NAN }
+}
+
+/// The portfolio before and after a trade.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WhatIf {
+    pub before: PortfolioMetrics,
+    pub after: PortfolioMetrics,
+    /// Correlation of the traded asset with the portfolio before the trade.
+    pub correlation_to_portfolio: f64,
+}
+
+/// Re-evaluates the portfolio with `trade_value` added to position `asset`
+/// (negative to sell). Selling more than is held is an error.
+pub fn what_if(
+    values: &[f64],
+    covariance: &[Vec<f64>],
+    asset: usize,
+    trade_value: f64,
+    confidence: f64,
+) -> Result<WhatIf, String> {
+    if asset >= values.len() {
+        return Err(format!("asset index {} is out of range for {} positions", asset, values.len()));
+    }
+    let mut after = values.to_vec();
+    after[asset] += trade_value;
+    if after[asset] < -1e-9 * values[asset].abs().max(1.0) {
+        return Err(format!("the trade sells {} but only {} is held", -trade_value, values[asset]));
+    }
+    after[asset] = after[asset].max(0.0);
+    let before = portfolio_metrics(values, covariance, confidence)?;
+    let correlation = correlation_to_portfolio(&before.weights, covariance, asset);
+    Ok(WhatIf { after: portfolio_metrics(&after, covariance, confidence)?, before, correlation_to_portfolio: correlation })
+}
@@ -0,0 +1,123 @@
+use crate::candles::Candles;
+use crate::indicators;
+use crate::stats;
+
+/// Market regime for one bar.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Regime {
+    /// Not enough history for every rule yet.
+    Unknown,
+    Uptrend,
+    Downtrend,
+    Range,
+    HighVol,
+}
+
+impl Regime {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Regime::Unknown => "unknown",
+            Regime::Uptrend => "uptrend",
+            Regime::Downtrend => "downtrend",
+            Regime::Range => "range",
+            Regime::HighVol => "high_vol",
+        }
+    }
+}
+
+/// Thresholds shared by the strategy selector and the UI badges.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RegimeConfig {
+    /// SMA whose slope defines trend direction.
+    pub ma_period: usize,
+    /// Bars over which the MA slope is measured.
+    pub slope_lookback: usize,
+    /// Minimum MA change per bar (fraction, e.g. 0.0005 = 0.05%/bar) for a trend.
+    pub slope_threshold: f64,
+    pub adx_period: usize,
+    /// Minimum ADX for a trend; below it the market is ranging.
+    pub adx_threshold: f64,
+    /// Window of the realized volatility (std of one-bar returns).
+    pub vol_period: usize,
+    /// Window of the baseline the realized volatility is compared against.
+    pub vol_lookback: usize,
+    /// Realized vol above `vol_multiplier` x its baseline mean is high-vol.
+    pub vol_multiplier: f64,
+    /// Delta degrees of freedom of the realized volatility.
+    pub ddof: usize,
+}
+
+impl Default for RegimeConfig {
+    fn default() -> Self {
+        RegimeConfig {
+            ma_period: 50,
+            slope_lookback: 10,
+            slope_threshold: 0.0005,
+            adx_period: 14,
+            adx_threshold: 25.0,
+            vol_period: 20,
+            vol_lookback: 100,
+            vol_multiplier: 1.5,
+            ddof: 0,
+        }
+    }
+}
+
+/// Classifies each bar. High volatility takes precedence; otherwise a bar is
+/// trending when ADX confirms trend strength and the MA slope gives the
+/// direction, and ranging when either condition fails.
+pub fn classify_regime(candles: &Candles, cfg: &RegimeConfig) -> Vec<Regime> {
+    let n = candles.len();
+    let close = &candles.close;
+
+    let mut ma = indicators::sma(close, cfg.ma_period);
+    indicators::mask_warmup(&mut ma, cfg.ma_period.saturating_sub(1));
+    let (mut adx, _, _) = indicators::adx(&candles.high, &candles.low, close, cfg.adx_period);
+    indicators::mask_warmup(&mut adx, (2 * cfg.adx_period).saturating_sub(1));
+
+    let returns: Vec<f64> = (0..n)
+        .map(|i| if i == 0 || close[i - 1] == 0.0 { f64::NAN } else { close[i] / close[i - 1] - 1.0 })
+        .collect();
+    let vol = stats::rolling_std_ddof(&returns, cfg.vol_period, cfg.ddof);
+
+    let mut vol_sum = 0.0;
+    let mut vol_count = 0usize;
+    (0..n)
+        .map(|i| {
+            // Running mean of the trailing `vol_lookback` realized-vol values.
+            if !vol[i].is_nan() {
+                vol_sum += vol[i];
+                vol_count += 1;
+            }
+            if i >= cfg.vol_lookback && !vol[i - cfg.vol_lookback].is_nan() {
+                vol_sum -= vol[i - cfg.vol_lookback];
+                vol_count -= 1;
+            }
+
+            let slope = if i >= cfg.slope_lookback {
+                let prev = ma[i - cfg.slope_lookback];
+                (ma[i] / prev - 1.0) / cfg.slope_lookback as f64
+            } else {
+                f64::NAN
+            };
+            // The baseline always includes the current bar, so it is defined
+            // whenever the realized vol is.
+            if slope.is_nan() || adx[i].is_nan() || vol[i].is_nan() {
+                return Regime::Unknown;
+            }
+
+            let baseline = vol_sum / vol_count as f64;
+            if baseline > 0.0 && vol[i] > cfg.vol_multiplier * baseline {
+                Regime::HighVol
+            } else if adx[i] < cfg.adx_threshold {
+                Regime::Range
+            } else if slope > cfg.slope_threshold {
+                Regime::Uptrend
+            } else if slope < -cfg.slope_threshold {
+                Regime::Downtrend
+            } else {
+                Regime::Range
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,267 @@
+//! Trade risk: stop placement and position sizing.
+//!
+//! A trade's risk per share ("1R") is the distance from entry to its stop;
+//! sizing a position so that distance costs a fixed budget keeps every trade
+//! risking the same amount whatever its volatility.
+
+use crate::candles::Candles;
+use crate::indicators;
+
+/// Direction of a position.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Side {
+    Long,
+    Short,
+}
+
+impl std::str::FromStr for Side {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "long" | "buy" => Ok(Side::Long),
+            "short" | "sell" => Ok(Side::Short),
+            other => Err(format!("Unknown side '{}', expected 'long' or 'short'", other)),
+        }
+    }
+}
+
+impl Side {
+    /// +1 for long, -1 for short.
+    pub fn sign(self) -> f64 {
+        match self {
+            Side::Long => 1.0,
+            Side::Short => -1.0,
+        }
+    }
+}
+
+/// How a stop is placed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StopMethod {
+    /// `multiplier` x ATR(`period`) from entry.
+    Atr,
+    /// Beyond the lowest low (highest high when short) of the last `period`
+    /// bars, with a buffer of `multiplier` x ATR.
+    Swing,
+    /// `multiplier` percent from entry.
+    Pct,
+}
+
+impl std::str::FromStr for StopMethod {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "atr" => Ok(StopMethod::Atr),
+            "swing" => Ok(StopMethod::Swing),
+            "pct" | "percent" => Ok(StopMethod::Pct),
+            other => Err(format!("Unknown stop method '{}', expected 'atr', 'swing' or 'pct'", other)),
+        }
+    }
+}
+
+impl StopMethod {
+    pub fn default_multiplier(self) -> f64 {
+        match self {
+            StopMethod::Atr => 2.0,
+            StopMethod::Swing => 0.0,
+            StopMethod::Pct => 5.0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct StopSuggestion {
+    pub stop: f64,
+    /// `|entry - stop|`, the trade's 1R.
+    pub risk_per_share: f64,
+    /// `risk_per_share / entry * 100`.
+    pub distance_pct: f64,
+    /// Latest ATR(`period`); NaN for `Pct` with too little history.
+    pub atr: f64,
+    /// `risk_per_share / atr`.
+    pub distance_atr: f64,
+    /// Shares whose 1R equals the risk budget, when one was given.
+    pub position_size: Option<f64>,
+}
+
+/// Suggests a stop for an entry at `entry` after the last bar of `candles`.
+pub fn suggest_stop(
+    candles: &Candles,
+    entry: f64,
+    side: Side,
+    method: StopMethod,
+    multiplier: f64,
+    period: usize,
+    risk_budget: Option<f64>,
+) -> Result<StopSuggestion, String> {
+    if !entry.is_finite() || entry <= 0.0 {
+        return Err(format!("entry_price must be positive, got {}", entry));
+    }
+    if multiplier.is_nan() || multiplier < 0.0 {
+        return Err(format!("multiplier must be non-negative, got {}", multiplier));
+    }
+    if let Some(budget) = risk_budget {
+        if budget.is_nan() || budget <= 0.0 {
+            return Err(format!("risk_budget must be positive, got {}", budget));
+        }
+    }
+    let n = candles.len();
+    let atr = if n >= period.max(1) {
+        indicators::atr(&candles.high, &candles.low, &candles.close, period)[n - 1]
+    } else {
+        f64::NAN
+    };
+    if method != StopMethod::Pct && atr.is_nan() {
+        return Err(format!("need at least {} bars for the ATR, got {}", period, n));
+    }
+
+    let sign = side.sign();
+    let stop = match method {
+        StopMethod::Atr => entry - sign * multiplier * atr,
+        StopMethod::Swing => {
+            let start = n.saturating_sub(period);
+            let swing = match side {
+                Side::Long => candles.low[start..n].iter().copied().fold(f64::INFINITY, f64::min),
+                Side::Short => candles.high[start..n].iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            };
+            swing - sign * multiplier * atr
+        }
+        StopMethod::Pct => entry * (1.0 - sign * multiplier / 100.0),
+    };
+    let risk_per_share = sign * (entry - stop);
+    if risk_per_share.is_nan() || risk_per_share <= 0.0 {
+        return Err(format!("the stop ({}) is not on the losing side of the entry ({})", stop, entry));
+    }
+    Ok(StopSuggestion {
+        stop,
+        risk_per_share,
+        distance_pct: risk_per_share / entry * 100.0,
+        atr,
+        distance_atr: risk_per_share / atr,
+        position_size: risk_budget.map(|budget| budget / risk_per_share),
+    })
+}
+
+/// Money at risk in an open position if its stop is hit.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PositionRisk {
+    /// Long when the stop is below the entry.
+    pub side: Side,
+    pub risk_per_share: f64,
+    /// `risk_per_share * |quantity|`.
+    pub risk_amount: f64,
+    /// `risk_amount` as a percentage of the account.
+    pub risk_pct: f64,
+}
+
+pub fn position_risk(entry: f64, stop: f64, quantity: f64, account_value: f64) -> Result<PositionRisk, String> {
+    if !entry.is_finite() || entry <= 0.0 {
+        return Err(format!("entry must be positive, got {}", entry));
+    }
+    if !stop.is_finite() || stop < 0.0 || stop == entry {
+        return Err(format!("stop must be a non-negative price other than the entry, got {}", stop));
+    }
+    if !account_value.is_finite() || account_value <= 0.0 {
+        return Err(format!("account_value must be positive, got {}", account_value));
+    }
+    let side = if stop < entry { Side::Long } else { Side::Short };
+    let risk_per_share = (entry - stop).abs();
+    let risk_amount = risk_per_share * quantity.abs();
+    Ok(PositionRisk { side, risk_per_share, risk_amount, risk_pct: risk_amount / account_value * 100.0 })
+}
+
+/// A closed trade's result in units of its initial risk: `(exit - entry) /
+/// (entry - stop)`, which has the right sign for longs and shorts alike.
+/// NaN when the stop equals the entry.
+pub fn r_multiple(entry: f64, stop: f64, exit: f64) -> f64 {
+    let risk = entry - stop;
+    if risk == 0.0 {
+        f64::NAN
+    } else {
+        (exit - entry) / risk
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: &[f64], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len());
+        for (i, (a, e)) in actual.iter().zip(expected).enumerate() {
+            assert!((a - e).abs() < 1e-9 || (a.is_nan() && e.is_nan()), "index {}: {} != {}", i, a, e);
+        }
+    }
+
+    /// True ranges 4, 4, 4, 8: ATR(3) is 4 on the third bar, then 16/3.
+    fn candles() -> Candles {
+        Candles {
+            high: vec![102.0, 103.0, 104.0, 105.0],
+            low: vec![98.0, 99.0, 100.0, 97.0],
+            close: vec![100.0, 101.0, 102.0, 104.0],
+            ..Candles::default()
+        }
+    }
+
+    #[test]
+    fn atr_stops_sit_a_multiple_of_the_atr_away() {
+        let long = suggest_stop(&candles(), 104.0, Side::Long, StopMethod::Atr, 1.5, 3, Some(400.0)).unwrap();
+        assert_close(&[long.stop, long.risk_per_share, long.atr, long.distance_atr], &[96.0, 8.0, 16.0 / 3.0, 1.5]);
+        assert_close(&[long.distance_pct], &[8.0 / 104.0 * 100.0]);
+        assert_eq!(long.position_size, Some(50.0));
+
+        let short = suggest_stop(&candles(), 104.0, Side::Short, StopMethod::Atr, 1.5, 3, None).unwrap();
+        assert_close(&[short.stop, short.risk_per_share], &[112.0, 8.0]);
+        assert_eq!(short.position_size, None);
+    }
+
+    #[test]
+    fn swing_and_percent_stops() {
+        // Lowest low 97 and highest high 105 of the last three bars, 0.75 ATR beyond.
+        let long = suggest_stop(&candles(), 104.0, Side::Long, StopMethod::Swing, 0.75, 3, None).unwrap();
+        assert_close(&[long.stop, long.risk_per_share], &[93.0, 11.0]);
+        let short = suggest_stop(&candles(), 104.0, Side::Short, StopMethod::Swing, 0.75, 3, None).unwrap();
+        assert_close(&[short.stop, short.risk_per_share], &[109.0, 5.0]);
+
+        // Percent stops need no history; the ATR is then unknown.
+        let pct = suggest_stop(&candles(), 100.0, Side::Long, "pct".parse().unwrap(), 5.0, 14, Some(100.0)).unwrap();
+        assert_close(&[pct.stop, pct.risk_per_share, pct.distance_pct, pct.atr, pct.distance_atr], &[95.0, 5.0, 5.0, f64::NAN, f64::NAN]);
+        assert_eq!(pct.position_size, Some(20.0));
+    }
+
+    #[test]
+    fn suggest_stop_rejects_bad_input() {
+        assert!(suggest_stop(&candles(), 0.0, Side::Long, StopMethod::Pct, 5.0, 3, None).is_err());
+        assert!(suggest_stop(&candles(), 104.0, Side::Long, StopMethod::Atr, -1.0, 3, None).is_err());
+        assert!(suggest_stop(&candles(), 104.0, Side::Long, StopMethod::Atr, 2.0, 3, Some(0.0)).is_err());
+        assert!(suggest_stop(&candles(), 104.0, Side::Long, StopMethod::Atr, 2.0, 14, None).is_err());
+        // The swing low is above a long entry at 96.
+        assert!(suggest_stop(&candles(), 96.0, Side::Long, StopMethod::Swing, 0.0, 3, None).is_err());
+    }
+
+    #[test]
+    fn position_risk_infers_the_side_from_the_stop() {
+        let long = position_risk(50.0, 48.0, -100.0, 10_000.0).unwrap();
+        assert_eq!(long.side, Side::Long);
+        assert_close(&[long.risk_per_share, long.risk_amount, long.risk_pct], &[2.0, 200.0, 2.0]);
+        let short = position_risk(50.0, 52.5, 40.0, 5_000.0).unwrap();
+        assert_eq!(short.side, Side::Short);
+        assert_close(&[short.risk_per_share, short.risk_amount, short.risk_pct], &[2.5, 100.0, 2.0]);
+
+        assert!(position_risk(50.0, 50.0, 10.0, 5_000.0).is_err());
+        assert!(position_risk(50.0, -1.0, 10.0, 5_000.0).is_err());
+        assert!(position_risk(f64::NAN, 48.0, 10.0, 5_000.0).is_err());
+        assert!(position_risk(50.0, 48.0, 10.0, 0.0).is_err());
+    }
+
+    #[test]
+    fn r_multiples_count_in_units_of_initial_risk() {
+        assert_close(
+            &[r_multiple(100.0, 95.0, 110.0), r_multiple(100.0, 95.0, 97.5), r_multiple(100.0, 105.0, 90.0), r_multiple(100.0, 105.0, 110.0)],
+            &[2.0, -0.5, 2.0, -2.0],
+        );
+        assert!(r_multiple(100.0, 100.0, 110.0).is_nan());
+    }
+}
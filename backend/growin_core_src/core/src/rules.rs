@@ -0,0 +1,554 @@
+//! A small, side-effect free expression language for user-defined screens:
+//!
+//! ```text
+//! rsi(14) < 30 and close > sma(200) and volume > sma(volume, 20) * 2
+//! ```
+//!
+//! Expressions are parsed into an AST and evaluated column-wise over a
+//! [`Candles`] series, so user strings never reach a Python `eval`.
+
+use crate::candles::Candles;
+use crate::indicators;
+
+/// Bounds the depth of the expression tree, so neither the recursive-descent
+/// parser nor the recursive evaluator can overflow the stack. Nesting and
+/// each link of an operator chain (`a + b + c` is two deep) both count.
+const MAX_DEPTH: usize = 64;
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Number(f64),
+    Ident(String),
+    LParen,
+    RParen,
+    Comma,
+    Op(BinOp),
+    Minus,
+    And,
+    Or,
+    Not,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+    Ne,
+    And,
+    Or,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Column {
+    Open,
+    High,
+    Low,
+    Close,
+    Volume,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expr {
+    Number(f64),
+    Column(Column),
+    Call { name: String, args: Vec<Expr> },
+    Neg(Box<Expr>),
+    Not(Box<Expr>),
+    Binary { op: BinOp, lhs: Box<Expr>, rhs: Box<Expr> },
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            ' ' | '\t' | '\n' | '\r' => i += 1,
+            '(' => { tokens.push(Token::LParen); i += 1; }
+            ')' => { tokens.push(Token::RParen); i += 1; }
+            ',' => { tokens.push(Token::Comma); i += 1; }
+            '+' => { tokens.push(Token::Op(BinOp::Add)); i += 1; }
+            '-' => { tokens.push(Token::Minus); i += 1; }
+            '*' => { tokens.push(Token::Op(BinOp::Mul)); i += 1; }
+            '/' => { tokens.push(Token::Op(BinOp::Div)); i += 1; }
+            '<' | '>' | '=' | '!' => {
+                let eq = chars.get(i + 1) == Some(&'=');
+                let op = match (c, eq) {
+                    ('<', false) => BinOp::Lt,
+                    ('<', true) => BinOp::Le,
+                    ('>', false) => BinOp::Gt,
+                    ('>', true) => BinOp::Ge,
+                    ('=', true) => BinOp::Eq,
+                    ('!', true) => BinOp::Ne,
+                    _ => return Err(format!("unexpected '{}' at position {}", c, i)),
+                };
+                tokens.push(Token::Op(op));
+                i += if eq { 2 } else { 1 };
+            }
+            c if c.is_ascii_digit() || c == '.' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                    i += 1;
+                }
+                let text: String = chars[start..i].iter().collect();
+                let value = text.parse().map_err(|_| format!("invalid number '{}' at position {}", text, start))?;
+                tokens.push(Token::Number(value));
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                let word: String = chars[start..i].iter().collect::<String>().to_lowercase();
+                tokens.push(match word.as_str() {
+                    "and" => Token::And,
+                    "or" => Token::Or,
+                    "not" => Token::Not,
+                    _ => Token::Ident(word),
+                });
+            }
+            other => return Err(format!("unexpected character '{}' at position {}", other, i)),
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let t = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        t
+    }
+
+    fn expect(&mut self, token: Token) -> Result<(), String> {
+        match self.next() {
+            Some(t) if t == token => Ok(()),
+            Some(t) => Err(format!("expected {:?}, found {:?}", token, t)),
+            None => Err(format!("expected {:?}, found end of rule", token)),
+        }
+    }
+
+    fn enter(&mut self) -> Result<(), String> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err("rule is nested too deeply or chains too many operators".to_string());
+        }
+        Ok(())
+    }
+
+    fn or(&mut self) -> Result<Expr, String> {
+        let depth = self.depth;
+        self.enter()?;
+        let mut lhs = self.and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.next();
+            self.enter()?;
+            lhs = Expr::Binary { op: BinOp::Or, lhs: Box::new(lhs), rhs: Box::new(self.and()?) };
+        }
+        self.depth = depth;
+        Ok(lhs)
+    }
+
+    fn and(&mut self) -> Result<Expr, String> {
+        let depth = self.depth;
+        let mut lhs = self.not()?;
+        while self.peek() == Some(&Token::And) {
+            self.next();
+            self.enter()?;
+            lhs = Expr::Binary { op: BinOp::And, lhs: Box::new(lhs), rhs: Box::new(self.not()?) };
+        }
+        self.depth = depth;
+        Ok(lhs)
+    }
+
+    fn not(&mut self) -> Result<Expr, String> {
+        if self.peek() == Some(&Token::Not) {
+            self.next();
+            self.enter()?;
+            let inner = self.not()?;
+            self.depth -= 1;
+            return Ok(Expr::Not(Box::new(inner)));
+        }
+        self.comparison()
+    }
+
+    fn comparison(&mut self) -> Result<Expr, String> {
+        let lhs = self.additive()?;
+        if let Some(Token::Op(op @ (BinOp::Lt | BinOp::Le | BinOp::Gt | BinOp::Ge | BinOp::Eq | BinOp::Ne))) = self.peek() {
+            let op = *op;
+            self.next();
+            self.enter()?;
+            let rhs = self.additive()?;
+            self.depth -= 1;
+            return Ok(Expr::Binary { op, lhs: Box::new(lhs), rhs: Box::new(rhs) });
+        }
+        Ok(lhs)
+    }
+
+    fn additive(&mut self) -> Result<Expr, String> {
+        let depth = self.depth;
+        let mut lhs = self.multiplicative()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Op(BinOp::Add)) => BinOp::Add,
+                Some(Token::Minus) => BinOp::Sub,
+                _ => break,
+            };
+            self.next();
+            self.enter()?;
+            lhs = Expr::Binary { op, lhs: Box::new(lhs), rhs: Box::new(self.multiplicative()?) };
+        }
+        self.depth = depth;
+        Ok(lhs)
+    }
+
+    fn multiplicative(&mut self) -> Result<Expr, String> {
+        let depth = self.depth;
+        let mut lhs = self.unary()?;
+        while let Some(Token::Op(op @ (BinOp::Mul | BinOp::Div))) = self.peek() {
+            let op = *op;
+            self.next();
+            self.enter()?;
+            lhs = Expr::Binary { op, lhs: Box::new(lhs), rhs: Box::new(self.unary()?) };
+        }
+        self.depth = depth;
+        Ok(lhs)
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        if self.peek() == Some(&Token::Minus) {
+            self.next();
+            self.enter()?;
+            let inner = self.unary()?;
+            self.depth -= 1;
+            return Ok(Expr::Neg(Box::new(inner)));
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Expr, String> {
+        match self.next() {
+            Some(Token::Number(v)) => Ok(Expr::Number(v)),
+            Some(Token::LParen) => {
+                let inner = self.or()?;
+                self.expect(Token::RParen)?;
+                Ok(inner)
+            }
+            Some(Token::Ident(name)) => {
+                if self.peek() == Some(&Token::LParen) {
+                    self.next();
+                    let mut args = Vec::new();
+                    if self.peek() != Some(&Token::RParen) {
+                        loop {
+                            args.push(self.or()?);
+                            if self.peek() == Some(&Token::Comma) {
+                                self.next();
+                            } else {
+                                break;
+                            }
+                        }
+                    }
+                    self.expect(Token::RParen)?;
+                    return Ok(Expr::Call { name, args });
+                }
+                let column = match name.as_str() {
+                    "open" => Column::Open,
+                    "high" => Column::High,
+                    "low" => Column::Low,
+                    "close" | "price" => Column::Close,
+                    "volume" => Column::Volume,
+                    _ => return Err(format!("unknown identifier '{}'", name)),
+                };
+                Ok(Expr::Column(column))
+            }
+            Some(t) => Err(format!("unexpected token {:?}", t)),
+            None => Err("unexpected end of rule".to_string()),
+        }
+    }
+}
+
+/// Parses a rule string into an expression tree.
+pub fn parse_rule(input: &str) -> Result<Expr, String> {
+    let mut parser = Parser { tokens: tokenize(input)?, pos: 0, depth: 0 };
+    let expr = parser.or()?;
+    if let Some(t) = parser.peek() {
+        return Err(format!("unexpected trailing token {:?}", t));
+    }
+    Ok(expr)
+}
+
+enum Value {
+    Num(Vec<f64>),
+    Bool(Vec<bool>),
+}
+
+impl Value {
+    fn into_num(self, what: &str) -> Result<Vec<f64>, String> {
+        match self {
+            Value::Num(v) => Ok(v),
+            Value::Bool(_) => Err(format!("{} expects a numeric argument, got a condition", what)),
+        }
+    }
+
+    fn into_bool(self, what: &str) -> Result<Vec<bool>, String> {
+        match self {
+            Value::Bool(v) => Ok(v),
+            Value::Num(_) => Err(format!("{} expects a condition, got a numeric value", what)),
+        }
+    }
+}
+
+struct Evaluator<'a> {
+    candles: &'a Candles,
+}
+
+impl Evaluator<'_> {
+    fn column(&self, column: Column) -> Result<Vec<f64>, String> {
+        let c = self.candles;
+        Ok(match column {
+            Column::Open => c.open.clone(),
+            Column::High => c.high.clone(),
+            Column::Low => c.low.clone(),
+            Column::Close => c.close.clone(),
+            Column::Volume if c.has_volume() => c.volume.clone(),
+            Column::Volume => return Err("rule uses 'volume' but candles have no volume column".to_string()),
+        })
+    }
+
+    fn eval(&self, expr: &Expr) -> Result<Value, String> {
+        let n = self.candles.len();
+        Ok(match expr {
+            Expr::Number(v) => Value::Num(vec![*v; n]),
+            Expr::Column(c) => Value::Num(self.column(*c)?),
+            Expr::Neg(inner) => Value::Num(self.eval(inner)?.into_num("'-'")?.into_iter().map(|x| -x).collect()),
+            Expr::Not(inner) => Value::Bool(self.eval(inner)?.into_bool("'not'")?.into_iter().map(|b| !b).collect()),
+            Expr::Binary { op, lhs, rhs } => self.binary(*op, lhs, rhs)?,
+            Expr::Call { name, args } => self.call(name, args)?,
+        })
+    }
+
+    fn binary(&self, op: BinOp, lhs: &Expr, rhs: &Expr) -> Result<Value, String> {
+        let (l, r) = (self.eval(lhs)?, self.eval(rhs)?);
+        if matches!(op, BinOp::And | BinOp::Or) {
+            let what = if op == BinOp::And { "'and'" } else { "'or'" };
+            let (l, r) = (l.into_bool(what)?, r.into_bool(what)?);
+            let combine = |a: bool, b: bool| if op == BinOp::And { a && b } else { a || b };
+            return Ok(Value::Bool(l.iter().zip(&r).map(|(&a, &b)| combine(a, b)).collect()));
+        }
+
+        let (l, r) = (l.into_num("operator")?, r.into_num("operator")?);
+        let pairs = l.iter().zip(&r);
+        Ok(match op {
+            BinOp::Add => Value::Num(pairs.map(|(a, b)| a + b).collect()),
+            BinOp::Sub => Value::Num(pairs.map(|(a, b)| a - b).collect()),
+            BinOp::Mul => Value::Num(pairs.map(|(a, b)| a * b).collect()),
+            BinOp::Div => Value::Num(pairs.map(|(a, b)| if *b == 0.0 { f64::NAN } else { a / b }).collect()),
+            // Comparisons involving NaN (warm-up) are false, never accidental signals.
+            BinOp::Lt => Value::Bool(pairs.map(|(a, b)| a < b).collect()),
+            BinOp::Le => Value::Bool(pairs.map(|(a, b)| a <= b).collect()),
+            BinOp::Gt => Value::Bool(pairs.map(|(a, b)| a > b).collect()),
+            BinOp::Ge => Value::Bool(pairs.map(|(a, b)| a >= b).collect()),
+            BinOp::Eq => Value::Bool(pairs.map(|(a, b)| a == b).collect()),
+            BinOp::Ne => Value::Bool(pairs.map(|(a, b)| !a.is_nan() && !b.is_nan() && a != b).collect()),
+            BinOp::And | BinOp::Or => unreachable!(),
+        })
+    }
+
+    /// Splits `(source, period)` or `(period)` arguments; the source defaults to close.
+    fn source_and_period(&self, name: &str, args: &[Expr], default: Option<usize>) -> Result<(Vec<f64>, usize), String> {
+        let (source, period) = match args {
+            [] => (None, None),
+            [p] if matches!(p, Expr::Number(_)) => (None, Some(p)),
+            [s] => (Some(s), None),
+            [s, p] => (Some(s), Some(p)),
+            _ => return Err(format!("{}() takes at most 2 arguments", name)),
+        };
+        let source = match source {
+            Some(s) => self.eval(s)?.into_num(name)?,
+            None => self.candles.close.clone(),
+        };
+        let period = match period {
+            Some(p) => literal_period(name, p)?,
+            None => default.ok_or_else(|| format!("{}() requires a period", name))?,
+        };
+        Ok((source, period))
+    }
+
+    fn call(&self, name: &str, args: &[Expr]) -> Result<Value, String> {
+        let c = self.candles;
+        let num = |v: Vec<f64>| Ok(Value::Num(v));
+        match name {
+            "sma" | "ema" | "rsi" => {
+                let default = if name == "rsi" { Some(14) } else { None };
+                let (source, period) = self.source_and_period(name, args, default)?;
+                let (mut values, valid_from) = match name {
+                    "sma" => (indicators::sma(&source, period), period - 1),
+                    "ema" => (indicators::ema(&source, period), period - 1),
+                    _ => (indicators::rsi(&source, period), period),
+                };
+                values.resize(source.len(), f64::NAN);
+                indicators::mask_warmup(&mut values, valid_from);
+                num(values)
+            }
+            "atr" => {
+                let period = match args {
+                    [] => 14,
+                    [p] => literal_period(name, p)?,
+                    _ => return Err("atr() takes at most 1 argument".to_string()),
+                };
+                let mut values = indicators::atr(&c.high, &c.low, &c.close, period);
+                indicators::mask_warmup(&mut values, period - 1);
+                num(values)
+            }
+            "highest" | "lowest" => {
+                let (source, period) = self.source_and_period(name, args, None)?;
+                num(if name == "highest" { indicators::rolling_max(&source, period) } else { indicators::rolling_min(&source, period) })
+            }
+            "prev" => {
+                let (source, lag) = match args {
+                    [s] => (self.eval(s)?.into_num(name)?, 1),
+                    [s, p] => (self.eval(s)?.into_num(name)?, literal_period(name, p)?),
+                    _ => return Err("prev() takes 1 or 2 arguments".to_string()),
+                };
+                num((0..source.len()).map(|i| if i >= lag { source[i - lag] } else { f64::NAN }).collect())
+            }
+            "abs" => match args {
+                [x] => num(self.eval(x)?.into_num(name)?.into_iter().map(f64::abs).collect()),
+                _ => Err("abs() takes 1 argument".to_string()),
+            },
+            "min" | "max" => match args {
+                [a, b] => {
+                    let (a, b) = (self.eval(a)?.into_num(name)?, self.eval(b)?.into_num(name)?);
+                    let pick = if name == "min" { f64::min } else { f64::max };
+                    num(a.iter().zip(&b).map(|(x, y)| if x.is_nan() || y.is_nan() { f64::NAN } else { pick(*x, *y) }).collect())
+                }
+                _ => Err(format!("{}() takes 2 arguments", name)),
+            },
+            "crosses_above" | "crosses_below" => match args {
+                [a, b] => {
+                    let (a, b) = (self.eval(a)?.into_num(name)?, self.eval(b)?.into_num(name)?);
+                    let above = name == "crosses_above";
+                    let crossed = (0..a.len())
+                        .map(|i| {
+                            i > 0 && if above {
+                                a[i - 1] <= b[i - 1] && a[i] > b[i]
+                            } else {
+                                a[i - 1] >= b[i - 1] && a[i] < b[i]
+                            }
+                        })
+                        .collect();
+                    Ok(Value::Bool(crossed))
+                }
+                _ => Err(format!("{}() takes 2 arguments", name)),
+            },
+            other => Err(format!("unknown function '{}'", other)),
+        }
+    }
+}
+
+/// Period arguments must be positive integer literals so rules stay cheap to
+/// evaluate and cannot allocate unbounded windows from computed values.
+fn literal_period(name: &str, expr: &Expr) -> Result<usize, String> {
+    match expr {
+        Expr::Number(v) if *v >= 1.0 && v.fract() == 0.0 => Ok(*v as usize),
+        _ => Err(format!("{}() period must be a positive integer literal", name)),
+    }
+}
+
+/// Evaluates a parsed rule, which must produce a condition.
+pub fn evaluate(expr: &Expr, candles: &Candles) -> Result<Vec<bool>, String> {
+    Evaluator { candles }.eval(expr)?.into_bool("rule")
+}
+
+/// Evaluates a parsed expression that must produce a numeric series, e.g.
+/// `rsi(14)` or `close / sma(200) - 1`.
+pub fn evaluate_numeric(expr: &Expr, candles: &Candles) -> Result<Vec<f64>, String> {
+    Evaluator { candles }.eval(expr)?.into_num("expression")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candles(n: usize) -> Candles {
+        let close: Vec<f64> = (0..n).map(|i| 100.0 + (i as f64 * 0.7).sin() * 5.0 - i as f64 * 0.05).collect();
+        Candles {
+            open: close.clone(),
+            high: close.iter().map(|c| c + 1.0).collect(),
+            low: close.iter().map(|c| c - 1.0).collect(),
+            volume: (0..n).map(|i| if i % 9 == 0 { 5000.0 } else { 1000.0 }).collect(),
+            close,
+            timestamps: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn the_documented_screen_matches_its_parts() {
+        let c = candles(260);
+        let rule = parse_rule("rsi(14) < 30 and close > sma(200) and volume > sma(volume,20)*2").unwrap();
+        let parts = ["rsi(14) < 30", "close > sma(200)", "volume > sma(volume, 20) * 2"]
+            .map(|p| evaluate(&parse_rule(p).unwrap(), &c).unwrap());
+        let signal = evaluate(&rule, &c).unwrap();
+        for i in 0..c.len() {
+            assert_eq!(signal[i], parts.iter().all(|p| p[i]), "bar {}", i);
+        }
+        assert!(signal[..199].iter().all(|&s| !s));
+    }
+
+    #[test]
+    fn operators_follow_the_usual_precedence() {
+        let c = candles(3);
+        let value = evaluate_numeric(&parse_rule("1 + 2 * 3 - -4 / 2").unwrap(), &c).unwrap();
+        assert_eq!(value, vec![9.0; 3]);
+        let cond = evaluate(&parse_rule("not 1 > 2 and 2 > 1 or 1 > 2").unwrap(), &c).unwrap();
+        assert_eq!(cond, vec![true; 3]);
+    }
+
+    #[test]
+    fn long_operator_chains_are_rejected() {
+        let sum = vec!["close"; 100_000].join(" + ");
+        assert!(parse_rule(&sum).unwrap_err().contains("too many operators"));
+        let conditions = vec!["close > 1"; 100_000].join(" and ");
+        assert!(parse_rule(&conditions).is_err());
+        let product = vec!["close"; 100_000].join(" * ");
+        assert!(parse_rule(&product).is_err());
+        let nested = format!("{}close{}", "(".repeat(100_000), ")".repeat(100_000));
+        assert!(parse_rule(&nested).is_err());
+    }
+
+    #[test]
+    fn chains_within_the_limit_still_parse() {
+        let c = candles(3);
+        let sum = vec!["1"; 40].join(" + ");
+        assert_eq!(evaluate_numeric(&parse_rule(&sum).unwrap(), &c).unwrap(), vec![40.0; 3]);
+        let conditions = vec!["close > 1"; 20].join(" and ");
+        assert_eq!(evaluate(&parse_rule(&conditions).unwrap(), &c).unwrap(), vec![true; 3]);
+    }
+
+    #[test]
+    fn bad_rules_report_an_error() {
+        let c = candles(3);
+        assert!(parse_rule("close >").is_err());
+        assert!(parse_rule("foo > 1").is_err());
+        assert!(evaluate(&parse_rule("close + 1").unwrap(), &c).is_err());
+        assert!(evaluate(&parse_rule("sma(close, 0) > 1").unwrap(), &c).is_err());
+    }
+}
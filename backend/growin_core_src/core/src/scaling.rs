@@ -0,0 +1,96 @@
+use crate::stats;
+
+/// Scaling strategy. Every strategy reduces to a per-column affine map
+/// `x' = (x - center) / scale`, which is all that needs to be persisted.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ScalerKind {
+    /// Maps the fitted [min, max] onto `feature_range`.
+    MinMax { range: (f64, f64) },
+    /// Zero mean, unit (population) variance.
+    Standard,
+    /// Centers on the median and scales by the inter-quantile range.
+    Robust { quantile_range: (f64, f64) },
+}
+
+impl ScalerKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ScalerKind::MinMax { .. } => "minmax",
+            ScalerKind::Standard => "standard",
+            ScalerKind::Robust { .. } => "robust",
+        }
+    }
+
+    /// Fits center/scale for one column, ignoring NaNs. Constant columns get
+    /// scale 1.0 so they transform to a constant instead of NaN/inf.
+    fn fit_column(&self, column: &[f64]) -> (f64, f64) {
+        let sorted = stats::sorted_finite(column);
+        if sorted.is_empty() {
+            return (0.0, 1.0);
+        }
+        let nonzero = |s: f64| if s == 0.0 || !s.is_finite() { 1.0 } else { s };
+        match *self {
+            ScalerKind::MinMax { range: (lo, hi) } => {
+                let (min, max) = (sorted[0], sorted[sorted.len() - 1]);
+                let scale = nonzero((max - min) / (hi - lo));
+                (min - lo * scale, scale)
+            }
+            ScalerKind::Standard => (stats::mean(&sorted), nonzero(stats::std_dev(&sorted))),
+            ScalerKind::Robust { quantile_range: (q_lo, q_hi) } => {
+                let spread = stats::quantile_sorted(&sorted, q_hi / 100.0) - stats::quantile_sorted(&sorted, q_lo / 100.0);
+                (stats::quantile_sorted(&sorted, 0.5), nonzero(spread))
+            }
+        }
+    }
+
+    /// Fits a row-major matrix column by column.
+    pub fn fit(&self, rows: &[Vec<f64>]) -> Result<FittedScaler, String> {
+        let width = matrix_width(rows)?;
+        let mut center = Vec::with_capacity(width);
+        let mut scale = Vec::with_capacity(width);
+        for j in 0..width {
+            let column: Vec<f64> = rows.iter().map(|r| r[j]).collect();
+            let (c, s) = self.fit_column(&column);
+            center.push(c);
+            scale.push(s);
+        }
+        Ok(FittedScaler { center, scale })
+    }
+}
+
+/// Width of a row-major matrix, rejecting ragged rows.
+fn matrix_width(rows: &[Vec<f64>]) -> Result<usize, String> {
+    let width = rows.first().map_or(0, |r| r.len());
+    if let Some(i) = rows.iter().position(|r| r.len() != width) {
+        return Err(format!("row {} has {} columns, expected {}", i, rows[i].len(), width));
+    }
+    Ok(width)
+}
+
+/// Persistable per-column parameters of a fitted scaler.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FittedScaler {
+    pub center: Vec<f64>,
+    pub scale: Vec<f64>,
+}
+
+impl FittedScaler {
+    fn apply(&self, rows: &[Vec<f64>], f: impl Fn(f64, f64, f64) -> f64) -> Result<Vec<Vec<f64>>, String> {
+        let width = matrix_width(rows)?;
+        if !rows.is_empty() && width != self.center.len() {
+            return Err(format!("expected {} columns, got {}", self.center.len(), width));
+        }
+        Ok(rows
+            .iter()
+            .map(|r| r.iter().enumerate().map(|(j, &x)| f(x, self.center[j], self.scale[j])).collect())
+            .collect())
+    }
+
+    pub fn transform(&self, rows: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, String> {
+        self.apply(rows, |x, c, s| (x - c) / s)
+    }
+
+    pub fn inverse_transform(&self, rows: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, String> {
+        self.apply(rows, |x, c, s| x * s + c)
+    }
+}
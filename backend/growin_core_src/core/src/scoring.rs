@@ -0,0 +1,235 @@
+use crate::stats;
+
+/// How raw indicator values are made comparable before weighting.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Normalization {
+    /// Percentile rank within the column (ties share their average rank).
+    Rank,
+    /// Z-score within the column, clipped to ±3 standard deviations.
+    ZScore,
+}
+
+impl std::str::FromStr for Normalization {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "rank" => Ok(Normalization::Rank),
+            "zscore" | "z" => Ok(Normalization::ZScore),
+            other => Err(format!("Unknown normalization '{}', expected 'rank' or 'zscore'", other)),
+        }
+    }
+}
+
+/// Percentile ranks in [0, 1]; NaNs stay NaN and a single value ranks 0.5.
+pub fn percentile_ranks(values: &[f64]) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..values.len()).filter(|&i| !values[i].is_nan()).collect();
+    order.sort_by(|&a, &b| values[a].partial_cmp(&values[b]).unwrap());
+
+    let mut ranks = vec![f64::NAN; values.len()];
+    let n = order.len();
+    let mut i = 0;
+    while i < n {
+        let mut j = i;
+        while j + 1 < n && values[order[j + 1]] == values[order[i]] {
+            j += 1;
+        }
+        let avg_rank = (i + j) as f64 / 2.0;
+        let pct = if n > 1 { avg_rank / (n - 1) as f64 } else { 0.5 };
+        for &idx in &order[i..=j] {
+            ranks[idx] = pct;
+        }
+        i = j + 1;
+    }
+    ranks
+}
+
+/// Maps a column onto [-1, 1] so that every indicator contributes on the same scale.
+fn normalize_column(values: &[f64], method: Normalization, ddof: usize) -> Vec<f64> {
+    match method {
+        Normalization::Rank => percentile_ranks(values).into_iter().map(|p| 2.0 * p - 1.0).collect(),
+        Normalization::ZScore => {
+            let finite: Vec<f64> = values.iter().copied().filter(|x| !x.is_nan()).collect();
+            let (mu, sd) = (stats::mean(&finite), stats::std_dev_ddof(&finite, ddof));
+            values
+                .iter()
+                .map(|&x| {
+                    if x.is_nan() {
+                        f64::NAN
+                    } else if sd == 0.0 || sd.is_nan() {
+                        0.0
+                    } else {
+                        ((x - mu) / sd / 3.0).clamp(-1.0, 1.0)
+                    }
+                })
+                .collect()
+        }
+    }
+}
+
+/// Weighted composite score on a 0–100 scale for each row of `columns`
+/// (one column per indicator, one row per observation).
+///
+/// Each column is normalized to [-1, 1]; the row score is the weighted mean
+/// with weights normalized by their absolute sum, mapped so that 0 is the
+/// worst, 50 neutral and 100 the best. A negative weight marks an indicator
+/// where lower is better. Missing (NaN) cells are skipped and the remaining
+/// weights renormalized; rows with no usable values score NaN. Z-scores use
+/// a std over `n - ddof` degrees of freedom.
+pub fn composite_scores(columns: &[Vec<f64>], weights: &[f64], method: Normalization, ddof: usize) -> Vec<f64> {
+    let n_rows = columns.first().map_or(0, |c| c.len());
+    let normalized: Vec<Vec<f64>> = columns.iter().map(|c| normalize_column(c, method, ddof)).collect();
+
+    (0..n_rows)
+        .map(|i| {
+            let (mut total, mut weight_sum) = (0.0, 0.0);
+            for (col, &w) in normalized.iter().zip(weights) {
+                let v = col[i];
+                if !v.is_nan() && w != 0.0 {
+                    total += w * v;
+                    weight_sum += w.abs();
+                }
+            }
+            if weight_sum == 0.0 {
+                f64::NAN
+            } else {
+                50.0 * (1.0 + total / weight_sum)
+            }
+        })
+        .collect()
+}
+
+
+/// A sentiment index with exponential time decay; see `decayed_sentiment`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SentimentIndex {
+    /// Article times, ascending.
+    pub timestamps: Vec<i64>,
+    /// The index just after each article.
+    pub values: Vec<f64>,
+    /// The index at the as-of time; NaN without articles.
+    pub score: f64,
+    /// Decayed weight behind `score`: the article count it is worth, for
+    /// telling a fresh reading from a stale one.
+    pub weight: f64,
+}
+
+/// Folds per-article sentiment into an index that weights each article by
+/// `weights[i] * 0.5^(age / half_life_secs)`, i.e. the decayed weighted mean
+/// of every article so far.
+///
+/// Articles may come in any order; NaN scores and articles after `as_of`
+/// (default: the latest article) are skipped.
+pub fn decayed_sentiment(
+    scores: &[f64],
+    timestamps: &[i64],
+    weights: &[f64],
+    half_life_secs: f64,
+    as_of: Option<i64>,
+) -> Result<SentimentIndex, String> {
+    if !half_life_secs.is_finite() || half_life_secs <= 0.0 {
+        return Err(format!("half_life must be positive, got {}", half_life_secs));
+    }
+    if let Some(w) = weights.iter().find(|w| w.is_nan() || **w < 0.0) {
+        return Err(format!("source weights must be non-negative, got {}", w));
+    }
+    let mut order: Vec<usize> = (0..scores.len())
+        .filter(|&i| !scores[i].is_nan() && as_of.is_none_or(|t| timestamps[i] <= t))
+        .collect();
+    order.sort_by_key(|&i| timestamps[i]);
+
+    let decay = |dt: i64| (-std::f64::consts::LN_2 * dt as f64 / half_life_secs).exp();
+    let (mut total, mut weight) = (0.0, 0.0);
+    let mut last = None;
+    let mut out = SentimentIndex { timestamps: Vec::new(), values: Vec::new(), score: f64::NAN, weight: 0.0 };
+    for i in order {
+        let t = timestamps[i];
+        if let Some(prev) = last {
+            let k = decay(t - prev);
+            total *= k;
+            weight *= k;
+        }
+        total += weights[i] * scores[i];
+        weight += weights[i];
+        last = Some(t);
+        out.timestamps.push(t);
+        out.values.push(if weight > 0.0 { total / weight } else { f64::NAN });
+    }
+    if let Some(prev) = last {
+        // Decay rescales the numerator and denominator alike, so only the
+        // weight changes between the last article and the as-of time.
+        out.score = *out.values.last().unwrap();
+        out.weight = weight * decay(as_of.unwrap_or(prev) - prev);
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: &[f64], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len());
+        for (i, (a, e)) in actual.iter().zip(expected).enumerate() {
+            assert!((a - e).abs() < 1e-9 || (a.is_nan() && e.is_nan()), "index {}: {} != {}", i, a, e);
+        }
+    }
+
+    #[test]
+    fn percentile_ranks_average_ties_and_skip_nan() {
+        assert_close(&percentile_ranks(&[3.0, 1.0, f64::NAN, 3.0, 2.0]), &[2.5 / 3.0, 0.0, f64::NAN, 2.5 / 3.0, 1.0 / 3.0]);
+        assert_close(&percentile_ranks(&[7.0]), &[0.5]);
+    }
+
+    #[test]
+    fn composite_scores_weight_ranks_with_lower_is_better_columns() {
+        let columns = vec![vec![1.0, 2.0, 3.0], vec![30.0, 20.0, 10.0]];
+        // The second column is negated, so both agree that row 2 is best.
+        assert_close(&composite_scores(&columns, &[1.0, -1.0], Normalization::Rank, 0), &[0.0, 50.0, 100.0]);
+        // Weights are normalized by their absolute sum: 0.75 x -1 + 0.25 x 1.
+        assert_close(&composite_scores(&columns, &[3.0, 1.0], Normalization::Rank, 0), &[25.0, 50.0, 75.0]);
+    }
+
+    #[test]
+    fn composite_scores_skip_missing_cells() {
+        let columns = vec![vec![1.0, 2.0, 3.0, f64::NAN], vec![f64::NAN, 20.0, 10.0, f64::NAN]];
+        // Row 0 falls back to the first column alone; row 3 has nothing.
+        assert_close(&composite_scores(&columns, &[1.0, -1.0], Normalization::Rank, 0), &[0.0, 25.0, 100.0, f64::NAN]);
+        assert_close(&composite_scores(&columns, &[0.0, 0.0], Normalization::Rank, 0), &[f64::NAN; 4]);
+    }
+
+    #[test]
+    fn zscore_composites_clip_at_three_deviations() {
+        // Mean 2, population std sqrt(2/3): z / 3 = ±1 / sqrt(6).
+        let z = 1.0 / 6.0f64.sqrt();
+        assert_close(&composite_scores(&[vec![1.0, 2.0, 3.0]], &[1.0], Normalization::ZScore, 0), &[50.0 * (1.0 - z), 50.0, 50.0 * (1.0 + z)]);
+        // 20 sits sqrt(19) deviations above nineteen zeros, past the clip.
+        let mut outlier = vec![0.0; 19];
+        outlier.push(20.0);
+        let scores = composite_scores(&[outlier], &[1.0], "zscore".parse().unwrap(), 0);
+        assert_close(&[scores[0], scores[19]], &[50.0 * (1.0 - 1.0 / 19.0f64.sqrt() / 3.0), 100.0]);
+        assert_close(&composite_scores(&[vec![4.0; 3]], &[1.0], Normalization::ZScore, 1), &[50.0; 3]);
+    }
+
+    #[test]
+    fn decayed_sentiment_halves_older_articles() {
+        // The first article is one half-life old when the second lands:
+        // (0.5 x 1 - 1) / (0.5 + 1) = -1/3.
+        let index = decayed_sentiment(&[-1.0, 1.0, f64::NAN, 5.0], &[10, 0, 5, 30], &[1.0; 4], 10.0, Some(20)).unwrap();
+        assert_eq!(index.timestamps, vec![0, 10]);
+        assert_close(&index.values, &[1.0, -1.0 / 3.0]);
+        assert_close(&[index.score, index.weight], &[-1.0 / 3.0, 0.75]);
+
+        let index = decayed_sentiment(&[1.0, -1.0], &[0, 0], &[3.0, 1.0], 10.0, None).unwrap();
+        assert_close(&index.values, &[1.0, 0.5]);
+        assert_close(&[index.score, index.weight], &[0.5, 4.0]);
+    }
+
+    #[test]
+    fn decayed_sentiment_rejects_bad_parameters() {
+        assert!(decayed_sentiment(&[1.0], &[0], &[1.0], 0.0, None).is_err());
+        assert!(decayed_sentiment(&[1.0], &[0], &[-1.0], 10.0, None).is_err());
+        let empty = decayed_sentiment(&[], &[], &[], 10.0, None).unwrap();
+        assert!(empty.values.is_empty() && empty.score.is_nan() && empty.weight == 0.0);
+    }
+}
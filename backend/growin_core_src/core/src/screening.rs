@@ -0,0 +1,507 @@
+use rayon::prelude::*;
+use std::collections::HashMap;
+
+use crate::candles::Candles;
+use crate::indicators;
+use crate::rules::{self, Expr};
+use crate::scoring;
+use crate::stats;
+
+// --- Cross-Sectional Momentum ---
+
+/// Momentum result for one ticker.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MomentumRank {
+    /// Return per lookback (NaN when history is too short).
+    pub returns: Vec<f64>,
+    /// Mean cross-sectional percentile of the available lookbacks, 0–100.
+    pub score: f64,
+    /// 1 = strongest; `None` when no lookback could be computed.
+    pub rank: Option<usize>,
+}
+
+/// Return from `lookback + skip_recent` bars ago to `skip_recent` bars ago.
+fn skipped_return(prices: &[f64], lookback: usize, skip_recent: usize) -> f64 {
+    let n = prices.len();
+    if n < lookback + skip_recent + 1 {
+        return f64::NAN;
+    }
+    let end = prices[n - 1 - skip_recent];
+    let start = prices[n - 1 - skip_recent - lookback];
+    if start == 0.0 { f64::NAN } else { end / start - 1.0 }
+}
+
+/// Blended momentum across a universe. Per-ticker returns are computed in
+/// parallel; each lookback is then ranked cross-sectionally and the ranks
+/// averaged, so no single horizon dominates the blend.
+pub fn momentum_ranks(series: &[Vec<f64>], lookbacks: &[usize], skip_recent: usize) -> Vec<MomentumRank> {
+    let returns: Vec<Vec<f64>> = series
+        .par_iter()
+        .map(|prices| lookbacks.iter().map(|&l| skipped_return(prices, l, skip_recent)).collect())
+        .collect();
+
+    let ranks_by_lookback: Vec<Vec<f64>> = (0..lookbacks.len())
+        .map(|j| scoring::percentile_ranks(&returns.iter().map(|r| r[j]).collect::<Vec<_>>()))
+        .collect();
+
+    let scores: Vec<f64> = (0..series.len())
+        .map(|i| {
+            let available: Vec<f64> = ranks_by_lookback.iter().map(|r| r[i]).filter(|v| !v.is_nan()).collect();
+            if available.is_empty() {
+                f64::NAN
+            } else {
+                100.0 * available.iter().sum::<f64>() / available.len() as f64
+            }
+        })
+        .collect();
+
+    let mut order: Vec<usize> = (0..series.len()).filter(|&i| !scores[i].is_nan()).collect();
+    order.sort_by(|&a, &b| scores[b].partial_cmp(&scores[a]).unwrap());
+    let mut rank = vec![None; series.len()];
+    for (pos, &i) in order.iter().enumerate() {
+        rank[i] = Some(pos + 1);
+    }
+
+    returns
+        .into_iter()
+        .zip(scores)
+        .zip(rank)
+        .map(|((returns, score), rank)| MomentumRank { returns, score, rank })
+        .collect()
+}
+
+// --- Gap Screener ---
+
+/// An opening gap and its fill status.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PriceGap {
+    /// Bar whose open gapped away from the previous close.
+    pub index: usize,
+    /// 1 = gap up, -1 = gap down.
+    pub direction: i8,
+    /// Open vs previous close, in percent.
+    pub gap_pct: f64,
+    pub prev_close: f64,
+    pub open: f64,
+    /// First bar (from the gap bar onwards) that traded back to the previous close.
+    pub fill_index: Option<usize>,
+}
+
+impl PriceGap {
+    pub fn bars_to_fill(&self) -> Option<usize> {
+        self.fill_index.map(|f| f - self.index)
+    }
+}
+
+/// Finds opens at least `min_gap_pct` percent away from the prior close and
+/// tracks whether (and when) price traded back to that close within
+/// `max_fill_bars` bars (unbounded when `None`).
+pub fn detect_price_gaps(candles: &Candles, min_gap_pct: f64, max_fill_bars: Option<usize>) -> Vec<PriceGap> {
+    let n = candles.len();
+    let mut gaps = Vec::new();
+    for i in 1..n {
+        let prev_close = candles.close[i - 1];
+        let open = candles.open[i];
+        if prev_close <= 0.0 {
+            continue;
+        }
+        let gap_pct = (open / prev_close - 1.0) * 100.0;
+        if gap_pct.abs() < min_gap_pct || gap_pct == 0.0 {
+            continue;
+        }
+        let direction: i8 = if gap_pct > 0.0 { 1 } else { -1 };
+        let last = max_fill_bars.map_or(n - 1, |m| (i + m).min(n - 1));
+        let fill_index = (i..=last).find(|&j| {
+            if direction == 1 { candles.low[j] <= prev_close } else { candles.high[j] >= prev_close }
+        });
+        gaps.push(PriceGap { index: i, direction, gap_pct, prev_close, open, fill_index });
+    }
+    gaps
+}
+
+// --- Screener ---
+
+/// Threshold comparison used by screener filters.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Comparison {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+    Ne,
+}
+
+impl Comparison {
+    pub fn test(&self, value: f64, threshold: f64) -> bool {
+        match self {
+            Comparison::Lt => value < threshold,
+            Comparison::Le => value <= threshold,
+            Comparison::Gt => value > threshold,
+            Comparison::Ge => value >= threshold,
+            Comparison::Eq => value == threshold,
+            Comparison::Ne => !value.is_nan() && value != threshold,
+        }
+    }
+}
+
+impl std::str::FromStr for Comparison {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "<" => Ok(Comparison::Lt),
+            "<=" => Ok(Comparison::Le),
+            ">" => Ok(Comparison::Gt),
+            ">=" => Ok(Comparison::Ge),
+            "==" => Ok(Comparison::Eq),
+            "!=" => Ok(Comparison::Ne),
+            other => Err(format!("Unknown comparison '{}', expected one of < <= > >= == !=", other)),
+        }
+    }
+}
+
+/// One screener condition. Every filter reports the value it tested under
+/// its name, so results show why a ticker passed.
+#[derive(Clone, Debug)]
+pub enum ScreenFilter {
+    /// Latest value of a rule-language expression (e.g. `rsi(14)`).
+    Indicator { name: String, expr: Expr, op: Comparison, threshold: f64 },
+    /// A field of the per-ticker fundamentals dict; missing fields fail.
+    Fundamental { field: String, op: Comparison, threshold: f64 },
+    /// Average share volume over the trailing window.
+    MinVolume { window: usize, floor: f64 },
+    /// Average close x volume over the trailing window.
+    MinDollarVolume { window: usize, floor: f64 },
+}
+
+/// Mean of the last `window` values, NaN when history is too short.
+fn trailing_mean(values: &[f64], window: usize) -> f64 {
+    if values.len() < window || window == 0 {
+        return f64::NAN;
+    }
+    values[values.len() - window..].iter().sum::<f64>() / window as f64
+}
+
+impl ScreenFilter {
+    /// Returns the value tested and whether it passed.
+    fn apply(&self, candles: &Candles, fundamentals: Option<&HashMap<String, f64>>) -> Result<(String, f64, bool), String> {
+        Ok(match self {
+            ScreenFilter::Indicator { name, expr, op, threshold } => {
+                let series = rules::evaluate_numeric(expr, candles)?;
+                let v = series.last().copied().unwrap_or(f64::NAN);
+                (name.clone(), v, op.test(v, *threshold))
+            }
+            ScreenFilter::Fundamental { field, op, threshold } => {
+                let v = fundamentals.and_then(|f| f.get(field)).copied().unwrap_or(f64::NAN);
+                (field.clone(), v, op.test(v, *threshold))
+            }
+            ScreenFilter::MinVolume { window, floor } => {
+                let v = trailing_mean(&candles.volume, *window);
+                ("avg_volume".to_string(), v, v >= *floor)
+            }
+            ScreenFilter::MinDollarVolume { window, floor } => {
+                let dollar: Vec<f64> = candles.close.iter().zip(&candles.volume).map(|(c, v)| c * v).collect();
+                let v = trailing_mean(&dollar, *window);
+                ("avg_dollar_volume".to_string(), v, v >= *floor)
+            }
+        })
+    }
+}
+
+/// Filter name -> tested value for a ticker that passed.
+pub type ScreenMatch = Vec<(String, f64)>;
+
+/// Checks one ticker against every filter, stopping at the first failure.
+/// Returns the tested values when all filters pass.
+pub fn screen_ticker(
+    candles: &Candles,
+    fundamentals: Option<&HashMap<String, f64>>,
+    filters: &[ScreenFilter],
+) -> Result<Option<ScreenMatch>, String> {
+    let mut matched = Vec::with_capacity(filters.len());
+    for filter in filters {
+        let (name, value, passed) = filter.apply(candles, fundamentals)?;
+        if !passed {
+            return Ok(None);
+        }
+        matched.push((name, value));
+    }
+    Ok(Some(matched))
+}
+
+// --- 52-Week Highs/Lows ---
+
+/// Rolling high/low and how far price sits from each.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HighLowMetrics {
+    pub high: Vec<f64>,
+    pub low: Vec<f64>,
+    /// Percent below the rolling high (<= 0).
+    pub pct_from_high: Vec<f64>,
+    /// Percent above the rolling low (>= 0).
+    pub pct_from_low: Vec<f64>,
+    /// Price set (or matched) the rolling high on this bar.
+    pub new_high: Vec<bool>,
+    pub new_low: Vec<bool>,
+}
+
+/// Trailing `window`-bar high/low metrics; NaN/false until the first full window.
+pub fn high_low_metrics(prices: &[f64], window: usize) -> HighLowMetrics {
+    let high = indicators::rolling_max(prices, window);
+    let low = indicators::rolling_min(prices, window);
+    let pct = |p: f64, level: f64| if level == 0.0 { f64::NAN } else { (p / level - 1.0) * 100.0 };
+    HighLowMetrics {
+        pct_from_high: prices.iter().zip(&high).map(|(&p, &h)| pct(p, h)).collect(),
+        pct_from_low: prices.iter().zip(&low).map(|(&p, &l)| pct(p, l)).collect(),
+        new_high: prices.iter().zip(&high).map(|(&p, &h)| p >= h).collect(),
+        new_low: prices.iter().zip(&low).map(|(&p, &l)| p <= l).collect(),
+        high,
+        low,
+    }
+}
+
+// --- Moving-Average Crosses ---
+
+/// State of a fast/slow moving-average pair at the last bar.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MaCross {
+    /// 1 = golden cross, -1 = death cross within the lookback; 0 = none.
+    pub cross: i8,
+    /// Bars since that cross (0 = the last bar).
+    pub bars_ago: Option<usize>,
+    /// Fast vs slow MA in percent at the last bar (NaN during warm-up).
+    pub separation_pct: f64,
+    pub fast: f64,
+    pub slow: f64,
+}
+
+/// Finds the most recent fast/slow SMA cross within the last `lookback` bars.
+pub fn ma_cross(prices: &[f64], fast: usize, slow: usize, lookback: usize) -> MaCross {
+    let n = prices.len();
+    let mut fast_ma = indicators::sma(prices, fast);
+    let mut slow_ma = indicators::sma(prices, slow);
+    indicators::mask_warmup(&mut fast_ma, fast.saturating_sub(1));
+    indicators::mask_warmup(&mut slow_ma, slow.saturating_sub(1));
+
+    let diff = |i: usize| fast_ma[i] - slow_ma[i];
+    let first = n.saturating_sub(lookback).max(1);
+    let (cross, bars_ago) = (first..n)
+        .rev()
+        .find_map(|i| {
+            let (prev, cur) = (diff(i - 1), diff(i));
+            if prev <= 0.0 && cur > 0.0 {
+                Some((1, Some(n - 1 - i)))
+            } else if prev >= 0.0 && cur < 0.0 {
+                Some((-1, Some(n - 1 - i)))
+            } else {
+                None
+            }
+        })
+        .unwrap_or((0, None));
+
+    let (f, s) = if n == 0 { (f64::NAN, f64::NAN) } else { (fast_ma[n - 1], slow_ma[n - 1]) };
+    let separation_pct = if s == 0.0 { f64::NAN } else { (f / s - 1.0) * 100.0 };
+    MaCross { cross, bars_ago, separation_pct, fast: f, slow: s }
+}
+
+// --- Relative Strength ---
+
+/// Quarterly horizons and weights of the IBD-style strength score: the most
+/// recent quarter counts double.
+const RS_HORIZONS: [(usize, f64); 4] = [(63, 0.4), (126, 0.2), (189, 0.2), (252, 0.2)];
+
+/// Relative strength of one ticker against the benchmark.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RelativeStrength {
+    /// Price / benchmark over the common (end-aligned) history.
+    pub rs_line: Vec<f64>,
+    /// OLS slope of the RS line over the trailing window, in percent of its mean per bar.
+    pub slope: f64,
+    /// Weighted 3/6/9/12-month return; NaN with under a year of history.
+    pub score: f64,
+}
+
+/// RS line, trend and strength score. Series are aligned on their last bar,
+/// so a shorter history only shortens the RS line.
+pub fn relative_strength(prices: &[f64], benchmark: &[f64], window: usize) -> RelativeStrength {
+    let n = prices.len().min(benchmark.len());
+    let (p, b) = (&prices[prices.len() - n..], &benchmark[benchmark.len() - n..]);
+    let rs_line: Vec<f64> = p.iter().zip(b).map(|(&p, &b)| if b == 0.0 { f64::NAN } else { p / b }).collect();
+
+    let slope = if window >= 2 && n >= window {
+        let tail = &rs_line[n - window..];
+        let x: Vec<f64> = (0..window).map(|i| i as f64).collect();
+        let mean = stats::mean(&tail.iter().copied().filter(|v| v.is_finite()).collect::<Vec<_>>());
+        match stats::ols(&x, tail) {
+            Some((_, beta)) if mean != 0.0 => beta / mean * 100.0,
+            _ => f64::NAN,
+        }
+    } else {
+        f64::NAN
+    };
+
+    let score = RS_HORIZONS
+        .iter()
+        .map(|&(h, w)| w * skipped_return(prices, h, 0))
+        .sum::<f64>();
+    RelativeStrength { rs_line, slope, score }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: &[f64], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len());
+        for (i, (a, e)) in actual.iter().zip(expected).enumerate() {
+            assert!((a - e).abs() < 1e-9 || (a.is_nan() && e.is_nan()), "index {}: {} != {}", i, a, e);
+        }
+    }
+
+    #[test]
+    fn momentum_ranks_order_the_universe_by_blended_percentile() {
+        let series = vec![vec![100.0, 110.0, 121.0], vec![100.0, 90.0, 81.0], vec![100.0; 3], vec![100.0]];
+        let ranks = momentum_ranks(&series, &[1, 2], 0);
+        assert_close(&ranks[0].returns, &[0.1, 0.21]);
+        assert_close(&ranks[1].returns, &[-0.1, -0.19]);
+        assert_close(&ranks[3].returns, &[f64::NAN, f64::NAN]);
+        // Both lookbacks rank A > C > B, so the blend keeps that order.
+        assert_close(&ranks.iter().map(|r| r.score).collect::<Vec<_>>(), &[100.0, 0.0, 50.0, f64::NAN]);
+        assert_eq!(ranks.iter().map(|r| r.rank).collect::<Vec<_>>(), vec![Some(1), Some(3), Some(2), None]);
+    }
+
+    #[test]
+    fn momentum_averages_lookbacks_that_disagree() {
+        // X leads on the 2-bar return and trails on the 1-bar one; Y the reverse.
+        let series = vec![vec![100.0, 120.0, 90.0], vec![100.0, 80.0, 88.0]];
+        let ranks = momentum_ranks(&series, &[1, 2], 0);
+        assert_close(&ranks[0].returns, &[-0.25, -0.1]);
+        assert_close(&ranks[1].returns, &[0.1, -0.12]);
+        assert_close(&[ranks[0].score, ranks[1].score], &[50.0, 50.0]);
+    }
+
+    #[test]
+    fn momentum_skips_the_most_recent_bars() {
+        let ranks = momentum_ranks(&[vec![100.0, 110.0, 121.0, 50.0]], &[2, 3], 1);
+        // 121 / 100 over two bars ending a bar ago; three bars need a fifth price.
+        assert_close(&ranks[0].returns, &[0.21, f64::NAN]);
+        assert_eq!((ranks[0].score, ranks[0].rank), (50.0, Some(1)));
+    }
+
+    fn bars(open: &[f64], high: &[f64], low: &[f64], close: &[f64]) -> Candles {
+        Candles { open: open.to_vec(), high: high.to_vec(), low: low.to_vec(), close: close.to_vec(), ..Candles::default() }
+    }
+
+    #[test]
+    fn gaps_record_direction_size_and_fill() {
+        let candles = bars(
+            &[10.0, 10.5, 10.6, 9.5, 9.6],
+            &[10.2, 10.8, 10.7, 9.7, 9.8],
+            &[9.8, 10.3, 9.9, 9.4, 9.5],
+            &[10.0, 10.6, 10.0, 9.6, 9.7],
+        );
+        let gaps = detect_price_gaps(&candles, 2.0, None);
+        assert_eq!(gaps.len(), 2);
+        // Bar 1 opens 5% up and bar 2's low trades back to the 10.0 close.
+        assert_eq!((gaps[0].index, gaps[0].direction, gaps[0].prev_close, gaps[0].open), (1, 1, 10.0, 10.5));
+        assert_close(&[gaps[0].gap_pct], &[5.0]);
+        assert_eq!((gaps[0].fill_index, gaps[0].bars_to_fill()), (Some(2), Some(1)));
+        // Bar 3 opens 5% down and never trades back up to 10.0.
+        assert_eq!((gaps[1].index, gaps[1].direction), (3, -1));
+        assert_close(&[gaps[1].gap_pct], &[-5.0]);
+        assert_eq!(gaps[1].fill_index, None);
+
+        let unfilled = detect_price_gaps(&candles, 2.0, Some(0));
+        assert_eq!(unfilled[0].fill_index, None);
+        assert!(detect_price_gaps(&candles, 6.0, None).is_empty());
+    }
+
+    #[test]
+    fn screen_ticker_reports_every_tested_value() {
+        let close = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let candles = Candles { volume: vec![100.0, 200.0, 300.0, 400.0, 500.0], ..bars(&close, &close, &close, &close) };
+        let fundamentals: HashMap<String, f64> = [("pe".to_string(), 15.0)].into_iter().collect();
+        let filters = vec![
+            ScreenFilter::Indicator { name: "close".into(), expr: rules::parse_rule("close").unwrap(), op: Comparison::Gt, threshold: 4.0 },
+            ScreenFilter::Fundamental { field: "pe".into(), op: Comparison::Lt, threshold: 20.0 },
+            ScreenFilter::MinVolume { window: 2, floor: 450.0 },
+            // (4 x 400 + 5 x 500) / 2
+            ScreenFilter::MinDollarVolume { window: 2, floor: 2050.0 },
+        ];
+        let matched = screen_ticker(&candles, Some(&fundamentals), &filters).unwrap().unwrap();
+        assert_eq!(
+            matched,
+            vec![
+                ("close".to_string(), 5.0),
+                ("pe".to_string(), 15.0),
+                ("avg_volume".to_string(), 450.0),
+                ("avg_dollar_volume".to_string(), 2050.0),
+            ]
+        );
+
+        // A missing fundamental and a window longer than the history both
+        // fail; a rule on a column the bars lack is an error.
+        assert_eq!(screen_ticker(&candles, None, &filters).unwrap(), None);
+        let long = [ScreenFilter::MinVolume { window: 6, floor: 0.0 }];
+        assert_eq!(screen_ticker(&candles, None, &long).unwrap(), None);
+        let bad = [ScreenFilter::Indicator { name: "x".into(), expr: rules::parse_rule("volume").unwrap(), op: Comparison::Gt, threshold: 0.0 }];
+        assert!(screen_ticker(&bars(&close, &close, &close, &close), None, &bad).is_err());
+    }
+
+    #[test]
+    fn comparisons_parse_and_treat_nan_as_failing() {
+        let ops: Vec<Comparison> = ["<", "<=", ">", ">=", "==", "!="].iter().map(|s| s.parse().unwrap()).collect();
+        assert_eq!(ops.iter().map(|op| op.test(1.0, 1.0)).collect::<Vec<_>>(), [false, true, false, true, true, false]);
+        assert!(ops.iter().all(|op| !op.test(f64::NAN, 1.0)));
+        assert!("=>".parse::<Comparison>().is_err());
+    }
+
+    #[test]
+    fn high_low_metrics_measure_distance_from_the_window_extremes() {
+        let m = high_low_metrics(&[3.0, 1.0, 2.0, 5.0, 4.0, 0.5], 3);
+        let nan = f64::NAN;
+        assert_close(&m.high, &[nan, nan, 3.0, 5.0, 5.0, 5.0]);
+        assert_close(&m.low, &[nan, nan, 1.0, 1.0, 2.0, 0.5]);
+        assert_close(&m.pct_from_high, &[nan, nan, -100.0 / 3.0, 0.0, -20.0, -90.0]);
+        assert_close(&m.pct_from_low, &[nan, nan, 100.0, 400.0, 100.0, 0.0]);
+        assert_eq!(m.new_high, [false, false, false, true, false, false]);
+        assert_eq!(m.new_low, [false, false, false, false, false, true]);
+    }
+
+    #[test]
+    fn ma_cross_finds_the_latest_cross_in_the_lookback() {
+        // SMA(2) - SMA(3) runs -0.5, -1/6, 0.5, 0.5 from bar 2: golden at bar 4.
+        let golden = ma_cross(&[5.0, 4.0, 3.0, 4.0, 5.0, 6.0], 2, 3, 5);
+        assert_eq!((golden.cross, golden.bars_ago), (1, Some(1)));
+        assert_close(&[golden.fast, golden.slow, golden.separation_pct], &[5.5, 5.0, 10.0]);
+        assert_eq!(ma_cross(&[5.0, 4.0, 3.0, 4.0, 5.0, 6.0], 2, 3, 1).cross, 0);
+
+        let death = ma_cross(&[1.0, 2.0, 3.0, 2.0, 1.0], 2, 3, 5);
+        assert_eq!((death.cross, death.bars_ago), (-1, Some(0)));
+        assert_close(&[death.separation_pct], &[-25.0]);
+
+        let short = ma_cross(&[1.0, 2.0], 2, 3, 5);
+        assert_eq!((short.cross, short.bars_ago), (0, None));
+        assert!(short.slow.is_nan() && short.separation_pct.is_nan());
+    }
+
+    #[test]
+    fn relative_strength_aligns_on_the_last_bar() {
+        let rs = relative_strength(&[2.0, 4.0, 6.0], &[9.0, 2.0, 2.0, 3.0], 3);
+        assert_close(&rs.rs_line, &[1.0, 2.0, 2.0]);
+        // OLS slope 0.5 per bar over a mean of 5/3.
+        assert_close(&[rs.slope], &[30.0]);
+        assert!(rs.score.is_nan());
+        assert!(relative_strength(&[2.0, 4.0], &[0.0, 2.0], 3).slope.is_nan());
+        assert!(relative_strength(&[2.0, 4.0], &[0.0, 2.0], 2).rs_line[0].is_nan());
+    }
+
+    #[test]
+    fn relative_strength_score_weights_the_latest_quarter_double() {
+        let prices: Vec<f64> = (0..253).map(|i| 100.0 + i as f64).collect();
+        let rs = relative_strength(&prices, &prices, 20);
+        let expected = 0.4 * (352.0 / 289.0 - 1.0) + 0.2 * (352.0 / 226.0 - 1.0) + 0.2 * (352.0 / 163.0 - 1.0) + 0.2 * (352.0 / 100.0 - 1.0);
+        assert_close(&[rs.score, rs.slope], &[expected, 0.0]);
+    }
+}
@@ -0,0 +1,126 @@
+//! Prefix search over a symbol universe.
+//!
+//! Every searchable key (the ticker, the full company name, and each later
+//! word of the name) is kept lower-cased in one sorted array, so the keys
+//! starting with a prefix form a contiguous range found by two binary
+//! searches. That is the lookup a trie gives, without a node per character.
+
+/// What part of an entry a key came from, best match first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MatchKind {
+    /// The query is the whole ticker.
+    ExactSymbol,
+    SymbolPrefix,
+    NamePrefix,
+    /// A later word of the name starts with the query.
+    WordPrefix,
+}
+
+impl MatchKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            MatchKind::ExactSymbol => "exact",
+            MatchKind::SymbolPrefix => "symbol",
+            MatchKind::NamePrefix => "name",
+            MatchKind::WordPrefix => "word",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SymbolEntry {
+    pub symbol: String,
+    pub name: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SearchHit<'a> {
+    pub entry: &'a SymbolEntry,
+    pub kind: MatchKind,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct SymbolIndex {
+    entries: Vec<SymbolEntry>,
+    /// (lower-cased key, entry index, what the key was taken from), sorted
+    /// by key.
+    keys: Vec<(String, u32, KeyKind)>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum KeyKind {
+    Symbol,
+    Name,
+    Word,
+}
+
+impl SymbolIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds entries and re-sorts the keys once; prefer one call with the
+    /// whole universe over many single additions.
+    pub fn extend(&mut self, entries: impl IntoIterator<Item = SymbolEntry>) {
+        for entry in entries {
+            let id = self.entries.len() as u32;
+            self.keys.push((entry.symbol.to_lowercase(), id, KeyKind::Symbol));
+            let name = entry.name.trim().to_lowercase();
+            if !name.is_empty() {
+                for (i, _) in name.match_indices(' ').filter(|&(i, _)| name[i + 1..].chars().next().is_some_and(|c| c != ' ')) {
+                    self.keys.push((name[i + 1..].to_string(), id, KeyKind::Word));
+                }
+                self.keys.push((name, id, KeyKind::Name));
+            }
+            self.entries.push(entry);
+        }
+        self.keys.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+    }
+
+    /// Up to `limit` entries matching `prefix` (case-insensitive), best
+    /// first: exact ticker, ticker prefix, name prefix, then name-word
+    /// prefix; ties go to the shorter, then alphabetically first, ticker.
+    pub fn search(&self, prefix: &str, limit: usize) -> Vec<SearchHit<'_>> {
+        let query = prefix.trim().to_lowercase();
+        if query.is_empty() || limit == 0 {
+            return Vec::new();
+        }
+        let start = self.keys.partition_point(|k| k.0.as_str() < query.as_str());
+        let end = start + self.keys[start..].partition_point(|k| k.0.starts_with(&query));
+
+        // Best match per entry.
+        let mut best: Vec<(MatchKind, u32)> = self.keys[start..end]
+            .iter()
+            .map(|(key, id, kind)| {
+                let kind = match kind {
+                    KeyKind::Symbol if key.len() == query.len() => MatchKind::ExactSymbol,
+                    KeyKind::Symbol => MatchKind::SymbolPrefix,
+                    KeyKind::Name => MatchKind::NamePrefix,
+                    KeyKind::Word => MatchKind::WordPrefix,
+                };
+                (kind, *id)
+            })
+            .collect();
+        best.sort_unstable_by_key(|&(kind, id)| (id, kind));
+        best.dedup_by_key(|&mut (_, id)| id);
+
+        let rank = |&(kind, id): &(MatchKind, u32)| {
+            let symbol = self.entries[id as usize].symbol.as_str();
+            (kind, symbol.len(), symbol)
+        };
+        if best.len() > limit {
+            best.select_nth_unstable_by_key(limit - 1, rank);
+            best.truncate(limit);
+        }
+        best.sort_by_key(rank);
+        best.into_iter().map(|(kind, id)| SearchHit { entry: &self.entries[id as usize], kind }).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
@@ -0,0 +1,159 @@
+//! Sector and industry classification of tickers.
+//!
+//! A [`SectorMap`] holds curated GICS-style classifications. Lookups fall
+//! back to the normalized ticker and its bare symbol, and for instruments
+//! missing from the map a sector is inferred from the instrument's type and
+//! name as ingested from the broker, so exposure breakdowns are never left
+//! with a large "Unknown" bucket for want of a curated entry.
+
+use std::collections::HashMap;
+
+use crate::ticker::normalize_ticker;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Classification {
+    pub sector: String,
+    pub industry: Option<String>,
+}
+
+/// Where a classification came from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Source {
+    Map,
+    Inferred,
+    Unknown,
+}
+
+impl Source {
+    pub fn name(self) -> &'static str {
+        match self {
+            Source::Map => "map",
+            Source::Inferred => "inferred",
+            Source::Unknown => "unknown",
+        }
+    }
+}
+
+/// Name keywords (upper case) and the sector they imply, most specific
+/// first.
+const NAME_KEYWORDS: &[(&str, &str)] = &[
+    ("REIT", "Real Estate"),
+    ("PROPERT", "Real Estate"),
+    ("BANK", "Financials"),
+    ("INSURANCE", "Financials"),
+    ("ASSURANCE", "Financials"),
+    ("CAPITAL", "Financials"),
+    ("PHARMA", "Health Care"),
+    ("THERAPEUTIC", "Health Care"),
+    ("BIO", "Health Care"),
+    ("MEDICAL", "Health Care"),
+    ("HEALTH", "Health Care"),
+    ("SEMICONDUCTOR", "Information Technology"),
+    ("SOFTWARE", "Information Technology"),
+    ("TECHNOLOG", "Information Technology"),
+    ("MICRO", "Information Technology"),
+    ("OIL", "Energy"),
+    ("PETROLEUM", "Energy"),
+    ("ENERGY", "Energy"),
+    ("GAS", "Energy"),
+    ("MINING", "Materials"),
+    ("GOLD", "Materials"),
+    ("METAL", "Materials"),
+    ("CHEMICAL", "Materials"),
+    ("TELECOM", "Communication Services"),
+    ("MEDIA", "Communication Services"),
+    ("ENTERTAINMENT", "Communication Services"),
+    ("UTILIT", "Utilities"),
+    ("WATER", "Utilities"),
+    ("ELECTRIC", "Utilities"),
+    ("AEROSPACE", "Industrials"),
+    ("DEFENCE", "Industrials"),
+    ("DEFENSE", "Industrials"),
+    ("AIRLINE", "Industrials"),
+    ("MOTOR", "Consumer Discretionary"),
+    ("RETAIL", "Consumer Discretionary"),
+    ("FOOD", "Consumer Staples"),
+    ("BEVERAGE", "Consumer Staples"),
+    ("TOBACCO", "Consumer Staples"),
+];
+
+/// Sector implied by an instrument's type (e.g. Trading212's "ETF") and
+/// name, or None when neither says anything.
+pub fn infer_sector(name: Option<&str>, instrument_type: Option<&str>) -> Option<String> {
+    if let Some(kind) = instrument_type {
+        match kind.to_ascii_uppercase().as_str() {
+            "ETF" | "ETP" | "ETC" | "ETN" | "FUND" => return Some("Funds".to_string()),
+            "CRYPTO" | "CRYPTOCURRENCY" => return Some("Crypto".to_string()),
+            _ => {}
+        }
+    }
+    let name = name?.to_ascii_uppercase();
+    NAME_KEYWORDS.iter().find(|(keyword, _)| name.contains(keyword)).map(|(_, sector)| sector.to_string())
+}
+
+/// Ticker -> classification, keyed by normalized ticker.
+#[derive(Clone, Debug, Default)]
+pub struct SectorMap {
+    entries: HashMap<String, Classification>,
+}
+
+impl SectorMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds or replaces a ticker's classification.
+    pub fn insert(&mut self, ticker: &str, classification: Classification) {
+        self.entries.insert(normalize_ticker(ticker), classification);
+    }
+
+    /// Adds `ticker,sector[,industry]` lines; a header line starting with
+    /// "ticker" and blank lines are skipped. Returns the entries read.
+    pub fn load_csv(&mut self, text: &str) -> Result<usize, String> {
+        let mut count = 0;
+        for (i, line) in text.lines().enumerate() {
+            let fields: Vec<&str> = line.split(',').map(|f| f.trim().trim_matches('"')).collect();
+            if line.trim().is_empty() || (i == 0 && fields[0].eq_ignore_ascii_case("ticker")) {
+                continue;
+            }
+            let (ticker, sector) = match fields.as_slice() {
+                [ticker, sector, ..] if !ticker.is_empty() && !sector.is_empty() => (*ticker, *sector),
+                _ => return Err(format!("line {}: expected 'ticker,sector[,industry]', got '{}'", i + 1, line)),
+            };
+            let industry = fields.get(2).filter(|f| !f.is_empty()).map(|f| f.to_string());
+            self.insert(ticker, Classification { sector: sector.to_string(), industry });
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// The curated classification of `ticker`, trying the normalized ticker
+    /// and then its symbol without an exchange suffix (`VOD.L` -> `VOD`).
+    pub fn get(&self, ticker: &str) -> Option<&Classification> {
+        let normalized = normalize_ticker(ticker);
+        self.entries.get(&normalized).or_else(|| {
+            let (symbol, _) = normalized.split_once('.')?;
+            self.entries.get(symbol)
+        })
+    }
+
+    /// Classification of `ticker`, inferring the sector from its metadata
+    /// when it is not in the map.
+    pub fn classify(&self, ticker: &str, name: Option<&str>, instrument_type: Option<&str>) -> (Option<Classification>, Source) {
+        if let Some(c) = self.get(ticker) {
+            return (Some(c.clone()), Source::Map);
+        }
+        match infer_sector(name, instrument_type) {
+            Some(sector) => (Some(Classification { sector, industry: None }), Source::Inferred),
+            None => (None, Source::Unknown),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
@@ -0,0 +1,499 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::candles::Candles;
+use crate::indicators;
+use crate::stats;
+
+/// Entry/exit arrays in the layout vectorized backtesters consume.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SignalSet {
+    pub long_entries: Vec<bool>,
+    pub long_exits: Vec<bool>,
+    pub short_entries: Vec<bool>,
+    pub short_exits: Vec<bool>,
+    /// Position held at the close of each bar: 1 long, -1 short, 0 flat.
+    pub position: Vec<i8>,
+}
+
+impl SignalSet {
+    fn with_len(n: usize) -> Self {
+        SignalSet {
+            long_entries: vec![false; n],
+            long_exits: vec![false; n],
+            short_entries: vec![false; n],
+            short_exits: vec![false; n],
+            position: vec![0; n],
+        }
+    }
+}
+
+/// Band-based state machine with hysteresis over a z-score series.
+///
+/// Flat: enter long when `z < -entry_z`, short when `z > entry_z`.
+/// Long: exit once `z >= -exit_z`; short: exit once `z <= exit_z`.
+/// An optional `stop_z` closes positions whose z-score keeps diverging past it.
+/// The gap between entry and exit bands prevents flip-flopping around a level.
+pub fn zscore_band_signals(z: &[f64], entry_z: f64, exit_z: f64, stop_z: Option<f64>, allow_short: bool) -> SignalSet {
+    let n = z.len();
+    let mut s = SignalSet::with_len(n);
+    let mut pos: i8 = 0;
+    for (i, &zi) in z.iter().enumerate() {
+        if !zi.is_nan() {
+            match pos {
+                1 if zi >= -exit_z || stop_z.is_some_and(|stop| zi < -stop) => {
+                    s.long_exits[i] = true;
+                    pos = 0;
+                }
+                -1 if zi <= exit_z || stop_z.is_some_and(|stop| zi > stop) => {
+                    s.short_exits[i] = true;
+                    pos = 0;
+                }
+                0 if zi < -entry_z && stop_z.is_none_or(|stop| zi >= -stop) => {
+                    s.long_entries[i] = true;
+                    pos = 1;
+                }
+                0 if allow_short && zi > entry_z && stop_z.is_none_or(|stop| zi <= stop) => {
+                    s.short_entries[i] = true;
+                    pos = -1;
+                }
+                _ => {}
+            }
+        }
+        s.position[i] = pos;
+    }
+    s
+}
+
+// --- Pairs Trading ---
+
+/// Spread `a - hedge_ratio * b`. Without an explicit ratio it is estimated by
+/// OLS of `a` on `b` (the Engle-Granger first step). Returns (spread, ratio).
+pub fn pair_spread(a: &[f64], b: &[f64], hedge_ratio: Option<f64>) -> Option<(Vec<f64>, f64)> {
+    let ratio = match hedge_ratio {
+        Some(r) => r,
+        None => stats::ols(b, a)?.1,
+    };
+    Some((a.iter().zip(b).map(|(x, y)| x - ratio * y).collect(), ratio))
+}
+
+/// Z-score of a spread, rolling over `window` bars or over the full sample,
+/// against a std over `n - ddof` degrees of freedom.
+pub fn spread_zscore(spread: &[f64], window: Option<usize>, ddof: usize) -> Vec<f64> {
+    match window {
+        Some(w) => stats::rolling_zscore_ddof(spread, w, ddof),
+        None => {
+            let finite: Vec<f64> = spread.iter().copied().filter(|x| x.is_finite()).collect();
+            let (mu, sd) = (stats::mean(&finite), stats::std_dev_ddof(&finite, ddof));
+            spread.iter().map(|x| if sd == 0.0 || sd.is_nan() { 0.0 } else { (x - mu) / sd }).collect()
+        }
+    }
+}
+
+// --- Breakouts ---
+
+/// One detected breakout.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Breakout {
+    pub index: usize,
+    /// 1 = upside, -1 = downside.
+    pub direction: i8,
+    pub close: f64,
+    /// Channel level that was broken.
+    pub level: f64,
+    /// Distance beyond the level in ATR units (NaN before the ATR is ready).
+    pub strength: f64,
+    /// Volume relative to its average (NaN without volume).
+    pub volume_ratio: f64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BreakoutResult {
+    pub up: Vec<bool>,
+    pub down: Vec<bool>,
+    pub upper: Vec<f64>,
+    pub lower: Vec<f64>,
+    pub breakouts: Vec<Breakout>,
+}
+
+/// Closes beyond the Donchian channel of the *previous* `period` bars,
+/// optionally widened by `atr_mult` x ATR. With `volume_mult` set, a breakout
+/// also needs volume above that multiple of its prior `period`-bar average.
+/// A zero `period` has no channel and finds no breakouts.
+pub fn breakout_signals(candles: &Candles, period: usize, atr_mult: f64, volume_mult: Option<f64>) -> BreakoutResult {
+    let n = candles.len();
+    if period == 0 {
+        return BreakoutResult { up: vec![false; n], down: vec![false; n], upper: vec![f64::NAN; n], lower: vec![f64::NAN; n], breakouts: Vec::new() };
+    }
+    let prior = |v: Vec<f64>| -> Vec<f64> { (0..n).map(|i| if i == 0 { f64::NAN } else { v[i - 1] }).collect() };
+    let highest = prior(indicators::rolling_max(&candles.high, period));
+    let lowest = prior(indicators::rolling_min(&candles.low, period));
+
+    let mut atr = indicators::atr(&candles.high, &candles.low, &candles.close, period);
+    indicators::mask_warmup(&mut atr, period - 1);
+    let avg_volume = if candles.has_volume() {
+        prior(stats::rolling_mean(&candles.volume, period))
+    } else {
+        vec![f64::NAN; n]
+    };
+
+    let widen = |i: usize| if atr_mult > 0.0 && i > 0 { atr_mult * atr[i - 1] } else { 0.0 };
+    let upper: Vec<f64> = (0..n).map(|i| highest[i] + widen(i)).collect();
+    let lower: Vec<f64> = (0..n).map(|i| lowest[i] - widen(i)).collect();
+
+    let mut result = BreakoutResult { up: vec![false; n], down: vec![false; n], ..Default::default() };
+    for i in 0..n {
+        let close = candles.close[i];
+        let volume_ratio = if candles.has_volume() && avg_volume[i] > 0.0 { candles.volume[i] / avg_volume[i] } else { f64::NAN };
+        let volume_ok = volume_mult.is_none_or(|m| volume_ratio > m);
+        let direction = if close > upper[i] {
+            1
+        } else if close < lower[i] {
+            -1
+        } else {
+            continue;
+        };
+        if !volume_ok {
+            continue;
+        }
+        let level = if direction == 1 { upper[i] } else { lower[i] };
+        let atr_ref = if i > 0 { atr[i - 1] } else { f64::NAN };
+        if direction == 1 { result.up[i] = true } else { result.down[i] = true }
+        result.breakouts.push(Breakout {
+            index: i,
+            direction,
+            close,
+            level,
+            strength: if atr_ref > 0.0 { (close - level).abs() / atr_ref } else { f64::NAN },
+            volume_ratio,
+        });
+    }
+    result.upper = upper;
+    result.lower = lower;
+    result
+}
+
+// --- Unusual Volume ---
+
+/// Volume relative to its baseline: the mean of the previous `window` bars, or
+/// with `timestamps` the mean of the same time-of-day slot over the previous
+/// `window` sessions (so the opening/closing auctions are not compared with
+/// lunchtime volume). Ratios are NaN until the baseline has `window` samples.
+pub fn volume_ratios(volume: &[f64], window: usize, timestamps: Option<&[i64]>, utc_offset_secs: i64) -> Vec<f64> {
+    let ratio = |v: f64, history: &VecDeque<f64>| -> f64 {
+        if history.len() < window {
+            return f64::NAN;
+        }
+        let avg = history.iter().sum::<f64>() / history.len() as f64;
+        if avg > 0.0 { v / avg } else { f64::NAN }
+    };
+
+    match timestamps {
+        None => {
+            let mut history = VecDeque::with_capacity(window + 1);
+            volume
+                .iter()
+                .map(|&v| {
+                    let r = ratio(v, &history);
+                    history.push_back(v);
+                    if history.len() > window {
+                        history.pop_front();
+                    }
+                    r
+                })
+                .collect()
+        }
+        Some(ts) => {
+            let mut slots: HashMap<i64, VecDeque<f64>> = HashMap::new();
+            volume
+                .iter()
+                .zip(ts)
+                .map(|(&v, &t)| {
+                    let slot = (t + utc_offset_secs).rem_euclid(86_400);
+                    let history = slots.entry(slot).or_default();
+                    let r = ratio(v, history);
+                    history.push_back(v);
+                    if history.len() > window {
+                        history.pop_front();
+                    }
+                    r
+                })
+                .collect()
+        }
+    }
+}
+
+// --- Multi-Timeframe Confirmation ---
+
+/// How aligned timeframe signals are combined.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ConfirmRule {
+    /// Every timeframe must point the same way.
+    All,
+    /// More than half of the timeframes must point the same way.
+    Majority,
+}
+
+impl std::str::FromStr for ConfirmRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "all" => Ok(ConfirmRule::All),
+            "majority" => Ok(ConfirmRule::Majority),
+            other => Err(format!("Unknown rule '{}', expected 'all' or 'majority'", other)),
+        }
+    }
+}
+
+/// As-of join: each base timestamp takes the latest signal stamped at or
+/// before it (0 before the first one). Both timelines must be ascending.
+pub fn align_asof(timestamps: &[i64], signals: &[i8], base: &[i64]) -> Vec<i8> {
+    let mut j = 0;
+    let mut current = 0;
+    base.iter()
+        .map(|&t| {
+            while j < timestamps.len() && timestamps[j] <= t {
+                current = signals[j];
+                j += 1;
+            }
+            current
+        })
+        .collect()
+}
+
+/// Combines aligned -1/0/1 signals bar by bar.
+pub fn combine_signals(aligned: &[Vec<i8>], rule: ConfirmRule) -> Vec<i8> {
+    let n = aligned.first().map_or(0, |a| a.len());
+    let k = aligned.len();
+    (0..n)
+        .map(|i| {
+            let longs = aligned.iter().filter(|a| a[i] > 0).count();
+            let shorts = aligned.iter().filter(|a| a[i] < 0).count();
+            let needed = match rule {
+                ConfirmRule::All => k,
+                ConfirmRule::Majority => k / 2 + 1,
+            };
+            if longs >= needed {
+                1
+            } else if shorts >= needed {
+                -1
+            } else {
+                0
+            }
+        })
+        .collect()
+}
+
+// --- Signal Quality ---
+
+/// Forward-return statistics of a signal at one horizon.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HorizonStats {
+    pub horizon: usize,
+    /// Signals with a full forward window.
+    pub count: usize,
+    /// Fraction of signals whose direction-adjusted return, net of costs,
+    /// was positive.
+    pub hit_rate: f64,
+    /// Mean raw forward return after a signal.
+    pub avg_return: f64,
+    /// Mean gain of winning (direction-adjusted, net) signals.
+    pub avg_win: f64,
+    /// Mean loss of losing signals, as a positive number.
+    pub avg_loss: f64,
+    /// `hit_rate * avg_win - (1 - hit_rate) * avg_loss`.
+    pub expectancy: f64,
+}
+
+/// Forward returns after each non-zero signal (`1` long, `-1` short), at
+/// every horizon. Signals too close to the end of the series are skipped.
+///
+/// `round_trip_cost` (a fraction, e.g. FX fees on both legs) is taken off
+/// every trade's direction-adjusted return; `avg_return` stays raw.
+pub fn signal_stats(prices: &[f64], signals: &[i8], horizons: &[usize], round_trip_cost: f64) -> Vec<HorizonStats> {
+    let n = prices.len().min(signals.len());
+    horizons
+        .iter()
+        .map(|&h| {
+            let (mut count, mut wins, mut raw_sum, mut win_sum, mut loss_sum) = (0usize, 0usize, 0.0, 0.0, 0.0);
+            for i in 0..n.saturating_sub(h) {
+                let s = signals[i];
+                if s == 0 || prices[i] == 0.0 || prices[i].is_nan() || prices[i + h].is_nan() {
+                    continue;
+                }
+                let raw = prices[i + h] / prices[i] - 1.0;
+                let directed = f64::from(s) * raw - round_trip_cost;
+                count += 1;
+                raw_sum += raw;
+                if directed > 0.0 {
+                    wins += 1;
+                    win_sum += directed;
+                } else {
+                    loss_sum -= directed;
+                }
+            }
+            let losses = count - wins;
+            let avg = |sum: f64, k: usize| if k == 0 { f64::NAN } else { sum / k as f64 };
+            let hit_rate = avg(wins as f64, count);
+            let (avg_win, avg_loss) = (avg(win_sum, wins), avg(loss_sum, losses));
+            // Equals hit_rate * avg_win - (1 - hit_rate) * avg_loss, but stays
+            // defined when there are no wins or no losses.
+            let expectancy = if count == 0 {
+                f64::NAN
+            } else {
+                (win_sum - loss_sum) / count as f64
+            };
+            HorizonStats { horizon: h, count, hit_rate, avg_return: avg(raw_sum, count), avg_win, avg_loss, expectancy }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flags(v: &[bool]) -> Vec<usize> {
+        v.iter().enumerate().filter(|(_, &f)| f).map(|(i, _)| i).collect()
+    }
+
+    fn assert_close(actual: &[f64], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len());
+        for (i, (a, e)) in actual.iter().zip(expected).enumerate() {
+            assert!((a - e).abs() < 1e-9 || (a.is_nan() && e.is_nan()), "index {}: {} != {}", i, a, e);
+        }
+    }
+
+    #[test]
+    fn zscore_bands_enter_beyond_entry_and_exit_inside_exit() {
+        let z = [0.0, -2.5, -1.0, -0.2, 2.5, 1.0, 0.4, f64::NAN, -4.0];
+        let s = zscore_band_signals(&z, 2.0, 0.5, None, true);
+        assert_eq!(s.position, [0, 1, 1, 0, -1, -1, 0, 0, 1]);
+        assert_eq!((flags(&s.long_entries), flags(&s.long_exits)), (vec![1, 8], vec![3]));
+        assert_eq!((flags(&s.short_entries), flags(&s.short_exits)), (vec![4], vec![6]));
+
+        let long_only = zscore_band_signals(&z, 2.0, 0.5, None, false);
+        assert_eq!(long_only.position, [0, 1, 1, 0, 0, 0, 0, 0, 1]);
+    }
+
+    #[test]
+    fn zscore_stop_closes_and_blocks_runaway_moves() {
+        let s = zscore_band_signals(&[-2.5, -3.5, -4.0, 3.5, 2.5, 3.2], 2.0, 0.5, Some(3.0), true);
+        // Stopped out at -3.5; no re-entry beyond the stop, then a short from 2.5.
+        assert_eq!(s.position, [1, 0, 0, 0, -1, 0]);
+        assert_eq!(flags(&s.long_exits), [1]);
+        assert_eq!(flags(&s.short_exits), [5]);
+    }
+
+    #[test]
+    fn pair_spread_estimates_the_hedge_ratio_by_ols() {
+        let (a, b) = ([3.0, 5.0, 7.0, 9.0], [1.0, 2.0, 3.0, 4.0]);
+        let (spread, ratio) = pair_spread(&a, &b, None).unwrap();
+        // a = 1 + 2b exactly, so the spread is the intercept.
+        assert_close(&[ratio], &[2.0]);
+        assert_close(&spread, &[1.0; 4]);
+        assert_eq!(pair_spread(&a, &b, Some(1.0)).unwrap(), (vec![2.0, 3.0, 4.0, 5.0], 1.0));
+        assert!(pair_spread(&a, &[2.0; 4], None).is_none());
+    }
+
+    #[test]
+    fn spread_zscore_uses_the_full_sample_or_a_window() {
+        let spread = [1.0, 2.0, 3.0, 5.0];
+        // Mean 2.75; population variance 2.1875, sample variance 35/12.
+        let pop = 2.1875f64.sqrt();
+        assert_close(&spread_zscore(&spread, None, 0), &spread.map(|x| (x - 2.75) / pop));
+        let sample = (35.0f64 / 12.0).sqrt();
+        assert_close(&spread_zscore(&spread, None, 1), &spread.map(|x| (x - 2.75) / sample));
+        assert_close(&spread_zscore(&[4.0; 3], None, 0), &[0.0; 3]);
+
+        // [1, 2, 3] has mean 2 and std sqrt(2/3); [2, 3, 5] mean 10/3 and std sqrt(14/9).
+        let rolling = spread_zscore(&spread, Some(3), 0);
+        assert_close(&rolling, &[f64::NAN, f64::NAN, 1.0 / (2.0f64 / 3.0).sqrt(), (5.0 - 10.0 / 3.0) / (14.0f64 / 9.0).sqrt()]);
+    }
+
+    #[test]
+    fn breakouts_clear_the_prior_channel() {
+        let candles = Candles {
+            high: vec![10.0, 11.0, 10.5, 12.0, 9.0],
+            low: vec![9.0, 10.0, 9.5, 10.0, 7.0],
+            close: vec![9.5, 10.5, 10.0, 11.8, 7.5],
+            volume: vec![100.0, 100.0, 100.0, 300.0, 100.0],
+            ..Candles::default()
+        };
+        // Channels of the two bars before each bar; ATR(2) is 1.25, 1.125,
+        // 1.5625 on bars 1-3, and the prior two-bar volume averages 100, 200.
+        let r = breakout_signals(&candles, 2, 0.0, None);
+        assert_close(&r.upper, &[f64::NAN, f64::NAN, 11.0, 11.0, 12.0]);
+        assert_close(&r.lower, &[f64::NAN, f64::NAN, 9.0, 9.5, 9.5]);
+        assert_eq!((flags(&r.up), flags(&r.down)), (vec![3], vec![4]));
+        let b = &r.breakouts;
+        assert_eq!((b[0].index, b[0].direction, b[0].close, b[0].level), (3, 1, 11.8, 11.0));
+        assert_close(&[b[0].strength, b[0].volume_ratio], &[0.8 / 1.125, 3.0]);
+        assert_eq!((b[1].index, b[1].direction, b[1].level), (4, -1, 9.5));
+        assert_close(&[b[1].strength, b[1].volume_ratio], &[2.0 / 1.5625, 0.5]);
+
+        let confirmed = breakout_signals(&candles, 2, 0.0, Some(2.0));
+        assert_eq!(confirmed.breakouts.iter().map(|b| b.index).collect::<Vec<_>>(), [3]);
+
+        // One ATR of headroom swallows the upside break but not the downside one.
+        let widened = breakout_signals(&candles, 2, 1.0, None);
+        assert_eq!((flags(&widened.up), flags(&widened.down)), (vec![], vec![4]));
+        assert_close(&[widened.breakouts[0].level, widened.breakouts[0].strength], &[7.9375, 0.4375 / 1.5625]);
+    }
+
+    #[test]
+    fn volume_ratios_compare_with_the_trailing_mean() {
+        let ratios = volume_ratios(&[100.0, 200.0, 300.0, 600.0, 50.0], 2, None, 0);
+        assert_close(&ratios, &[f64::NAN, f64::NAN, 2.0, 2.4, 50.0 / 450.0]);
+        assert!(volume_ratios(&[0.0, 0.0, 5.0], 2, None, 0)[2].is_nan());
+    }
+
+    #[test]
+    fn volume_ratios_compare_time_of_day_slots() {
+        // A 09:00 and a 15:00 bar on three days; the afternoon trades ten times more.
+        let day = 86_400;
+        let ts: Vec<i64> = (0..3).flat_map(|d| [d * day + 9 * 3600, d * day + 15 * 3600]).collect();
+        let volume = [100.0, 1000.0, 200.0, 2000.0, 300.0, 1500.0];
+        let expected = [f64::NAN, f64::NAN, f64::NAN, f64::NAN, 2.0, 1.0];
+        assert_close(&volume_ratios(&volume, 2, Some(&ts), 0), &expected);
+        assert_close(&volume_ratios(&volume, 2, Some(&ts), -5 * 3600), &expected);
+    }
+
+    #[test]
+    fn align_asof_carries_the_latest_higher_timeframe_signal() {
+        let aligned = align_asof(&[10, 20, 30], &[1, -1, 0], &[5, 10, 15, 25, 35]);
+        assert_eq!(aligned, [0, 1, 1, -1, 0]);
+    }
+
+    #[test]
+    fn combine_signals_needs_all_or_a_majority() {
+        let aligned = vec![vec![1, 1, -1, 0], vec![1, -1, -1, 0], vec![1, 1, -1, 1]];
+        assert_eq!(combine_signals(&aligned, "all".parse().unwrap()), [1, 0, -1, 0]);
+        assert_eq!(combine_signals(&aligned, "Majority".parse().unwrap()), [1, 1, -1, 0]);
+        // Two of four is not a majority.
+        let even = vec![vec![1], vec![1], vec![-1], vec![0]];
+        assert_eq!(combine_signals(&even, ConfirmRule::Majority), [0]);
+        assert!("any".parse::<ConfirmRule>().is_err());
+    }
+
+    #[test]
+    fn signal_stats_score_direction_adjusted_forward_returns() {
+        let prices = [100.0, 110.0, 99.0, 99.0, 108.9];
+        let signals = [1, -1, 0, 1, 1];
+        let stats = signal_stats(&prices, &signals, &[1, 2], 0.0);
+        // One bar ahead: +10%, a short into -10% and +10%, all winners; the
+        // last signal has no forward bar.
+        let h1 = &stats[0];
+        assert_eq!((h1.horizon, h1.count), (1, 3));
+        assert_close(&[h1.hit_rate, h1.avg_return, h1.avg_win, h1.expectancy], &[1.0, 0.1 / 3.0, 0.1, 0.1]);
+        assert!(h1.avg_loss.is_nan());
+        // Two bars ahead: the long loses 1%, the short gains 10%.
+        let h2 = &stats[1];
+        assert_eq!(h2.count, 2);
+        assert_close(&[h2.hit_rate, h2.avg_return, h2.avg_win, h2.avg_loss, h2.expectancy], &[0.5, -0.055, 0.1, 0.01, 0.045]);
+        assert_close(&[h2.expectancy], &[h2.hit_rate * h2.avg_win - (1.0 - h2.hit_rate) * h2.avg_loss]);
+
+        let costed = &signal_stats(&prices, &signals, &[1], 0.02)[0];
+        assert_close(&[costed.avg_return, costed.avg_win, costed.expectancy], &[0.1 / 3.0, 0.08, 0.08]);
+        assert!(signal_stats(&prices, &signals, &[5], 0.0)[0].expectancy.is_nan());
+    }
+}
@@ -0,0 +1,419 @@
+use std::collections::HashMap;
+
+use crate::lanes::{self, Real};
+use crate::moments::RollingMoments;
+
+/// Arithmetic mean of a slice (0.0 for an empty slice).
+pub fn mean(data: &[f64]) -> f64 {
+    if data.is_empty() {
+        return 0.0;
+    }
+    lanes::sum(data) / data.len() as f64
+}
+
+/// Population standard deviation of a slice (0.0 for an empty slice).
+pub fn std_dev(data: &[f64]) -> f64 {
+    if data.is_empty() {
+        return 0.0;
+    }
+    let mu = mean(data);
+    let variance = lanes::sum_sq_dev(data, mu) / data.len() as f64;
+    variance.sqrt()
+}
+
+/// Standard deviation over `n - ddof` degrees of freedom; `ddof = 1` is the
+/// sample std. NaN for `ddof` or fewer values, except as [`std_dev`] for
+/// `ddof = 0`.
+pub fn std_dev_ddof(data: &[f64], ddof: usize) -> f64 {
+    if ddof == 0 {
+        return std_dev(data);
+    }
+    if data.len() <= ddof {
+        return f64::NAN;
+    }
+    (lanes::sum_sq_dev(data, mean(data)) / (data.len() - ddof) as f64).sqrt()
+}
+
+/// Rolling mean and std over `period` bars (`period - ddof` degrees of
+/// freedom) in one pass, NaN until the first full window and for any window
+/// containing a NaN or ±Inf.
+fn rolling_moments<T: Real>(data: &[T], period: usize, ddof: usize) -> Vec<(T, T)> {
+    if period == 0 {
+        return vec![(T::nan(), T::nan()); data.len()];
+    }
+    let mut moments = RollingMoments::with_ddof(period, ddof);
+    data.iter()
+        .map(|&x| moments.push(x).map_or((T::nan(), T::nan()), |(mu, variance)| (mu, variance.sqrt())))
+        .collect()
+}
+
+/// Rolling population standard deviation, on the same running moments as
+/// Bollinger Bands. The output is NaN until the first full window and for
+/// any window containing a NaN or ±Inf.
+pub fn rolling_std<T: Real>(data: &[T], period: usize) -> Vec<T> {
+    rolling_std_ddof(data, period, 0)
+}
+
+/// [`rolling_std`] over `period - ddof` degrees of freedom; `ddof = 1`
+/// matches pandas' `rolling().std()`.
+pub fn rolling_std_ddof<T: Real>(data: &[T], period: usize, ddof: usize) -> Vec<T> {
+    rolling_moments(data, period, ddof).into_iter().map(|(_, sd)| sd).collect()
+}
+
+/// Rolling mean over `period` bars, NaN until the first full window and for
+/// any window containing a NaN or ±Inf.
+pub fn rolling_mean<T: Real>(data: &[T], period: usize) -> Vec<T> {
+    rolling_moments(data, period, 0).into_iter().map(|(mu, _)| mu).collect()
+}
+
+/// z-score of `x` against a window's mean and std; 0.0 for a flat window.
+pub fn zscore<T: Real>(x: T, mu: T, sd: T) -> T {
+    if sd == T::zero() { T::zero() } else { (x - mu) / sd }
+}
+
+/// Rolling z-score `(x - mean) / std` over `period` bars (population std).
+/// NaN during warm-up and 0.0 for flat windows.
+pub fn rolling_zscore<T: Real>(data: &[T], period: usize) -> Vec<T> {
+    rolling_zscore_ddof(data, period, 0)
+}
+
+/// [`rolling_zscore`] against a std over `period - ddof` degrees of freedom.
+pub fn rolling_zscore_ddof<T: Real>(data: &[T], period: usize, ddof: usize) -> Vec<T> {
+    (rolling_moments(data, period, ddof).into_iter().zip(data))
+        .map(|((mu, sd), &x)| if mu.is_nan() { T::nan() } else { zscore(x, mu, sd) })
+        .collect()
+}
+
+/// Quantile of an already sorted slice using linear interpolation
+/// (matches numpy's default method). Returns NaN for an empty slice.
+pub fn quantile_sorted(sorted: &[f64], q: f64) -> f64 {
+    if sorted.is_empty() {
+        return f64::NAN;
+    }
+    let pos = q.clamp(0.0, 1.0) * (sorted.len() - 1) as f64;
+    let lo = pos.floor() as usize;
+    let hi = pos.ceil() as usize;
+    let frac = pos - lo as f64;
+    sorted[lo] + (sorted[hi] - sorted[lo]) * frac
+}
+
+/// Sorted copy of a slice with NaNs removed.
+pub fn sorted_finite(data: &[f64]) -> Vec<f64> {
+    let mut sorted: Vec<f64> = data.iter().copied().filter(|x| !x.is_nan()).collect();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    sorted
+}
+
+/// Ordinary least squares fit `y = alpha + beta * x` over pairs where both
+/// values are finite. Returns `None` when x has no variance.
+pub fn ols(x: &[f64], y: &[f64]) -> Option<(f64, f64)> {
+    let pairs: Vec<(f64, f64)> = x.iter().zip(y).map(|(&a, &b)| (a, b)).filter(|(a, b)| a.is_finite() && b.is_finite()).collect();
+    if pairs.len() < 2 {
+        return None;
+    }
+    let n = pairs.len() as f64;
+    let mean_x = pairs.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = pairs.iter().map(|p| p.1).sum::<f64>() / n;
+    let cov: f64 = pairs.iter().map(|(a, b)| (a - mean_x) * (b - mean_y)).sum();
+    let var: f64 = pairs.iter().map(|(a, _)| (a - mean_x).powi(2)).sum();
+    if var == 0.0 {
+        return None;
+    }
+    let beta = cov / var;
+    Some((mean_y - beta * mean_x, beta))
+}
+
+/// Inverse of the standard normal CDF (Acklam's rational approximation,
+/// relative error below 1.2e-9). NaN outside (0, 1).
+pub fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [-39.696_830_286_653_76, 220.946_098_424_520_5, -275.928_510_446_968_7, 138.357_751_867_269, -30.664_798_066_147_16, 2.506_628_277_459_239];
+    const B: [f64; 5] = [-54.476_098_798_224_06, 161.585_836_858_040_9, -155.698_979_859_886_6, 66.801_311_887_719_72, -13.280_681_552_885_72];
+    const C: [f64; 6] = [-0.007_784_894_002_430_293, -0.322_396_458_041_136_5, -2.400_758_277_161_838, -2.549_732_539_343_734, 4.374_664_141_464_968, 2.938_163_982_698_783];
+    const D: [f64; 4] = [0.007_784_695_709_041_462, 0.322_467_129_070_039_8, 2.445_134_137_142_996, 3.754_408_661_907_416];
+    const LOW: f64 = 0.024_25;
+    if p.is_nan() || p <= 0.0 || p >= 1.0 {
+        return f64::NAN;
+    }
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5]) / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+    if p < LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p > 1.0 - LOW {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    }
+}
+
+// --- Entropy & Complexity ---
+
+/// Counts template pairs of length `len` that match within tolerance `r`
+/// (Chebyshev distance), using the first `n_templates` starting points.
+fn count_matches(series: &[f64], len: usize, r: f64, n_templates: usize) -> u64 {
+    let mut count = 0;
+    for i in 0..n_templates {
+        for j in (i + 1)..n_templates {
+            let matched = (0..len).all(|k| (series[i + k] - series[j + k]).abs() <= r);
+            if matched {
+                count += 1;
+            }
+        }
+    }
+    count
+}
+
+/// Sample entropy SampEn(m, r) = -ln(A / B), where B counts matching template
+/// pairs of length `m` and A those of length `m + 1`. Self-matches are excluded.
+/// Returns NaN when the series is too short or no matches exist.
+pub fn sample_entropy(series: &[f64], m: usize, r: f64) -> f64 {
+    let n = series.len();
+    if m == 0 || n <= m + 1 {
+        return f64::NAN;
+    }
+    // Both template lengths use the same N - m starting points.
+    let n_templates = n - m;
+    let b = count_matches(series, m, r, n_templates);
+    let a = count_matches(series, m + 1, r, n_templates);
+    if a == 0 || b == 0 {
+        return f64::NAN;
+    }
+    -((a as f64) / (b as f64)).ln()
+}
+
+/// Permutation entropy (Bandt & Pompe) over ordinal patterns of length `order`
+/// sampled every `delay` steps. When `normalize` is set the result is divided by
+/// ln(order!) so it lies in [0, 1].
+pub fn permutation_entropy(series: &[f64], order: usize, delay: usize, normalize: bool) -> f64 {
+    if order < 2 || delay == 0 {
+        return f64::NAN;
+    }
+    let span = (order - 1) * delay;
+    if series.len() <= span {
+        return f64::NAN;
+    }
+
+    let mut patterns: HashMap<Vec<usize>, u64> = HashMap::new();
+    let n_windows = series.len() - span;
+    for start in 0..n_windows {
+        let mut pattern: Vec<usize> = (0..order).collect();
+        // Stable sort keeps ties in temporal order, the usual convention.
+        pattern.sort_by(|&a, &b| {
+            series[start + a * delay]
+                .partial_cmp(&series[start + b * delay])
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        *patterns.entry(pattern).or_insert(0) += 1;
+    }
+
+    let total = n_windows as f64;
+    let entropy: f64 = patterns
+        .values()
+        .map(|&c| {
+            let p = c as f64 / total;
+            -p * p.ln()
+        })
+        .sum();
+
+    if normalize {
+        let max_entropy: f64 = (2..=order).map(|k| (k as f64).ln()).sum();
+        entropy / max_entropy
+    } else {
+        entropy
+    }
+}
+
+// --- Anomaly Detection ---
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AnomalyMethod {
+    /// Distance from the mean in standard deviations.
+    ZScore,
+    /// Distance from the median in interquartile ranges.
+    Iqr,
+}
+
+impl std::str::FromStr for AnomalyMethod {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "zscore" | "z" => Ok(AnomalyMethod::ZScore),
+            "iqr" => Ok(AnomalyMethod::Iqr),
+            other => Err(format!("Unknown anomaly method '{}', expected 'zscore' or 'iqr'", other)),
+        }
+    }
+}
+
+/// Centre and scale of a baseline sample, computed once so that every
+/// observation scored against the same sample costs O(1).
+#[derive(Clone, Copy, Debug)]
+struct Baseline {
+    center: f64,
+    scale: f64,
+}
+
+impl Baseline {
+    /// Non-finite values (the leading NaN of a `pct_change`, say) are left
+    /// out of the sample for both methods; `ddof` applies to the z-score std.
+    fn new(sample: &[f64], method: AnomalyMethod, ddof: usize) -> Self {
+        let finite: Vec<f64> = sample.iter().copied().filter(|x| x.is_finite()).collect();
+        match method {
+            AnomalyMethod::ZScore => Baseline { center: mean(&finite), scale: std_dev_ddof(&finite, ddof) },
+            AnomalyMethod::Iqr => {
+                let mut sorted = finite;
+                sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                let iqr = quantile_sorted(&sorted, 0.75) - quantile_sorted(&sorted, 0.25);
+                Baseline { center: quantile_sorted(&sorted, 0.5), scale: iqr }
+            }
+        }
+    }
+
+    /// 0.0 when the baseline has no spread.
+    fn score(&self, value: f64) -> f64 {
+        if self.scale == 0.0 || self.scale.is_nan() { 0.0 } else { (value - self.center) / self.scale }
+    }
+}
+
+/// Flags returns whose score exceeds `threshold` in absolute value.
+///
+/// With a `window`, each return is scored against the preceding `window`
+/// returns only (so an outlier does not dilute its own baseline) and the
+/// first `window` observations are never flagged. Without a window the whole
+/// series is the baseline. z-scores use a std over `n - ddof` degrees of
+/// freedom.
+pub fn detect_return_anomalies(
+    returns: &[f64],
+    method: AnomalyMethod,
+    window: Option<usize>,
+    threshold: f64,
+    ddof: usize,
+) -> (Vec<bool>, Vec<f64>) {
+    let n = returns.len();
+    let mut flags = vec![false; n];
+    let mut scores = vec![0.0; n];
+    let whole = match window {
+        Some(w) if w > 0 => None,
+        _ => Some(Baseline::new(returns, method, ddof)),
+    };
+    // A windowed z-score baseline slides in O(1) per bar; the IQR one is
+    // rebuilt from its window.
+    let mut rolling = match (whole, window, method) {
+        (None, Some(w), AnomalyMethod::ZScore) => Some(RollingMoments::with_ddof(w, ddof)),
+        _ => None,
+    };
+
+    for i in 0..n {
+        let value = returns[i];
+        let score = match (whole, window, &rolling) {
+            _ if value.is_nan() => None,
+            (Some(baseline), _, _) => Some(baseline.score(value)),
+            (None, Some(w), Some(moments)) if i >= w => {
+                let (center, variance) = moments.finite_moments();
+                Some(Baseline { center, scale: variance.sqrt() }.score(value))
+            }
+            (None, Some(w), None) if i >= w => Some(Baseline::new(&returns[i - w..i], method, ddof).score(value)),
+            _ => None,
+        };
+        if let Some(moments) = &mut rolling {
+            moments.push(value);
+        }
+        if let Some(score) = score {
+            scores[i] = score;
+            flags[i] = score.abs() > threshold;
+        }
+    }
+
+    (flags, scores)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn returns() -> Vec<f64> {
+        let mut r = vec![f64::NAN];
+        r.extend((0..40).map(|i| 0.01 * ((i * 7 % 11) as f64 - 5.0) / 5.0));
+        r[25] = 0.2;
+        r
+    }
+
+    #[test]
+    fn zscore_anomalies_skip_nan_in_the_baseline() {
+        let r = returns();
+        let (flags, scores) = detect_return_anomalies(&r, AnomalyMethod::ZScore, None, 3.0, 0);
+        let finite: Vec<f64> = r.iter().copied().filter(|x| x.is_finite()).collect();
+        let (mu, sd) = (mean(&finite), std_dev(&finite));
+        assert_eq!(scores[0], 0.0);
+        assert!(!flags[0]);
+        for i in 1..r.len() {
+            assert!((scores[i] - (r[i] - mu) / sd).abs() < 1e-12);
+        }
+        assert_eq!(flags.iter().filter(|&&f| f).count(), 1);
+        assert!(flags[25]);
+    }
+
+    #[test]
+    fn iqr_anomalies_score_against_the_whole_series() {
+        let r = returns();
+        let (flags, scores) = detect_return_anomalies(&r, AnomalyMethod::Iqr, None, 3.0, 0);
+        let sorted = sorted_finite(&r);
+        let (q1, q2, q3) = (quantile_sorted(&sorted, 0.25), quantile_sorted(&sorted, 0.5), quantile_sorted(&sorted, 0.75));
+        assert!((scores[25] - (0.2 - q2) / (q3 - q1)).abs() < 1e-12);
+        assert!(flags[25]);
+    }
+
+    #[test]
+    fn windowed_anomalies_skip_the_first_window() {
+        let r = returns();
+        let (flags, scores) = detect_return_anomalies(&r, AnomalyMethod::ZScore, Some(10), 3.0, 0);
+        assert!(scores[..10].iter().all(|&s| s == 0.0));
+        let baseline = &r[15..25];
+        assert!((scores[25] - (0.2 - mean(baseline)) / std_dev(baseline)).abs() < 1e-12);
+        assert!(flags[25]);
+    }
+
+    #[test]
+    fn windowed_z_scores_match_a_rebuilt_baseline() {
+        let mut r = returns();
+        r[3] = f64::NAN;
+        r[17] = f64::INFINITY;
+        r.extend((0..40).map(|i| ((i * 13 % 7) as f64 - 3.0) * 0.004));
+        for ddof in [0, 1] {
+            let (_, scores) = detect_return_anomalies(&r, AnomalyMethod::ZScore, Some(10), 3.0, ddof);
+            for i in 10..r.len() {
+                let expected = if r[i].is_nan() { 0.0 } else { Baseline::new(&r[i - 10..i], AnomalyMethod::ZScore, ddof).score(r[i]) };
+                assert!(scores[i] == expected || (scores[i] - expected).abs() < 1e-9, "bar {i}: {} vs {expected}", scores[i]);
+            }
+        }
+    }
+
+    #[test]
+    fn rolling_std_matches_bbands_for_each_ddof() {
+        let data: Vec<f64> = (0..30).map(|i| 100.0 + ((i * 37 % 17) as f64).sqrt()).collect();
+        for ddof in [0, 1] {
+            let sd = rolling_std_ddof(&data, 5, ddof);
+            let (upper, middle, _) = crate::indicators::bbands_ddof(&data, 5, 1.0, ddof);
+            assert!(sd[..4].iter().all(|v| v.is_nan()));
+            for i in 4..data.len() {
+                assert_eq!(middle[i] + sd[i], upper[i]);
+            }
+        }
+        let window = &data[10..15];
+        let mu = mean(window);
+        let sample = (window.iter().map(|x| (x - mu).powi(2)).sum::<f64>() / 4.0).sqrt();
+        assert!((rolling_std_ddof(&data, 5, 1)[14] - sample).abs() < 1e-12);
+    }
+
+    #[test]
+    fn std_dev_ddof_is_the_sample_std_for_ddof_1() {
+        let data = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        assert_eq!(std_dev_ddof(&data, 0), 2.0);
+        assert!((std_dev_ddof(&data, 1) - (32.0f64 / 7.0).sqrt()).abs() < 1e-12);
+        assert!(std_dev_ddof(&[1.0], 1).is_nan());
+        assert_eq!(std_dev_ddof(&[], 0), 0.0);
+    }
+}
@@ -0,0 +1,107 @@
+/// SOTA Ticker Normalization: Resolves discrepancies between Trading212, 
+/// Yahoo Finance, Alpaca, and Finnhub.
+pub fn normalize_ticker(ticker: &str) -> String {
+    if ticker.is_empty() {
+        return String::new();
+    }
+
+    // 1. Basic Cleaning
+    let mut normalized = ticker.to_uppercase().trim().replace("$", "");
+    
+    // 2. Already Normalized (contains dot)
+    if normalized.contains('.') {
+        return normalized;
+    }
+
+    // 3. Handle Platform-Specific Artifacts
+    let original = normalized.clone();
+    
+    // Strip T212 suffixes
+    let suffixes = ["_EQ", "_US", "_BE", "_DE", "_GB", "_FR", "_NL", "_ES", "_IT"];
+    loop {
+        let mut changed = false;
+        for s in suffixes.iter() {
+            if normalized.ends_with(s) {
+                normalized = normalized[..normalized.len() - s.len()].to_string();
+                changed = true;
+            }
+        }
+        if !changed { break; }
+    }
+    normalized = normalized.replace("_", "");
+
+    // 4. SPECIAL MAPPINGS
+    let special_mappings = [
+        ("SSLNL", "SSLN"), ("SGLNL", "SGLN"), ("3GLD", "3GLD"), ("SGLN", "SGLN"),
+        ("PHGP", "PHGP"), ("PHAU", "PHAU"), ("3LTS", "3LTS"), ("3USL", "3USL"),
+        ("LLOY1", "LLOY"), ("VOD1", "VOD"), ("BARC1", "BARC"), ("TSCO1", "TSCO"),
+        ("BPL1", "BP"), ("BPL", "BP"), ("AZNL1", "AZN"), ("AZNL", "AZN"),
+        ("SGLN1", "SGLN"), ("MAG5", "MAG5"), ("MAG5L", "MAG5"), ("MAG7", "MAG7"),
+        ("MAG7L", "MAG7"), ("GLD3", "GLD3"), ("3UKL", "3UKL"), ("5QQQ", "5QQQ"),
+        ("TSL3", "TSL3"), ("NVD3", "NVD3"), ("AVL", "AV"), ("UUL", "UU"),
+        ("BAL", "BA"), ("SLL", "SL"), ("AU", "AUT"), ("RBL", "RKT"), ("MICCL", "MICC")
+    ];
+    
+    for (k, v) in special_mappings.iter() {
+        if normalized == *k {
+            log::debug!("normalize_ticker: special mapping {} -> {}", k, v);
+            normalized = v.to_string();
+            break;
+        }
+    }
+
+    // 5. Suffix Protection for Leveraged Products
+    let stems = ["LLOY", "BARC", "VOD", "HSBA", "TSCO", "BP", "AZN", "RR", "NG", "SGLN", "SSLN", "GSK", "SHELL", "BATS", "AHT", "NWG", "GLEN"];
+    if normalized.ends_with('1') && normalized.len() > 3 {
+        let stem_check = normalized[..normalized.len()-1].to_string();
+        if stems.contains(&stem_check.as_str()) {
+            normalized = stem_check;
+        }
+    }
+
+    // 6. Global Exchange Logic
+    let us_exclusions = [
+        "AAPL", "MSFT", "GOOG", "AMZN", "NVDA", "TSLA", "META", "NFLX",
+        "AMD", "INTC", "PYPL", "ADBE", "CSCO", "PEP", "COST", "AVGO", "QCOM", "TXN",
+        "ORCL", "CRM", "IBM", "UBER", "ABNB", "SNOW", "PLTR", "SQ", "SHOP", "SPOT",
+        "GOOGL", "SMCI", "MSTR", "COIN", "HOOD", "ARM", "DKNG", "SOFI", "MARA", "RIOT",
+        "CRWD", "PANW", "NET", "DDOG", "ZS", "TEAM", "MDB", "OKTA", "DOCU",
+        "JPM", "BAC", "WFC", "C", "GS", "MS", "BLK", "AXP", "V", "MA", "COF", "USB",
+        "CAT", "DE", "GE", "GM", "F", "BA", "LMT", "RTX", "HON", "UPS", "FDX", "UNP", "MMM",
+        "WMT", "TGT", "HD", "LOW", "MCD", "SBUX", "NKE", "KO", "PEP", "PG", "CL", "MO", "PM", "DIS", "CMCSA",
+        "JNJ", "PFE", "MRK", "ABBV", "LLY", "UNH", "CVS", "AMGN", "GILD", "BMY", "ISRG", "TMO", "ABT", "DHR",
+        "XOM", "CVX", "COP", "SLB", "EOG", "OXY", "KMI", "HAL", "T", "VZ", "TMUS",
+        "SPY", "QQQ", "DIA", "IWM", "IVV", "VOO", "VTI", "GLD", "SLV", "ARKK", "SMH", "XLF", "XLE", "XLK", "XLV",
+        "F", "T", "C", "V", "Z", "O", "D", "R", "K", "X", "S", "M", "A", "G"
+    ];
+
+    let is_explicit_uk = original.contains("_EQ") && !original.contains("_US");
+    let mut is_likely_uk = (normalized.len() <= 3 || (normalized.len() <= 5 && normalized.ends_with('L'))) && !us_exclusions.contains(&normalized.as_str());
+    
+    // Additional check: 4-char tickers not ending in L are likely US (unless explicit EQ)
+    if normalized.len() == 4 && !normalized.ends_with('L') {
+        is_likely_uk = false;
+    }
+
+    // Force likelihood for known UK stems
+    if stems.iter().any(|&s| normalized.starts_with(s)) {
+        is_likely_uk = true;
+    }
+
+    if is_likely_uk && normalized.ends_with('L') && normalized.len() > 3 && !us_exclusions.contains(&normalized.as_str()) {
+        normalized.pop();
+    }
+
+    let is_leveraged = (normalized.starts_with('3') || normalized.starts_with('5') || normalized.starts_with('7')) ||
+                       (normalized.ends_with('2') || normalized.ends_with('3') || normalized.ends_with('5') || normalized.ends_with('7'));
+
+    if (is_explicit_uk || is_likely_uk || is_leveraged) && !normalized.ends_with(".L") && !normalized.contains('.') {
+        log::debug!(
+            "normalize_ticker: {} -> {}.L (explicit_uk={}, likely_uk={}, leveraged={})",
+            ticker, normalized, is_explicit_uk, is_likely_uk, is_leveraged
+        );
+        return format!("{}.L", normalized);
+    }
+
+    normalized
+}
@@ -0,0 +1,105 @@
+//! JavaScript bindings for the web frontend (`wasm` feature).
+//!
+//! Lets the chart compute indicator overlays locally instead of asking the
+//! backend again on every timeframe change. Results match the Python
+//! functions value for value. Build with:
+//!
+//! ```text
+//! cargo rustc -p growin-core --release --features wasm --target wasm32-unknown-unknown --crate-type cdylib
+//! wasm-bindgen --target web --out-dir pkg target/wasm32-unknown-unknown/release/growin_core.wasm
+//! ```
+//!
+//! Arrays cross as `Float64Array`; invalid periods and all-NaN inputs throw.
+
+use wasm_bindgen::prelude::*;
+
+use crate::{indicators, ticker};
+
+/// MACD line, signal line and histogram.
+#[wasm_bindgen(getter_with_clone)]
+pub struct Macd {
+    pub macd: Vec<f64>,
+    pub signal: Vec<f64>,
+    pub histogram: Vec<f64>,
+}
+
+/// Bollinger Bands; `middle` is the SMA.
+#[wasm_bindgen(getter_with_clone)]
+pub struct Bands {
+    pub upper: Vec<f64>,
+    pub middle: Vec<f64>,
+    pub lower: Vec<f64>,
+}
+
+/// Throws on the inputs the Python layer rejects.
+fn validate(name: &str, data: &[f64], periods: &[(&str, usize)]) -> Result<(), JsError> {
+    if let Some((period, _)) = periods.iter().find(|(_, p)| *p == 0) {
+        return Err(JsError::new(&format!("'{}' must be a positive integer, got 0", period)));
+    }
+    if !data.is_empty() && data.iter().all(|v| v.is_nan()) {
+        return Err(JsError::new(&format!("'{}' contains only NaN values", name)));
+    }
+    Ok(())
+}
+
+#[wasm_bindgen(js_name = normalizeTicker)]
+pub fn normalize_ticker(ticker: &str) -> String {
+    ticker::normalize_ticker(ticker)
+}
+
+/// RSI with Wilder's smoothing; the first `period` values are 50.
+#[wasm_bindgen]
+pub fn rsi(prices: &[f64], period: usize) -> Result<Vec<f64>, JsError> {
+    validate("prices", prices, &[("period", period)])?;
+    Ok(indicators::rsi(prices, period))
+}
+
+#[wasm_bindgen]
+pub fn sma(data: &[f64], period: usize) -> Result<Vec<f64>, JsError> {
+    validate("data", data, &[("period", period)])?;
+    Ok(indicators::sma(data, period))
+}
+
+#[wasm_bindgen]
+pub fn ema(data: &[f64], period: usize) -> Result<Vec<f64>, JsError> {
+    validate("data", data, &[("period", period)])?;
+    Ok(indicators::ema(data, period))
+}
+
+#[wasm_bindgen]
+pub fn macd(data: &[f64], fast: usize, slow: usize, signal: usize) -> Result<Macd, JsError> {
+    validate("data", data, &[("fast", fast), ("slow", slow), ("signal", signal)])?;
+    let (macd, signal, histogram) = indicators::macd(data, fast, slow, signal);
+    Ok(Macd { macd, signal, histogram })
+}
+
+#[wasm_bindgen]
+pub fn bbands(data: &[f64], period: usize, std_dev: f64) -> Result<Bands, JsError> {
+    validate("data", data, &[("period", period)])?;
+    let (upper, middle, lower) = indicators::bbands(data, period, std_dev);
+    Ok(Bands { upper, middle, lower })
+}
+
+/// Pivot levels per period, for the period after it.
+#[wasm_bindgen(getter_with_clone)]
+pub struct Pivots {
+    pub pp: Vec<f64>,
+    pub r1: Vec<f64>,
+    pub r2: Vec<f64>,
+    pub r3: Vec<f64>,
+    pub s1: Vec<f64>,
+    pub s2: Vec<f64>,
+    pub s3: Vec<f64>,
+}
+
+/// `method` is "classic", "fibonacci" or "camarilla".
+#[wasm_bindgen]
+pub fn pivots(high: &[f64], low: &[f64], close: &[f64], method: &str) -> Result<Pivots, JsError> {
+    let method: indicators::PivotMethod = method.parse().map_err(|e: String| JsError::new(&e))?;
+    if high.len() != close.len() || low.len() != close.len() {
+        return Err(JsError::new("high, low and close must have the same length"));
+    }
+    validate("close", close, &[])?;
+    let [pp, r1, r2, r3, s1, s2, s3] = indicators::pivots(high, low, close, method);
+    Ok(Pivots { pp, r1, r2, r3, s1, s2, s3 })
+}
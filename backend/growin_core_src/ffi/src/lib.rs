@@ -0,0 +1,196 @@
+//! C ABI over the core for the mobile apps.
+//!
+//! Swift and Kotlin call ticker normalization and the indicators directly
+//! through `include/growin_core.h` (regenerated on every build) instead of
+//! going through a Python service. Indicators read `len` doubles and write
+//! `len` doubles into caller-allocated outputs, with the same warm-up values
+//! as the Python functions, and report failures as a [`GrowinStatus`].
+//! Strings returned by the library must be released with
+//! [`growin_string_free`].
+
+use std::ffi::{c_char, CStr, CString};
+use std::ptr;
+use std::slice;
+
+use growin_core::{indicators, ticker};
+
+/// Outcome of an indicator call. Outputs are left untouched on failure.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GrowinStatus {
+    Ok = 0,
+    /// An input or output pointer is null while `len > 0`.
+    NullPointer = 1,
+    /// A period is zero.
+    InvalidPeriod = 2,
+    /// The input is non-empty but entirely NaN.
+    InsufficientData = 3,
+}
+
+/// Borrows `len` values at `ptr`; null is accepted when `len` is 0.
+unsafe fn input<'a>(ptr: *const f64, len: usize) -> Option<&'a [f64]> {
+    match len {
+        0 => Some(&[]),
+        _ if ptr.is_null() => None,
+        _ => Some(slice::from_raw_parts(ptr, len)),
+    }
+}
+
+/// Mutable counterpart of [`input`].
+unsafe fn output<'a>(ptr: *mut f64, len: usize) -> Option<&'a mut [f64]> {
+    match len {
+        0 => Some(&mut []),
+        _ if ptr.is_null() => None,
+        _ => Some(slice::from_raw_parts_mut(ptr, len)),
+    }
+}
+
+/// The checks the Python layer raises `InvalidPeriodError` and
+/// `InsufficientDataError` for.
+fn validate(data: &[f64], periods: &[usize]) -> Result<(), GrowinStatus> {
+    if periods.contains(&0) {
+        return Err(GrowinStatus::InvalidPeriod);
+    }
+    if !data.is_empty() && data.iter().all(|v| v.is_nan()) {
+        return Err(GrowinStatus::InsufficientData);
+    }
+    Ok(())
+}
+
+/// Normalizes a broker/vendor ticker (e.g. "LLOY1_EQ" -> "LLOY.L").
+///
+/// Returns a new string to release with `growin_string_free`, or null if
+/// `ticker` is null or not valid UTF-8.
+///
+/// # Safety
+/// `ticker` must be null or point to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn growin_normalize_ticker(ticker: *const c_char) -> *mut c_char {
+    if ticker.is_null() {
+        return ptr::null_mut();
+    }
+    let Ok(ticker) = CStr::from_ptr(ticker).to_str() else {
+        return ptr::null_mut();
+    };
+    CString::new(ticker::normalize_ticker(ticker)).map_or(ptr::null_mut(), CString::into_raw)
+}
+
+/// Releases a string returned by this library. Null is ignored.
+///
+/// # Safety
+/// `s` must be null or a pointer returned by this library that has not been
+/// freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn growin_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+/// RSI with Wilder's smoothing; the first `period` values are 50.
+///
+/// # Safety
+/// `prices` and `out` must each point to `len` doubles (or be null when `len`
+/// is 0) and must not overlap.
+#[no_mangle]
+pub unsafe extern "C" fn growin_rsi(prices: *const f64, len: usize, period: usize, out: *mut f64) -> GrowinStatus {
+    let (Some(prices), Some(out)) = (input(prices, len), output(out, len)) else {
+        return GrowinStatus::NullPointer;
+    };
+    if let Err(status) = validate(prices, &[period]) {
+        return status;
+    }
+    indicators::rsi_into(prices, period, out);
+    GrowinStatus::Ok
+}
+
+/// Simple moving average, zero until the first full window.
+///
+/// # Safety
+/// `data` and `out` must each point to `len` doubles (or be null when `len`
+/// is 0) and must not overlap.
+#[no_mangle]
+pub unsafe extern "C" fn growin_sma(data: *const f64, len: usize, period: usize, out: *mut f64) -> GrowinStatus {
+    let (Some(data), Some(out)) = (input(data, len), output(out, len)) else {
+        return GrowinStatus::NullPointer;
+    };
+    if let Err(status) = validate(data, &[period]) {
+        return status;
+    }
+    indicators::sma_into(data, period, out);
+    GrowinStatus::Ok
+}
+
+/// Exponential moving average seeded with the first window's mean, zero
+/// before it.
+///
+/// # Safety
+/// `data` and `out` must each point to `len` doubles (or be null when `len`
+/// is 0) and must not overlap.
+#[no_mangle]
+pub unsafe extern "C" fn growin_ema(data: *const f64, len: usize, period: usize, out: *mut f64) -> GrowinStatus {
+    let (Some(data), Some(out)) = (input(data, len), output(out, len)) else {
+        return GrowinStatus::NullPointer;
+    };
+    if let Err(status) = validate(data, &[period]) {
+        return status;
+    }
+    indicators::ema_into(data, period, out);
+    GrowinStatus::Ok
+}
+
+/// MACD line, signal line and histogram.
+///
+/// # Safety
+/// `data` and the three outputs must each point to `len` doubles (or be null
+/// when `len` is 0) and must not overlap.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn growin_macd(
+    data: *const f64,
+    len: usize,
+    fast: usize,
+    slow: usize,
+    signal: usize,
+    macd_out: *mut f64,
+    signal_out: *mut f64,
+    hist_out: *mut f64,
+) -> GrowinStatus {
+    let (Some(data), Some(macd_out), Some(signal_out), Some(hist_out)) =
+        (input(data, len), output(macd_out, len), output(signal_out, len), output(hist_out, len))
+    else {
+        return GrowinStatus::NullPointer;
+    };
+    if let Err(status) = validate(data, &[fast, slow, signal]) {
+        return status;
+    }
+    indicators::macd_into(data, fast, slow, signal, macd_out, signal_out, hist_out);
+    GrowinStatus::Ok
+}
+
+/// Bollinger Bands: `std_dev` population standard deviations around the SMA.
+///
+/// # Safety
+/// `data` and the three outputs must each point to `len` doubles (or be null
+/// when `len` is 0) and must not overlap.
+#[no_mangle]
+pub unsafe extern "C" fn growin_bbands(
+    data: *const f64,
+    len: usize,
+    period: usize,
+    std_dev: f64,
+    upper: *mut f64,
+    middle: *mut f64,
+    lower: *mut f64,
+) -> GrowinStatus {
+    let (Some(data), Some(upper), Some(middle), Some(lower)) =
+        (input(data, len), output(upper, len), output(middle, len), output(lower, len))
+    else {
+        return GrowinStatus::NullPointer;
+    };
+    if let Err(status) = validate(data, &[period]) {
+        return status;
+    }
+    indicators::bbands_into(data, period, std_dev, upper, middle, lower);
+    GrowinStatus::Ok
+}
@@ -0,0 +1,123 @@
+//! Awaitable wrappers for the heavy entry points.
+//!
+//! The asyncio backend must not block its event loop on a universe scan.
+//! `*_async` functions validate their arguments on the calling thread, run
+//! the computation on the rayon pool without the GIL, and take the GIL only
+//! to build the Python result, which is handed to the caller's event loop
+//! with `call_soon_threadsafe`.
+//!
+//! This is a small bridge rather than `pyo3-async-runtimes`: the work is
+//! CPU-bound and already scheduled on rayon, so a tokio or async-std runtime
+//! would only add a second thread pool to hop through. Cancelling the
+//! returned future discards the result (the computation runs to completion),
+//! and a result for a loop that was closed in the meantime is dropped with a
+//! warning.
+
+use pyo3::panic::PanicException;
+use pyo3::prelude::*;
+use std::any::Any;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::{Arc, Condvar, Mutex};
+
+/// Set once a worker has released the GIL for good.
+#[derive(Clone, Default)]
+struct Released(Arc<(Mutex<bool>, Condvar)>);
+
+impl Released {
+    fn wait(&self) {
+        let (done, signal) = &*self.0;
+        let mut done = done.lock().unwrap();
+        while !*done {
+            done = signal.wait(done).unwrap();
+        }
+    }
+}
+
+/// Signals [`Released`] when dropped, including while unwinding.
+struct ReleaseOnDrop(Released);
+
+impl Drop for ReleaseOnDrop {
+    fn drop(&mut self) {
+        let (done, signal) = &*(self.0).0;
+        *done.lock().unwrap() = true;
+        signal.notify_all();
+    }
+}
+
+/// Completes a future from its event loop's thread unless it was cancelled.
+///
+/// Waits for the worker to release the GIL first: scheduling this callback
+/// drops the GIL briefly, and without the wait the awaiting coroutine could
+/// finish and the interpreter shut down under a worker still holding it.
+#[pyclass]
+struct Resolve {
+    future: PyObject,
+    outcome: Mutex<Option<(bool, PyObject)>>,
+    released: Released,
+}
+
+#[pymethods]
+impl Resolve {
+    fn __call__(&self, py: Python<'_>) -> PyResult<()> {
+        let released = self.released.clone();
+        py.allow_threads(move || released.wait());
+        let Some((ok, value)) = self.outcome.lock().unwrap().take() else {
+            return Ok(());
+        };
+        let future = self.future.bind(py);
+        if future.call_method0("done")?.is_truthy()? {
+            return Ok(());
+        }
+        future.call_method1(if ok { "set_result" } else { "set_exception" }, (value,))?;
+        Ok(())
+    }
+}
+
+/// A panic as the exception its future fails with.
+fn panic_error(payload: Box<dyn Any + Send>) -> PyErr {
+    let message = match payload.downcast::<String>() {
+        Ok(s) => *s,
+        Err(payload) => payload.downcast_ref::<&str>().map_or("panic in async job", |s| s).to_string(),
+    };
+    PanicException::new_err(message)
+}
+
+/// Runs `compute` on the rayon pool and returns a future resolved with
+/// `convert` of its output. A panic in either fails the future with a
+/// `PanicException` instead of taking down the pool.
+///
+/// Must be called from a coroutine: the future belongs to the running loop,
+/// and without one asyncio raises `RuntimeError`.
+pub fn spawn<T, C, F>(py: Python<'_>, compute: C, convert: F) -> PyResult<PyObject>
+where
+    T: Send + 'static,
+    C: FnOnce() -> T + Send + 'static,
+    F: FnOnce(Python<'_>, T) -> PyResult<PyObject> + Send + 'static,
+{
+    let event_loop = py.import("asyncio")?.call_method0("get_running_loop")?;
+    let future = event_loop.call_method0("create_future")?;
+    let (event_loop_ref, future_ref) = (event_loop.unbind(), future.clone().unbind());
+    rayon::spawn(move || {
+        let released = Released::default();
+        let _guard = ReleaseOnDrop(released.clone());
+        let computed = catch_unwind(AssertUnwindSafe(compute));
+        Python::with_gil(|py| {
+            let result = computed
+                .map_err(panic_error)
+                .and_then(|value| catch_unwind(AssertUnwindSafe(|| convert(py, value))).unwrap_or_else(|p| Err(panic_error(p))));
+            let outcome = match result {
+                Ok(value) => (true, value),
+                Err(err) => (false, err.into_value(py).into_any()),
+            };
+            let resolve = Resolve { future: future_ref, outcome: Mutex::new(Some(outcome)), released };
+            let done = Py::new(py, resolve)
+                .and_then(|resolve| event_loop_ref.call_method1(py, "call_soon_threadsafe", (resolve,)));
+            // Only fails if the loop was closed while the job ran.
+            if let Err(err) = done {
+                log::warn!("could not deliver async result: {}", err);
+            }
+            drop(event_loop_ref);
+        })
+    });
+    Ok(future.unbind())
+}
@@ -0,0 +1,154 @@
+//! Python bindings of the alert engine.
+
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::collections::HashMap;
+
+use growin_core::alerts::{self, AlertCondition, Bar, Direction};
+
+use crate::candles::PyCandles;
+use crate::config;
+
+/// A latest bar from Python: a price, or a dict with "close" and optionally
+/// "high" and "low".
+struct LatestBar(Bar);
+
+impl<'py> FromPyObject<'py> for LatestBar {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        if let Ok(price) = ob.extract::<f64>() {
+            return Ok(LatestBar(Bar::from_price(price)));
+        }
+        let Ok(d) = ob.downcast::<PyDict>() else {
+            return Err(PyTypeError::new_err("a latest bar must be a price or a dict with 'close'"));
+        };
+        let close: f64 = match d.get_item("close")? {
+            Some(v) => v.extract()?,
+            None => return Err(PyValueError::new_err("a latest bar dict needs a 'close'")),
+        };
+        let field = |name: &str| -> PyResult<f64> { d.get_item(name)?.map_or(Ok(close), |v| v.extract()) };
+        Ok(LatestBar(Bar { high: field("high")?, low: field("low")?, close }))
+    }
+}
+
+/// Edge-triggered price alerts for many tickers, evaluated one bar at a time.
+///
+/// Each `evaluate` call is one new bar per ticker; an alert fires on the bar
+/// where its condition becomes true and, unless `once`, re-arms after it
+/// turns false. Level crosses (price, RSI) only fire after the value has
+/// been seen on the other side.
+///
+/// ```python
+/// engine = AlertEngine()
+/// engine.add_price_cross("AAPL", 200.0)
+/// engine.add_rsi("AAPL", 30.0, direction="below")
+/// engine.prime("AAPL", history)    # warm up RSI / new-high windows
+/// engine.evaluate({"AAPL": 201.5, "MSFT": {"high": 412.0, "low": 405.1, "close": 410.2}})
+/// ```
+#[pyclass(module = "growin_core")]
+pub struct AlertEngine {
+    engine: alerts::AlertEngine,
+}
+
+impl AlertEngine {
+    fn add(&mut self, ticker: &str, condition: AlertCondition, once: bool) -> PyResult<u64> {
+        self.engine.add(ticker, condition, once).map_err(PyValueError::new_err)
+    }
+}
+
+#[pymethods]
+impl AlertEngine {
+    #[new]
+    fn new() -> Self {
+        AlertEngine { engine: alerts::AlertEngine::new() }
+    }
+
+    /// Alert when the close crosses `level` ("above" or "below").
+    ///
+    /// Returns:
+    ///     int: The alert id.
+    #[pyo3(signature = (ticker, level, direction="above", once=true))]
+    fn add_price_cross(&mut self, ticker: &str, level: f64, direction: &str, once: bool) -> PyResult<u64> {
+        let direction: Direction = direction.parse().map_err(PyValueError::new_err)?;
+        self.add(ticker, AlertCondition::PriceCross { level, direction }, once)
+    }
+
+    /// Alert when RSI(`period`) crosses `level`. The RSI warms up over the
+    /// `period` bars after the first alert for that period is added.
+    #[pyo3(signature = (ticker, level, period=14, direction="below", once=true))]
+    fn add_rsi(&mut self, ticker: &str, level: f64, period: usize, direction: &str, once: bool) -> PyResult<u64> {
+        let direction: Direction = direction.parse().map_err(PyValueError::new_err)?;
+        self.add(ticker, AlertCondition::Rsi { period, level, direction }, once)
+    }
+
+    /// Alert when the close is at least `pct` percent (either way) from the
+    /// close `lookback` bars earlier.
+    #[pyo3(signature = (ticker, pct, lookback=1, once=true))]
+    fn add_pct_move(&mut self, ticker: &str, pct: f64, lookback: usize, once: bool) -> PyResult<u64> {
+        self.add(ticker, AlertCondition::PctMove { pct, lookback }, once)
+    }
+
+    /// Alert when the bar's high beats every high of the previous `window`
+    /// bars (default: the configured `annualization`, 52 weeks of days).
+    #[pyo3(signature = (ticker, window=None, once=true))]
+    fn add_new_high(&mut self, ticker: &str, window: Option<usize>, once: bool) -> PyResult<u64> {
+        let window = window.unwrap_or_else(|| config::current().annualization);
+        self.add(ticker, AlertCondition::NewHigh { window }, once)
+    }
+
+    /// Alert when the bar's low undercuts every low of the previous `window`
+    /// bars; see `add_new_high`.
+    #[pyo3(signature = (ticker, window=None, once=true))]
+    fn add_new_low(&mut self, ticker: &str, window: Option<usize>, once: bool) -> PyResult<u64> {
+        let window = window.unwrap_or_else(|| config::current().annualization);
+        self.add(ticker, AlertCondition::NewLow { window }, once)
+    }
+
+    /// Remove an alert. Returns whether it was registered.
+    fn remove(&mut self, id: u64) -> bool {
+        self.engine.remove(id)
+    }
+
+    /// Feed history for a ticker's alerts without firing them; bars are
+    /// treated as already seen. Add the alerts first.
+    fn prime(&mut self, py: Python<'_>, ticker: &str, candles: PyCandles) {
+        let bars: Vec<Bar> = (0..candles.len())
+            .map(|i| Bar { high: candles.high[i], low: candles.low[i], close: candles.close[i] })
+            .collect();
+        py.allow_threads(|| self.engine.prime(ticker, &bars));
+    }
+
+    /// Advance every ticker by one bar and collect the alerts that fired.
+    ///
+    /// Args:
+    ///     latest_bars (Dict[str, float | dict]): ticker -> latest price, or
+    ///         {"close", "high", "low"} (high/low default to close).
+    ///
+    /// Returns:
+    ///     List[dict]: {"id", "ticker", "kind", "value"} per fired alert,
+    ///     oldest alert first. `value` is the close, RSI, percent move or new
+    ///     high/low that triggered it.
+    fn evaluate<'py>(&mut self, py: Python<'py>, latest_bars: HashMap<String, LatestBar>) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        let latest: Vec<(String, Bar)> = latest_bars.into_iter().map(|(t, b)| (t, b.0)).collect();
+        let fired = py.allow_threads(|| self.engine.evaluate(&latest));
+        fired
+            .into_iter()
+            .map(|f| {
+                let d = PyDict::new(py);
+                d.set_item("id", f.id)?;
+                d.set_item("ticker", f.ticker)?;
+                d.set_item("kind", f.kind)?;
+                d.set_item("value", f.value)?;
+                Ok(d)
+            })
+            .collect()
+    }
+
+    fn __len__(&self) -> usize {
+        self.engine.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("AlertEngine(alerts={})", self.engine.len())
+    }
+}
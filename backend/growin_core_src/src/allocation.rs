@@ -0,0 +1,237 @@
+//! Python bindings of the cash allocation math.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, HashMap};
+
+use growin_core::allocation::{allocate_pie, exposure, optimize_allocation, ExposureBucket, Holding, PieSlice};
+use growin_core::money::round;
+
+use crate::instrument::Instrument;
+use crate::sectors::SectorMap;
+
+/// Share precision of fractional orders, in decimal places.
+const FRACTIONAL_DP: u32 = 8;
+
+/// Group name for holdings whose metadata lacks a classification.
+const UNKNOWN: &str = "Unknown";
+
+/// Split a pie deposit into per-slice share quantities, in exact decimal.
+///
+/// Args:
+///     target_weights (Dict[str, Decimal]): ticker -> target weight (any
+///         non-negative scale; normalized to sum to 1).
+///     prices (Dict[str, Decimal]): ticker -> current price, for every
+///         ticker in `target_weights`.
+///     cash (Decimal): Amount to invest.
+///     min_order_value (Decimal): Smallest order the broker accepts
+///         (default 1); slices below it are skipped and their weight spread
+///         over the rest.
+///     quantity_dp (int): Share quantity precision in decimal places
+///         (default 8, for fractional shares; 0 for whole shares).
+///     dp (int, optional): Decimal places for cash outputs (default 2).
+///
+/// Returns:
+///     Dict: {"orders": {ticker: {"quantity", "value", "target_value"}},
+///     "invested", "remaining_cash"}. Orders never cost more than `cash`,
+///     and the leftover is less than one quantity step of any slice.
+#[pyfunction]
+#[pyo3(
+    name = "allocate_pie",
+    signature = (target_weights, prices, cash, min_order_value=Decimal::ONE, quantity_dp=FRACTIONAL_DP, dp=Some(2))
+)]
+pub fn py_allocate_pie<'py>(
+    py: Python<'py>,
+    target_weights: HashMap<String, Decimal>,
+    prices: HashMap<String, Decimal>,
+    cash: Decimal,
+    min_order_value: Decimal,
+    quantity_dp: u32,
+    dp: Option<u32>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let mut slices = Vec::with_capacity(target_weights.len());
+    for (ticker, weight) in target_weights {
+        let Some(&price) = prices.get(&ticker) else {
+            return Err(PyValueError::new_err(format!("no price for '{}'", ticker)));
+        };
+        slices.push(PieSlice { ticker, weight, price });
+    }
+    // Ties in the top-up go to the earlier slice, so fix the order.
+    slices.sort_by(|a, b| a.ticker.cmp(&b.ticker));
+    let allocation = py
+        .allow_threads(|| allocate_pie(&slices, cash, min_order_value, quantity_dp))
+        .map_err(PyValueError::new_err)?;
+
+    let orders = PyDict::new(py);
+    for order in allocation.orders {
+        let d = PyDict::new(py);
+        d.set_item("quantity", order.quantity.normalize())?;
+        d.set_item("value", round(order.value, dp))?;
+        d.set_item("target_value", round(order.target_value, dp))?;
+        orders.set_item(order.ticker, d)?;
+    }
+    let out = PyDict::new(py);
+    out.set_item("orders", orders)?;
+    out.set_item("invested", round(allocation.invested, dp))?;
+    out.set_item("remaining_cash", round(allocation.remaining_cash, dp))?;
+    Ok(out)
+}
+
+/// Buy quantities that minimize deviation from target weights, in exact
+/// decimal.
+///
+/// Args:
+///     cash (Decimal): Amount available.
+///     prices (Dict[str, Decimal]): ticker -> current price, for every
+///         ticker in `weights`.
+///     weights (Dict[str, Decimal]): ticker -> target weight (normalized
+///         to sum to 1).
+///     fractional (bool): Fractional shares to 8 decimal places (default
+///         True); False buys whole shares only.
+///     max_error (Decimal, optional): Tolerance on the absolute weight error
+///         of any position.
+///     dp (int, optional): Decimal places for cash outputs (default 2).
+///
+/// Returns:
+///     Dict: {"positions": {ticker: {"quantity", "value", "target_weight",
+///     "weight", "weight_error"}}, "invested", "remaining_cash",
+///     "max_weight_error", "within_tolerance"}. Weights are fractions of
+///     `cash`; `within_tolerance` is None without `max_error`.
+#[pyfunction]
+#[pyo3(name = "optimize_allocation", signature = (cash, prices, weights, fractional=true, max_error=None, dp=Some(2)))]
+pub fn py_optimize_allocation<'py>(
+    py: Python<'py>,
+    cash: Decimal,
+    prices: HashMap<String, Decimal>,
+    weights: HashMap<String, Decimal>,
+    fractional: bool,
+    max_error: Option<Decimal>,
+    dp: Option<u32>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let mut positions: Vec<(String, Decimal, Decimal)> = Vec::with_capacity(weights.len());
+    for (ticker, weight) in weights {
+        let Some(&price) = prices.get(&ticker) else {
+            return Err(PyValueError::new_err(format!("no price for '{}'", ticker)));
+        };
+        positions.push((ticker, price, weight));
+    }
+    positions.sort_by(|a, b| a.0.cmp(&b.0));
+    let price_list: Vec<Decimal> = positions.iter().map(|p| p.1).collect();
+    let weight_list: Vec<Decimal> = positions.iter().map(|p| p.2).collect();
+    let total: Decimal = weight_list.iter().sum();
+    let quantity_dp = if fractional { FRACTIONAL_DP } else { 0 };
+    let allocation = py
+        .allow_threads(|| optimize_allocation(&price_list, &weight_list, cash, quantity_dp))
+        .map_err(PyValueError::new_err)?;
+
+    let out_positions = PyDict::new(py);
+    for (i, (ticker, price, weight)) in positions.into_iter().enumerate() {
+        let quantity = allocation.quantities[i];
+        let error = allocation.weight_errors[i];
+        let d = PyDict::new(py);
+        d.set_item("quantity", quantity.normalize())?;
+        d.set_item("value", round(quantity * price, dp))?;
+        d.set_item("target_weight", (weight / total).normalize())?;
+        d.set_item("weight", (quantity * price / cash).normalize())?;
+        d.set_item("weight_error", error.normalize())?;
+        out_positions.set_item(ticker, d)?;
+    }
+    let max_weight_error = allocation.max_abs_weight_error();
+    let out = PyDict::new(py);
+    out.set_item("positions", out_positions)?;
+    out.set_item("invested", round(allocation.invested, dp))?;
+    out.set_item("remaining_cash", round(allocation.remaining_cash, dp))?;
+    out.set_item("max_weight_error", max_weight_error.normalize())?;
+    out.set_item("within_tolerance", max_error.map(|tol| max_weight_error <= tol))?;
+    Ok(out)
+}
+
+fn buckets_to_dict<'py>(
+    py: Python<'py>,
+    groups: &BTreeMap<String, ExposureBucket>,
+    dp: Option<u32>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let out = PyDict::new(py);
+    for (name, bucket) in groups {
+        let d = PyDict::new(py);
+        d.set_item("value", round(bucket.value, dp))?;
+        d.set_item("weight", bucket.weight.normalize())?;
+        out.set_item(name, d)?;
+    }
+    Ok(out)
+}
+
+/// Portfolio exposure by currency, sector, region and instrument type.
+///
+/// Leveraged ETPs count at their effective exposure: a 3x ETP worth 10% of
+/// the portfolio adds 30% to its groups, and a -3x short ETP takes 30% off.
+///
+/// Args:
+///     positions (Dict[str, Decimal]): ticker -> market value in the account
+///         currency.
+///     metadata (Dict[str, dict | Instrument]): ticker -> {"currency",
+///         "sector", "region", "instrument_type", "leverage"}, or an
+///         `Instrument`. Missing classifications are grouped as "Unknown";
+///         `leverage` defaults to 1.
+///     dp (int, optional): Decimal places for values (default 2).
+///     sector_map (SectorMap, optional): Classifies holdings whose metadata
+///         has no "sector", inferring from "name"/"type" when unmapped.
+///
+/// Returns:
+///     Dict: {"total_value", "net_exposure", "gross_exposure", "currency",
+///     "sector", "region", "instrument_type"}, each of the last four
+///     {group: {"value", "weight"}} with weights as fractions of
+///     `total_value`.
+#[pyfunction]
+#[pyo3(name = "exposure_report", signature = (positions, metadata, dp=Some(2), sector_map=None))]
+pub fn py_exposure_report<'py>(
+    py: Python<'py>,
+    positions: HashMap<String, Decimal>,
+    metadata: HashMap<String, Bound<'py, PyAny>>,
+    dp: Option<u32>,
+    sector_map: Option<PyRef<'py, SectorMap>>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let mut holdings = Vec::with_capacity(positions.len());
+    for (ticker, value) in positions {
+        let meta = match metadata.get(&ticker) {
+            Some(m) => match m.downcast::<Instrument>() {
+                Ok(instrument) => Some(instrument.get().metadata_dict(py)?),
+                Err(_) => Some(m.downcast::<PyDict>()?.clone()),
+            },
+            None => None,
+        };
+        let meta = meta.as_ref();
+        let field = |name: &str| -> PyResult<Option<Bound<'py, PyAny>>> {
+            Ok(meta.map(|m| m.get_item(name)).transpose()?.flatten().filter(|v| !v.is_none()))
+        };
+        let text = |name: &str| -> PyResult<String> { field(name)?.map_or(Ok(UNKNOWN.to_string()), |v| v.extract()) };
+        let currency = field("currency")?.map_or(Ok(UNKNOWN.to_string()), |v| v.extract::<String>().map(|c| c.to_ascii_uppercase()));
+        let leverage: Decimal = field("leverage")?.map_or(Ok(Decimal::ONE), |v| v.extract())?;
+        holdings.push(Holding {
+            currency: currency?,
+            sector: match (field("sector")?, &sector_map) {
+                (Some(v), _) => v.extract()?,
+                (None, Some(map)) => map.classify_with(&ticker, meta)?.0.map_or(UNKNOWN.to_string(), |c| c.sector),
+                (None, None) => UNKNOWN.to_string(),
+            },
+            region: text("region")?,
+            instrument_type: text("instrument_type")?,
+            ticker,
+            value,
+            leverage,
+        });
+    }
+    let report = py.allow_threads(|| exposure(&holdings)).map_err(PyValueError::new_err)?;
+
+    let out = PyDict::new(py);
+    out.set_item("total_value", round(report.total_value, dp))?;
+    out.set_item("net_exposure", round(report.net_exposure, dp))?;
+    out.set_item("gross_exposure", round(report.gross_exposure, dp))?;
+    out.set_item("currency", buckets_to_dict(py, &report.by_currency, dp)?)?;
+    out.set_item("sector", buckets_to_dict(py, &report.by_sector, dp)?)?;
+    out.set_item("region", buckets_to_dict(py, &report.by_region, dp)?)?;
+    out.set_item("instrument_type", buckets_to_dict(py, &report.by_instrument_type, dp)?)?;
+    Ok(out)
+}
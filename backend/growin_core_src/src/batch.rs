@@ -0,0 +1,220 @@
+//! Many indicators over one set of bars in a single call.
+//!
+//! A scanner computing a dozen indicators per ticker would otherwise cross
+//! into Rust once per indicator and convert the price columns every time.
+//! `compute_indicators` converts the bars once and runs each requested
+//! indicator on the same borrowed columns, through the same functions (and
+//! so the same validation, padding and cache) as the `calculate_*` calls.
+
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::PyClass;
+use pyo3::types::{PyDict, PyTuple};
+
+use crate::candles::PyCandles;
+use crate::{profile, technicals};
+
+/// One `(name, params)` or `(name, params, key)` spec.
+pub struct Spec<'py> {
+    indicator: String,
+    params: Option<Bound<'py, PyDict>>,
+    key: Option<String>,
+}
+
+impl<'py> FromPyObject<'py> for Spec<'py> {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        let invalid = || PyTypeError::new_err("an indicator spec must be a (name, params) or (name, params, key) tuple");
+        let tuple = ob.downcast::<PyTuple>().map_err(|_| invalid())?;
+        if !(2..=3).contains(&tuple.len()) {
+            return Err(invalid());
+        }
+        let key = if tuple.len() == 3 { tuple.get_item(2)?.extract()? } else { None };
+        Ok(Spec { indicator: tuple.get_item(0)?.extract()?, params: tuple.get_item(1)?.extract()?, key })
+    }
+}
+
+/// Parameters of one spec, taken by name with defaults; anything left over
+/// is an error rather than silently ignored.
+struct Params<'py> {
+    indicator: String,
+    dict: Option<Bound<'py, PyDict>>,
+    used: Vec<&'static str>,
+}
+
+impl<'py> Params<'py> {
+    fn get<T: FromPyObject<'py>>(&mut self, key: &'static str, default: T) -> PyResult<T> {
+        self.used.push(key);
+        match self.dict.as_ref().map(|d| d.get_item(key)).transpose()?.flatten() {
+            Some(value) => value.extract(),
+            None => Ok(default),
+        }
+    }
+
+    fn finish(&self) -> PyResult<()> {
+        let Some(dict) = &self.dict else { return Ok(()) };
+        for key in dict.keys() {
+            let key: String = key.extract()?;
+            if !self.used.contains(&key.as_str()) {
+                return Err(PyValueError::new_err(format!("unknown parameter '{}' for '{}'", key, self.indicator)));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Default result key: the indicator name followed by the given parameter
+/// values, e.g. "rsi_14" or "sma_20_volume".
+fn default_key(indicator: &str, params: Option<&Bound<'_, PyDict>>) -> PyResult<String> {
+    let mut key = indicator.to_string();
+    for (_, value) in params.into_iter().flat_map(|d| d.iter()) {
+        key.push('_');
+        key.push_str(&value.str()?.to_cow()?);
+    }
+    Ok(key)
+}
+
+/// The column a single-series indicator reads, by `source` name.
+fn source<'a>(candles: &'a PyCandles, name: &str) -> PyResult<&'a [f64]> {
+    match name {
+        "close" => Ok(&candles.close),
+        "open" => Ok(&candles.open),
+        "high" => Ok(&candles.high),
+        "low" => Ok(&candles.low),
+        "volume" => volume(candles),
+        other => Err(PyValueError::new_err(format!(
+            "Unknown source '{}', expected 'close', 'open', 'high', 'low' or 'volume'",
+            other
+        ))),
+    }
+}
+
+fn volume(candles: &PyCandles) -> PyResult<&[f64]> {
+    if !candles.has_volume() {
+        return Err(PyValueError::new_err("the bars have no 'volume' column"));
+    }
+    Ok(&candles.volume)
+}
+
+fn into_object<T: PyClass + Into<PyClassInitializer<T>>>(py: Python<'_>, value: T) -> PyResult<PyObject> {
+    Ok(Py::new(py, value)?.into_any())
+}
+
+/// Runs one spec.
+fn compute(py: Python<'_>, candles: &PyCandles, indicator: &str, p: &mut Params<'_>) -> PyResult<PyObject> {
+    let column = |values| candles.column(values);
+    let (high, low, close) = (column(&candles.high), column(&candles.low), column(&candles.close));
+    let out = match indicator {
+        "rsi" | "sma" | "ema" | "wma" | "dema" | "tema" | "zlema" | "mcginley" | "roc" | "momentum" | "cmo" => {
+            let data = column(source(candles, &p.get::<String>("source", "close".to_string())?)?);
+            let default_period = match indicator {
+                "sma" | "wma" | "dema" | "tema" => 20,
+                "roc" => 12,
+                "momentum" => 10,
+                _ => 14,
+            };
+            let period = p.get("period", default_period)?;
+            match indicator {
+                "rsi" => crate::calculate_rsi(py, data, period)?,
+                "sma" => crate::calculate_sma(py, data, period)?,
+                "ema" => crate::calculate_ema(py, data, period)?,
+                "wma" => technicals::py_calculate_wma(py, data, period)?,
+                "dema" => technicals::py_calculate_dema(py, data, period)?,
+                "tema" => technicals::py_calculate_tema(py, data, period)?,
+                "zlema" => technicals::py_calculate_zlema(py, data, period)?,
+                "mcginley" => technicals::py_calculate_mcginley(py, data, period)?,
+                "roc" => technicals::py_calculate_roc(py, data, period)?,
+                "momentum" => technicals::py_calculate_momentum(py, data, period)?,
+                _ => technicals::py_calculate_cmo(py, data, period)?,
+            }
+        }
+        "macd" => {
+            let data = column(source(candles, &p.get::<String>("source", "close".to_string())?)?);
+            let result = crate::calculate_macd(py, data, p.get("fast", 12)?, p.get("slow", 26)?, p.get("signal", 9)?)?;
+            into_object(py, result)?
+        }
+        "bbands" => {
+            let data = column(source(candles, &p.get::<String>("source", "close".to_string())?)?);
+            into_object(py, crate::calculate_bbands(py, data, p.get("period", 20)?, p.get("std_dev", 2.0)?)?)?
+        }
+        "atr" => technicals::py_calculate_atr(py, high, low, close, p.get("period", 14)?)?,
+        "adx" => into_object(py, technicals::py_calculate_adx(py, high, low, close, p.get("period", 14)?)?)?,
+        "stochastic" => {
+            let (k, d, smooth) = (p.get("k_period", 14)?, p.get("d_period", 3)?, p.get("smooth_k", 3)?);
+            into_object(py, technicals::py_calculate_stochastic(py, high, low, close, k, d, smooth)?)?
+        }
+        "supertrend" => {
+            let (period, multiplier) = (p.get("period", 10)?, p.get("multiplier", 3.0)?);
+            into_object(py, technicals::py_calculate_supertrend(py, high, low, close, period, multiplier)?)?
+        }
+        "cci" => technicals::py_calculate_cci(py, high, low, close, p.get("period", 20)?, p.get("constant", 0.015)?)?,
+        "williams_r" => technicals::py_calculate_williams_r(py, high, low, close, p.get("period", 14)?)?,
+        "aroon" => into_object(py, technicals::py_calculate_aroon(py, high, low, p.get("period", 25)?)?)?,
+        "obv" => technicals::py_calculate_obv(py, close, column(volume(candles)?))?,
+        "mfi" => technicals::py_calculate_mfi(py, high, low, close, column(volume(candles)?), p.get("period", 14)?)?,
+        "cmf" => technicals::py_calculate_cmf(py, high, low, close, column(volume(candles)?), p.get("period", 20)?)?,
+        "vwap" => {
+            let window = p.get("window", None)?;
+            technicals::py_calculate_vwap(py, high, low, close, column(volume(candles)?), window, None, 0)?
+        }
+        other => {
+            return Err(PyValueError::new_err(format!(
+                "Unknown indicator '{}'; see compute_indicators for the supported names",
+                other
+            )))
+        }
+    };
+    p.finish()?;
+    Ok(out)
+}
+
+/// Compute many indicators over one set of bars in a single call.
+///
+/// The bars are converted once and every indicator runs on the same
+/// columns, instead of each `calculate_*` call converting its own copy.
+/// Results equal the matching `calculate_*` call's, warm-up padding and
+/// cache included.
+///
+/// Args:
+///     ohlcv (Mapping | CandleSeries): Bars; see `CandleSeries`. Only the
+///         columns the specs use are needed.
+///     specs (List[Tuple]): `(name, params)` or `(name, params, key)`, where
+///         `params` is a dict (or None) of the matching function's keyword
+///         arguments. Supported names: "rsi", "sma", "ema", "wma", "dema",
+///         "tema", "zlema", "mcginley", "roc", "momentum", "cmo", "macd" and
+///         "bbands" (these also take `source`: "close" (default), "open",
+///         "high", "low" or "volume"); "atr", "adx", "stochastic",
+///         "supertrend", "cci", "williams_r" and "aroon"; and with volume
+///         "obv", "mfi", "cmf" and "vwap" (rolling with `window`).
+///
+/// Returns:
+///     Dict[str, object]: key -> the value the `calculate_*` function
+///     returns (an array when the columns were numpy arrays, a list
+///     otherwise, or its result object for multi-output indicators), in
+///     spec order. Keys default to the name followed by the parameter
+///     values: ("rsi", {"period": 14}) -> "rsi_14".
+///
+/// Raises:
+///     ValueError: For an unknown indicator, parameter or source, a
+///         duplicate key, or a volume indicator on bars without volume.
+#[pyfunction]
+#[pyo3(name = "compute_indicators", signature = (ohlcv, specs))]
+pub fn py_compute_indicators<'py>(
+    py: Python<'py>,
+    ohlcv: PyCandles,
+    specs: Vec<Spec<'py>>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let _span = profile::span("compute_indicators");
+    let out = PyDict::new(py);
+    for Spec { indicator, params, key } in specs {
+        let key = match key {
+            Some(key) => key,
+            None => default_key(&indicator, params.as_ref())?,
+        };
+        if out.contains(&key)? {
+            return Err(PyValueError::new_err(format!("duplicate indicator key '{}'", key)));
+        }
+        let mut params = Params { indicator: indicator.clone(), dict: params, used: Vec::new() };
+        out.set_item(key, compute(py, &ohlcv, &indicator, &mut params)?)?;
+    }
+    Ok(out)
+}
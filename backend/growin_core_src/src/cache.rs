@@ -0,0 +1,369 @@
+//! Opt-in memoization of indicator results.
+//!
+//! Charts recompute the same RSI/MACD series on every re-render. With the
+//! cache enabled, results are keyed by the function name, its parameters
+//! and a 128-bit digest of the input values, and evicted least-recently-used
+//! once the configured size is exceeded. Disabled (the default), lookups cost a single
+//! atomic load.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::any::{Any, TypeId};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use growin_core::lanes::Real;
+
+/// Approximate heap footprint of a cached result, in bytes.
+pub trait Weigh {
+    fn weight(&self) -> usize;
+}
+
+impl<T> Weigh for Vec<T> {
+    fn weight(&self) -> usize {
+        self.len() * std::mem::size_of::<T>()
+    }
+}
+
+impl<A: Weigh, const N: usize> Weigh for [A; N] {
+    fn weight(&self) -> usize {
+        self.iter().map(Weigh::weight).sum()
+    }
+}
+
+impl<A: Weigh, B: Weigh> Weigh for (A, B) {
+    fn weight(&self) -> usize {
+        self.0.weight() + self.1.weight()
+    }
+}
+
+impl<A: Weigh, B: Weigh, C: Weigh> Weigh for (A, B, C) {
+    fn weight(&self) -> usize {
+        self.0.weight() + self.1.weight() + self.2.weight()
+    }
+}
+
+/// Identifies a call. The inputs are reduced to a 128-bit digest, but
+/// everything else is compared exactly, so a digest collision would also
+/// need the same function, parameters, types and input lengths.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct Key {
+    function: String,
+    params: Vec<u64>,
+    types: (TypeId, TypeId),
+    lengths: Vec<usize>,
+    digest: u128,
+}
+
+struct Entry {
+    value: Arc<dyn Any + Send + Sync>,
+    bytes: usize,
+    /// Position in `Cache::recency`.
+    last_used: u64,
+}
+
+struct Cache {
+    max_bytes: usize,
+    bytes: usize,
+    tick: u64,
+    hits: u64,
+    misses: u64,
+    entries: HashMap<Key, Entry>,
+    /// Keys by last use, least recent first.
+    recency: BTreeMap<u64, Key>,
+}
+
+impl Cache {
+    fn new(max_bytes: usize) -> Self {
+        Cache { max_bytes, bytes: 0, tick: 0, hits: 0, misses: 0, entries: HashMap::new(), recency: BTreeMap::new() }
+    }
+
+    fn get<O: Clone + 'static>(&mut self, key: &Key) -> Option<O> {
+        let hit = match self.entries.get_mut(key) {
+            Some(entry) => {
+                self.tick += 1;
+                let key = self.recency.remove(&entry.last_used).expect("every entry has a recency slot");
+                self.recency.insert(self.tick, key);
+                entry.last_used = self.tick;
+                entry.value.downcast_ref::<O>().cloned()
+            }
+            None => None,
+        };
+        match hit {
+            Some(_) => self.hits += 1,
+            None => self.misses += 1,
+        }
+        hit
+    }
+
+    fn insert<O: Weigh + Send + Sync + 'static>(&mut self, key: Key, value: O) {
+        let bytes = value.weight();
+        if bytes > self.max_bytes {
+            return;
+        }
+        self.tick += 1;
+        self.recency.insert(self.tick, key.clone());
+        let entry = Entry { value: Arc::new(value), bytes, last_used: self.tick };
+        if let Some(old) = self.entries.insert(key, entry) {
+            self.bytes -= old.bytes;
+            self.recency.remove(&old.last_used);
+        }
+        self.bytes += bytes;
+        self.evict();
+    }
+
+    fn evict(&mut self) {
+        while self.bytes > self.max_bytes {
+            let Some((_, key)) = self.recency.pop_first() else {
+                break;
+            };
+            if let Some(old) = self.entries.remove(&key) {
+                self.bytes -= old.bytes;
+            }
+        }
+    }
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static CACHE: Mutex<Option<Cache>> = Mutex::new(None);
+
+fn key<T: Real, O: 'static>(function: &str, params: &[f64], columns: &[&[T]]) -> Key {
+    // Two SipHash passes with different salts make up the 128 bits.
+    let [mut lo, mut hi] = [DefaultHasher::new(), DefaultHasher::new()];
+    0u8.hash(&mut lo);
+    1u8.hash(&mut hi);
+    for data in columns {
+        for x in *data {
+            let bits = x.to_f64().unwrap_or(f64::NAN).to_bits();
+            bits.hash(&mut lo);
+            bits.hash(&mut hi);
+        }
+    }
+    Key {
+        function: function.to_string(),
+        params: params.iter().map(|p| p.to_bits()).collect(),
+        types: (TypeId::of::<T>(), TypeId::of::<O>()),
+        lengths: columns.iter().map(|c| c.len()).collect(),
+        digest: (u128::from(hi.finish()) << 64) | u128::from(lo.finish()),
+    }
+}
+
+/// Returns the cached result for `(function, params, data)` or computes and
+/// stores it. Computes directly when caching is disabled.
+pub fn memoize<T, O, F>(function: &str, params: &[f64], data: &[T], compute: F) -> O
+where
+    T: Real,
+    O: Weigh + Clone + Send + Sync + 'static,
+    F: FnOnce() -> O,
+{
+    memoize_columns(function, params, &[data], compute)
+}
+
+/// [`memoize`] for indicators of several input series (high, low, close...),
+/// keyed on all of them.
+pub fn memoize_columns<T, O, F>(function: &str, params: &[f64], columns: &[&[T]], compute: F) -> O
+where
+    T: Real,
+    O: Weigh + Clone + Send + Sync + 'static,
+    F: FnOnce() -> O,
+{
+    if !ENABLED.load(Ordering::Relaxed) {
+        return compute();
+    }
+    let key = key::<T, O>(function, params, columns);
+    if let Some(hit) = CACHE.lock().unwrap().as_mut().and_then(|c| c.get::<O>(&key)) {
+        return hit;
+    }
+    // Computed without the lock so concurrent misses don't serialize.
+    let value = compute();
+    if let Some(cache) = CACHE.lock().unwrap().as_mut() {
+        cache.insert(key, value.clone());
+    }
+    value
+}
+
+/// Enable memoization of indicator results.
+///
+/// Identical calls (same function, parameters and input values) return the
+/// stored result instead of recomputing it. Calling again resizes the cache
+/// and keeps its contents.
+///
+/// Args:
+///     max_mb (float): Memory budget for cached results (default 256).
+#[pyfunction]
+#[pyo3(name = "enable_cache", signature = (max_mb=256.0))]
+pub fn py_enable_cache(max_mb: f64) -> PyResult<()> {
+    enable(max_mb)
+}
+
+/// Disable memoization and free all cached results.
+#[pyfunction]
+#[pyo3(name = "disable_cache")]
+pub fn py_disable_cache() {
+    disable();
+}
+
+/// See `enable_cache`; also applied by `set_config`.
+pub fn enable(max_mb: f64) -> PyResult<()> {
+    if max_mb.is_nan() || max_mb <= 0.0 {
+        return Err(PyValueError::new_err("max_mb must be positive"));
+    }
+    enable_bytes((max_mb * 1024.0 * 1024.0) as usize);
+    Ok(())
+}
+
+fn enable_bytes(max_bytes: usize) {
+    let mut guard = CACHE.lock().unwrap();
+    let cache = guard.get_or_insert_with(|| Cache::new(max_bytes));
+    cache.max_bytes = max_bytes;
+    cache.evict();
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn disable() {
+    ENABLED.store(false, Ordering::Relaxed);
+    *CACHE.lock().unwrap() = None;
+}
+
+/// Drop all cached results, keeping the cache enabled.
+#[pyfunction]
+#[pyo3(name = "clear_cache")]
+pub fn py_clear_cache() {
+    if let Some(cache) = CACHE.lock().unwrap().as_mut() {
+        *cache = Cache::new(cache.max_bytes);
+    }
+}
+
+/// Cache usage counters.
+///
+/// Returns:
+///     Dict: {"enabled": bool, "entries": int, "size_mb": float,
+///     "max_mb": float, "hits": int, "misses": int}.
+#[pyfunction]
+#[pyo3(name = "cache_stats")]
+pub fn py_cache_stats(py: Python<'_>) -> PyResult<Bound<'_, PyDict>> {
+    let out = PyDict::new(py);
+    let guard = CACHE.lock().unwrap();
+    let mb = |bytes: usize| bytes as f64 / (1024.0 * 1024.0);
+    out.set_item("enabled", ENABLED.load(Ordering::Relaxed))?;
+    out.set_item("entries", guard.as_ref().map_or(0, |c| c.entries.len()))?;
+    out.set_item("size_mb", guard.as_ref().map_or(0.0, |c| mb(c.bytes)))?;
+    out.set_item("max_mb", guard.as_ref().map_or(0.0, |c| mb(c.max_bytes)))?;
+    out.set_item("hits", guard.as_ref().map_or(0, |c| c.hits))?;
+    out.set_item("misses", guard.as_ref().map_or(0, |c| c.misses))?;
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn k(function: &str, data: &[f64]) -> Key {
+        key::<f64, Vec<f64>>(function, &[14.0], &[data])
+    }
+
+    #[test]
+    fn keys_tell_calls_apart() {
+        let data = [1.0, 2.0, 3.0];
+        assert_eq!(k("rsi", &data), k("rsi", &data));
+        assert_ne!(k("rsi", &data), k("ema", &data));
+        assert_ne!(k("rsi", &data), k("rsi", &[1.0, 2.0, 4.0]));
+        assert_ne!(k("rsi", &data), key::<f64, Vec<f64>>("rsi", &[15.0], &[&data]));
+        assert_ne!(k("rsi", &data), key::<f32, Vec<f64>>("rsi", &[14.0], &[&[1.0, 2.0, 3.0]]));
+        assert_ne!(k("rsi", &data), key::<f64, Vec<f32>>("rsi", &[14.0], &[&data]));
+        // The same values split differently across columns are a different call.
+        let split = key::<f64, Vec<f64>>("atr", &[], &[&data[..1], &data[1..]]);
+        assert_ne!(split, key::<f64, Vec<f64>>("atr", &[], &[&data[..2], &data[2..]]));
+    }
+
+    #[test]
+    fn hits_and_misses_are_counted() {
+        let mut cache = Cache::new(1024);
+        let key = k("rsi", &[1.0]);
+        assert_eq!(cache.get::<Vec<f64>>(&key), None);
+        cache.insert(key.clone(), vec![50.0]);
+        assert_eq!(cache.get::<Vec<f64>>(&key), Some(vec![50.0]));
+        assert_eq!(cache.get::<Vec<f64>>(&k("rsi", &[2.0])), None);
+        assert_eq!((cache.hits, cache.misses), (1, 2));
+        assert_eq!((cache.entries.len(), cache.bytes), (1, 8));
+    }
+
+    #[test]
+    fn the_least_recently_used_entry_is_evicted_first() {
+        let mut cache = Cache::new(3 * 80);
+        let keys: Vec<Key> = (0..4).map(|i| k("sma", &[i as f64])).collect();
+        for key in &keys[..3] {
+            cache.insert(key.clone(), vec![0.0; 10]);
+        }
+        assert!(cache.get::<Vec<f64>>(&keys[0]).is_some());
+        cache.insert(keys[3].clone(), vec![0.0; 10]);
+        assert!(!cache.entries.contains_key(&keys[1]));
+        for i in [0, 2, 3] {
+            assert!(cache.entries.contains_key(&keys[i]), "entry {}", i);
+        }
+        assert_eq!(cache.bytes, 3 * 80);
+        assert_eq!(cache.recency.len(), cache.entries.len());
+    }
+
+    #[test]
+    fn replacing_an_entry_keeps_the_accounting() {
+        let mut cache = Cache::new(1024);
+        let key = k("sma", &[1.0]);
+        cache.insert(key.clone(), vec![0.0; 10]);
+        cache.insert(key.clone(), vec![0.0; 4]);
+        assert_eq!((cache.entries.len(), cache.recency.len(), cache.bytes), (1, 1, 32));
+    }
+
+    #[test]
+    fn shrinking_evicts_and_oversized_results_are_not_stored() {
+        let mut cache = Cache::new(1024);
+        for i in 0..10 {
+            cache.insert(k("sma", &[i as f64]), vec![0.0; 10]);
+        }
+        cache.max_bytes = 200;
+        cache.evict();
+        assert_eq!((cache.entries.len(), cache.bytes), (2, 160));
+        assert!(cache.entries.contains_key(&k("sma", &[9.0])));
+
+        cache.insert(k("sma", &[10.0]), vec![0.0; 100]);
+        assert!(!cache.entries.contains_key(&k("sma", &[10.0])));
+        assert_eq!(cache.entries.len(), 2);
+    }
+
+    #[test]
+    fn memoize_stays_within_max_mb() {
+        // The only test touching the global cache.
+        enable_bytes(100);
+        let mut computed = 0;
+        for round in 0..2 {
+            for i in 0..3 {
+                let data = [i as f64];
+                let value: Vec<f64> = memoize("sma", &[1.0], &data[..], || {
+                    computed += 1;
+                    vec![i as f64; 5]
+                });
+                assert_eq!(value, vec![i as f64; 5], "round {}", round);
+            }
+        }
+        {
+            let guard = CACHE.lock().unwrap();
+            let cache = guard.as_ref().unwrap();
+            // Two 40-byte results fit in 100 bytes, so every lookup misses.
+            assert_eq!((cache.entries.len(), cache.bytes), (2, 80));
+            assert_eq!((cache.hits, cache.misses), (0, 6));
+        }
+        assert_eq!(computed, 6);
+        let again: Vec<f64> = memoize("sma", &[1.0], &[2.0][..], || unreachable!());
+        assert_eq!(again, vec![2.0; 5]);
+        assert_eq!(CACHE.lock().unwrap().as_ref().unwrap().hits, 1);
+
+        disable();
+        assert!(CACHE.lock().unwrap().is_none());
+        let value: Vec<f64> = memoize("sma", &[1.0], &[2.0][..], || vec![0.0]);
+        assert_eq!(value, vec![0.0]);
+    }
+}
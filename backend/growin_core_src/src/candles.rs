@@ -0,0 +1,444 @@
+use numpy::{PyArray1, PyArrayMethods, PyReadonlyArray1, PyUntypedArrayMethods};
+use pyo3::exceptions::{PyKeyError, PyValueError};
+use pyo3::prelude::*;
+use std::ops::Deref;
+
+use growin_core::candles::{self, Candles};
+
+use crate::errors::{self, LengthMismatchError};
+use crate::results::{BbandsResult, IndicatorResult, MacdResult};
+use crate::series::{IntoSeriesOutput, Series};
+use crate::technicals;
+
+/// Looks up `key` on a mapping-like object, treating a `KeyError` as absent.
+fn get_column<'py, T: FromPyObject<'py>>(ob: &Bound<'py, PyAny>, key: &str) -> PyResult<Option<T>> {
+    match ob.get_item(key) {
+        Ok(value) if value.is_none() => Ok(None),
+        Ok(value) => Ok(Some(value.extract()?)),
+        Err(e) if e.is_instance_of::<PyKeyError>(ob.py()) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// OHLCV bars extracted from any Python mapping with column keys
+/// (a dict of lists, a pandas DataFrame, ...) or a `CandleSeries`.
+///
+/// Only `close` is required. Missing `open`/`high`/`low` columns fall back to
+/// `close` (a line series), a missing `volume` column is left empty and
+/// `timestamp`/`timestamps` (epoch seconds) are optional.
+#[derive(Clone, Debug, Default)]
+pub struct PyCandles {
+    pub candles: Candles,
+    /// Whether the columns came in as numpy arrays, so that series computed
+    /// from them go back as arrays.
+    pub arrays: bool,
+}
+
+impl Deref for PyCandles {
+    type Target = Candles;
+
+    fn deref(&self) -> &Candles {
+        &self.candles
+    }
+}
+
+impl PyCandles {
+    /// A column as an indicator input, borrowed in place.
+    pub fn column<'a>(&'a self, values: &'a [f64]) -> Series<'a> {
+        Series::borrowed(values, self.arrays)
+    }
+}
+
+/// A column's values, copied once from an array instead of element by
+/// element (a sequence's are taken as extracted); also whether it was one.
+fn values(series: Series<'_>) -> (Vec<f64>, bool) {
+    let as_array = series.is_array();
+    let values = match series {
+        Series::List(values) | Series::Buffer(values) => values.into_owned(),
+        series => series.f64_slice(&mut Vec::new()).to_vec(),
+    };
+    (values, as_array)
+}
+
+impl<'py> FromPyObject<'py> for PyCandles {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        let _span = crate::profile::span("candles.from_python");
+        if let Ok(series) = ob.downcast::<CandleSeries>() {
+            let series = series.get();
+            return Ok(PyCandles { candles: series.to_candles(ob.py())?, arrays: series.arrays });
+        }
+        let close = get_column::<Series>(ob, "close")?
+            .ok_or_else(|| PyValueError::new_err("candles must provide a 'close' column"))?;
+        let (close, arrays) = values(close);
+        let n = close.len();
+
+        let column = |key: &str, fallback_to_close: bool| -> PyResult<Vec<f64>> {
+            match get_column::<Series>(ob, key)?.map(|s| values(s).0) {
+                Some(values) if values.len() != n => Err(LengthMismatchError::new_err(format!(
+                    "candles column '{}' has length {}, expected {}",
+                    key,
+                    values.len(),
+                    n
+                ))),
+                Some(values) => Ok(values),
+                None if fallback_to_close => Ok(close.clone()),
+                None => Ok(Vec::new()),
+            }
+        };
+
+        let open = column("open", true)?;
+        let high = column("high", true)?;
+        let low = column("low", true)?;
+        let volume = column("volume", false)?;
+
+        let timestamps: Vec<i64> = match get_column(ob, "timestamp")? {
+            Some(ts) => ts,
+            None => get_column(ob, "timestamps")?.unwrap_or_default(),
+        };
+        if !timestamps.is_empty() && timestamps.len() != n {
+            return Err(LengthMismatchError::new_err(format!(
+                "candles timestamps have length {}, expected {}",
+                timestamps.len(),
+                n
+            )));
+        }
+
+        let candles = Candles { open, high, low, close, volume, timestamps };
+        candles.log_quality_issues();
+        Ok(PyCandles { candles, arrays })
+    }
+}
+
+/// A `CandleSeries` column: a contiguous float64 array held by reference,
+/// or values copied into Rust from anything else.
+enum Column {
+    Array(Py<PyArray1<f64>>),
+    Values(Vec<f64>),
+}
+
+impl Column {
+    fn new(py: Python<'_>, series: Series<'_>) -> Self {
+        match series {
+            Series::F64(array) if array.as_slice().is_ok() => Column::Array(array.as_unbound().clone_ref(py)),
+            series => Column::Values(values(series).0),
+        }
+    }
+
+    fn len(&self, py: Python<'_>) -> usize {
+        match self {
+            Column::Array(array) => PyUntypedArrayMethods::len(array.bind(py)),
+            Column::Values(values) => values.len(),
+        }
+    }
+
+    /// The column as an indicator input, borrowed in place; values go back
+    /// as arrays when `as_array`.
+    fn series<'a>(&'a self, py: Python<'a>, as_array: bool) -> PyResult<Series<'a>> {
+        match self {
+            Column::Array(array) => Ok(Series::F64(array.bind(py).extract::<PyReadonlyArray1<f64>>()?)),
+            Column::Values(values) => Ok(Series::borrowed(values, as_array)),
+        }
+    }
+
+    /// The array itself, or the values as a new array or list.
+    fn to_python(&self, py: Python<'_>, as_array: bool) -> PyResult<PyObject> {
+        match self {
+            Column::Array(array) => Ok(array.clone_ref(py).into_any()),
+            Column::Values(values) => values.clone().into_output(py, as_array),
+        }
+    }
+
+    fn to_vec(&self, py: Python<'_>) -> PyResult<Vec<f64>> {
+        match self {
+            Column::Array(array) => Ok(array.bind(py).to_vec()?),
+            Column::Values(values) => Ok(values.clone()),
+        }
+    }
+}
+
+/// OHLCV bars held once, with the indicators as methods.
+///
+/// Contiguous float64 arrays are held by reference rather than copied, so
+/// writing to one afterwards shows in the series; other columns are copied
+/// once into contiguous buffers. Methods compute on the stored columns in
+/// place (`series.rsi(14)` is `calculate_rsi(close, 14)` without converting
+/// the closes again) and take the same parameters and defaults as their
+/// `calculate_*` functions, whose outputs they return: float64 arrays when
+/// the columns were numpy arrays, lists otherwise. Functions taking
+/// `candles` accept a `CandleSeries` as well.
+///
+/// Args:
+///     close (List[float] | np.ndarray): Bar closes.
+///     open, high, low (List[float] | np.ndarray, optional): Default to
+///         `close` (a line series).
+///     volume (List[float] | np.ndarray, optional): Bar volumes, needed by
+///         the volume indicators.
+///     timestamps (List[int], optional): Bar times in epoch seconds.
+///
+/// Raises:
+///     LengthMismatchError: If the columns differ in length.
+#[pyclass(module = "growin_core", frozen)]
+pub struct CandleSeries {
+    close: Column,
+    /// None where the column defaults to `close`.
+    open: Option<Column>,
+    high: Option<Column>,
+    low: Option<Column>,
+    volume: Option<Column>,
+    timestamps: Vec<i64>,
+    len: usize,
+    /// Whether `close` was a numpy array; see `PyCandles::arrays`.
+    arrays: bool,
+}
+
+impl CandleSeries {
+    fn from_columns(candles: Candles, arrays: bool) -> Self {
+        let Candles { open, high, low, close, volume, timestamps } = candles;
+        CandleSeries {
+            len: close.len(),
+            close: Column::Values(close),
+            open: Some(Column::Values(open)),
+            high: Some(Column::Values(high)),
+            low: Some(Column::Values(low)),
+            volume: (!volume.is_empty()).then_some(Column::Values(volume)),
+            timestamps,
+            arrays,
+        }
+    }
+
+    fn column<'a>(&'a self, py: Python<'a>, column: &'a Option<Column>) -> PyResult<Series<'a>> {
+        column.as_ref().unwrap_or(&self.close).series(py, self.arrays)
+    }
+
+    fn close<'a>(&'a self, py: Python<'a>) -> PyResult<Series<'a>> {
+        self.close.series(py, self.arrays)
+    }
+
+    fn hlc<'a>(&'a self, py: Python<'a>) -> PyResult<(Series<'a>, Series<'a>, Series<'a>)> {
+        Ok((self.column(py, &self.high)?, self.column(py, &self.low)?, self.close(py)?))
+    }
+
+    fn output(&self, py: Python<'_>, column: &Option<Column>) -> PyResult<PyObject> {
+        column.as_ref().unwrap_or(&self.close).to_python(py, self.arrays)
+    }
+
+    fn volume<'a>(&'a self, py: Python<'a>) -> PyResult<Series<'a>> {
+        match &self.volume {
+            Some(volume) => volume.series(py, self.arrays),
+            None => Err(PyValueError::new_err("this CandleSeries has no volume")),
+        }
+    }
+
+    fn log_quality_issues(&self, py: Python<'_>) -> PyResult<()> {
+        let (high, low, close) = self.hlc(py)?;
+        let mut owned: [Vec<f64>; 3] = Default::default();
+        let [h, l, c] = &mut owned;
+        candles::log_quality_issues(high.f64_slice(h), low.f64_slice(l), close.f64_slice(c), &self.timestamps);
+        Ok(())
+    }
+
+    /// The bars as owned columns, for the functions taking `candles`.
+    fn to_candles(&self, py: Python<'_>) -> PyResult<Candles> {
+        let column = |c: &Option<Column>| c.as_ref().unwrap_or(&self.close).to_vec(py);
+        let candles = Candles {
+            open: column(&self.open)?,
+            high: column(&self.high)?,
+            low: column(&self.low)?,
+            close: self.close.to_vec(py)?,
+            volume: self.volume.as_ref().map_or(Ok(Vec::new()), |v| v.to_vec(py))?,
+            timestamps: self.timestamps.clone(),
+        };
+        Ok(candles)
+    }
+}
+
+#[pymethods]
+impl CandleSeries {
+    #[new]
+    #[pyo3(signature = (close, open=None, high=None, low=None, volume=None, timestamps=None))]
+    fn py_new(
+        py: Python<'_>,
+        close: Series<'_>,
+        open: Option<Series<'_>>,
+        high: Option<Series<'_>>,
+        low: Option<Series<'_>>,
+        volume: Option<Series<'_>>,
+        timestamps: Option<Vec<i64>>,
+    ) -> PyResult<Self> {
+        let arrays = close.is_array();
+        let close = Column::new(py, close);
+        let n = close.len(py);
+        let mut lengths = vec![("close", n)];
+        let mut column = |name: &'static str, s: Option<Series<'_>>| {
+            s.map(|s| {
+                let column = Column::new(py, s);
+                lengths.push((name, column.len(py)));
+                column
+            })
+        };
+        let open = column("open", open);
+        let high = column("high", high);
+        let low = column("low", low);
+        let volume = column("volume", volume);
+        let timestamps = timestamps.unwrap_or_default();
+        if !timestamps.is_empty() {
+            lengths.push(("timestamps", timestamps.len()));
+        }
+        errors::check_lengths(&lengths)?;
+        let series = CandleSeries { close, open, high, low, volume, timestamps, len: n, arrays };
+        series.log_quality_issues(py)?;
+        Ok(series)
+    }
+
+    /// Build from a mapping of columns (a dict of lists, a pandas
+    /// DataFrame, ...), as the functions taking `candles` read it.
+    #[staticmethod]
+    fn from_candles(candles: PyCandles) -> Self {
+        CandleSeries::from_columns(candles.candles, candles.arrays)
+    }
+
+    #[getter]
+    fn open(&self, py: Python<'_>) -> PyResult<PyObject> {
+        self.output(py, &self.open)
+    }
+
+    #[getter]
+    fn high(&self, py: Python<'_>) -> PyResult<PyObject> {
+        self.output(py, &self.high)
+    }
+
+    #[getter]
+    fn low(&self, py: Python<'_>) -> PyResult<PyObject> {
+        self.output(py, &self.low)
+    }
+
+    #[getter(close)]
+    fn get_close(&self, py: Python<'_>) -> PyResult<PyObject> {
+        self.close.to_python(py, self.arrays)
+    }
+
+    /// None when the bars have no volume.
+    #[getter(volume)]
+    fn get_volume(&self, py: Python<'_>) -> PyResult<Option<PyObject>> {
+        self.volume.as_ref().map(|v| v.to_python(py, self.arrays)).transpose()
+    }
+
+    /// None when the bars have no timestamps.
+    #[getter]
+    fn timestamps(&self) -> Option<Vec<i64>> {
+        (!self.timestamps.is_empty()).then(|| self.timestamps.clone())
+    }
+
+    /// See `calculate_rsi`.
+    #[pyo3(signature = (period=14))]
+    fn rsi(&self, py: Python<'_>, period: usize) -> PyResult<PyObject> {
+        crate::calculate_rsi(py, self.close(py)?, period)
+    }
+
+    /// See `calculate_sma`.
+    #[pyo3(signature = (period=20))]
+    fn sma(&self, py: Python<'_>, period: usize) -> PyResult<PyObject> {
+        crate::calculate_sma(py, self.close(py)?, period)
+    }
+
+    /// See `calculate_ema`.
+    #[pyo3(signature = (period=14))]
+    fn ema(&self, py: Python<'_>, period: usize) -> PyResult<PyObject> {
+        crate::calculate_ema(py, self.close(py)?, period)
+    }
+
+    /// See `calculate_macd`.
+    #[pyo3(signature = (fast=12, slow=26, signal=9))]
+    fn macd(&self, py: Python<'_>, fast: usize, slow: usize, signal: usize) -> PyResult<MacdResult> {
+        crate::calculate_macd(py, self.close(py)?, fast, slow, signal)
+    }
+
+    /// See `calculate_bbands`.
+    #[pyo3(signature = (period=20, std_dev=2.0))]
+    fn bbands(&self, py: Python<'_>, period: usize, std_dev: f64) -> PyResult<BbandsResult> {
+        crate::calculate_bbands(py, self.close(py)?, period, std_dev)
+    }
+
+    /// See `calculate_atr`.
+    #[pyo3(signature = (period=14))]
+    fn atr(&self, py: Python<'_>, period: usize) -> PyResult<PyObject> {
+        let (high, low, close) = self.hlc(py)?;
+        technicals::py_calculate_atr(py, high, low, close, period)
+    }
+
+    /// See `calculate_stochastic`.
+    #[pyo3(signature = (k_period=14, d_period=3, smooth_k=3))]
+    fn stochastic(&self, py: Python<'_>, k_period: usize, d_period: usize, smooth_k: usize) -> PyResult<IndicatorResult> {
+        let (high, low, close) = self.hlc(py)?;
+        technicals::py_calculate_stochastic(py, high, low, close, k_period, d_period, smooth_k)
+    }
+
+    /// See `calculate_adx`.
+    #[pyo3(signature = (period=14))]
+    fn adx(&self, py: Python<'_>, period: usize) -> PyResult<IndicatorResult> {
+        let (high, low, close) = self.hlc(py)?;
+        technicals::py_calculate_adx(py, high, low, close, period)
+    }
+
+    /// See `calculate_supertrend`.
+    #[pyo3(signature = (period=10, multiplier=3.0))]
+    fn supertrend(&self, py: Python<'_>, period: usize, multiplier: f64) -> PyResult<IndicatorResult> {
+        let (high, low, close) = self.hlc(py)?;
+        technicals::py_calculate_supertrend(py, high, low, close, period, multiplier)
+    }
+
+    /// See `calculate_cci`.
+    #[pyo3(signature = (period=20, constant=0.015))]
+    fn cci(&self, py: Python<'_>, period: usize, constant: f64) -> PyResult<PyObject> {
+        let (high, low, close) = self.hlc(py)?;
+        technicals::py_calculate_cci(py, high, low, close, period, constant)
+    }
+
+    /// See `calculate_williams_r`.
+    #[pyo3(signature = (period=14))]
+    fn williams_r(&self, py: Python<'_>, period: usize) -> PyResult<PyObject> {
+        let (high, low, close) = self.hlc(py)?;
+        technicals::py_calculate_williams_r(py, high, low, close, period)
+    }
+
+    /// See `calculate_obv`. Raises ValueError without volume, as do the
+    /// other volume indicators.
+    fn obv(&self, py: Python<'_>) -> PyResult<PyObject> {
+        technicals::py_calculate_obv(py, self.close(py)?, self.volume(py)?)
+    }
+
+    /// See `calculate_mfi`.
+    #[pyo3(signature = (period=14))]
+    fn mfi(&self, py: Python<'_>, period: usize) -> PyResult<PyObject> {
+        let (high, low, close) = self.hlc(py)?;
+        technicals::py_calculate_mfi(py, high, low, close, self.volume(py)?, period)
+    }
+
+    /// See `calculate_vwap`; `sessions=True` restarts it each day of the
+    /// stored timestamps.
+    #[pyo3(signature = (window=None, sessions=false, utc_offset_minutes=0))]
+    fn vwap(&self, py: Python<'_>, window: Option<usize>, sessions: bool, utc_offset_minutes: i64) -> PyResult<PyObject> {
+        let timestamps = match sessions {
+            true if self.timestamps.is_empty() => {
+                return Err(PyValueError::new_err("session VWAP needs a CandleSeries with timestamps"))
+            }
+            true => Some(self.timestamps.clone()),
+            false => None,
+        };
+        let (high, low, close) = self.hlc(py)?;
+        technicals::py_calculate_vwap(py, high, low, close, self.volume(py)?, window, timestamps, utc_offset_minutes)
+    }
+
+    fn __len__(&self) -> usize {
+        self.len
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "CandleSeries(bars={}, volume={}, timestamps={})",
+            self.len,
+            if self.volume.is_some() { "True" } else { "False" },
+            if self.timestamps.is_empty() { "False" } else { "True" }
+        )
+    }
+}
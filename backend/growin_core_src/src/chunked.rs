@@ -0,0 +1,180 @@
+//! Python iterators over the chunk-at-a-time indicator states.
+//!
+//! Callers pass any iterable of blocks (lists or 1-D numpy arrays) and get
+//! back an iterator yielding one output block per input block.
+
+use pyo3::prelude::*;
+use pyo3::types::PyIterator;
+use std::sync::Mutex;
+
+use growin_core::chunked::{
+    process_block, BbandsState, EmaSeed, EmaState, MacdState, RollingStatsState, RsiState, SmaState, Streaming,
+};
+
+use crate::config::{self, Padding};
+use crate::errors;
+use crate::series::{as_slice, IntoSeriesOutput, Series};
+
+/// Reshapes per-value tuples into three columns.
+fn unzip3(values: Vec<(f64, f64, f64)>) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let mut out = (Vec::with_capacity(values.len()), Vec::with_capacity(values.len()), Vec::with_capacity(values.len()));
+    for (a, b, c) in values {
+        out.0.push(a);
+        out.1.push(b);
+        out.2.push(c);
+    }
+    out
+}
+
+/// Outputs whose warm-up the padding policy may blank out.
+pub trait Warmup {
+    /// Blanks the columns of output `index` that are still before their
+    /// `valid_from`.
+    fn blank(&mut self, index: usize, valid_from: &[usize]);
+}
+
+impl Warmup for f64 {
+    fn blank(&mut self, index: usize, valid_from: &[usize]) {
+        if index < valid_from[0] {
+            *self = f64::NAN;
+        }
+    }
+}
+
+impl Warmup for (f64, f64, f64) {
+    fn blank(&mut self, index: usize, valid_from: &[usize]) {
+        for (value, &from) in [&mut self.0, &mut self.1, &mut self.2].into_iter().zip(valid_from) {
+            if index < from {
+                *value = f64::NAN;
+            }
+        }
+    }
+}
+
+/// Advances the state over one block and converts the result to Python.
+type BlockFn = Box<dyn FnMut(Python<'_>, &[f64], bool) -> PyResult<PyObject> + Send>;
+
+/// Iterator over output blocks; see the `*_chunked` functions.
+#[pyclass(module = "growin_core")]
+pub struct ChunkIterator {
+    source: Py<PyIterator>,
+    // Only ever used through `&mut self`; the mutex just makes it `Sync`.
+    step: Mutex<BlockFn>,
+}
+
+impl ChunkIterator {
+    /// `valid_from` holds each output column's warm-up length, blanked when
+    /// the configured padding is "nan".
+    fn new<S, F, O>(blocks: &Bound<'_, PyAny>, mut state: S, valid_from: Vec<usize>, finish: F) -> PyResult<Self>
+    where
+        S: Streaming,
+        S::Output: Warmup,
+        F: Fn(Vec<S::Output>) -> O + Send + Sync + 'static,
+        O: IntoSeriesOutput + Send,
+    {
+        let pad = config::current().padding == Padding::Nan;
+        let mut seen = 0;
+        let step: BlockFn = Box::new(move |py, block, as_array| {
+            let out = py.allow_threads(|| {
+                let mut values = process_block(&mut state, block);
+                if pad {
+                    for (i, value) in values.iter_mut().enumerate() {
+                        value.blank(seen + i, &valid_from);
+                    }
+                }
+                seen += values.len();
+                finish(values)
+            });
+            out.into_output(py, as_array)
+        });
+        Ok(ChunkIterator { source: PyIterator::from_object(blocks)?.unbind(), step: Mutex::new(step) })
+    }
+}
+
+#[pymethods]
+impl ChunkIterator {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self, py: Python<'_>) -> PyResult<Option<PyObject>> {
+        let Some(block) = self.source.bind(py).clone().next() else {
+            return Ok(None);
+        };
+        let block: Series = block?.extract()?;
+        let (values, as_array): (Vec<f64>, bool) = match block {
+            Series::F64(array) => (as_slice(&array, &mut Vec::new()).to_vec(), true),
+            Series::F32(array) => (array.as_array().iter().map(|&x| x as f64).collect(), true),
+            Series::List(values) => (values.into_owned(), false),
+            Series::Buffer(values) => (values.into_owned(), true),
+        };
+        let step = self.step.get_mut().unwrap();
+        step(py, &values, as_array).map(Some)
+    }
+}
+
+/// RSI over an iterable of price blocks.
+///
+/// Args:
+///     blocks (Iterable[List[float] | np.ndarray]): Consecutive slices of one series.
+///     period (int): Lookback period (default 14).
+///
+/// Returns:
+///     Iterator: One RSI block per input block (arrays come back as float64
+///     arrays); concatenated, they equal `calculate_rsi` on the whole series.
+#[pyfunction]
+#[pyo3(name = "calculate_rsi_chunked", signature = (blocks, period=14))]
+pub fn py_calculate_rsi_chunked(blocks: &Bound<'_, PyAny>, period: usize) -> PyResult<ChunkIterator> {
+    errors::check_period("period", period)?;
+    ChunkIterator::new(blocks, RsiState::new(period), vec![period], |v| v)
+}
+
+/// SMA over an iterable of blocks; see `calculate_rsi_chunked`.
+#[pyfunction]
+#[pyo3(name = "calculate_sma_chunked", signature = (blocks, period=20))]
+pub fn py_calculate_sma_chunked(blocks: &Bound<'_, PyAny>, period: usize) -> PyResult<ChunkIterator> {
+    errors::check_period("period", period)?;
+    ChunkIterator::new(blocks, SmaState::new(period), vec![period - 1], |v| v)
+}
+
+/// EMA over an iterable of blocks; see `calculate_rsi_chunked`. Matches
+/// `calculate_ema` once at least `period` values have been seen.
+#[pyfunction]
+#[pyo3(name = "calculate_ema_chunked", signature = (blocks, period=14))]
+pub fn py_calculate_ema_chunked(blocks: &Bound<'_, PyAny>, period: usize) -> PyResult<ChunkIterator> {
+    errors::check_period("period", period)?;
+    ChunkIterator::new(blocks, EmaState::new(period, EmaSeed::Lanes), vec![period - 1], |v| v)
+}
+
+/// MACD over an iterable of blocks, yielding (macd, signal, histogram) per block.
+#[pyfunction]
+#[pyo3(name = "calculate_macd_chunked", signature = (blocks, fast=12, slow=26, signal=9))]
+pub fn py_calculate_macd_chunked(blocks: &Bound<'_, PyAny>, fast: usize, slow: usize, signal: usize) -> PyResult<ChunkIterator> {
+    errors::check_period("fast", fast)?;
+    errors::check_period("slow", slow)?;
+    errors::check_period("signal", signal)?;
+    let seeded = fast.max(slow) - 1;
+    let valid_from = vec![seeded, seeded + signal - 1, seeded + signal - 1];
+    ChunkIterator::new(blocks, MacdState::new(fast, slow, signal), valid_from, unzip3)
+}
+
+/// Bollinger Bands over an iterable of blocks, yielding (upper, middle, lower) per block.
+#[pyfunction]
+#[pyo3(name = "calculate_bbands_chunked", signature = (blocks, period=20, std_dev=2.0))]
+pub fn py_calculate_bbands_chunked(blocks: &Bound<'_, PyAny>, period: usize, std_dev: f64) -> PyResult<ChunkIterator> {
+    errors::check_period("period", period)?;
+    let state = BbandsState::with_ddof(period, std_dev, config::current().ddof());
+    ChunkIterator::new(blocks, state, vec![period - 1; 3], unzip3)
+}
+
+/// Rolling mean, std and z-score over an iterable of blocks, yielding
+/// (mean, std, zscore) per block; the std is `rolling_std`'s. NaN until
+/// `period` values have been seen and for windows containing a NaN or ±Inf; the
+/// z-score of a flat window is 0.
+#[pyfunction]
+#[pyo3(name = "rolling_stats_chunked", signature = (blocks, period=20))]
+pub fn py_rolling_stats_chunked(blocks: &Bound<'_, PyAny>, period: usize) -> PyResult<ChunkIterator> {
+    errors::check_period("period", period)?;
+    let state = RollingStatsState::with_ddof(period, config::current().ddof());
+    ChunkIterator::new(blocks, state, vec![0; 3], unzip3)
+}
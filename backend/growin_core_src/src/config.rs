@@ -0,0 +1,204 @@
+//! Process-wide settings.
+//!
+//! Build a `Config` once at startup and install it with `set_config`; calls
+//! read the installed settings instead of each taking its own keyword
+//! arguments for padding, std conventions and the like. Until then the
+//! defaults apply, which match the extension's historical behaviour.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::sync::RwLock;
+
+use growin_core::indicators;
+use growin_core::lanes::Real;
+
+use crate::{cache, errors};
+
+/// How `calculate_*` indicators fill their warm-up values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Padding {
+    /// Zeros (50 for RSI), as the Python fallbacks in `financial_math` do.
+    Zero,
+    /// NaN, as pandas' rolling windows do.
+    Nan,
+}
+
+/// Which library's statistical conventions to follow where they differ.
+///
+/// This sets the standard deviation behind Bollinger Bands, `rolling_std`
+/// and `rolling_zscore` (chunked, streaming or not), return anomalies, the
+/// z-scores of `mean_reversion_signals`, `pair_signals` and composite
+/// scores, volatility features and the regime volatility. `StandardScaler`
+/// keeps scikit-learn's population std and `sample_entropy`'s default
+/// tolerance its usual population std in either mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Compat {
+    /// Population standard deviation (`ddof=0`), as numpy's `std`.
+    Native,
+    /// Sample standard deviation (`ddof=1`), as `Series.rolling().std()`.
+    Pandas,
+}
+
+/// Global settings; see `set_config`.
+///
+/// Args:
+///     padding (str): "zero" (default) or "nan" for indicator warm-ups.
+///     annualization (int): Periods per year (default 252 trading days);
+///         the default window of yearly metrics such as `high_low_metrics`.
+///     compat (str): "native" (default) or "pandas" statistical conventions:
+///         population or sample standard deviations wherever the extension
+///         takes one, except in `StandardScaler` and `sample_entropy`.
+///     currency (str): ISO 4217 base currency of the account (default "GBP").
+///     cache_mb (float | None): Indicator cache budget; None (default)
+///         leaves the cache disabled.
+///     max_risk_pct (float): Largest share of the account one position may
+///         risk to its stop, in percent (default 2).
+#[pyclass(module = "growin_core", frozen)]
+#[derive(Clone, Debug)]
+pub struct Config {
+    pub padding: Padding,
+    #[pyo3(get)]
+    pub annualization: usize,
+    pub compat: Compat,
+    #[pyo3(get)]
+    pub currency: String,
+    #[pyo3(get)]
+    pub cache_mb: Option<f64>,
+    #[pyo3(get)]
+    pub max_risk_pct: f64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            padding: Padding::Zero,
+            annualization: 252,
+            compat: Compat::Native,
+            currency: "GBP".to_string(),
+            cache_mb: None,
+            max_risk_pct: 2.0,
+        }
+    }
+}
+
+impl Config {
+    /// Applies the padding policy to a series whose first `valid_from`
+    /// values are warm-up.
+    pub fn pad<T: Real>(&self, values: &mut [T], valid_from: usize) {
+        if self.padding == Padding::Nan {
+            indicators::mask_warmup(values, valid_from);
+        }
+    }
+
+    /// Delta degrees of freedom for rolling standard deviations.
+    pub fn ddof(&self) -> usize {
+        match self.compat {
+            Compat::Native => 0,
+            Compat::Pandas => 1,
+        }
+    }
+
+    /// Settings that change indicator output, for cache keys.
+    pub fn cache_key(&self) -> f64 {
+        (self.padding as u8 * 2 + self.compat as u8) as f64
+    }
+}
+
+#[pymethods]
+impl Config {
+    #[new]
+    #[pyo3(signature = (padding="zero", annualization=252, compat="native", currency="GBP", cache_mb=None, max_risk_pct=2.0))]
+    fn py_new(
+        padding: &str,
+        annualization: usize,
+        compat: &str,
+        currency: &str,
+        cache_mb: Option<f64>,
+        max_risk_pct: f64,
+    ) -> PyResult<Self> {
+        let padding = match padding {
+            "zero" => Padding::Zero,
+            "nan" => Padding::Nan,
+            other => return Err(PyValueError::new_err(format!("padding must be 'zero' or 'nan', got '{}'", other))),
+        };
+        let compat = match compat {
+            "native" => Compat::Native,
+            "pandas" => Compat::Pandas,
+            other => return Err(PyValueError::new_err(format!("compat must be 'native' or 'pandas', got '{}'", other))),
+        };
+        errors::check_period("annualization", annualization)?;
+        if currency.len() != 3 || !currency.chars().all(|c| c.is_ascii_alphabetic()) {
+            return Err(PyValueError::new_err(format!("currency must be a 3-letter ISO code, got '{}'", currency)));
+        }
+        if cache_mb.is_some_and(|mb| mb.is_nan() || mb <= 0.0) {
+            return Err(PyValueError::new_err("cache_mb must be positive"));
+        }
+        if max_risk_pct.is_nan() || max_risk_pct <= 0.0 {
+            return Err(PyValueError::new_err("max_risk_pct must be positive"));
+        }
+        let currency = currency.to_ascii_uppercase();
+        Ok(Config { padding, annualization, compat, currency, cache_mb, max_risk_pct })
+    }
+
+    #[getter]
+    fn padding(&self) -> &'static str {
+        match self.padding {
+            Padding::Zero => "zero",
+            Padding::Nan => "nan",
+        }
+    }
+
+    #[getter]
+    fn compat(&self) -> &'static str {
+        match self.compat {
+            Compat::Native => "native",
+            Compat::Pandas => "pandas",
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        let cache_mb = self.cache_mb.map_or("None".to_string(), |mb| format!("{:?}", mb));
+        format!(
+            "Config(padding='{}', annualization={}, compat='{}', currency='{}', cache_mb={}, max_risk_pct={:?})",
+            self.padding(),
+            self.annualization,
+            self.compat(),
+            self.currency,
+            cache_mb,
+            self.max_risk_pct
+        )
+    }
+}
+
+static CURRENT: RwLock<Option<Config>> = RwLock::new(None);
+
+/// The installed settings, or the defaults.
+pub fn current() -> Config {
+    CURRENT.read().unwrap().clone().unwrap_or_default()
+}
+
+/// Install process-wide settings, typically once at startup.
+///
+/// Applies `cache_mb` immediately (enabling, resizing or disabling the
+/// cache); the other settings take effect on the next call that reads them.
+///
+/// Args:
+///     config (Config): The settings to install.
+#[pyfunction]
+#[pyo3(name = "set_config")]
+pub fn py_set_config(config: Config) -> PyResult<()> {
+    match config.cache_mb {
+        Some(mb) => cache::enable(mb)?,
+        None => cache::disable(),
+    }
+    log::debug!("installed {:?}", config);
+    *CURRENT.write().unwrap() = Some(config);
+    Ok(())
+}
+
+/// The installed settings (defaults until `set_config` is called).
+#[pyfunction]
+#[pyo3(name = "get_config")]
+pub fn py_get_config() -> Config {
+    current()
+}
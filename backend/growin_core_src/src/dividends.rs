@@ -0,0 +1,102 @@
+//! Python bindings of the dividend income projection.
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use growin_core::calendar::Date;
+use growin_core::dividends::{project_dividends, Dividend};
+use growin_core::money::round;
+
+use crate::isa::civil_date;
+
+/// Forward dividend calendar for the income view.
+///
+/// Each holding's payment frequency is inferred from how often it paid in
+/// the latest year, and its last year of payments is repeated forward, so
+/// uneven interim/final dividends keep their pattern.
+///
+/// Args:
+///     positions (Dict[str, Decimal]): ticker -> shares held.
+///     dividend_history (Dict[str, List[Tuple[date | str, Decimal]]]):
+///         ticker -> (payment date, amount per share) pairs, in any order.
+///     horizon_months (int): Months to project (default 12).
+///     as_of (date | str, optional): Project payments after this date
+///         (default today, UTC).
+///     dp (int, optional): Decimal places for income amounts (default 2).
+///
+/// Returns:
+///     Dict: {"calendar": {"YYYY-MM": {"total", "payments": [{"ticker",
+///     "date", "per_share", "amount"}]}} for every month from `as_of`'s to
+///     the horizon's end,
+///     "holdings": {ticker: {"frequency", "annual_per_share",
+///     "annual_income", "next_payment"}}, "total"}. Holdings without a
+///     payment history are left out.
+#[pyfunction]
+#[pyo3(name = "project_dividends", signature = (positions, dividend_history, horizon_months=12, as_of=None, dp=Some(2)))]
+pub fn py_project_dividends<'py>(
+    py: Python<'py>,
+    positions: HashMap<String, Decimal>,
+    dividend_history: HashMap<String, Vec<(Bound<'py, PyAny>, Decimal)>>,
+    horizon_months: u32,
+    as_of: Option<Bound<'py, PyAny>>,
+    dp: Option<u32>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let as_of = match as_of {
+        Some(date) => civil_date(&date)?,
+        None => Date::from_timestamp(SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs() as i64)),
+    };
+    let mut holdings = Vec::with_capacity(positions.len());
+    for (ticker, quantity) in positions {
+        let history = match dividend_history.get(&ticker) {
+            Some(h) => h
+                .iter()
+                .map(|(date, per_share)| Ok(Dividend { date: civil_date(date)?, per_share: *per_share }))
+                .collect::<PyResult<Vec<_>>>()?,
+            None => Vec::new(),
+        };
+        holdings.push((ticker, quantity, history));
+    }
+    let projection = py.allow_threads(|| project_dividends(&holdings, as_of, horizon_months));
+
+    let by_month = projection.by_month();
+    let calendar = PyDict::new(py);
+    let mut total = Decimal::ZERO;
+    for k in 0..=horizon_months as i32 {
+        let month = as_of.add_months(k).month_label();
+        let payments = by_month.get(&month).map_or(&[][..], |p| &p[..]);
+        let month_total: Decimal = payments.iter().map(|p| p.amount).sum();
+        total += month_total;
+        let list = payments
+            .iter()
+            .map(|p| {
+                let d = PyDict::new(py);
+                d.set_item("ticker", &p.ticker)?;
+                d.set_item("date", p.date.to_string())?;
+                d.set_item("per_share", p.per_share.normalize())?;
+                d.set_item("amount", round(p.amount, dp))?;
+                Ok(d)
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+        let entry = PyDict::new(py);
+        entry.set_item("total", round(month_total, dp))?;
+        entry.set_item("payments", list)?;
+        calendar.set_item(month, entry)?;
+    }
+    let profiles = PyDict::new(py);
+    for p in &projection.profiles {
+        let d = PyDict::new(py);
+        d.set_item("frequency", p.frequency.name())?;
+        d.set_item("annual_per_share", p.annual_per_share.normalize())?;
+        d.set_item("annual_income", round(p.annual_income, dp))?;
+        d.set_item("next_payment", p.next_payment.map(|date| date.to_string()))?;
+        profiles.set_item(&p.ticker, d)?;
+    }
+    let out = PyDict::new(py);
+    out.set_item("calendar", calendar)?;
+    out.set_item("holdings", profiles)?;
+    out.set_item("total", round(total, dp))?;
+    Ok(out)
+}
@@ -0,0 +1,101 @@
+//! Exception hierarchy exposed as `growin_core.errors`.
+//!
+//! Every error derives from `GrowinError`, itself a `ValueError`, so callers
+//! that already catch `ValueError` keep working while new code can catch the
+//! specific failure.
+
+use pyo3::create_exception;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use growin_core::lanes::Real;
+
+create_exception!(growin_core.errors, GrowinError, PyValueError, "Base class of all growin_core errors.");
+create_exception!(
+    growin_core.errors,
+    InvalidPeriodError,
+    GrowinError,
+    "A period, window or lookback is zero or otherwise out of range."
+);
+create_exception!(
+    growin_core.errors,
+    LengthMismatchError,
+    GrowinError,
+    "Series that must be aligned bar for bar have different lengths."
+);
+create_exception!(
+    growin_core.errors,
+    InsufficientDataError,
+    GrowinError,
+    "The input has no usable (finite) values to compute from."
+);
+
+/// Rejects a zero period.
+pub fn check_period(name: &str, period: usize) -> PyResult<()> {
+    check_min_period(name, period, 1)
+}
+
+/// Rejects a period below `min`.
+pub fn check_min_period(name: &str, period: usize, min: usize) -> PyResult<()> {
+    if period >= min {
+        return Ok(());
+    }
+    Err(InvalidPeriodError::new_err(if min == 1 {
+        format!("'{}' must be a positive integer, got {}", name, period)
+    } else {
+        format!("'{}' must be at least {}, got {}", name, min, period)
+    }))
+}
+
+/// Requires every named length to equal the first one.
+pub fn check_lengths(lengths: &[(&str, usize)]) -> PyResult<()> {
+    let Some(&(first, n)) = lengths.first() else {
+        return Ok(());
+    };
+    match lengths.iter().find(|(_, len)| *len != n) {
+        Some((name, len)) => Err(LengthMismatchError::new_err(format!(
+            "'{}' has length {}, expected {} to match '{}'",
+            name, len, n, first
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Rejects a non-empty series in which every value is NaN, which would
+/// otherwise come back as an all-NaN or zero-padded result. Returns the
+/// number of NaN values otherwise.
+pub fn check_has_data<T: Real>(name: &str, data: &[T]) -> PyResult<usize> {
+    let nans = data.iter().filter(|v| v.is_nan()).count();
+    if nans > 0 && nans == data.len() {
+        return Err(InsufficientDataError::new_err(format!("'{}' contains only NaN values", name)));
+    }
+    Ok(nans)
+}
+
+/// `d[key]`, or None when it is missing or None.
+pub fn optional_field<'py, T: FromPyObject<'py>>(d: &Bound<'py, PyDict>, key: &str) -> PyResult<Option<T>> {
+    match d.get_item(key)? {
+        Some(v) if !v.is_none() => v.extract().map(Some),
+        _ => Ok(None),
+    }
+}
+
+/// `d[key]`, rejecting a missing or None value; `what` names the dict.
+pub fn required_field<'py, T: FromPyObject<'py>>(d: &Bound<'py, PyDict>, what: &str, key: &str) -> PyResult<T> {
+    optional_field(d, key)?.ok_or_else(|| PyValueError::new_err(format!("{} needs a '{}'", what, key)))
+}
+
+/// Builds the `growin_core.errors` submodule and makes it importable.
+pub fn register(parent: &Bound<'_, PyModule>) -> PyResult<()> {
+    let py = parent.py();
+    let m = PyModule::new(py, "errors")?;
+    m.add("GrowinError", py.get_type::<GrowinError>())?;
+    m.add("InvalidPeriodError", py.get_type::<InvalidPeriodError>())?;
+    m.add("LengthMismatchError", py.get_type::<LengthMismatchError>())?;
+    m.add("InsufficientDataError", py.get_type::<InsufficientDataError>())?;
+    parent.add_submodule(&m)?;
+    // `from growin_core.errors import ...` resolves through sys.modules.
+    py.import("sys")?.getattr("modules")?.set_item("growin_core.errors", &m)?;
+    Ok(())
+}
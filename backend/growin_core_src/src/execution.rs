@@ -0,0 +1,109 @@
+//! Python bindings of the execution quality analysis.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::collections::{BTreeMap, HashMap};
+
+use growin_core::candles::Candles;
+use growin_core::execution::{execution_quality, ExecutionFill, QualitySummary, Slippage};
+use growin_core::risk::Side;
+
+use crate::candles::PyCandles;
+use crate::errors::{check_period, optional_field, required_field};
+
+fn set_slippage(d: &Bound<'_, PyDict>, benchmark: &str, s: &Slippage) -> PyResult<()> {
+    d.set_item(format!("cost_vs_{}", benchmark), s.cost)?;
+    d.set_item(format!("bps_vs_{}", benchmark), s.bps())
+}
+
+fn summary_to_dict<'py>(py: Python<'py>, s: &QualitySummary) -> PyResult<Bound<'py, PyDict>> {
+    let d = PyDict::new(py);
+    d.set_item("fills", s.fills)?;
+    d.set_item("notional", s.notional)?;
+    set_slippage(&d, "arrival", &s.vs_arrival)?;
+    set_slippage(&d, "vwap", &s.vs_vwap)?;
+    set_slippage(&d, "close", &s.vs_close)?;
+    Ok(d)
+}
+
+fn summaries_to_dict<'py>(py: Python<'py>, groups: &BTreeMap<String, QualitySummary>) -> PyResult<Bound<'py, PyDict>> {
+    let out = PyDict::new(py);
+    for (key, s) in groups {
+        out.set_item(key, summary_to_dict(py, s)?)?;
+    }
+    Ok(out)
+}
+
+/// What execution is costing: fill slippage against arrival price, VWAP
+/// and close.
+///
+/// Each fill is matched to the last bar of its ticker opening at or before
+/// it. Costs are positive when the fill was worse than the benchmark (paid
+/// more on a buy, received less on a sell), in the fills' price currency.
+///
+/// Args:
+///     fills (List[dict]): {"ticker", "timestamp" (epoch seconds), "side"
+///         ("buy" | "sell"), "quantity", "price", "arrival_price"
+///         (optional; defaults to the bar's open)}.
+///     candles (Dict[str, Dict[str, List[float]]]): ticker -> OHLCV with
+///         timestamps of bar opens.
+///     vwap_bars (int): Bars, ending at the fill's bar, that the interval
+///         VWAP covers (default 1). Without volume the typical prices are
+///         averaged.
+///
+/// Returns:
+///     Dict: {"fills": [{"arrival_price", "vwap", "close", "cost_vs_*",
+///     "bps_vs_*"}] in input order, "by_ticker": {ticker: summary},
+///     "by_month": {"YYYY-MM" (UTC): summary}, "total": summary}, each
+///     summary {"fills", "notional", "cost_vs_*", "bps_vs_*"} for * in
+///     arrival, vwap and close. Summary bps weight fills by value.
+#[pyfunction]
+#[pyo3(name = "execution_quality", signature = (fills, candles, vwap_bars=1))]
+pub fn py_execution_quality<'py>(
+    py: Python<'py>,
+    fills: Vec<Bound<'py, PyDict>>,
+    candles: HashMap<String, PyCandles>,
+    vwap_bars: usize,
+) -> PyResult<Bound<'py, PyDict>> {
+    check_period("vwap_bars", vwap_bars)?;
+    let fills = fills
+        .iter()
+        .map(|f| {
+            let side: String = required_field(f, "fill", "side")?;
+            Ok(ExecutionFill {
+                ticker: required_field(f, "fill", "ticker")?,
+                timestamp: required_field(f, "fill", "timestamp")?,
+                side: side.parse::<Side>().map_err(PyValueError::new_err)?,
+                quantity: required_field(f, "fill", "quantity")?,
+                price: required_field(f, "fill", "price")?,
+                arrival_price: optional_field(f, "arrival_price")?,
+            })
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    let candles: HashMap<String, Candles> = candles.into_iter().map(|(t, c)| (t, c.candles)).collect();
+    let report = py
+        .allow_threads(|| execution_quality(&fills, &candles, vwap_bars))
+        .map_err(PyValueError::new_err)?;
+
+    let per_fill = report
+        .fills
+        .iter()
+        .map(|q| {
+            let d = PyDict::new(py);
+            d.set_item("arrival_price", q.arrival_price)?;
+            d.set_item("vwap", q.vwap)?;
+            d.set_item("close", q.close)?;
+            set_slippage(&d, "arrival", &q.vs_arrival)?;
+            set_slippage(&d, "vwap", &q.vs_vwap)?;
+            set_slippage(&d, "close", &q.vs_close)?;
+            Ok(d)
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    let out = PyDict::new(py);
+    out.set_item("fills", per_fill)?;
+    out.set_item("by_ticker", summaries_to_dict(py, &report.by_ticker)?)?;
+    out.set_item("by_month", summaries_to_dict(py, &report.by_month)?)?;
+    out.set_item("total", summary_to_dict(py, &report.total)?)?;
+    Ok(out)
+}
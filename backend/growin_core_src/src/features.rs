@@ -0,0 +1,98 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyString};
+
+use growin_core::features::{build_features, FeatureKind, FeatureSpec};
+
+use crate::candles::PyCandles;
+use crate::{config, errors};
+
+/// Reads an integer parameter from an optional params dict.
+pub fn param_usize(params: Option<&Bound<'_, PyDict>>, key: &str, default: usize) -> PyResult<usize> {
+    match params.map(|p| p.get_item(key)).transpose()?.flatten() {
+        Some(value) => value.extract(),
+        None => Ok(default),
+    }
+}
+
+/// Like [`param_usize`] but rejects zero, which no window length accepts.
+pub fn param_period(params: Option<&Bound<'_, PyDict>>, key: &str, default: usize) -> PyResult<usize> {
+    let value = param_usize(params, key, default)?;
+    errors::check_period(key, value)?;
+    Ok(value)
+}
+
+/// Parses one spec entry: either a bare name (`"rsi"`) or a
+/// `(name, params)` tuple such as `("rsi", {"period": 14, "shift": 1})`.
+fn parse_feature_spec(item: &Bound<'_, PyAny>) -> PyResult<FeatureSpec> {
+    let (kind_name, params): (String, Option<Bound<'_, PyDict>>) = if item.is_instance_of::<PyString>() {
+        (item.extract()?, None)
+    } else {
+        item.extract()?
+    };
+    let p = params.as_ref();
+
+    let kind = match kind_name.to_lowercase().as_str() {
+        "return" | "returns" => FeatureKind::Return { lag: param_period(p, "lag", 1)? },
+        "log_return" => FeatureKind::LogReturn { lag: param_period(p, "lag", 1)? },
+        "rsi" => FeatureKind::Rsi { period: param_period(p, "period", 14)? },
+        "macd_hist" | "macd" => FeatureKind::MacdHist {
+            fast: param_period(p, "fast", 12)?,
+            slow: param_period(p, "slow", 26)?,
+            signal: param_period(p, "signal", 9)?,
+        },
+        "volatility" => FeatureKind::Volatility { period: param_period(p, "period", 20)? },
+        "vol_ratio" => FeatureKind::VolRatio {
+            fast: param_period(p, "fast", 5)?,
+            slow: param_period(p, "slow", 20)?,
+        },
+        "dist_sma" => FeatureKind::DistSma { period: param_period(p, "period", 50)? },
+        "dist_ema" => FeatureKind::DistEma { period: param_period(p, "period", 50)? },
+        "volume_ratio" => FeatureKind::VolumeRatio { period: param_period(p, "period", 20)? },
+        "range" => FeatureKind::Range,
+        "gap" => FeatureKind::Gap,
+        other => return Err(PyValueError::new_err(format!("Unknown feature '{}'", other))),
+    };
+
+    let shift = param_usize(p, "shift", 0)?;
+    let name = match p.map(|d| d.get_item("name")).transpose()?.flatten() {
+        Some(name) => name.extract()?,
+        None if shift > 0 => format!("{}_shift{}", kind.default_name(), shift),
+        None => kind.default_name(),
+    };
+
+    Ok(FeatureSpec { kind, shift, name })
+}
+
+/// Build a feature matrix for the ML layer in a single call.
+///
+/// Args:
+///     candles (Mapping[str, List[float]]): Columns `close` (required) and
+///         optionally `open`, `high`, `low`, `volume`.
+///     feature_spec (List[str | Tuple[str, dict]]): Features to compute, e.g.
+///         `[("return", {"lag": 1, "shift": 1}), ("rsi", {"period": 14}), "macd_hist"]`.
+///         Supported: return, log_return, rsi, macd_hist, volatility, vol_ratio,
+///         dist_sma, dist_ema, volume_ratio, range, gap. Every feature accepts `shift`
+///         (lag in bars) and `name` (column name override).
+///
+/// Returns:
+///     Tuple[List[str], List[List[float]]]: (column names, row-major matrix with
+///     one row per bar). Warm-up cells are NaN.
+#[pyfunction]
+#[pyo3(name = "build_features")]
+pub fn py_build_features(
+    py: Python<'_>,
+    candles: PyCandles,
+    feature_spec: Vec<Bound<'_, PyAny>>,
+) -> PyResult<(Vec<String>, Vec<Vec<f64>>)> {
+    let specs = feature_spec.iter().map(parse_feature_spec).collect::<PyResult<Vec<_>>>()?;
+    let ddof = config::current().ddof();
+    let rows: Vec<Vec<f64>> = py
+        .allow_threads(|| {
+            let columns = build_features(&candles, &specs, ddof)?;
+            Ok::<_, String>((0..candles.len()).map(|i| columns.iter().map(|col| col[i]).collect()).collect())
+        })
+        .map_err(PyValueError::new_err)?;
+    let names = specs.into_iter().map(|s| s.name).collect();
+    Ok((names, rows))
+}
@@ -0,0 +1,215 @@
+//! The `Instrument` reference-data class.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use rust_decimal::Decimal;
+
+use growin_core::instrument;
+
+use crate::errors::{optional_field, required_field};
+
+/// Trading212 quantities go down to this many decimal places.
+const T212_LOT_SIZE: Decimal = Decimal::from_parts(1, 0, 0, false, 8);
+
+/// A tradable instrument: identifiers, quote currency and trading limits.
+///
+/// Build one with `Instrument.from_t212` from Trading212's instrument
+/// metadata or `Instrument.from_provider` from a data provider's quote info,
+/// then pass it to `normalize_ticker`, `validate_order`, `exposure_report`
+/// (as a metadata value) or `uk_trade_costs` in place of loose dicts.
+///
+/// Args:
+///     symbol (str): Symbol as the source spells it.
+///     currency (str): Quote currency (ISO 4217; "GBX" for pence).
+///     type (str): "stock" (default), "etf", "etp", "fund", "crypto", ...
+///     isin (str, optional), name (str, optional), exchange (str, optional).
+///     lot_size (Decimal): Smallest quantity increment (default 1).
+///     tick_size (Decimal, optional): Smallest price increment.
+///     min_quantity (Decimal, optional): Smallest order (default `lot_size`).
+///     max_open_quantity (Decimal, optional): Largest position allowed.
+#[pyclass(module = "growin_core", frozen)]
+#[derive(Clone, Debug)]
+pub struct Instrument {
+    pub inner: instrument::Instrument,
+}
+
+impl Instrument {
+    /// The fields `exposure_report` and `SectorMap.classify` read from
+    /// metadata dicts.
+    pub fn metadata_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let d = PyDict::new(py);
+        d.set_item("currency", &self.inner.currency)?;
+        d.set_item("instrument_type", &self.inner.kind)?;
+        d.set_item("type", &self.inner.kind)?;
+        d.set_item("name", self.inner.name.as_deref())?;
+        Ok(d)
+    }
+}
+
+#[pymethods]
+impl Instrument {
+    #[new]
+    #[pyo3(signature = (
+        symbol, currency, r#type="stock", isin=None, name=None, exchange=None, lot_size=Decimal::ONE, tick_size=None,
+        min_quantity=None, max_open_quantity=None
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        symbol: String,
+        currency: &str,
+        r#type: &str,
+        isin: Option<String>,
+        name: Option<String>,
+        exchange: Option<String>,
+        lot_size: Decimal,
+        tick_size: Option<Decimal>,
+        min_quantity: Option<Decimal>,
+        max_open_quantity: Option<Decimal>,
+    ) -> PyResult<Self> {
+        if symbol.trim().is_empty() {
+            return Err(PyValueError::new_err("symbol must not be empty"));
+        }
+        if lot_size <= Decimal::ZERO || tick_size.is_some_and(|t| t <= Decimal::ZERO) {
+            return Err(PyValueError::new_err("lot_size and tick_size must be positive"));
+        }
+        // Pence quotes keep their case-sensitive "GBp" spelling as GBX.
+        let currency = if currency == "GBp" { "GBX".to_string() } else { currency.to_ascii_uppercase() };
+        Ok(Instrument {
+            inner: instrument::Instrument {
+                symbol,
+                isin,
+                name,
+                currency,
+                exchange,
+                kind: instrument::Instrument::canonical_kind(r#type),
+                lot_size,
+                tick_size,
+                min_quantity: min_quantity.unwrap_or(lot_size),
+                max_open_quantity,
+            },
+        })
+    }
+
+    /// From an entry of Trading212's `/equity/metadata/instruments`
+    /// ("ticker", "type", "isin", "currencyCode", "name", "minTradeQuantity",
+    /// "maxOpenQuantity"). Quantities are fractional to 8 places.
+    #[staticmethod]
+    fn from_t212(data: &Bound<'_, PyDict>) -> PyResult<Self> {
+        let kind: Option<String> = optional_field(data, "type")?;
+        let currency: String = required_field(data, "instrument", "currencyCode")?;
+        Instrument::new(
+            required_field(data, "instrument", "ticker")?,
+            &currency,
+            kind.as_deref().unwrap_or("stock"),
+            optional_field(data, "isin")?,
+            optional_field(data, "name")?,
+            None,
+            T212_LOT_SIZE,
+            None,
+            optional_field(data, "minTradeQuantity")?,
+            optional_field(data, "maxOpenQuantity")?,
+        )
+    }
+
+    /// From provider quote info such as yfinance's `Ticker.info` ("symbol",
+    /// "currency", "quoteType", "longName" or "shortName", "exchange",
+    /// "isin"). Whole shares only.
+    #[staticmethod]
+    fn from_provider(data: &Bound<'_, PyDict>) -> PyResult<Self> {
+        let kind: Option<String> = optional_field(data, "quoteType")?;
+        let currency: String = required_field(data, "instrument", "currency")?;
+        let name = match optional_field(data, "longName")? {
+            Some(name) => Some(name),
+            None => optional_field(data, "shortName")?,
+        };
+        Instrument::new(
+            required_field(data, "instrument", "symbol")?,
+            &currency,
+            kind.as_deref().unwrap_or("stock"),
+            optional_field(data, "isin")?,
+            name,
+            optional_field(data, "exchange")?,
+            Decimal::ONE,
+            None,
+            None,
+            None,
+        )
+    }
+
+    #[getter]
+    fn symbol(&self) -> &str {
+        &self.inner.symbol
+    }
+
+    /// The symbol as `normalize_ticker` resolves it.
+    #[getter]
+    fn normalized_symbol(&self) -> String {
+        self.inner.normalized_symbol()
+    }
+
+    #[getter]
+    fn isin(&self) -> Option<&str> {
+        self.inner.isin.as_deref()
+    }
+
+    #[getter]
+    fn name(&self) -> Option<&str> {
+        self.inner.name.as_deref()
+    }
+
+    #[getter]
+    fn currency(&self) -> &str {
+        &self.inner.currency
+    }
+
+    #[getter]
+    fn exchange(&self) -> Option<&str> {
+        self.inner.exchange.as_deref()
+    }
+
+    #[getter]
+    fn r#type(&self) -> &str {
+        &self.inner.kind
+    }
+
+    #[getter]
+    fn lot_size(&self) -> Decimal {
+        self.inner.lot_size.normalize()
+    }
+
+    #[getter]
+    fn tick_size(&self) -> Option<Decimal> {
+        self.inner.tick_size.map(|t| t.normalize())
+    }
+
+    #[getter]
+    fn min_quantity(&self) -> Decimal {
+        self.inner.min_quantity.normalize()
+    }
+
+    #[getter]
+    fn max_open_quantity(&self) -> Option<Decimal> {
+        self.inner.max_open_quantity.map(|q| q.normalize())
+    }
+
+    /// The fields as a dict, keyed like the constructor's arguments.
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let d = PyDict::new(py);
+        d.set_item("symbol", self.symbol())?;
+        d.set_item("currency", self.currency())?;
+        d.set_item("type", self.r#type())?;
+        d.set_item("isin", self.isin())?;
+        d.set_item("name", self.name())?;
+        d.set_item("exchange", self.exchange())?;
+        d.set_item("lot_size", self.lot_size())?;
+        d.set_item("tick_size", self.tick_size())?;
+        d.set_item("min_quantity", self.min_quantity())?;
+        d.set_item("max_open_quantity", self.max_open_quantity())?;
+        Ok(d)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Instrument(symbol='{}', currency='{}', type='{}')", self.inner.symbol, self.inner.currency, self.inner.kind)
+    }
+}
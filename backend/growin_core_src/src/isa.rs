@@ -0,0 +1,100 @@
+//! Python bindings of the ISA allowance tracking.
+
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyDate, PyDateAccess, PyDict};
+use rust_decimal::Decimal;
+
+use growin_core::calendar::Date;
+use growin_core::isa::{isa_allowance, tax_year_label, Contribution, IsaYear};
+use growin_core::money::round;
+
+/// The annual ISA allowance since 2017/18.
+const ISA_ALLOWANCE: Decimal = Decimal::from_parts(20_000, 0, 0, false, 0);
+
+/// A `datetime.date`/`datetime.datetime` or an ISO "YYYY-MM-DD" string as
+/// a calendar date.
+pub fn civil_date(value: &Bound<'_, PyAny>) -> PyResult<Date> {
+    if let Ok(date) = value.downcast::<PyDate>() {
+        return Date::new(date.get_year(), u32::from(date.get_month()), u32::from(date.get_day())).map_err(PyValueError::new_err);
+    }
+    let Ok(text) = value.extract::<String>() else {
+        return Err(PyTypeError::new_err("dates must be datetime.date objects or 'YYYY-MM-DD' strings"));
+    };
+    let invalid = || PyValueError::new_err(format!("invalid date '{}', expected 'YYYY-MM-DD'", text));
+    let mut parts = text.get(..10).ok_or_else(invalid)?.splitn(3, '-');
+    let mut next = || parts.next().and_then(|p| p.parse::<u32>().ok()).ok_or_else(invalid);
+    let (year, month, day) = (next()?, next()?, next()?);
+    Date::new(year as i32, month, day).map_err(|_| invalid())
+}
+
+fn year_to_dict<'py>(py: Python<'py>, year: &IsaYear, allowance: Decimal, dp: Option<u32>) -> PyResult<Bound<'py, PyDict>> {
+    let d = PyDict::new(py);
+    d.set_item("tax_year", tax_year_label(year.tax_year))?;
+    d.set_item("allowance", round(allowance, dp))?;
+    d.set_item("used", round(year.subscribed, dp))?;
+    d.set_item("remaining", round(year.remaining.max(Decimal::ZERO), dp))?;
+    d.set_item("over_subscribed", round((-year.remaining).max(Decimal::ZERO), dp))?;
+    d.set_item("withdrawn", round(year.withdrawn, dp))?;
+    d.set_item("replaced", round(year.replaced, dp))?;
+    d.set_item("replaceable", round(year.replaceable, dp))?;
+    Ok(d)
+}
+
+/// ISA allowance used and remaining per UK tax year (6 April - 5 April).
+///
+/// Args:
+///     contributions (List[Tuple[date | str, Decimal]]): (date, amount) pairs;
+///         deposits are positive, withdrawals negative. Dates are
+///         `datetime.date` objects or "YYYY-MM-DD" strings, in UK time.
+///     tax_year (int, optional): Start year of a single tax year to report
+///         (2024 for 2024/25).
+///     flexible (bool): Flexible ISA (default True, as at Trading212): money
+///         withdrawn can be paid back in the same tax year without using
+///         allowance.
+///     allowance (Decimal): Annual allowance (default 20000).
+///     dp (int, optional): Decimal places to round to (default 2).
+///
+/// Returns:
+///     Dict: {"tax_year": "2024/25", "allowance", "used", "remaining",
+///     "over_subscribed", "withdrawn", "replaced", "replaceable"} for
+///     `tax_year`, or {label: that dict} for every year with contributions.
+#[pyfunction]
+#[pyo3(
+    name = "isa_allowance_report",
+    signature = (contributions, tax_year=None, flexible=true, allowance=ISA_ALLOWANCE, dp=Some(2))
+)]
+pub fn py_isa_allowance_report<'py>(
+    py: Python<'py>,
+    contributions: Vec<(Bound<'py, PyAny>, Decimal)>,
+    tax_year: Option<i32>,
+    flexible: bool,
+    allowance: Decimal,
+    dp: Option<u32>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let contributions = contributions
+        .iter()
+        .map(|(date, amount)| {
+            let Date { year, month, day } = civil_date(date)?;
+            Ok(Contribution { year, month, day, amount: *amount })
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    let years = py.allow_threads(|| isa_allowance(&contributions, allowance, flexible));
+
+    if let Some(ty) = tax_year {
+        let year = years.into_iter().find(|y| y.tax_year == ty).unwrap_or(IsaYear {
+            tax_year: ty,
+            subscribed: Decimal::ZERO,
+            withdrawn: Decimal::ZERO,
+            replaced: Decimal::ZERO,
+            replaceable: Decimal::ZERO,
+            remaining: allowance,
+        });
+        return year_to_dict(py, &year, allowance, dp);
+    }
+    let out = PyDict::new(py);
+    for year in &years {
+        out.set_item(tax_year_label(year.tax_year), year_to_dict(py, year, allowance, dp)?)?;
+    }
+    Ok(out)
+}
@@ -1,376 +1,441 @@
 use pyo3::prelude::*;
 
+mod aio;
+mod alerts;
+mod allocation;
+mod batch;
+mod cache;
+mod candles;
+mod chunked;
+mod config;
+mod dividends;
+mod errors;
+mod execution;
+mod features;
+mod instrument;
+mod isa;
+mod logging;
+mod microstructure;
+mod ml;
+mod money;
+mod orders;
+mod portfolio;
+mod profile;
+mod regime;
+mod results;
+mod risk;
+mod rules;
+mod scaling;
+mod scoring;
+mod screening;
+mod search;
+mod sectors;
+mod series;
+mod signals;
+mod stats;
+mod store;
+mod streaming;
+mod technicals;
+
+// Leading `::` because the `growin_core` module function below shadows the
+// core crate name here.
+use ::growin_core::lanes::Real;
+use ::growin_core::{indicators, ticker};
+use config::Config;
+use results::{BbandsResult, MacdResult};
+use series::{dispatch_series, dispatch_series_into, Series};
+
 /// SOTA Ticker Normalization: Resolves discrepancies between Trading212, 
-/// Yahoo Finance, Alpaca, and Finnhub.
+/// Yahoo Finance, Alpaca, and Finnhub. Accepts a symbol or an `Instrument`.
 #[pyfunction]
-fn normalize_ticker(ticker: String) -> PyResult<String> {
-    if ticker.is_empty() {
-        return Ok("".to_string());
-    }
-
-    // 1. Basic Cleaning
-    let mut normalized = ticker.to_uppercase().trim().replace("$", "");
-    
-    // 2. Already Normalized (contains dot)
-    if normalized.contains('.') {
-        return Ok(normalized);
-    }
-
-    // 3. Handle Platform-Specific Artifacts
-    let original = normalized.clone();
-    
-    // Strip T212 suffixes
-    let suffixes = ["_EQ", "_US", "_BE", "_DE", "_GB", "_FR", "_NL", "_ES", "_IT"];
-    loop {
-        let mut changed = false;
-        for s in suffixes.iter() {
-            if normalized.ends_with(s) {
-                normalized = normalized[..normalized.len() - s.len()].to_string();
-                changed = true;
-            }
-        }
-        if !changed { break; }
-    }
-    normalized = normalized.replace("_", "");
-
-    // 4. SPECIAL MAPPINGS
-    let special_mappings = [
-        ("SSLNL", "SSLN"), ("SGLNL", "SGLN"), ("3GLD", "3GLD"), ("SGLN", "SGLN"),
-        ("PHGP", "PHGP"), ("PHAU", "PHAU"), ("3LTS", "3LTS"), ("3USL", "3USL"),
-        ("LLOY1", "LLOY"), ("VOD1", "VOD"), ("BARC1", "BARC"), ("TSCO1", "TSCO"),
-        ("BPL1", "BP"), ("BPL", "BP"), ("AZNL1", "AZN"), ("AZNL", "AZN"),
-        ("SGLN1", "SGLN"), ("MAG5", "MAG5"), ("MAG5L", "MAG5"), ("MAG7", "MAG7"),
-        ("MAG7L", "MAG7"), ("GLD3", "GLD3"), ("3UKL", "3UKL"), ("5QQQ", "5QQQ"),
-        ("TSL3", "TSL3"), ("NVD3", "NVD3"), ("AVL", "AV"), ("UUL", "UU"),
-        ("BAL", "BA"), ("SLL", "SL"), ("AU", "AUT"), ("RBL", "RKT"), ("MICCL", "MICC")
-    ];
-    
-    for (k, v) in special_mappings.iter() {
-        if normalized == *k {
-            normalized = v.to_string();
-            break;
-        }
-    }
-
-    // 5. Suffix Protection for Leveraged Products
-    let stems = ["LLOY", "BARC", "VOD", "HSBA", "TSCO", "BP", "AZN", "RR", "NG", "SGLN", "SSLN", "GSK", "SHELL", "BATS", "AHT", "NWG", "GLEN"];
-    if normalized.ends_with('1') && normalized.len() > 3 {
-        let stem_check = normalized[..normalized.len()-1].to_string();
-        if stems.contains(&stem_check.as_str()) {
-            normalized = stem_check;
-        }
-    }
-
-    // 6. Global Exchange Logic
-    let us_exclusions = [
-        "AAPL", "MSFT", "GOOG", "AMZN", "NVDA", "TSLA", "META", "NFLX",
-        "AMD", "INTC", "PYPL", "ADBE", "CSCO", "PEP", "COST", "AVGO", "QCOM", "TXN",
-        "ORCL", "CRM", "IBM", "UBER", "ABNB", "SNOW", "PLTR", "SQ", "SHOP", "SPOT",
-        "GOOGL", "SMCI", "MSTR", "COIN", "HOOD", "ARM", "DKNG", "SOFI", "MARA", "RIOT",
-        "CRWD", "PANW", "NET", "DDOG", "ZS", "TEAM", "MDB", "OKTA", "DOCU",
-        "JPM", "BAC", "WFC", "C", "GS", "MS", "BLK", "AXP", "V", "MA", "COF", "USB",
-        "CAT", "DE", "GE", "GM", "F", "BA", "LMT", "RTX", "HON", "UPS", "FDX", "UNP", "MMM",
-        "WMT", "TGT", "HD", "LOW", "MCD", "SBUX", "NKE", "KO", "PEP", "PG", "CL", "MO", "PM", "DIS", "CMCSA",
-        "JNJ", "PFE", "MRK", "ABBV", "LLY", "UNH", "CVS", "AMGN", "GILD", "BMY", "ISRG", "TMO", "ABT", "DHR",
-        "XOM", "CVX", "COP", "SLB", "EOG", "OXY", "KMI", "HAL", "T", "VZ", "TMUS",
-        "SPY", "QQQ", "DIA", "IWM", "IVV", "VOO", "VTI", "GLD", "SLV", "ARKK", "SMH", "XLF", "XLE", "XLK", "XLV",
-        "F", "T", "C", "V", "Z", "O", "D", "R", "K", "X", "S", "M", "A", "G"
-    ];
-
-    let is_explicit_uk = original.contains("_EQ") && !original.contains("_US");
-    let mut is_likely_uk = (normalized.len() <= 3 || (normalized.len() <= 5 && normalized.ends_with('L'))) && !us_exclusions.contains(&normalized.as_str());
-    
-    // Additional check: 4-char tickers not ending in L are likely US (unless explicit EQ)
-    if normalized.len() == 4 && !normalized.ends_with('L') {
-        is_likely_uk = false;
-    }
-
-    // Force likelihood for known UK stems
-    if stems.iter().any(|&s| normalized.starts_with(s)) {
-        is_likely_uk = true;
-    }
-
-    if is_likely_uk && normalized.ends_with('L') && normalized.len() > 3 && !us_exclusions.contains(&normalized.as_str()) {
-        normalized.pop();
+fn normalize_ticker(ticker: &Bound<'_, PyAny>) -> PyResult<String> {
+    let _span = profile::span("normalize_ticker");
+    if let Ok(instrument) = ticker.downcast::<instrument::Instrument>() {
+        return Ok(instrument.get().inner.normalized_symbol());
     }
-
-    let is_leveraged = (normalized.starts_with('3') || normalized.starts_with('5') || normalized.starts_with('7')) ||
-                       (normalized.ends_with('2') || normalized.ends_with('3') || normalized.ends_with('5') || normalized.ends_with('7'));
-
-    if is_explicit_uk || is_likely_uk || is_leveraged {
-        if !normalized.ends_with(".L") && !normalized.contains('.') {
-            return Ok(format!("{}.L", normalized));
-        }
-    }
-
-    Ok(normalized)
+    Ok(ticker::normalize_ticker(&ticker.extract::<String>()?))
 }
 
 /// Calculate Relative Strength Index (RSI).
 /// 
 /// Args:
-///     prices (List[float]): List of closing prices.
+///     prices (List[float] | np.ndarray): Closing prices. float32/float64 arrays
+///         are computed in their own dtype and returned as arrays.
 ///     period (int): Lookback period (default 14).
 /// 
 /// Returns:
-///     List[float]: RSI values (aligned with input, first `period` are 50.0).
+///     List[float] | np.ndarray: RSI values (aligned with input, first `period`
+///     are 50.0, or NaN under `Config(padding="nan")`).
+///
+/// Raises:
+///     InvalidPeriodError: If `period` is 0.
+///     InsufficientDataError: If `prices` is non-empty but entirely NaN.
 #[pyfunction]
 #[pyo3(signature = (prices, period=14))]
-fn calculate_rsi(prices: Vec<f64>, period: usize) -> PyResult<Vec<f64>> {
-    if prices.len() < period {
-        return Ok(vec![50.0; prices.len()]);
-    }
-
-    let mut rsi_values = Vec::with_capacity(prices.len());
-    let mut gains = Vec::with_capacity(prices.len());
-    let mut losses = Vec::with_capacity(prices.len());
-
-    // Calculate diffs
-    let mut diffs = Vec::with_capacity(prices.len());
-    diffs.push(0.0); // First element has no diff
-    for i in 1..prices.len() {
-        diffs.push(prices[i] - prices[i-1]);
-    }
-
-    // Initialize with 50.0 for the warm-up period
-    for _ in 0..period {
-        rsi_values.push(50.0);
-        gains.push(0.0);
-        losses.push(0.0);
-    }
-
-    // Initial average gain/loss
-    let mut avg_gain = 0.0;
-    let mut avg_loss = 0.0;
-    
-    // We need at least 'period' changes, so 'period + 1' prices.
-    let warmup_end = if diffs.len() > period { period } else { diffs.len() - 1 };
-    
-    for i in 1..=warmup_end {
-        let change = diffs[i];
-        if change > 0.0 {
-            avg_gain += change;
-        } else {
-            avg_loss += change.abs();
-        }
-    }
-    
-    let denominator = if warmup_end > 0 { warmup_end as f64 } else { 1.0 };
-    avg_gain /= denominator;
-    avg_loss /= denominator;
-
-    // First RSI calculation (at index `period`)
-    // Actually standard RSI usually starts validity `period` indices in.
-    // We already pushed `period` placeholders.
-
-    // Calculate subsequent values using Wilder's Smoothing
-    for i in period..prices.len() {
-        let change = diffs[i];
-        let (gain, loss) = if change > 0.0 {
-            (change, 0.0)
-        } else {
-            (0.0, change.abs())
-        };
-
-        // Wilder's Smoothing
-        // smoothed_gain = (previous_gain * (period - 1) + current_gain) / period
-        
-        // Note: For the very first point after warm up, we use the simple average we just calculated?
-        // Standard Algo:
-        // first Avg Gain = Sum of gains over past 14 periods / 14.
-        // subsequent Avg Gain = ((previous Avg Gain) * 13 + current Gain) / 14.
-        
-        if i == period {
-            // This is the first calculated point, utilizing the simple average of the *previous* 14 candles
-            // Wait, standard RSI(14) needs 15 data points to produce the first value?
-        } else {
-             avg_gain = ((avg_gain * (period as f64 - 1.0)) + gain) / period as f64;
-             avg_loss = ((avg_loss * (period as f64 - 1.0)) + loss) / period as f64;
-        }
-
-        let rs = if avg_loss == 0.0 {
-            100.0 
-        } else {
-            avg_gain / avg_loss
-        };
-
-        let rsi = 100.0 - (100.0 / (1.0 + rs));
-        rsi_values.push(rsi);
-    }
-    
-    // Fill remaining if any mismatch or ensure size match?
-    // The loop runs from `period` to `prices.len()`.
-    // The `rsi_values` started with `period` elements.
-    // So final length is `period + (prices.len() - period) = prices.len()`. Correct.
-
-    Ok(rsi_values)
+fn calculate_rsi(py: Python<'_>, prices: Series<'_>, period: usize) -> PyResult<PyObject> {
+    let _span = profile::span("calculate_rsi");
+    errors::check_period("period", period)?;
+    prices.check_has_data("prices")?;
+    let cfg = config::current();
+    dispatch_series!(py, prices, cached("rsi", [period, cfg.cache_key()]), |p| {
+        let mut out = indicators::rsi(p, period);
+        cfg.pad(&mut out, period);
+        out
+    })
 }
 
 /// Calculate Simple Moving Average (SMA).
 #[pyfunction]
 #[pyo3(signature = (data, period=20))]
-fn calculate_sma(data: Vec<f64>, period: usize) -> PyResult<Vec<f64>> {
-    let mut sma = Vec::with_capacity(data.len());
-    let mut sum = 0.0;
-
-    for i in 0..data.len() {
-        sum += data[i];
-        if i >= period {
-            sum -= data[i - period];
-            sma.push(sum / period as f64);
-        } else if i == period - 1 {
-            sma.push(sum / period as f64);
-        } else {
-            sma.push(0.0); // Padding
-        }
-    }
-    Ok(sma)
+fn calculate_sma(py: Python<'_>, data: Series<'_>, period: usize) -> PyResult<PyObject> {
+    let _span = profile::span("calculate_sma");
+    errors::check_period("period", period)?;
+    data.check_has_data("data")?;
+    let cfg = config::current();
+    dispatch_series!(py, data, cached("sma", [period, cfg.cache_key()]), |d| {
+        let mut out = indicators::sma(d, period);
+        cfg.pad(&mut out, period - 1);
+        out
+    })
 }
 
 /// Calculate Exponential Moving Average (EMA).
 #[pyfunction]
 #[pyo3(signature = (data, period=14))]
-fn calculate_ema(data: Vec<f64>, period: usize) -> PyResult<Vec<f64>> {
-    if data.is_empty() {
-        return Ok(vec![]);
-    }
-    
-    let mut ema = Vec::with_capacity(data.len());
-    let k = 2.0 / (period as f64 + 1.0);
-    
-    // First value is usually SMA of first 'period' elements, or just the first price if period=1?
-    // Commonly initialized with First Price or SMA.
-    // Let's use SMA of first `period` if enough data, else first price.
-    
-    let start_idx = if data.len() >= period { period - 1 } else { 0 };
-    
-    // Padding with NaNs or 0s until valid? 
-    // Pandas TA typically produces NaNs. We use 0.0 for simplicity in this context or handle in Python.
-    // To match previous SMA behavior (padding 0.0), we pad.
-    
-    for _ in 0..start_idx {
-        ema.push(0.0);
-    }
+fn calculate_ema(py: Python<'_>, data: Series<'_>, period: usize) -> PyResult<PyObject> {
+    let _span = profile::span("calculate_ema");
+    errors::check_period("period", period)?;
+    data.check_has_data("data")?;
+    let cfg = config::current();
+    dispatch_series!(py, data, cached("ema", [period, cfg.cache_key()]), |d| {
+        let mut out = indicators::ema(d, period);
+        cfg.pad(&mut out, period - 1);
+        out
+    })
+}
 
-    let mut current_ema = if start_idx < data.len() {
-        // Calculate SMA for the first valid point
-        let sum: f64 = data[0..=start_idx].iter().sum();
-        sum / (start_idx + 1) as f64
-    } else {
-        data[0]
-    };
-    
-    if start_idx < data.len() {
-        ema.push(current_ema);
-        
-        for i in (start_idx + 1)..data.len() {
-            current_ema = (data[i] * k) + (current_ema * (1.0 - k));
-            ema.push(current_ema);
-        }
-    }
+fn check_macd_periods(fast: usize, slow: usize, signal: usize) -> PyResult<()> {
+    errors::check_period("fast", fast)?;
+    errors::check_period("slow", slow)?;
+    errors::check_period("signal", signal)
+}
 
-    Ok(ema)
+/// Pads the MACD line's warm-up and the signal line's (shared with the
+/// histogram) per the configured policy.
+fn pad_macd<T: Real>(cfg: &Config, fast: usize, slow: usize, signal: usize, line: &mut [T], signal_line: &mut [T], hist: &mut [T]) {
+    let seeded = fast.max(slow) - 1;
+    cfg.pad(line, seeded);
+    cfg.pad(signal_line, seeded + signal - 1);
+    cfg.pad(hist, seeded + signal - 1);
 }
 
 /// Calculate MACD (Moving Average Convergence Divergence).
-/// Returns tuple of (macd_line, signal_line, histogram)
+/// Returns a `MacdResult` (.macd, .signal, .histogram, .params, .valid_from)
+/// that also unpacks as (macd_line, signal_line, histogram).
 #[pyfunction]
 #[pyo3(signature = (data, fast=12, slow=26, signal=9))]
-fn calculate_macd(data: Vec<f64>, fast: usize, slow: usize, signal: usize) -> PyResult<(Vec<f64>, Vec<f64>, Vec<f64>)> {
-    // Helper to calculate EMA internally
-    let get_ema = |d: &Vec<f64>, p: usize| -> Vec<f64> {
-        let mut res = Vec::with_capacity(d.len());
-        let k = 2.0 / (p as f64 + 1.0);
-        
-        // Simple init: just use price as starts or 0 padding
-        // Replicating logic: Pad 0 until p-1, then SMA, then EMA.
-        for _ in 0..(p-1) {
-            res.push(0.0);
-        }
-        
-        if d.len() >= p {
-             let sum: f64 = d[0..p].iter().sum();
-             let mut curr = sum / p as f64;
-             res.push(curr);
-             
-             for i in p..d.len() {
-                 curr = (d[i] * k) + (curr * (1.0 - k));
-                 res.push(curr);
-             }
+fn calculate_macd(py: Python<'_>, data: Series<'_>, fast: usize, slow: usize, signal: usize) -> PyResult<MacdResult> {
+    let _span = profile::span("calculate_macd");
+    check_macd_periods(fast, slow, signal)?;
+    data.check_has_data("data")?;
+    let len = data.len();
+    let cfg = config::current();
+    let out = dispatch_series!(py, data, cached("macd", [fast, slow, signal, cfg.cache_key()]), |d| {
+        let (mut line, mut signal_line, mut hist) = indicators::macd(d, fast, slow, signal);
+        pad_macd(&cfg, fast, slow, signal, &mut line, &mut signal_line, &mut hist);
+        (line, signal_line, hist)
+    })?;
+    MacdResult::new(py, out, len, fast, slow, signal)
+}
+
+/// Calculate Bollinger Bands.
+/// Returns a `BbandsResult` (.upper, .middle, .lower, .params, .valid_from)
+/// that also unpacks as (upper, middle, lower).
+#[pyfunction]
+#[pyo3(signature = (data, period=20, std_dev=2.0))]
+fn calculate_bbands(py: Python<'_>, data: Series<'_>, period: usize, std_dev: f64) -> PyResult<BbandsResult> {
+    let _span = profile::span("calculate_bbands");
+    errors::check_period("period", period)?;
+    data.check_has_data("data")?;
+    let len = data.len();
+    let cfg = config::current();
+    let out = dispatch_series!(py, data, cached("bbands", [period, std_dev, cfg.cache_key()]), |d| {
+        let (mut upper, mut middle, mut lower) = indicators::bbands_ddof(d, period, std_dev, cfg.ddof());
+        for band in [&mut upper, &mut middle, &mut lower] {
+            cfg.pad(band, period - 1);
         }
-        res
-    };
+        (upper, middle, lower)
+    })?;
+    BbandsResult::new(py, out, len, period, std_dev)
+}
 
-    let ema_fast = get_ema(&data, fast);
-    let ema_slow = get_ema(&data, slow);
-    
-    let mut macd_line = Vec::with_capacity(data.len());
-    for i in 0..data.len() {
-        // Only valid if both are non-zero? Or simple subtraction
-        macd_line.push(ema_fast[i] - ema_slow[i]);
-    }
-    
-    // Signal line is EMA of MACD line
-    // BUT we need to ignore the initial zeros in calculation/padding
-    // Doing a "naive" EMA on the whole macd_line including leading zeros might skew it near start.
-    // However, for this SOTA implementation, let's keep it consistent.
-    let signal_line = get_ema(&macd_line, signal);
-    
-    let mut histogram = Vec::with_capacity(data.len());
-    for i in 0..data.len() {
-        histogram.push(macd_line[i] - signal_line[i]);
-    }
+// In-place variants: write into preallocated numpy buffers of the input's
+// dtype (float64 for lists) and length, avoiding per-call allocations.
 
-    Ok((macd_line, signal_line, histogram))
+/// RSI written into `out`.
+#[pyfunction]
+#[pyo3(signature = (prices, out, period=14))]
+fn calculate_rsi_into(py: Python<'_>, prices: Series<'_>, out: Bound<'_, PyAny>, period: usize) -> PyResult<()> {
+    let _span = profile::span("calculate_rsi_into");
+    errors::check_period("period", period)?;
+    prices.check_has_data("prices")?;
+    let cfg = config::current();
+    dispatch_series_into!(py, prices, [out], |p| {
+        indicators::rsi_into(p, period, out);
+        cfg.pad(out, period);
+    })
 }
 
-/// Calculate Bollinger Bands.
-/// Returns (upper, middle, lower)
+/// SMA written into `out`.
 #[pyfunction]
-#[pyo3(signature = (data, period=20, std_dev=2.0))]
-fn calculate_bbands(data: Vec<f64>, period: usize, std_dev: f64) -> PyResult<(Vec<f64>, Vec<f64>, Vec<f64>)> {
-    let mut upper = Vec::with_capacity(data.len());
-    let mut middle = Vec::with_capacity(data.len()); // This is SMA
-    let mut lower = Vec::with_capacity(data.len());
+#[pyo3(signature = (data, out, period=20))]
+fn calculate_sma_into(py: Python<'_>, data: Series<'_>, out: Bound<'_, PyAny>, period: usize) -> PyResult<()> {
+    let _span = profile::span("calculate_sma_into");
+    errors::check_period("period", period)?;
+    data.check_has_data("data")?;
+    let cfg = config::current();
+    dispatch_series_into!(py, data, [out], |d| {
+        indicators::sma_into(d, period, out);
+        cfg.pad(out, period - 1);
+    })
+}
 
-    for i in 0..data.len() {
-        if i < period - 1 {
-            upper.push(0.0);
-            middle.push(0.0);
-            lower.push(0.0);
-            continue;
-        }
+/// EMA written into `out`.
+#[pyfunction]
+#[pyo3(signature = (data, out, period=14))]
+fn calculate_ema_into(py: Python<'_>, data: Series<'_>, out: Bound<'_, PyAny>, period: usize) -> PyResult<()> {
+    let _span = profile::span("calculate_ema_into");
+    errors::check_period("period", period)?;
+    data.check_has_data("data")?;
+    let cfg = config::current();
+    dispatch_series_into!(py, data, [out], |d| {
+        indicators::ema_into(d, period, out);
+        cfg.pad(out, period - 1);
+    })
+}
 
-        // Slice window safely
-        let start_idx = (i + 1) - period;
-        let window = &data[start_idx..=i];
-        let sum: f64 = window.iter().sum();
-        let mean = sum / period as f64;
-        
-        let mut variance = 0.0;
-        for &x in window {
-             variance += (x - mean).powi(2);
-        }
-        variance /= period as f64;
-        let std = variance.sqrt();
-        
-        middle.push(mean);
-        upper.push(mean + (std_dev * std));
-        lower.push(mean - (std_dev * std));
-    }
+/// MACD written into `macd_out`, `signal_out` and `hist_out`.
+#[pyfunction]
+#[pyo3(signature = (data, macd_out, signal_out, hist_out, fast=12, slow=26, signal=9))]
+#[allow(clippy::too_many_arguments)]
+fn calculate_macd_into(
+    py: Python<'_>,
+    data: Series<'_>,
+    macd_out: Bound<'_, PyAny>,
+    signal_out: Bound<'_, PyAny>,
+    hist_out: Bound<'_, PyAny>,
+    fast: usize,
+    slow: usize,
+    signal: usize,
+) -> PyResult<()> {
+    let _span = profile::span("calculate_macd_into");
+    check_macd_periods(fast, slow, signal)?;
+    data.check_has_data("data")?;
+    let cfg = config::current();
+    dispatch_series_into!(py, data, [macd_out, signal_out, hist_out], |d| {
+        indicators::macd_into(d, fast, slow, signal, macd_out, signal_out, hist_out);
+        pad_macd(&cfg, fast, slow, signal, macd_out, signal_out, hist_out);
+    })
+}
 
-    Ok((upper, middle, lower))
+/// Bollinger Bands written into `upper`, `middle` and `lower`.
+#[pyfunction]
+#[pyo3(signature = (data, upper, middle, lower, period=20, std_dev=2.0))]
+fn calculate_bbands_into(
+    py: Python<'_>,
+    data: Series<'_>,
+    upper: Bound<'_, PyAny>,
+    middle: Bound<'_, PyAny>,
+    lower: Bound<'_, PyAny>,
+    period: usize,
+    std_dev: f64,
+) -> PyResult<()> {
+    let _span = profile::span("calculate_bbands_into");
+    errors::check_period("period", period)?;
+    data.check_has_data("data")?;
+    let cfg = config::current();
+    dispatch_series_into!(py, data, [upper, middle, lower], |d| {
+        indicators::bbands_ddof_into(d, period, std_dev, cfg.ddof(), upper, middle, lower);
+        for band in [&mut *upper, &mut *middle, &mut *lower] {
+            cfg.pad(band, period - 1);
+        }
+    })
 }
 
 
+
 /// A Python module implemented in Rust.
 #[pymodule]
 fn growin_core(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    errors::register(m)?;
+    logging::init(m.py())?;
     m.add_function(wrap_pyfunction!(normalize_ticker, m)?)?;
     m.add_function(wrap_pyfunction!(calculate_rsi, m)?)?;
     m.add_function(wrap_pyfunction!(calculate_sma, m)?)?;
     m.add_function(wrap_pyfunction!(calculate_ema, m)?)?;
     m.add_function(wrap_pyfunction!(calculate_macd, m)?)?;
     m.add_function(wrap_pyfunction!(calculate_bbands, m)?)?;
+    m.add_function(wrap_pyfunction!(technicals::py_calculate_atr, m)?)?;
+    m.add_function(wrap_pyfunction!(technicals::py_calculate_stochastic, m)?)?;
+    m.add_function(wrap_pyfunction!(technicals::py_calculate_adx, m)?)?;
+    m.add_function(wrap_pyfunction!(technicals::py_calculate_obv, m)?)?;
+    m.add_function(wrap_pyfunction!(technicals::py_calculate_vwap, m)?)?;
+    m.add_function(wrap_pyfunction!(technicals::py_calculate_cci, m)?)?;
+    m.add_function(wrap_pyfunction!(technicals::py_calculate_williams_r, m)?)?;
+    m.add_function(wrap_pyfunction!(technicals::py_calculate_mfi, m)?)?;
+    m.add_function(wrap_pyfunction!(technicals::py_calculate_supertrend, m)?)?;
+    m.add_function(wrap_pyfunction!(technicals::py_calculate_roc, m)?)?;
+    m.add_function(wrap_pyfunction!(technicals::py_calculate_momentum, m)?)?;
+    m.add_function(wrap_pyfunction!(technicals::py_calculate_trix, m)?)?;
+    m.add_function(wrap_pyfunction!(technicals::py_calculate_aroon, m)?)?;
+    m.add_function(wrap_pyfunction!(technicals::py_calculate_ad, m)?)?;
+    m.add_function(wrap_pyfunction!(technicals::py_calculate_cmf, m)?)?;
+    m.add_function(wrap_pyfunction!(technicals::py_calculate_chaikin_oscillator, m)?)?;
+    m.add_function(wrap_pyfunction!(technicals::py_calculate_dema, m)?)?;
+    m.add_function(wrap_pyfunction!(technicals::py_calculate_tema, m)?)?;
+    m.add_function(wrap_pyfunction!(technicals::py_calculate_wma, m)?)?;
+    m.add_function(wrap_pyfunction!(technicals::py_calculate_vwma, m)?)?;
+    m.add_function(wrap_pyfunction!(technicals::py_calculate_zlema, m)?)?;
+    m.add_function(wrap_pyfunction!(technicals::py_calculate_pivots, m)?)?;
+    m.add_function(wrap_pyfunction!(technicals::py_fibonacci_levels, m)?)?;
+    m.add_function(wrap_pyfunction!(technicals::py_to_heikin_ashi, m)?)?;
+    m.add_function(wrap_pyfunction!(technicals::py_to_renko, m)?)?;
+    m.add_function(wrap_pyfunction!(technicals::py_calculate_linreg, m)?)?;
+    m.add_function(wrap_pyfunction!(technicals::py_calculate_alma, m)?)?;
+    m.add_function(wrap_pyfunction!(technicals::py_calculate_vortex, m)?)?;
+    m.add_function(wrap_pyfunction!(technicals::py_calculate_coppock, m)?)?;
+    m.add_function(wrap_pyfunction!(technicals::py_calculate_cmo, m)?)?;
+    m.add_function(wrap_pyfunction!(technicals::py_calculate_ao, m)?)?;
+    m.add_function(wrap_pyfunction!(technicals::py_calculate_pvo, m)?)?;
+    m.add_function(wrap_pyfunction!(technicals::py_calculate_eom, m)?)?;
+    m.add_function(wrap_pyfunction!(technicals::py_calculate_anchored_vwap, m)?)?;
+    m.add_function(wrap_pyfunction!(technicals::py_calculate_anchored_vwaps, m)?)?;
+    m.add_function(wrap_pyfunction!(technicals::py_calculate_mcginley, m)?)?;
+    m.add_function(wrap_pyfunction!(technicals::py_calculate_kdj, m)?)?;
+    m.add_function(wrap_pyfunction!(technicals::py_calculate_ulcer_index, m)?)?;
+    m.add_function(wrap_pyfunction!(technicals::py_calculate_upi, m)?)?;
+    m.add_function(wrap_pyfunction!(technicals::py_calculate_stc, m)?)?;
+    m.add_class::<MacdResult>()?;
+    m.add_class::<BbandsResult>()?;
+    m.add_class::<results::IndicatorResult>()?;
+    m.add_class::<candles::CandleSeries>()?;
+    m.add_function(wrap_pyfunction!(batch::py_compute_indicators, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_rsi_into, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_sma_into, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_ema_into, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_macd_into, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_bbands_into, m)?)?;
+    m.add_function(wrap_pyfunction!(chunked::py_calculate_rsi_chunked, m)?)?;
+    m.add_function(wrap_pyfunction!(chunked::py_calculate_sma_chunked, m)?)?;
+    m.add_function(wrap_pyfunction!(chunked::py_calculate_ema_chunked, m)?)?;
+    m.add_function(wrap_pyfunction!(chunked::py_calculate_macd_chunked, m)?)?;
+    m.add_function(wrap_pyfunction!(chunked::py_calculate_bbands_chunked, m)?)?;
+    m.add_function(wrap_pyfunction!(chunked::py_rolling_stats_chunked, m)?)?;
+    m.add_class::<chunked::ChunkIterator>()?;
+    m.add_class::<streaming::PyRsiState>()?;
+    m.add_class::<streaming::PySmaState>()?;
+    m.add_class::<streaming::PyEmaState>()?;
+    m.add_class::<streaming::PyMacdState>()?;
+    m.add_class::<streaming::PyBbandsState>()?;
+    m.add_class::<streaming::PyAtrState>()?;
+
+    // Statistics & Complexity
+    m.add_function(wrap_pyfunction!(stats::py_sample_entropy, m)?)?;
+    m.add_function(wrap_pyfunction!(stats::py_permutation_entropy, m)?)?;
+    m.add_function(wrap_pyfunction!(stats::py_detect_return_anomalies, m)?)?;
+    m.add_function(wrap_pyfunction!(stats::py_rolling_std, m)?)?;
+    m.add_function(wrap_pyfunction!(stats::py_rolling_zscore, m)?)?;
+
+    // Machine Learning
+    m.add_function(wrap_pyfunction!(features::py_build_features, m)?)?;
+    m.add_function(wrap_pyfunction!(ml::py_triple_barrier_labels, m)?)?;
+    m.add_function(wrap_pyfunction!(ml::py_frac_diff, m)?)?;
+    m.add_function(wrap_pyfunction!(ml::py_purged_kfold_splits, m)?)?;
+    m.add_class::<scaling::Scaler>()?;
+    m.add_class::<scaling::MinMaxScaler>()?;
+    m.add_class::<scaling::StandardScaler>()?;
+    m.add_class::<scaling::RobustScaler>()?;
+
+    // Scoring & Signals
+    m.add_function(wrap_pyfunction!(scoring::py_composite_score, m)?)?;
+    m.add_function(wrap_pyfunction!(scoring::py_composite_score_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(scoring::py_aggregate_sentiment, m)?)?;
+    m.add_function(wrap_pyfunction!(rules::py_evaluate_rules, m)?)?;
+    m.add_function(wrap_pyfunction!(regime::py_classify_regime, m)?)?;
+    m.add_function(wrap_pyfunction!(signals::py_mean_reversion_signals, m)?)?;
+    m.add_function(wrap_pyfunction!(signals::py_pair_spread, m)?)?;
+    m.add_function(wrap_pyfunction!(signals::py_pair_signals, m)?)?;
+    m.add_function(wrap_pyfunction!(signals::py_breakout_signals, m)?)?;
+    m.add_function(wrap_pyfunction!(signals::py_volume_spikes, m)?)?;
+    m.add_function(wrap_pyfunction!(signals::py_mtf_confirm, m)?)?;
+    m.add_function(wrap_pyfunction!(signals::py_signal_stats, m)?)?;
+
+    // Universe Screening
+    m.add_function(wrap_pyfunction!(screening::py_momentum_ranks, m)?)?;
+    m.add_function(wrap_pyfunction!(screening::py_detect_price_gaps, m)?)?;
+    m.add_function(wrap_pyfunction!(screening::py_detect_price_gaps_batch, m)?)?;
+    m.add_class::<screening::Screener>()?;
+    m.add_class::<search::SymbolIndex>()?;
+    m.add_function(wrap_pyfunction!(screening::py_high_low_metrics, m)?)?;
+    m.add_function(wrap_pyfunction!(screening::py_ma_cross_screen, m)?)?;
+    m.add_function(wrap_pyfunction!(screening::py_relative_strength, m)?)?;
+
+    // Market Microstructure
+    m.add_function(wrap_pyfunction!(microstructure::py_spread_stats, m)?)?;
+    m.add_function(wrap_pyfunction!(microstructure::py_roll_spread, m)?)?;
+    m.add_function(wrap_pyfunction!(microstructure::py_book_imbalance, m)?)?;
+    m.add_function(wrap_pyfunction!(microstructure::py_rolling_book_imbalance, m)?)?;
+
+    // Money (exact decimal)
+    m.add_function(wrap_pyfunction!(money::py_position_pnl, m)?)?;
+    m.add_function(wrap_pyfunction!(money::py_trade_fee, m)?)?;
+    m.add_function(wrap_pyfunction!(money::py_capital_gains_tax, m)?)?;
+    m.add_function(wrap_pyfunction!(money::py_fx_cost, m)?)?;
+    m.add_function(wrap_pyfunction!(money::py_uk_trade_costs, m)?)?;
+    m.add_function(wrap_pyfunction!(money::py_project_savings, m)?)?;
+    m.add_function(wrap_pyfunction!(dividends::py_project_dividends, m)?)?;
+    m.add_function(wrap_pyfunction!(isa::py_isa_allowance_report, m)?)?;
+    m.add_function(wrap_pyfunction!(allocation::py_allocate_pie, m)?)?;
+    m.add_function(wrap_pyfunction!(allocation::py_optimize_allocation, m)?)?;
+    m.add_function(wrap_pyfunction!(allocation::py_exposure_report, m)?)?;
+    m.add_class::<sectors::SectorMap>()?;
+    m.add_class::<instrument::Instrument>()?;
+
+    // Trade Risk
+    m.add_function(wrap_pyfunction!(risk::py_suggest_stop, m)?)?;
+    m.add_function(wrap_pyfunction!(risk::py_position_risk, m)?)?;
+    m.add_function(wrap_pyfunction!(risk::py_r_multiples, m)?)?;
+    m.add_function(wrap_pyfunction!(orders::py_validate_order, m)?)?;
+    m.add_function(wrap_pyfunction!(portfolio::py_what_if, m)?)?;
+    m.add_function(wrap_pyfunction!(execution::py_execution_quality, m)?)?;
+
+    // Live Market Data
+    m.add_class::<store::MarketDataStore>()?;
+    m.add_class::<alerts::AlertEngine>()?;
+
+    // Async variants (await from a running event loop)
+    m.add_function(wrap_pyfunction!(scoring::py_composite_score_batch_async, m)?)?;
+    m.add_function(wrap_pyfunction!(screening::py_momentum_ranks_async, m)?)?;
+    m.add_function(wrap_pyfunction!(screening::py_detect_price_gaps_batch_async, m)?)?;
+    m.add_function(wrap_pyfunction!(screening::py_ma_cross_screen_async, m)?)?;
+    m.add_function(wrap_pyfunction!(screening::py_relative_strength_async, m)?)?;
+
+    // Configuration
+    m.add_class::<Config>()?;
+    m.add_function(wrap_pyfunction!(config::py_set_config, m)?)?;
+    m.add_function(wrap_pyfunction!(config::py_get_config, m)?)?;
+
+    // Caching
+    m.add_function(wrap_pyfunction!(cache::py_enable_cache, m)?)?;
+    m.add_function(wrap_pyfunction!(cache::py_disable_cache, m)?)?;
+    m.add_function(wrap_pyfunction!(cache::py_clear_cache, m)?)?;
+    m.add_function(wrap_pyfunction!(cache::py_cache_stats, m)?)?;
+
+    // Diagnostics
+    m.add_function(wrap_pyfunction!(logging::py_set_log_level, m)?)?;
+    m.add_function(wrap_pyfunction!(logging::py_reset_log_cache, m)?)?;
+    m.add_function(wrap_pyfunction!(profile::py_profile_begin, m)?)?;
+    m.add_function(wrap_pyfunction!(profile::py_profile_end, m)?)?;
+    m.add_function(wrap_pyfunction!(profile::py_profile_report, m)?)?;
     Ok(())
 }
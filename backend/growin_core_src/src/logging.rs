@@ -0,0 +1,53 @@
+//! Routes Rust `log` records to Python's `logging` module.
+//!
+//! Records go to loggers named after the emitting Rust module, e.g.
+//! `growin_core.candles` or `growin_core.screening`, so they can be filtered
+//! and handled like any other Python logger. Effective levels are cached on
+//! the Rust side to keep disabled log calls free of GIL round-trips; use
+//! `set_log_level`, or `reset_log_cache` after reconfiguring `logging`
+//! directly, for a change to take effect.
+
+use pyo3::prelude::*;
+use pyo3_log::{Caching, Logger, ResetHandle};
+use std::sync::OnceLock;
+
+static RESET_HANDLE: OnceLock<ResetHandle> = OnceLock::new();
+
+/// Installs the bridge as the global `log` backend.
+pub fn init(py: Python<'_>) -> PyResult<()> {
+    // Installation fails if another extension in the process already owns
+    // the global logger; our records then go to that logger instead.
+    if let Ok(handle) = Logger::new(py, Caching::LoggersAndLevels)?.install() {
+        let _ = RESET_HANDLE.set(handle);
+    }
+    Ok(())
+}
+
+fn reset_cache() {
+    if let Some(handle) = RESET_HANDLE.get() {
+        handle.reset();
+    }
+}
+
+/// Set the level of the `growin_core` logger and its children.
+///
+/// Args:
+///     level (str): A `logging` level name ("DEBUG", "INFO", "WARNING",
+///         "ERROR" or "CRITICAL"), case-insensitive.
+#[pyfunction]
+#[pyo3(name = "set_log_level")]
+pub fn py_set_log_level(py: Python<'_>, level: &str) -> PyResult<()> {
+    py.import("logging")?
+        .call_method1("getLogger", ("growin_core",))?
+        .call_method1("setLevel", (level.to_uppercase(),))?;
+    reset_cache();
+    Ok(())
+}
+
+/// Drop cached logger levels. Call after changing `growin_core` logger
+/// levels or handlers through `logging` directly.
+#[pyfunction]
+#[pyo3(name = "reset_log_cache")]
+pub fn py_reset_log_cache() {
+    reset_cache();
+}
@@ -0,0 +1,138 @@
+//! Python bindings of the spread and order book analytics.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use growin_core::microstructure::{book_imbalance, roll_spread, rolling_book_imbalance, spread_stats};
+
+use crate::errors;
+
+/// Quoted bid-ask spread statistics, for ranking instruments by liquidity
+/// cost.
+///
+/// Args:
+///     bid (List[float]): Best bid per quote update.
+///     ask (List[float]): Best ask per quote update.
+///     timestamps (List[int]): Update times in epoch seconds, ascending.
+///     bucket_minutes (int): Width of the intraday profile buckets; must
+///         divide a day (default 30).
+///     utc_offset_minutes (int): Exchange offset from UTC for the profile
+///         (default 0).
+///     max_gap_minutes (int): Longest a quote is weighted for before the
+///         next update, so feed gaps and overnight closes do not dominate
+///         (default 60).
+///
+/// Returns:
+///     Dict: {"valid_quotes", "invalid_quotes", "mean_spread",
+///     "mean_spread_bps", "time_weighted_spread", "time_weighted_spread_bps",
+///     "min_spread_bps", "max_spread_bps", "profile": {"HH:MM":
+///     time-weighted bps}}. Crossed or non-positive quotes are skipped.
+#[pyfunction]
+#[pyo3(
+    name = "spread_stats",
+    signature = (bid, ask, timestamps, bucket_minutes=30, utc_offset_minutes=0, max_gap_minutes=60)
+)]
+pub fn py_spread_stats<'py>(
+    py: Python<'py>,
+    bid: Vec<f64>,
+    ask: Vec<f64>,
+    timestamps: Vec<i64>,
+    bucket_minutes: usize,
+    utc_offset_minutes: i64,
+    max_gap_minutes: usize,
+) -> PyResult<Bound<'py, PyDict>> {
+    errors::check_lengths(&[("bid", bid.len()), ("ask", ask.len()), ("timestamps", timestamps.len())])?;
+    errors::check_period("bucket_minutes", bucket_minutes)?;
+    errors::check_period("max_gap_minutes", max_gap_minutes)?;
+    let s = py
+        .allow_threads(|| {
+            spread_stats(&bid, &ask, &timestamps, bucket_minutes as i64 * 60, utc_offset_minutes * 60, max_gap_minutes as i64 * 60)
+        })
+        .map_err(PyValueError::new_err)?;
+
+    let profile = PyDict::new(py);
+    for (slot, bps) in &s.profile {
+        profile.set_item(format!("{:02}:{:02}", slot / 3600, slot % 3600 / 60), bps)?;
+    }
+    let out = PyDict::new(py);
+    out.set_item("valid_quotes", s.valid_quotes)?;
+    out.set_item("invalid_quotes", s.invalid_quotes)?;
+    out.set_item("mean_spread", s.mean_spread)?;
+    out.set_item("mean_spread_bps", s.mean_spread_bps)?;
+    out.set_item("time_weighted_spread", s.time_weighted_spread)?;
+    out.set_item("time_weighted_spread_bps", s.time_weighted_spread_bps)?;
+    out.set_item("min_spread_bps", s.min_spread_bps)?;
+    out.set_item("max_spread_bps", s.max_spread_bps)?;
+    out.set_item("profile", profile)?;
+    Ok(out)
+}
+
+/// Effective spread estimated from trade prices (Roll, 1984).
+///
+/// Bid-ask bounce makes consecutive price changes negatively correlated;
+/// useful where no quotes are available.
+///
+/// Args:
+///     prices (List[float]): Trade prices in time order.
+///
+/// Returns:
+///     Dict: {"spread", "spread_bps", "serial_covariance"}. The spread is
+///     NaN when the serial covariance is not negative.
+#[pyfunction]
+#[pyo3(name = "roll_spread")]
+pub fn py_roll_spread(py: Python<'_>, prices: Vec<f64>) -> PyResult<Bound<'_, PyDict>> {
+    let r = py.allow_threads(|| roll_spread(&prices)).map_err(errors::InsufficientDataError::new_err)?;
+    let out = PyDict::new(py);
+    out.set_item("spread", r.spread)?;
+    out.set_item("spread_bps", r.spread_bps)?;
+    out.set_item("serial_covariance", r.serial_covariance)?;
+    Ok(out)
+}
+
+/// Order book imbalance of one level-2 snapshot.
+///
+/// Args:
+///     bid_sizes (List[float]): Size at each bid level, best first.
+///     ask_sizes (List[float]): Size at each ask level, best first.
+///     levels (int, optional): Levels per side to include (default all).
+///
+/// Returns:
+///     float: (bid depth - ask depth) / (bid depth + ask depth), in [-1, 1];
+///     NaN for an empty book.
+#[pyfunction]
+#[pyo3(name = "book_imbalance", signature = (bid_sizes, ask_sizes, levels=None))]
+pub fn py_book_imbalance(bid_sizes: Vec<f64>, ask_sizes: Vec<f64>, levels: Option<usize>) -> PyResult<f64> {
+    if let Some(levels) = levels {
+        errors::check_period("levels", levels)?;
+    }
+    Ok(book_imbalance(&bid_sizes, &ask_sizes, levels))
+}
+
+/// Book imbalance series from level-2 snapshots, as a short-term signal
+/// feature.
+///
+/// Args:
+///     snapshots (List[Tuple[List[float], List[float]]]): (bid_sizes,
+///         ask_sizes) per snapshot, best level first.
+///     levels (int, optional): Levels per side to include (default 5; None
+///         for all).
+///     window (int): Snapshots in the rolling mean (default 20).
+///
+/// Returns:
+///     Tuple[List[float], List[float]]: (imbalance per snapshot, rolling
+///     mean, NaN until `window` snapshots).
+#[pyfunction]
+#[pyo3(name = "rolling_book_imbalance", signature = (snapshots, levels=Some(5), window=20))]
+pub fn py_rolling_book_imbalance(
+    py: Python<'_>,
+    snapshots: Vec<(Vec<f64>, Vec<f64>)>,
+    levels: Option<usize>,
+    window: usize,
+) -> PyResult<(Vec<f64>, Vec<f64>)> {
+    if let Some(levels) = levels {
+        errors::check_period("levels", levels)?;
+    }
+    errors::check_period("window", window)?;
+    Ok(py.allow_threads(|| rolling_book_imbalance(&snapshots, levels, window)))
+}
@@ -0,0 +1,139 @@
+use pyo3::exceptions::{PyIndexError, PyValueError};
+use pyo3::prelude::*;
+
+use growin_core::indicators;
+use growin_core::ml::{frac_diff, purged_kfold_splits, triple_barrier_labels};
+
+use crate::errors::{self, LengthMismatchError};
+
+/// Generate supervised-learning targets with the triple-barrier method.
+///
+/// Args:
+///     prices (List[float]): Closing prices.
+///     events (List[int], optional): Event bar indices to label. Defaults to every bar.
+///     pt_mult (float): Profit-taking barrier in ATR multiples (0 disables, default 2.0).
+///     sl_mult (float): Stop-loss barrier in ATR multiples (0 disables, default 1.0).
+///     max_holding (int): Vertical barrier in bars (default 10).
+///     high (List[float], optional): Bar highs for intrabar barrier touches and true range.
+///     low (List[float], optional): Bar lows for intrabar barrier touches and true range.
+///     atr_period (int): ATR lookback used to scale the barriers (default 14).
+///     sign_at_vertical (bool): Label vertical-barrier exits by the sign of their
+///         return instead of 0 (default False).
+///
+/// Returns:
+///     Tuple[List[int], List[int], List[float]]: (labels in {-1, 0, 1}, exit bar
+///     indices, realized returns), one entry per event.
+#[pyfunction]
+#[pyo3(name = "triple_barrier_labels", signature = (
+    prices, events=None, pt_mult=2.0, sl_mult=1.0, max_holding=10,
+    high=None, low=None, atr_period=14, sign_at_vertical=false
+))]
+#[allow(clippy::too_many_arguments)]
+pub fn py_triple_barrier_labels(
+    py: Python<'_>,
+    prices: Vec<f64>,
+    events: Option<Vec<usize>>,
+    pt_mult: f64,
+    sl_mult: f64,
+    max_holding: usize,
+    high: Option<Vec<f64>>,
+    low: Option<Vec<f64>>,
+    atr_period: usize,
+    sign_at_vertical: bool,
+) -> PyResult<(Vec<i8>, Vec<usize>, Vec<f64>)> {
+    let n = prices.len();
+    for (name, series) in [("high", &high), ("low", &low)] {
+        if let Some(series) = &series {
+            errors::check_lengths(&[("prices", n), (name, series.len())])?;
+        }
+    }
+    errors::check_period("atr_period", atr_period)?;
+    let events = events.unwrap_or_else(|| (0..n).collect());
+    if let Some(&bad) = events.iter().find(|&&e| e >= n) {
+        return Err(PyIndexError::new_err(format!("event index {} out of range", bad)));
+    }
+
+    let labels = py.allow_threads(|| {
+        let atr = indicators::atr(
+            high.as_deref().unwrap_or(&prices),
+            low.as_deref().unwrap_or(&prices),
+            &prices,
+            atr_period,
+        );
+        triple_barrier_labels(
+            &prices,
+            &atr,
+            &events,
+            pt_mult,
+            sl_mult,
+            max_holding,
+            high.as_deref(),
+            low.as_deref(),
+            sign_at_vertical,
+        )
+    });
+
+    let mut out = (Vec::with_capacity(labels.len()), Vec::with_capacity(labels.len()), Vec::with_capacity(labels.len()));
+    for l in labels {
+        out.0.push(l.label);
+        out.1.push(l.exit_index);
+        out.2.push(l.ret);
+    }
+    Ok(out)
+}
+
+/// Fractionally differentiate a series (fixed-width window method).
+///
+/// Args:
+///     series (List[float]): Input series, typically log prices.
+///     d (float): Differencing order, usually in (0, 1). d=1 is a plain first difference.
+///     threshold (float): Minimum absolute weight kept in the window (default 1e-5).
+///
+/// Returns:
+///     List[float]: Differenced series aligned with input; NaN during the window warm-up.
+#[pyfunction]
+#[pyo3(name = "frac_diff", signature = (series, d, threshold=1e-5))]
+pub fn py_frac_diff(py: Python<'_>, series: Vec<f64>, d: f64, threshold: f64) -> PyResult<Vec<f64>> {
+    if threshold <= 0.0 {
+        return Err(PyValueError::new_err("threshold must be positive"));
+    }
+    Ok(py.allow_threads(|| frac_diff(&series, d, threshold)))
+}
+
+/// Time-series cross-validation splits with purging and embargo.
+///
+/// Args:
+///     n_samples (int): Number of samples (bars/events) in time order.
+///     n_splits (int): Number of folds (default 5).
+///     embargo_pct (float): Fraction of `n_samples` embargoed after each test
+///         fold (default 0.01).
+///     t1 (List[int], optional): Index of the bar at which each sample's label is
+///         resolved (e.g. the exit indices from `triple_barrier_labels`). Without
+///         it every label is assumed to resolve on its own bar.
+///
+/// Returns:
+///     List[Tuple[List[int], List[int]]]: (train_indices, test_indices) per fold.
+#[pyfunction]
+#[pyo3(name = "purged_kfold_splits", signature = (n_samples, n_splits=5, embargo_pct=0.01, t1=None))]
+pub fn py_purged_kfold_splits(
+    py: Python<'_>,
+    n_samples: usize,
+    n_splits: usize,
+    embargo_pct: f64,
+    t1: Option<Vec<usize>>,
+) -> PyResult<Vec<(Vec<usize>, Vec<usize>)>> {
+    if n_splits < 2 || n_splits > n_samples {
+        return Err(PyValueError::new_err(format!(
+            "n_splits must be between 2 and n_samples ({}), got {}",
+            n_samples, n_splits
+        )));
+    }
+    if !(0.0..1.0).contains(&embargo_pct) {
+        return Err(PyValueError::new_err("embargo_pct must be in [0, 1)"));
+    }
+    if t1.as_ref().is_some_and(|t| t.len() != n_samples) {
+        return Err(LengthMismatchError::new_err("t1 must have n_samples entries"));
+    }
+    let embargo = (n_samples as f64 * embargo_pct).ceil() as usize;
+    Ok(py.allow_threads(|| purged_kfold_splits(n_samples, n_splits, embargo, t1.as_deref())))
+}
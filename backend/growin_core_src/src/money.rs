@@ -0,0 +1,246 @@
+//! Python bindings of the exact money arithmetic.
+//!
+//! Values cross the boundary as `decimal.Decimal`; ints and strings are
+//! accepted too, and floats are taken at their shortest repr.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use rust_decimal::Decimal;
+
+use growin_core::money::{
+    capital_gains_tax, fx_cost, needs_fx, position_pnl, project_savings, round, trade_fee, uk_trade_costs, Compounding,
+    CostBasisMethod, Fill, InstrumentClass, SavingsPoint,
+};
+use growin_core::ticker::normalize_ticker;
+
+use crate::instrument::Instrument;
+use crate::{config, errors};
+
+/// Trading212's FX fee, 0.15%.
+pub const T212_FX_FEE_BPS: Decimal = Decimal::from_parts(15, 0, 0, false, 0);
+
+/// Replay a ticker's fills and compute cost basis and P&L exactly.
+///
+/// Args:
+///     quantities (List[Decimal]): Signed fill quantities (buys > 0, sells < 0).
+///     prices (List[Decimal]): Fill prices.
+///     fees (List[Decimal], optional): Fee paid on each fill.
+///     method (str): "fifo" or "average" cost basis (default "fifo").
+///     mark_price (Decimal, optional): Price for unrealized P&L.
+///     dp (int, optional): Decimal places to round outputs to (default 2);
+///         None returns unrounded values.
+///     currency (str, optional): Currency the instrument trades in. When it
+///         differs from the configured account currency, every fill also
+///         pays `fx_fee_bps` on its notional.
+///     fx_fee_bps (Decimal): FX fee in basis points (default 15).
+///
+/// Returns:
+///     Dict[str, Decimal]: {"position", "cost_basis", "average_cost",
+///     "realized_pnl", "unrealized_pnl", "fees", "fx_fees"}, in the
+///     instrument's currency. `fees` includes `fx_fees`. `average_cost` is
+///     None when flat and `unrealized_pnl` is None without `mark_price`.
+#[pyfunction]
+#[pyo3(
+    name = "position_pnl",
+    signature = (quantities, prices, fees=None, method="fifo", mark_price=None, dp=Some(2), currency=None, fx_fee_bps=T212_FX_FEE_BPS)
+)]
+#[allow(clippy::too_many_arguments)]
+pub fn py_position_pnl<'py>(
+    py: Python<'py>,
+    quantities: Vec<Decimal>,
+    prices: Vec<Decimal>,
+    fees: Option<Vec<Decimal>>,
+    method: &str,
+    mark_price: Option<Decimal>,
+    dp: Option<u32>,
+    currency: Option<&str>,
+    fx_fee_bps: Decimal,
+) -> PyResult<Bound<'py, PyDict>> {
+    let method: CostBasisMethod = method.parse().map_err(PyValueError::new_err)?;
+    errors::check_lengths(&[("quantities", quantities.len()), ("prices", prices.len())])?;
+    if let Some(fees) = &fees {
+        errors::check_lengths(&[("quantities", quantities.len()), ("fees", fees.len())])?;
+    }
+    let account_ccy = config::current().currency;
+    let fx_fees: Vec<Decimal> = (0..quantities.len())
+        .map(|i| currency.map_or(Decimal::ZERO, |ccy| fx_cost(quantities[i] * prices[i], ccy, &account_ccy, fx_fee_bps)))
+        .collect();
+    let fills: Vec<Fill> = (0..quantities.len())
+        .map(|i| Fill {
+            quantity: quantities[i],
+            price: prices[i],
+            fee: fees.as_ref().map_or(Decimal::ZERO, |f| f[i]) + fx_fees[i],
+        })
+        .collect();
+    let pnl = py.allow_threads(|| position_pnl(&fills, method));
+
+    let out = PyDict::new(py);
+    out.set_item("position", pnl.position.normalize())?;
+    out.set_item("cost_basis", round(pnl.cost_basis, dp))?;
+    out.set_item("average_cost", pnl.average_cost().map(|c| round(c, dp.map(|dp| dp + 2))))?;
+    out.set_item("realized_pnl", round(pnl.realized_pnl, dp))?;
+    out.set_item("unrealized_pnl", mark_price.map(|m| round(pnl.unrealized_pnl(m), dp)))?;
+    out.set_item("fees", round(pnl.fees, dp))?;
+    out.set_item("fx_fees", round(fx_fees.iter().sum(), dp))?;
+    Ok(out)
+}
+
+/// FX fee on converting `amount`, in exact decimal; zero when no conversion
+/// is needed (GBX and GBP count as the same currency).
+///
+/// Args:
+///     amount (Decimal): Amount converted (sign ignored), in `from_ccy`.
+///     from_ccy (str): ISO 4217 code converted from.
+///     to_ccy (str): ISO 4217 code converted to.
+///     fee_bps (Decimal): Fee in basis points (default 15, Trading212's 0.15%).
+///     dp (int, optional): Decimal places to round to (default 2).
+///
+/// Returns:
+///     Decimal: The fee, in `from_ccy`.
+#[pyfunction]
+#[pyo3(name = "fx_cost", signature = (amount, from_ccy, to_ccy, fee_bps=T212_FX_FEE_BPS, dp=Some(2)))]
+pub fn py_fx_cost(amount: Decimal, from_ccy: &str, to_ccy: &str, fee_bps: Decimal, dp: Option<u32>) -> Decimal {
+    round(fx_cost(amount, from_ccy, to_ccy, fee_bps), dp)
+}
+
+/// Round-trip FX cost, as a fraction, of trading an instrument quoted in
+/// `currency` from the configured account currency.
+pub fn round_trip_fx(currency: Option<&str>, fee_bps: f64) -> f64 {
+    match currency {
+        Some(ccy) if needs_fx(ccy, &config::current().currency) => 2.0 * fee_bps / 10_000.0,
+        _ => 0.0,
+    }
+}
+
+/// Fee on a trade of `notional` value, in exact decimal.
+///
+/// Args:
+///     notional (Decimal): Trade value (sign ignored).
+///     rate (Decimal): Fee as a fraction of notional (e.g. 0.0015 for FX 0.15%).
+///     minimum (Decimal): Minimum fee (default 0).
+///     maximum (Decimal, optional): Fee cap.
+///     dp (int, optional): Decimal places to round to (default 2).
+#[pyfunction]
+#[pyo3(name = "trade_fee", signature = (notional, rate, minimum=Decimal::ZERO, maximum=None, dp=Some(2)))]
+pub fn py_trade_fee(notional: Decimal, rate: Decimal, minimum: Decimal, maximum: Option<Decimal>, dp: Option<u32>) -> Decimal {
+    round(trade_fee(notional, rate, minimum, maximum), dp)
+}
+
+/// Tax owed on realized gains, in exact decimal.
+///
+/// Args:
+///     realized_gains (Decimal): Net realized gains for the tax period.
+///     rate (Decimal): Tax rate as a fraction (e.g. 0.2).
+///     allowance (Decimal): Tax-free allowance deducted first (default 0).
+///     dp (int, optional): Decimal places to round to (default 2).
+#[pyfunction]
+#[pyo3(name = "capital_gains_tax", signature = (realized_gains, rate, allowance=Decimal::ZERO, dp=Some(2)))]
+pub fn py_capital_gains_tax(realized_gains: Decimal, rate: Decimal, allowance: Decimal, dp: Option<u32>) -> Decimal {
+    round(capital_gains_tax(realized_gains, rate, allowance), dp)
+}
+
+/// UK stamp duty (SDRT) and PTM levy on an LSE trade, in exact decimal.
+///
+/// Args:
+///     ticker (str | Instrument): Broker or vendor symbol, or an
+///         `Instrument`; normalized first, and only `.L` listings are liable.
+///     notional (Decimal): Trade value in GBP (sign ignored).
+///     side (str): "buy" (default) or "sell"; stamp duty is charged on
+///         purchases only.
+///     instrument_type (str, optional): "share" (investment trusts too),
+///         "etf" (exempt from both) or "aim" (exempt from stamp duty).
+///         Defaults to the `Instrument`'s class, or "share" for a symbol.
+///
+/// Returns:
+///     Dict[str, Decimal]: {"stamp_duty", "ptm_levy", "total"} in GBP.
+#[pyfunction]
+#[pyo3(name = "uk_trade_costs", signature = (ticker, notional, side="buy", instrument_type=None))]
+pub fn py_uk_trade_costs<'py>(
+    py: Python<'py>,
+    ticker: &Bound<'py, PyAny>,
+    notional: Decimal,
+    side: &str,
+    instrument_type: Option<&str>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let is_buy = match side.to_lowercase().as_str() {
+        "buy" => true,
+        "sell" => false,
+        other => return Err(PyValueError::new_err(format!("side must be 'buy' or 'sell', got '{}'", other))),
+    };
+    let (ticker, class) = match ticker.downcast::<Instrument>() {
+        Ok(instrument) => {
+            let instrument = &instrument.get().inner;
+            (instrument.normalized_symbol(), instrument.trade_class())
+        }
+        Err(_) => (normalize_ticker(&ticker.extract::<String>()?), InstrumentClass::Share),
+    };
+    let class = match instrument_type {
+        Some(kind) => kind.parse().map_err(PyValueError::new_err)?,
+        None => class,
+    };
+    let costs = uk_trade_costs(&ticker, notional, is_buy, class);
+    let out = PyDict::new(py);
+    out.set_item("stamp_duty", round(costs.stamp_duty, Some(2)))?;
+    out.set_item("ptm_levy", round(costs.ptm_levy, Some(2)))?;
+    out.set_item("total", round(costs.total(), Some(2)))?;
+    Ok(out)
+}
+
+/// Savings plan projection for the planning screen, month by month.
+///
+/// Contributions are paid at the end of each month. Interest accrues on the
+/// balance monthly and is credited at the compounding frequency.
+///
+/// Args:
+///     principal (Decimal): Opening balance.
+///     monthly_contribution (Decimal): Paid in at the end of every month.
+///     annual_rate (Decimal): Nominal yearly rate as a fraction (e.g. 0.05).
+///     years (int): Length of the projection.
+///     compounding (str): "monthly" (default), "quarterly" or "annually".
+///     dp (int, optional): Decimal places to round to (default 2).
+///
+/// Returns:
+///     Dict: {"month": List[int], "balance", "contributions", "growth":
+///     List[Decimal]} with one entry per month from 0 (the principal), and
+///     "final_balance", "total_contributions", "total_growth".
+///
+/// Raises:
+///     GrowinError: If the balance outgrows the decimal range.
+#[pyfunction]
+#[pyo3(
+    name = "project_savings",
+    signature = (principal, monthly_contribution, annual_rate, years, compounding="monthly", dp=Some(2))
+)]
+pub fn py_project_savings<'py>(
+    py: Python<'py>,
+    principal: Decimal,
+    monthly_contribution: Decimal,
+    annual_rate: Decimal,
+    years: u32,
+    compounding: &str,
+    dp: Option<u32>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let compounding: Compounding = compounding.parse().map_err(PyValueError::new_err)?;
+    if annual_rate <= -Decimal::ONE {
+        return Err(PyValueError::new_err(format!("annual_rate must be above -1, got {}", annual_rate)));
+    }
+    let months = years.checked_mul(12).ok_or_else(|| PyValueError::new_err("years is too large"))?;
+    let points = py
+        .allow_threads(|| project_savings(principal, monthly_contribution, annual_rate, months, compounding))
+        .map_err(errors::GrowinError::new_err)?;
+
+    let column = |f: fn(&SavingsPoint) -> Decimal| -> Vec<Decimal> {
+        points.iter().map(|p| round(f(p), dp)).collect()
+    };
+    let last = points.last().expect("the projection includes month 0");
+    let out = PyDict::new(py);
+    out.set_item("month", points.iter().map(|p| p.month).collect::<Vec<_>>())?;
+    out.set_item("balance", column(|p| p.balance))?;
+    out.set_item("contributions", column(|p| p.contributions))?;
+    out.set_item("growth", column(|p| p.growth))?;
+    out.set_item("final_balance", round(last.balance, dp))?;
+    out.set_item("total_contributions", round(last.contributions, dp))?;
+    out.set_item("total_growth", round(last.growth, dp))?;
+    Ok(out)
+}
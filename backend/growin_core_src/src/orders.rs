@@ -0,0 +1,93 @@
+//! Python bindings of the pre-trade order checks.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use rust_decimal::Decimal;
+
+use growin_core::orders::{validate_order, AccountState, InstrumentRules, Order, OrderType};
+
+use crate::errors::{optional_field, required_field};
+use crate::instrument::Instrument;
+
+fn rules_from_dict(d: &Bound<'_, PyDict>, market_open: Option<bool>) -> PyResult<InstrumentRules> {
+    let lot_size = match optional_field(d, "lot_size")? {
+        Some(lot) => lot,
+        None => Decimal::new(1, optional_field(d, "quantity_dp")?.unwrap_or(0)),
+    };
+    Ok(InstrumentRules {
+        min_quantity: optional_field(d, "min_quantity")?.unwrap_or(Decimal::ZERO),
+        lot_size,
+        tick_size: optional_field(d, "tick_size")?,
+        max_open_quantity: optional_field(d, "max_open_quantity")?,
+        market_open: match market_open {
+            Some(open) => open,
+            None => optional_field(d, "market_open")?.unwrap_or(true),
+        },
+    })
+}
+
+/// Check an order against instrument and account rules before placing it.
+///
+/// Args:
+///     order (dict): {"side": "buy" | "sell", "quantity", "type" ("market"
+///         (default), "limit", "stop" or "stop_limit"), "price"}. `price`
+///         is the limit/stop price, or the last price for market orders.
+///     instrument_metadata (Instrument | dict): An `Instrument`, or
+///         {"min_quantity" (default 0), "quantity_dp" (decimal places
+///         allowed, default 0) or "lot_size", "tick_size" (optional),
+///         "max_open_quantity" (optional), "market_open" (default True)}.
+///     account_state (dict): {"buying_power", "position_quantity" (default
+///         0), "max_position_value" (optional)}.
+///     market_open (bool, optional): Whether the instrument's market is open;
+///         overrides the metadata's "market_open" (default True).
+///
+/// Returns:
+///     Dict: {"valid": bool, "violations": [{"code", "field", "message"}]}.
+///     Codes: invalid_quantity, min_quantity, quantity_precision,
+///     invalid_price, price_tick, market_closed, insufficient_funds,
+///     max_open_quantity,
+///     max_position_value, insufficient_position.
+#[pyfunction]
+#[pyo3(name = "validate_order", signature = (order, instrument_metadata, account_state, market_open=None))]
+pub fn py_validate_order<'py>(
+    py: Python<'py>,
+    order: Bound<'py, PyDict>,
+    instrument_metadata: Bound<'py, PyAny>,
+    account_state: Bound<'py, PyDict>,
+    market_open: Option<bool>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let side: String = required_field(&order, "order", "side")?;
+    let order_type: String = optional_field(&order, "type")?.unwrap_or_else(|| "market".to_string());
+    let order = Order {
+        side: side.parse().map_err(PyValueError::new_err)?,
+        order_type: order_type.parse::<OrderType>().map_err(PyValueError::new_err)?,
+        quantity: required_field(&order, "order", "quantity")?,
+        price: required_field(&order, "order", "price")?,
+    };
+    let rules = match instrument_metadata.downcast::<Instrument>() {
+        Ok(instrument) => instrument.get().inner.order_rules(market_open.unwrap_or(true)),
+        Err(_) => rules_from_dict(instrument_metadata.downcast::<PyDict>()?, market_open)?,
+    };
+    let account = AccountState {
+        buying_power: required_field(&account_state, "account_state", "buying_power")?,
+        position_quantity: optional_field(&account_state, "position_quantity")?.unwrap_or(Decimal::ZERO),
+        max_position_value: optional_field(&account_state, "max_position_value")?,
+    };
+    let violations = validate_order(&order, &rules, &account);
+
+    let out = PyDict::new(py);
+    out.set_item("valid", violations.is_empty())?;
+    let list = violations
+        .into_iter()
+        .map(|v| {
+            let d = PyDict::new(py);
+            d.set_item("code", v.code)?;
+            d.set_item("field", v.field)?;
+            d.set_item("message", v.message)?;
+            Ok(d)
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    out.set_item("violations", list)?;
+    Ok(out)
+}
@@ -0,0 +1,134 @@
+//! Python bindings of the portfolio risk analytics.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{IntoPyDict, PyDict};
+use std::collections::{BTreeMap, HashMap};
+
+use growin_core::portfolio::{what_if, PortfolioMetrics};
+use growin_core::risk::Side;
+
+use crate::config;
+use crate::errors::required_field;
+
+/// The reported metrics, with volatility annualized.
+fn metric_fields(m: &PortfolioMetrics, annualization: f64) -> [(&'static str, f64); 7] {
+    [
+        ("value", m.value),
+        ("volatility", m.volatility * annualization.sqrt()),
+        ("var", m.var),
+        ("hhi", m.hhi),
+        ("effective_holdings", m.effective_holdings),
+        ("max_weight", m.max_weight),
+        ("average_correlation", m.average_correlation),
+    ]
+}
+
+fn sector_weights(tickers: &[String], weights: &[f64], sectors: &HashMap<String, String>) -> BTreeMap<String, f64> {
+    let mut out = BTreeMap::new();
+    for (ticker, w) in tickers.iter().zip(weights) {
+        let sector = sectors.get(ticker).map_or("Unknown", |s| s.as_str());
+        *out.entry(sector.to_string()).or_insert(0.0) += w;
+    }
+    out
+}
+
+/// Impact preview of a proposed trade on portfolio risk.
+///
+/// Args:
+///     portfolio (Dict[str, float]): ticker -> shares held.
+///     candidate_trade (dict): {"ticker", "side" ("buy" | "sell"),
+///         "quantity"}.
+///     prices (Dict[str, float]): ticker -> price, for every position and
+///         the candidate.
+///     covariance (Dict[str, Dict[str, float]]): Covariance of per-period
+///         (e.g. daily) returns, as `DataFrame.cov().to_dict()` gives.
+///     sectors (Dict[str, str], optional): ticker -> sector for the sector
+///         exposure breakdown.
+///     confidence (float): VaR confidence level (default 0.95).
+///
+/// Returns:
+///     Dict: {"before", "after", "change"}, each {"value", "volatility"
+///     (annualized with the configured periods per year), "var" (one-period
+///     parametric VaR as a positive amount), "hhi", "effective_holdings",
+///     "max_weight", "average_correlation"}, plus "correlation_to_portfolio"
+///     of the candidate before the trade and "sector_exposure": {"before",
+///     "after", "change": {sector: weight}} (None without `sectors`).
+#[pyfunction]
+#[pyo3(name = "what_if", signature = (portfolio, candidate_trade, prices, covariance, sectors=None, confidence=0.95))]
+pub fn py_what_if<'py>(
+    py: Python<'py>,
+    portfolio: HashMap<String, f64>,
+    candidate_trade: Bound<'py, PyDict>,
+    prices: HashMap<String, f64>,
+    covariance: HashMap<String, HashMap<String, f64>>,
+    sectors: Option<HashMap<String, String>>,
+    confidence: f64,
+) -> PyResult<Bound<'py, PyDict>> {
+    if !(confidence > 0.0 && confidence < 1.0) {
+        return Err(PyValueError::new_err(format!("confidence must be in (0, 1), got {}", confidence)));
+    }
+    let ticker: String = required_field(&candidate_trade, "candidate_trade", "ticker")?;
+    let side: String = required_field(&candidate_trade, "candidate_trade", "side")?;
+    let side: Side = side.parse().map_err(PyValueError::new_err)?;
+    let quantity: f64 = required_field(&candidate_trade, "candidate_trade", "quantity")?;
+
+    let mut tickers: Vec<String> = portfolio.keys().cloned().collect();
+    if !portfolio.contains_key(&ticker) {
+        tickers.push(ticker.clone());
+    }
+    tickers.sort();
+    let price = |t: &String| prices.get(t).copied().ok_or_else(|| PyValueError::new_err(format!("no price for '{}'", t)));
+    let values = tickers
+        .iter()
+        .map(|t| Ok(portfolio.get(t).copied().unwrap_or(0.0) * price(t)?))
+        .collect::<PyResult<Vec<f64>>>()?;
+    let matrix = tickers
+        .iter()
+        .map(|a| {
+            let row = covariance.get(a).ok_or_else(|| PyValueError::new_err(format!("no covariance row for '{}'", a)))?;
+            tickers
+                .iter()
+                .map(|b| {
+                    row.get(b)
+                        .or_else(|| covariance.get(b).and_then(|r| r.get(a)))
+                        .copied()
+                        .ok_or_else(|| PyValueError::new_err(format!("no covariance between '{}' and '{}'", a, b)))
+                })
+                .collect::<PyResult<Vec<f64>>>()
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    let asset = tickers.iter().position(|t| *t == ticker).expect("the candidate is in the universe");
+    let trade_value = side.sign() * quantity.abs() * price(&ticker)?;
+    let result = py
+        .allow_threads(|| what_if(&values, &matrix, asset, trade_value, confidence))
+        .map_err(PyValueError::new_err)?;
+
+    let annualization = config::current().annualization as f64;
+    let (before, after) = (metric_fields(&result.before, annualization), metric_fields(&result.after, annualization));
+    let change: Vec<(&str, f64)> = before.iter().zip(&after).map(|(b, a)| (b.0, a.1 - b.1)).collect();
+
+    let out = PyDict::new(py);
+    out.set_item("before", before.into_py_dict(py)?)?;
+    out.set_item("after", after.into_py_dict(py)?)?;
+    out.set_item("change", change.into_py_dict(py)?)?;
+    out.set_item("correlation_to_portfolio", result.correlation_to_portfolio)?;
+    match sectors {
+        Some(sectors) => {
+            let before = sector_weights(&tickers, &result.before.weights, &sectors);
+            let after = sector_weights(&tickers, &result.after.weights, &sectors);
+            let change: BTreeMap<&String, f64> = before
+                .keys()
+                .chain(after.keys())
+                .map(|s| (s, after.get(s).unwrap_or(&0.0) - before.get(s).unwrap_or(&0.0)))
+                .collect();
+            let exposure = PyDict::new(py);
+            exposure.set_item("before", &before)?;
+            exposure.set_item("after", &after)?;
+            exposure.set_item("change", change)?;
+            out.set_item("sector_exposure", exposure)?;
+        }
+        None => out.set_item("sector_exposure", py.None())?,
+    }
+    Ok(out)
+}
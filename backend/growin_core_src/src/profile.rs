@@ -0,0 +1,97 @@
+//! Lightweight in-process profiler.
+//!
+//! Instrumented sections record call counts and cumulative wall time while
+//! profiling is on. Entry points are recorded under their Python name
+//! (excluding argument conversion), the indicator math under
+//! `indicators.compute`, and data crossing the boundary under
+//! `series.from_python`, `series.to_python` and `candles.from_python`, which
+//! shows whether normalization, indicators or serialization dominates a
+//! request. Off (the default), a section costs one atomic load.
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static STATS: Mutex<Option<HashMap<&'static str, (u64, u128)>>> = Mutex::new(None);
+
+/// Times a section until dropped.
+pub struct Span {
+    name: &'static str,
+    start: Option<Instant>,
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        if let Some(start) = self.start {
+            let elapsed = start.elapsed().as_nanos();
+            let mut stats = STATS.lock().unwrap();
+            let entry = stats.get_or_insert_with(HashMap::new).entry(self.name).or_default();
+            entry.0 += 1;
+            entry.1 += elapsed;
+        }
+    }
+}
+
+/// Starts timing `name`; the section ends when the returned guard drops.
+pub fn span(name: &'static str) -> Span {
+    let start = ENABLED.load(Ordering::Relaxed).then(Instant::now);
+    Span { name, start }
+}
+
+/// Runs `f` inside a `name` section.
+pub fn timed<R>(name: &'static str, f: impl FnOnce() -> R) -> R {
+    let _span = span(name);
+    f()
+}
+
+/// Start (or restart) profiling.
+///
+/// Args:
+///     reset (bool): Discard previously recorded timings (default True).
+#[pyfunction]
+#[pyo3(name = "profile_begin", signature = (reset=true))]
+pub fn py_profile_begin(reset: bool) {
+    if reset {
+        *STATS.lock().unwrap() = None;
+    }
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Stop profiling, keeping recorded timings for `profile_report`.
+#[pyfunction]
+#[pyo3(name = "profile_end")]
+pub fn py_profile_end() {
+    ENABLED.store(false, Ordering::Relaxed);
+}
+
+/// Timings recorded since `profile_begin`.
+///
+/// Args:
+///     reset (bool): Clear the timings after reading them (default False).
+///
+/// Returns:
+///     Dict[str, dict]: section -> {"calls": int, "total_ns": int,
+///     "mean_ns": float}.
+#[pyfunction]
+#[pyo3(name = "profile_report", signature = (reset=false))]
+pub fn py_profile_report(py: Python<'_>, reset: bool) -> PyResult<Bound<'_, PyDict>> {
+    let stats = {
+        let mut guard = STATS.lock().unwrap();
+        if reset { guard.take() } else { guard.clone() }
+    };
+    let mut rows: Vec<(&str, (u64, u128))> = stats.unwrap_or_default().into_iter().collect();
+    rows.sort_by_key(|(name, _)| *name);
+    let out = PyDict::new(py);
+    for (name, (calls, total_ns)) in rows {
+        let row = PyDict::new(py);
+        row.set_item("calls", calls)?;
+        row.set_item("total_ns", total_ns)?;
+        row.set_item("mean_ns", total_ns as f64 / calls as f64)?;
+        out.set_item(name, row)?;
+    }
+    Ok(out)
+}
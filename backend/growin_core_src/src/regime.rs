@@ -0,0 +1,70 @@
+use pyo3::prelude::*;
+
+use growin_core::regime::{classify_regime, Regime, RegimeConfig};
+
+use crate::candles::PyCandles;
+use crate::{config, errors};
+
+/// Classify each bar into a market regime.
+///
+/// Rules (in priority order):
+///     1. high_vol: realized vol > vol_multiplier x its trailing mean.
+///     2. range: ADX < adx_threshold, or |MA slope| <= slope_threshold.
+///     3. uptrend / downtrend: by the sign of the MA slope.
+///
+/// Args:
+///     candles (Mapping[str, List[float]]): OHLC columns (`close` required;
+///         `high`/`low` improve the ADX).
+///     ma_period (int): Trend SMA period (default 50).
+///     slope_lookback (int): Bars used for the MA slope (default 10).
+///     slope_threshold (float): Minimum MA change per bar for a trend (default 0.0005).
+///     adx_period (int): ADX period (default 14).
+///     adx_threshold (float): Minimum ADX for a trend (default 25).
+///     vol_period (int): Realized-vol window (default 20).
+///     vol_lookback (int): Baseline window for realized vol (default 100).
+///     vol_multiplier (float): High-vol trigger relative to baseline (default 1.5).
+///
+/// Returns:
+///     List[str]: One of "uptrend", "downtrend", "range", "high_vol" or
+///     "unknown" (warm-up) per bar.
+#[pyfunction]
+#[pyo3(name = "classify_regime", signature = (
+    candles, ma_period=50, slope_lookback=10, slope_threshold=0.0005, adx_period=14,
+    adx_threshold=25.0, vol_period=20, vol_lookback=100, vol_multiplier=1.5
+))]
+#[allow(clippy::too_many_arguments)]
+pub fn py_classify_regime(
+    py: Python<'_>,
+    candles: PyCandles,
+    ma_period: usize,
+    slope_lookback: usize,
+    slope_threshold: f64,
+    adx_period: usize,
+    adx_threshold: f64,
+    vol_period: usize,
+    vol_lookback: usize,
+    vol_multiplier: f64,
+) -> PyResult<Vec<&'static str>> {
+    for (name, period) in [
+        ("ma_period", ma_period),
+        ("slope_lookback", slope_lookback),
+        ("adx_period", adx_period),
+        ("vol_period", vol_period),
+        ("vol_lookback", vol_lookback),
+    ] {
+        errors::check_period(name, period)?;
+    }
+    let cfg = RegimeConfig {
+        ma_period,
+        slope_lookback,
+        slope_threshold,
+        adx_period,
+        adx_threshold,
+        vol_period,
+        vol_lookback,
+        vol_multiplier,
+        ddof: config::current().ddof(),
+    };
+    let regimes = py.allow_threads(|| classify_regime(&candles, &cfg));
+    Ok(regimes.iter().map(Regime::as_str).collect())
+}
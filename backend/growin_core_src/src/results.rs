@@ -0,0 +1,219 @@
+//! Result objects of the multi-output indicators.
+//!
+//! Each carries its output series (lists or arrays, matching the input),
+//! the parameters that produced them and `valid_from`, the first index past
+//! every output's warm-up. They still unpack like the tuples they replace:
+//! `macd, signal, hist = calculate_macd(prices)`.
+
+use pyo3::exceptions::PyAttributeError;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyIterator, PyTuple};
+
+/// Splits a kernel's (a, b, c) output tuple.
+pub fn unpack3(py: Python<'_>, out: PyObject) -> PyResult<(PyObject, PyObject, PyObject)> {
+    out.extract(py)
+}
+
+/// Output of `calculate_macd`.
+#[pyclass(module = "growin_core", frozen)]
+pub struct MacdResult {
+    #[pyo3(get)]
+    macd: PyObject,
+    #[pyo3(get)]
+    signal: PyObject,
+    #[pyo3(get)]
+    histogram: PyObject,
+    fast: usize,
+    slow: usize,
+    signal_period: usize,
+    /// First index where the MACD line is seeded and the signal line has seen
+    /// `signal` seeded MACD values.
+    #[pyo3(get)]
+    valid_from: usize,
+}
+
+impl MacdResult {
+    pub fn new(py: Python<'_>, out: PyObject, len: usize, fast: usize, slow: usize, signal: usize) -> PyResult<Self> {
+        let (macd, signal_line, histogram) = unpack3(py, out)?;
+        let valid_from = (fast.max(slow) + signal - 2).min(len);
+        Ok(MacdResult { macd, signal: signal_line, histogram, fast, slow, signal_period: signal, valid_from })
+    }
+}
+
+#[pymethods]
+impl MacdResult {
+    /// {"fast", "slow", "signal"} periods.
+    #[getter]
+    fn params<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let d = PyDict::new(py);
+        d.set_item("fast", self.fast)?;
+        d.set_item("slow", self.slow)?;
+        d.set_item("signal", self.signal_period)?;
+        Ok(d)
+    }
+
+    /// (macd, signal, histogram), for tuple-style unpacking.
+    fn as_tuple<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyTuple>> {
+        PyTuple::new(py, [&self.macd, &self.signal, &self.histogram])
+    }
+
+    fn __iter__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyIterator>> {
+        self.as_tuple(py)?.try_iter()
+    }
+
+    fn __len__(&self) -> usize {
+        3
+    }
+
+    fn __getitem__<'py>(&self, py: Python<'py>, index: isize) -> PyResult<Bound<'py, PyAny>> {
+        self.as_tuple(py)?.as_any().get_item(index)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "MacdResult(fast={}, slow={}, signal={}, valid_from={})",
+            self.fast, self.slow, self.signal_period, self.valid_from
+        )
+    }
+}
+
+/// Output of `calculate_bbands`.
+#[pyclass(module = "growin_core", frozen)]
+pub struct BbandsResult {
+    #[pyo3(get)]
+    upper: PyObject,
+    #[pyo3(get)]
+    middle: PyObject,
+    #[pyo3(get)]
+    lower: PyObject,
+    period: usize,
+    std_dev: f64,
+    /// First index with a full window (`period - 1`).
+    #[pyo3(get)]
+    valid_from: usize,
+}
+
+impl BbandsResult {
+    pub fn new(py: Python<'_>, out: PyObject, len: usize, period: usize, std_dev: f64) -> PyResult<Self> {
+        let (upper, middle, lower) = unpack3(py, out)?;
+        Ok(BbandsResult { upper, middle, lower, period, std_dev, valid_from: (period - 1).min(len) })
+    }
+}
+
+#[pymethods]
+impl BbandsResult {
+    /// {"period", "std_dev"}.
+    #[getter]
+    fn params<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let d = PyDict::new(py);
+        d.set_item("period", self.period)?;
+        d.set_item("std_dev", self.std_dev)?;
+        Ok(d)
+    }
+
+    /// (upper, middle, lower), for tuple-style unpacking.
+    fn as_tuple<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyTuple>> {
+        PyTuple::new(py, [&self.upper, &self.middle, &self.lower])
+    }
+
+    fn __iter__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyIterator>> {
+        self.as_tuple(py)?.try_iter()
+    }
+
+    fn __len__(&self) -> usize {
+        3
+    }
+
+    fn __getitem__<'py>(&self, py: Python<'py>, index: isize) -> PyResult<Bound<'py, PyAny>> {
+        self.as_tuple(py)?.as_any().get_item(index)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("BbandsResult(period={}, std_dev={:?}, valid_from={})", self.period, self.std_dev, self.valid_from)
+    }
+}
+
+/// Output of the multi-series indicators added after MACD and Bollinger
+/// Bands (`calculate_stochastic`, ...): each output is an attribute named
+/// after it, in the order the tuple unpacks.
+#[pyclass(module = "growin_core", frozen)]
+pub struct IndicatorResult {
+    indicator: &'static str,
+    outputs: Vec<(&'static str, PyObject)>,
+    params: Py<PyDict>,
+    /// First index past every output's warm-up.
+    #[pyo3(get)]
+    valid_from: usize,
+}
+
+impl IndicatorResult {
+    /// Splits a kernel's output tuple into the series called `names`.
+    pub fn new(py: Python<'_>, indicator: &'static str, out: PyObject, names: &[&'static str], valid_from: usize) -> PyResult<Self> {
+        let items = out.bind(py).downcast::<PyTuple>()?;
+        let outputs = names.iter().zip(items.iter()).map(|(&name, item)| (name, item.unbind())).collect();
+        Ok(IndicatorResult { indicator, outputs, params: PyDict::new(py).unbind(), valid_from })
+    }
+
+    /// Records a parameter for `.params` and the repr.
+    pub fn param<'py>(self, py: Python<'py>, name: &str, value: impl IntoPyObject<'py>) -> PyResult<Self> {
+        self.params.bind(py).set_item(name, value)?;
+        Ok(self)
+    }
+}
+
+#[pymethods]
+impl IndicatorResult {
+    /// The parameters that produced the outputs.
+    #[getter]
+    fn params<'py>(&self, py: Python<'py>) -> Bound<'py, PyDict> {
+        self.params.bind(py).copy().unwrap()
+    }
+
+    /// Output names, in unpacking order.
+    #[getter]
+    fn names(&self) -> Vec<&'static str> {
+        self.outputs.iter().map(|(name, _)| *name).collect()
+    }
+
+    /// The outputs as a tuple, for tuple-style unpacking.
+    fn as_tuple<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyTuple>> {
+        PyTuple::new(py, self.outputs.iter().map(|(_, v)| v.clone_ref(py)))
+    }
+
+    /// {name: series}.
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let d = PyDict::new(py);
+        for (name, v) in &self.outputs {
+            d.set_item(name, v)?;
+        }
+        Ok(d)
+    }
+
+    fn __getattr__(&self, py: Python<'_>, name: &str) -> PyResult<PyObject> {
+        match self.outputs.iter().find(|(n, _)| *n == name) {
+            Some((_, v)) => Ok(v.clone_ref(py)),
+            None => Err(PyAttributeError::new_err(format!("'{}' has no output '{}'", self.indicator, name))),
+        }
+    }
+
+    fn __iter__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyIterator>> {
+        self.as_tuple(py)?.try_iter()
+    }
+
+    fn __len__(&self) -> usize {
+        self.outputs.len()
+    }
+
+    fn __getitem__<'py>(&self, py: Python<'py>, index: isize) -> PyResult<Bound<'py, PyAny>> {
+        self.as_tuple(py)?.as_any().get_item(index)
+    }
+
+    fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
+        let mut fields = Vec::new();
+        for (name, value) in self.params.bind(py).iter() {
+            fields.push(format!("{}={}", name, value.repr()?));
+        }
+        fields.push(format!("valid_from={}", self.valid_from));
+        Ok(format!("IndicatorResult({}, {})", self.indicator, fields.join(", ")))
+    }
+}
@@ -0,0 +1,136 @@
+//! Python bindings of the trade risk helpers.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::collections::HashMap;
+
+use growin_core::risk::{position_risk, r_multiple, suggest_stop, Side, StopMethod};
+
+use crate::candles::PyCandles;
+use crate::{config, errors};
+
+/// Recommended stop-loss for a new position, with its risk and size.
+///
+/// Args:
+///     candles (Dict[str, List[float]]): OHLC history up to the entry.
+///     entry_price (float): Planned entry.
+///     method (str): "atr" (`multiplier` x ATR from entry), "swing" (beyond
+///         the extreme of the last `period` bars, buffered by `multiplier` x
+///         ATR) or "pct" (`multiplier` percent from entry). Default "atr".
+///     multiplier (float, optional): Defaults to 2 (atr), 0 (swing) or 5 (pct).
+///     side (str): "long" (default) or "short".
+///     period (int): ATR period and swing lookback (default 14).
+///     risk_budget (float, optional): Amount to risk on the trade.
+///
+/// Returns:
+///     Dict: {"stop", "risk_per_share" (1R), "distance_pct", "atr",
+///     "distance_atr", "position_size"}. `position_size` is the share count
+///     whose 1R loss equals `risk_budget` (None without one).
+///
+/// Raises:
+///     ValueError: If the history is too short for the ATR, or the stop
+///         would not be on the losing side of the entry.
+#[pyfunction]
+#[pyo3(
+    name = "suggest_stop",
+    signature = (candles, entry_price, method="atr", multiplier=None, side="long", period=14, risk_budget=None)
+)]
+#[allow(clippy::too_many_arguments)]
+pub fn py_suggest_stop<'py>(
+    py: Python<'py>,
+    candles: PyCandles,
+    entry_price: f64,
+    method: &str,
+    multiplier: Option<f64>,
+    side: &str,
+    period: usize,
+    risk_budget: Option<f64>,
+) -> PyResult<Bound<'py, PyDict>> {
+    errors::check_period("period", period)?;
+    let method: StopMethod = method.parse().map_err(PyValueError::new_err)?;
+    let side: Side = side.parse().map_err(PyValueError::new_err)?;
+    let multiplier = multiplier.unwrap_or_else(|| method.default_multiplier());
+    let s = py
+        .allow_threads(|| suggest_stop(&candles, entry_price, side, method, multiplier, period, risk_budget))
+        .map_err(PyValueError::new_err)?;
+    let out = PyDict::new(py);
+    out.set_item("stop", s.stop)?;
+    out.set_item("risk_per_share", s.risk_per_share)?;
+    out.set_item("distance_pct", s.distance_pct)?;
+    out.set_item("atr", s.atr)?;
+    out.set_item("distance_atr", s.distance_atr)?;
+    out.set_item("position_size", s.position_size)?;
+    Ok(out)
+}
+
+/// Money a position risks to its stop, checked against the account limit.
+///
+/// Args:
+///     entry (float): Entry price.
+///     stop (float): Stop price; below the entry for longs, above for shorts.
+///     quantity (float): Shares held (sign ignored).
+///     account_value (float): Account equity.
+///     max_risk_pct (float, optional): Limit in percent of the account
+///         (default: the configured `max_risk_pct`).
+///
+/// Returns:
+///     Dict: {"side": "long" | "short", "risk_per_share", "risk_amount",
+///     "risk_pct", "max_risk_pct", "exceeds_limit": bool}.
+#[pyfunction]
+#[pyo3(name = "position_risk", signature = (entry, stop, quantity, account_value, max_risk_pct=None))]
+pub fn py_position_risk<'py>(
+    py: Python<'py>,
+    entry: f64,
+    stop: f64,
+    quantity: f64,
+    account_value: f64,
+    max_risk_pct: Option<f64>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let limit = max_risk_pct.unwrap_or_else(|| config::current().max_risk_pct);
+    let r = position_risk(entry, stop, quantity, account_value).map_err(PyValueError::new_err)?;
+    let out = PyDict::new(py);
+    out.set_item("side", if r.side == Side::Long { "long" } else { "short" })?;
+    out.set_item("risk_per_share", r.risk_per_share)?;
+    out.set_item("risk_amount", r.risk_amount)?;
+    out.set_item("risk_pct", r.risk_pct)?;
+    out.set_item("max_risk_pct", limit)?;
+    out.set_item("exceeds_limit", r.risk_pct > limit)?;
+    Ok(out)
+}
+
+/// Journal outcomes in R, multiples of each trade's initial risk.
+///
+/// Args:
+///     trades (List[dict]): Closed trades with "entry", "stop" (the initial
+///         stop) and "exit" prices.
+///
+/// Returns:
+///     Dict: {"r": List[float] per trade (NaN if stop == entry), "total",
+///     "average" (expectancy per trade), "win_rate", "best", "worst"}, the
+///     summaries over trades with a defined R (NaN when there are none).
+#[pyfunction]
+#[pyo3(name = "r_multiples")]
+pub fn py_r_multiples<'py>(py: Python<'py>, trades: Vec<HashMap<String, f64>>) -> PyResult<Bound<'py, PyDict>> {
+    let field = |i: usize, trade: &HashMap<String, f64>, name: &str| {
+        trade.get(name).copied().ok_or_else(|| PyValueError::new_err(format!("trade {} has no '{}'", i, name)))
+    };
+    let r = trades
+        .iter()
+        .enumerate()
+        .map(|(i, t)| Ok(r_multiple(field(i, t, "entry")?, field(i, t, "stop")?, field(i, t, "exit")?)))
+        .collect::<PyResult<Vec<f64>>>()?;
+    let defined: Vec<f64> = r.iter().copied().filter(|x| !x.is_nan()).collect();
+    let n = defined.len() as f64;
+    let (total, wins) = (defined.iter().sum::<f64>(), defined.iter().filter(|&&x| x > 0.0).count() as f64);
+    let or_nan = |v: f64| if defined.is_empty() { f64::NAN } else { v };
+
+    let out = PyDict::new(py);
+    out.set_item("r", r)?;
+    out.set_item("total", total)?;
+    out.set_item("average", or_nan(total / n))?;
+    out.set_item("win_rate", or_nan(wins / n))?;
+    out.set_item("best", or_nan(defined.iter().copied().fold(f64::NEG_INFINITY, f64::max)))?;
+    out.set_item("worst", or_nan(defined.iter().copied().fold(f64::INFINITY, f64::min)))?;
+    Ok(out)
+}
@@ -0,0 +1,27 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use growin_core::rules::{evaluate, parse_rule};
+
+use crate::candles::PyCandles;
+
+/// Evaluate a screening/strategy rule over candles.
+///
+/// Supports columns `open high low close volume`, numbers, `+ - * /`,
+/// comparisons `< <= > >= == !=`, `and`/`or`/`not`, parentheses and the
+/// functions `sma ema rsi atr highest lowest prev abs min max crosses_above
+/// crosses_below`. Indicator functions take `(period)` (source = close) or
+/// `(source, period)`; periods must be integer literals.
+///
+/// Args:
+///     candles (Mapping[str, List[float]]): OHLCV columns (`close` required).
+///     rule (str): e.g. "rsi(14) < 30 and close > sma(200) and volume > sma(volume, 20) * 2".
+///
+/// Returns:
+///     List[bool]: Signal per bar. Bars inside an indicator warm-up are False.
+#[pyfunction]
+#[pyo3(name = "evaluate_rules")]
+pub fn py_evaluate_rules(py: Python<'_>, candles: PyCandles, rule: &str) -> PyResult<Vec<bool>> {
+    let expr = parse_rule(rule).map_err(|e| PyValueError::new_err(format!("invalid rule: {}", e)))?;
+    py.allow_threads(|| evaluate(&expr, &candles)).map_err(|e| PyValueError::new_err(format!("invalid rule: {}", e)))
+}
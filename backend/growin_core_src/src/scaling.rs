@@ -0,0 +1,191 @@
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use growin_core::scaling::{FittedScaler, ScalerKind};
+
+/// Base class of the feature scalers. Holds the fitted parameters so that the
+/// exact same transform can be replayed in training and live inference via
+/// `to_dict()` / `Scaler.from_dict()`.
+///
+/// Matrices are row-major `List[List[float]]` (one row per sample), the layout
+/// returned by `build_features`. NaNs are ignored when fitting and passed
+/// through when transforming.
+#[pyclass(subclass, module = "growin_core")]
+pub struct Scaler {
+    kind: ScalerKind,
+    fitted: Option<FittedScaler>,
+}
+
+impl Scaler {
+    fn new(kind: ScalerKind) -> Self {
+        Scaler { kind, fitted: None }
+    }
+
+    fn fitted(&self) -> PyResult<&FittedScaler> {
+        self.fitted
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("scaler is not fitted; call fit() first"))
+    }
+
+    /// Wraps a base `Scaler` in the Python subclass matching its kind.
+    fn into_typed_object(self, py: Python<'_>) -> PyResult<PyObject> {
+        let kind = self.kind.clone();
+        let init = PyClassInitializer::from(self);
+        Ok(match kind {
+            ScalerKind::MinMax { .. } => Py::new(py, init.add_subclass(MinMaxScaler))?.into_any(),
+            ScalerKind::Standard => Py::new(py, init.add_subclass(StandardScaler))?.into_any(),
+            ScalerKind::Robust { .. } => Py::new(py, init.add_subclass(RobustScaler))?.into_any(),
+        })
+    }
+}
+
+/// Extracts a `(lo, hi)` pair from any two-element sequence, since JSON
+/// round-trips turn tuples into lists.
+fn extract_pair(value: &Bound<'_, PyAny>) -> PyResult<(f64, f64)> {
+    match value.extract::<Vec<f64>>()?.as_slice() {
+        &[lo, hi] => Ok((lo, hi)),
+        _ => Err(PyValueError::new_err("expected a (min, max) pair")),
+    }
+}
+
+#[pymethods]
+impl Scaler {
+    /// Fit per-column parameters. Returns self for chaining.
+    fn fit<'py>(mut slf: PyRefMut<'py, Self>, data: Vec<Vec<f64>>) -> PyResult<PyRefMut<'py, Self>> {
+        let fitted = slf.kind.fit(&data).map_err(PyValueError::new_err)?;
+        slf.fitted = Some(fitted);
+        Ok(slf)
+    }
+
+    /// Apply the fitted scaling to a row-major matrix.
+    fn transform(&self, data: Vec<Vec<f64>>) -> PyResult<Vec<Vec<f64>>> {
+        self.fitted()?.transform(&data).map_err(PyValueError::new_err)
+    }
+
+    /// Fit on `data` and return it transformed.
+    fn fit_transform(&mut self, data: Vec<Vec<f64>>) -> PyResult<Vec<Vec<f64>>> {
+        let fitted = self.kind.fit(&data).map_err(PyValueError::new_err)?;
+        let out = fitted.transform(&data).map_err(PyValueError::new_err)?;
+        self.fitted = Some(fitted);
+        Ok(out)
+    }
+
+    /// Map scaled values back to the original units.
+    fn inverse_transform(&self, data: Vec<Vec<f64>>) -> PyResult<Vec<Vec<f64>>> {
+        self.fitted()?.inverse_transform(&data).map_err(PyValueError::new_err)
+    }
+
+    #[getter]
+    fn is_fitted(&self) -> bool {
+        self.fitted.is_some()
+    }
+
+    #[getter]
+    fn center(&self) -> PyResult<Vec<f64>> {
+        Ok(self.fitted()?.center.clone())
+    }
+
+    #[getter]
+    fn scale(&self) -> PyResult<Vec<f64>> {
+        Ok(self.fitted()?.scale.clone())
+    }
+
+    /// Serialize the scaler (kind, options and fitted parameters) to a
+    /// JSON-compatible dict.
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let d = PyDict::new(py);
+        d.set_item("kind", self.kind.name())?;
+        match self.kind {
+            ScalerKind::MinMax { range } => d.set_item("feature_range", range)?,
+            ScalerKind::Robust { quantile_range } => d.set_item("quantile_range", quantile_range)?,
+            ScalerKind::Standard => {}
+        }
+        if let Some(fitted) = &self.fitted {
+            d.set_item("center", fitted.center.clone())?;
+            d.set_item("scale", fitted.scale.clone())?;
+        }
+        Ok(d)
+    }
+
+    /// Rebuild a scaler from `to_dict()` output.
+    #[staticmethod]
+    fn from_dict(py: Python<'_>, params: &Bound<'_, PyDict>) -> PyResult<PyObject> {
+        let get = |key: &str| params.get_item(key);
+        let kind_name: String = get("kind")?
+            .ok_or_else(|| PyValueError::new_err("missing 'kind'"))?
+            .extract()?;
+        let kind = match kind_name.as_str() {
+            "minmax" => ScalerKind::MinMax {
+                range: get("feature_range")?.map(|v| extract_pair(&v)).transpose()?.unwrap_or((0.0, 1.0)),
+            },
+            "standard" => ScalerKind::Standard,
+            "robust" => ScalerKind::Robust {
+                quantile_range: get("quantile_range")?.map(|v| extract_pair(&v)).transpose()?.unwrap_or((25.0, 75.0)),
+            },
+            other => return Err(PyValueError::new_err(format!("Unknown scaler kind '{}'", other))),
+        };
+
+        let center: Option<Vec<f64>> = get("center")?.map(|v| v.extract()).transpose()?;
+        let scale: Option<Vec<f64>> = get("scale")?.map(|v| v.extract()).transpose()?;
+        let fitted = match (center, scale) {
+            (Some(center), Some(scale)) if center.len() == scale.len() => Some(FittedScaler { center, scale }),
+            (None, None) => None,
+            _ => return Err(PyValueError::new_err("'center' and 'scale' must both be present with equal lengths")),
+        };
+
+        Scaler { kind, fitted }.into_typed_object(py)
+    }
+
+    fn __repr__(&self) -> String {
+        let width = self.fitted.as_ref().map_or(0, |f| f.center.len());
+        let fitted = if self.fitted.is_some() { "True" } else { "False" };
+        format!("Scaler(kind='{}', fitted={}, n_features={})", self.kind.name(), fitted, width)
+    }
+}
+
+/// Scale each feature to `feature_range` (default (0, 1)).
+#[pyclass(extends = Scaler, module = "growin_core")]
+pub struct MinMaxScaler;
+
+#[pymethods]
+impl MinMaxScaler {
+    #[new]
+    #[pyo3(signature = (feature_range=(0.0, 1.0)))]
+    fn new(feature_range: (f64, f64)) -> PyResult<(Self, Scaler)> {
+        if feature_range.0 >= feature_range.1 {
+            return Err(PyValueError::new_err("feature_range minimum must be below its maximum"));
+        }
+        Ok((MinMaxScaler, Scaler::new(ScalerKind::MinMax { range: feature_range })))
+    }
+}
+
+/// Standardize each feature to zero mean and unit variance.
+#[pyclass(extends = Scaler, module = "growin_core")]
+pub struct StandardScaler;
+
+#[pymethods]
+impl StandardScaler {
+    #[new]
+    fn new() -> (Self, Scaler) {
+        (StandardScaler, Scaler::new(ScalerKind::Standard))
+    }
+}
+
+/// Center on the median and scale by the inter-quantile range (default
+/// 25th–75th percentiles), which is insensitive to outliers.
+#[pyclass(extends = Scaler, module = "growin_core")]
+pub struct RobustScaler;
+
+#[pymethods]
+impl RobustScaler {
+    #[new]
+    #[pyo3(signature = (quantile_range=(25.0, 75.0)))]
+    fn new(quantile_range: (f64, f64)) -> PyResult<(Self, Scaler)> {
+        let (lo, hi) = quantile_range;
+        if !(0.0..=100.0).contains(&lo) || !(0.0..=100.0).contains(&hi) || lo >= hi {
+            return Err(PyValueError::new_err("quantile_range must satisfy 0 <= q_min < q_max <= 100"));
+        }
+        Ok((RobustScaler, Scaler::new(ScalerKind::Robust { quantile_range })))
+    }
+}
@@ -0,0 +1,219 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::collections::{BTreeMap, HashMap};
+
+use growin_core::scoring::{composite_scores, decayed_sentiment, Normalization, SentimentIndex};
+
+use crate::{aio, config};
+use crate::errors::{self, LengthMismatchError};
+
+/// Orders weighted indicators by name so results never depend on dict order.
+fn weighted_names(weights: &HashMap<String, f64>) -> Vec<(&String, f64)> {
+    let mut names: Vec<(&String, f64)> = weights.iter().map(|(k, &w)| (k, w)).collect();
+    names.sort_by(|a, b| a.0.cmp(b.0));
+    names
+}
+
+/// Combine one ticker's indicator histories into a 0–100 score series.
+///
+/// Each indicator is normalized against its own history, so the latest value
+/// of the result answers "how strong is this ticker now versus its past".
+///
+/// Args:
+///     indicator_values (Dict[str, List[float]]): Aligned indicator series.
+///     weights (Dict[str, float]): Weight per indicator; negative means lower is better.
+///         Indicators without a weight are ignored.
+///     normalization (str): "rank" (percentile) or "zscore" (default "rank").
+///
+/// Returns:
+///     List[float]: Score per observation (NaN where no weighted value is available).
+#[pyfunction]
+#[pyo3(name = "composite_score", signature = (indicator_values, weights, normalization="rank"))]
+pub fn py_composite_score(
+    py: Python<'_>,
+    indicator_values: HashMap<String, Vec<f64>>,
+    weights: HashMap<String, f64>,
+    normalization: &str,
+) -> PyResult<Vec<f64>> {
+    let method: Normalization = normalization.parse().map_err(PyValueError::new_err)?;
+    let mut columns = Vec::new();
+    let mut column_weights = Vec::new();
+    for (name, w) in weighted_names(&weights) {
+        if let Some(series) = indicator_values.get(name) {
+            columns.push(series.clone());
+            column_weights.push(w);
+        }
+    }
+    let n = indicator_values.values().next().map_or(0, |v| v.len());
+    if indicator_values.values().any(|v| v.len() != n) {
+        return Err(LengthMismatchError::new_err("all indicator series must have the same length"));
+    }
+    if columns.is_empty() {
+        return Ok(vec![f64::NAN; n]);
+    }
+    let ddof = config::current().ddof();
+    Ok(py.allow_threads(|| composite_scores(&columns, &column_weights, method, ddof)))
+}
+
+/// Cross-sectional composite score across a universe (the "Growin Score").
+///
+/// Each indicator is normalized across tickers, then combined with `weights`.
+///
+/// Args:
+///     universe (Dict[str, Dict[str, float]]): ticker -> {indicator: latest value}.
+///         Missing indicators are treated as unavailable for that ticker.
+///     weights (Dict[str, float]): Weight per indicator; negative means lower is better.
+///     normalization (str): "rank" (percentile) or "zscore" (default "rank").
+///
+/// Returns:
+///     Dict[str, float]: ticker -> score in [0, 100].
+#[pyfunction]
+#[pyo3(name = "composite_score_batch", signature = (universe, weights, normalization="rank"))]
+pub fn py_composite_score_batch(
+    py: Python<'_>,
+    universe: HashMap<String, HashMap<String, f64>>,
+    weights: HashMap<String, f64>,
+    normalization: &str,
+) -> PyResult<HashMap<String, f64>> {
+    let method: Normalization = normalization.parse().map_err(PyValueError::new_err)?;
+    let ddof = config::current().ddof();
+    Ok(py.allow_threads(|| composite_batch(&universe, &weights, method, ddof)))
+}
+
+/// Cross-sectional composite score per ticker.
+fn composite_batch(
+    universe: &HashMap<String, HashMap<String, f64>>,
+    weights: &HashMap<String, f64>,
+    method: Normalization,
+    ddof: usize,
+) -> HashMap<String, f64> {
+    let mut tickers: Vec<&String> = universe.keys().collect();
+    tickers.sort();
+
+    let names = weighted_names(weights);
+    let columns: Vec<Vec<f64>> = names
+        .iter()
+        .map(|(name, _)| {
+            tickers
+                .iter()
+                .map(|t| universe[*t].get(*name).copied().unwrap_or(f64::NAN))
+                .collect()
+        })
+        .collect();
+    let column_weights: Vec<f64> = names.iter().map(|(_, w)| *w).collect();
+
+    let scores = if columns.is_empty() {
+        vec![f64::NAN; tickers.len()]
+    } else {
+        composite_scores(&columns, &column_weights, method, ddof)
+    };
+    tickers.into_iter().cloned().zip(scores).collect()
+}
+
+/// Awaitable `composite_score_batch`; same arguments and result.
+#[pyfunction]
+#[pyo3(name = "composite_score_batch_async", signature = (universe, weights, normalization="rank".to_string()))]
+pub fn py_composite_score_batch_async(
+    py: Python<'_>,
+    universe: HashMap<String, HashMap<String, f64>>,
+    weights: HashMap<String, f64>,
+    normalization: String,
+) -> PyResult<PyObject> {
+    let method: Normalization = normalization.parse().map_err(PyValueError::new_err)?;
+    let ddof = config::current().ddof();
+    aio::spawn(
+        py,
+        move || composite_batch(&universe, &weights, method, ddof),
+        |py, scores| Ok(scores.into_pyobject(py)?.into_any().unbind()),
+    )
+}
+
+fn sentiment_to_dict<'py>(py: Python<'py>, index: SentimentIndex) -> PyResult<Bound<'py, PyDict>> {
+    let d = PyDict::new(py);
+    d.set_item("score", index.score)?;
+    d.set_item("weight", index.weight)?;
+    d.set_item("articles", index.values.len())?;
+    d.set_item("timestamps", index.timestamps)?;
+    d.set_item("index", index.values)?;
+    Ok(d)
+}
+
+/// Combine per-article sentiment into a time-decayed sentiment index.
+///
+/// Each article counts with its source's weight, halved every
+/// `half_life_hours` of age, so the index after an article is the decayed
+/// weighted mean of every article so far (in the scores' own scale).
+///
+/// Args:
+///     scores (List[float]): Sentiment per article, e.g. in [-1, 1]; NaN
+///         scores are skipped.
+///     timestamps (List[int]): Publication times in epoch seconds, in any order.
+///     half_life_hours (float): Age at which an article counts half.
+///     tickers (List[str], optional): Ticker per article, to index each
+///         ticker separately.
+///     sources (List[str], optional): Source per article, for `source_weights`.
+///     source_weights (Dict[str, float], optional): Weight per source;
+///         unlisted sources weigh 1.
+///     as_of (int, optional): Time to score at, in epoch seconds (default:
+///         the latest article); later articles are ignored.
+///
+/// Returns:
+///     Dict: {"score", "weight", "articles", "timestamps", "index"}, with
+///     "index" the value after each article (ascending "timestamps") and
+///     "weight" the decayed weight behind "score" at `as_of`, for judging
+///     staleness. With `tickers`, {ticker: that dict}.
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(
+    name = "aggregate_sentiment",
+    signature = (scores, timestamps, half_life_hours, tickers=None, sources=None, source_weights=None, as_of=None)
+)]
+pub fn py_aggregate_sentiment<'py>(
+    py: Python<'py>,
+    scores: Vec<f64>,
+    timestamps: Vec<i64>,
+    half_life_hours: f64,
+    tickers: Option<Vec<String>>,
+    sources: Option<Vec<String>>,
+    source_weights: Option<HashMap<String, f64>>,
+    as_of: Option<i64>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let mut lengths = vec![("scores", scores.len()), ("timestamps", timestamps.len())];
+    lengths.extend(tickers.as_ref().map(|t| ("tickers", t.len())));
+    lengths.extend(sources.as_ref().map(|s| ("sources", s.len())));
+    errors::check_lengths(&lengths)?;
+    let weights: Vec<f64> = match (&sources, &source_weights) {
+        (Some(sources), Some(table)) => sources.iter().map(|s| table.get(s).copied().unwrap_or(1.0)).collect(),
+        _ => vec![1.0; scores.len()],
+    };
+    let half_life_secs = half_life_hours * 3600.0;
+
+    let Some(tickers) = tickers else {
+        let index = py
+            .allow_threads(|| decayed_sentiment(&scores, &timestamps, &weights, half_life_secs, as_of))
+            .map_err(PyValueError::new_err)?;
+        return sentiment_to_dict(py, index);
+    };
+    let mut groups: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+    for (i, ticker) in tickers.iter().enumerate() {
+        groups.entry(ticker).or_default().push(i);
+    }
+    let indices = py
+        .allow_threads(|| {
+            groups
+                .into_iter()
+                .map(|(ticker, rows)| {
+                    let pick = |column: &[f64]| rows.iter().map(|&i| column[i]).collect::<Vec<_>>();
+                    let ts: Vec<i64> = rows.iter().map(|&i| timestamps[i]).collect();
+                    Ok((ticker, decayed_sentiment(&pick(&scores), &ts, &pick(&weights), half_life_secs, as_of)?))
+                })
+                .collect::<Result<Vec<_>, String>>()
+        })
+        .map_err(PyValueError::new_err)?;
+    let out = PyDict::new(py);
+    for (ticker, index) in indices {
+        out.set_item(ticker, sentiment_to_dict(py, index)?)?;
+    }
+    Ok(out)
+}
@@ -0,0 +1,545 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use growin_core::candles::Candles;
+use growin_core::rules;
+use growin_core::scoring;
+use growin_core::screening::{
+    detect_price_gaps, high_low_metrics, ma_cross, momentum_ranks, relative_strength, screen_ticker, MaCross,
+    MomentumRank, PriceGap, RelativeStrength, ScreenFilter, ScreenMatch,
+};
+
+use crate::aio;
+use crate::candles::PyCandles;
+use crate::config;
+use crate::errors::{self, InvalidPeriodError};
+use crate::store::MarketDataStore;
+
+/// Sorts a universe by ticker so results are independent of dict ordering.
+fn sorted_universe<T>(universe: HashMap<String, T>) -> Vec<(String, T)> {
+    let mut items: Vec<(String, T)> = universe.into_iter().collect();
+    items.sort_by(|a, b| a.0.cmp(&b.0));
+    items
+}
+
+/// Rank a universe by blended cross-sectional momentum.
+///
+/// Args:
+///     prices (Dict[str, List[float]]): ticker -> closing prices (oldest first).
+///     lookbacks (List[int]): Momentum horizons in bars (default [21, 63, 126, 252]).
+///     skip_recent (int): Most recent bars excluded to avoid short-term reversal (default 21).
+///
+/// Returns:
+///     Dict[str, dict]: ticker -> {"score": float 0–100, "rank": int | None (1 = best),
+///     "returns": {lookback: float}}.
+#[pyfunction]
+#[pyo3(name = "momentum_ranks", signature = (prices, lookbacks=vec![21, 63, 126, 252], skip_recent=21))]
+pub fn py_momentum_ranks<'py>(
+    py: Python<'py>,
+    prices: HashMap<String, Vec<f64>>,
+    lookbacks: Vec<usize>,
+    skip_recent: usize,
+) -> PyResult<Bound<'py, PyDict>> {
+    check_lookbacks(&lookbacks)?;
+    let (tickers, series): (Vec<String>, Vec<Vec<f64>>) = sorted_universe(prices).into_iter().unzip();
+    let results = py.allow_threads(|| momentum_ranks(&series, &lookbacks, skip_recent));
+    momentum_to_py(py, tickers, &lookbacks, results)
+}
+
+fn check_lookbacks(lookbacks: &[usize]) -> PyResult<()> {
+    if lookbacks.is_empty() || lookbacks.contains(&0) {
+        return Err(InvalidPeriodError::new_err("lookbacks must be a non-empty list of positive integers"));
+    }
+    Ok(())
+}
+
+fn momentum_to_py<'py>(
+    py: Python<'py>,
+    tickers: Vec<String>,
+    lookbacks: &[usize],
+    results: Vec<MomentumRank>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let out = PyDict::new(py);
+    for (ticker, r) in tickers.into_iter().zip(results) {
+        let entry = PyDict::new(py);
+        entry.set_item("score", r.score)?;
+        entry.set_item("rank", r.rank)?;
+        let returns = PyDict::new(py);
+        for (lookback, ret) in lookbacks.iter().zip(&r.returns) {
+            returns.set_item(lookback, ret)?;
+        }
+        entry.set_item("returns", returns)?;
+        out.set_item(ticker, entry)?;
+    }
+    Ok(out)
+}
+
+/// Awaitable `momentum_ranks`; same arguments and result.
+#[pyfunction]
+#[pyo3(name = "momentum_ranks_async", signature = (prices, lookbacks=vec![21, 63, 126, 252], skip_recent=21))]
+pub fn py_momentum_ranks_async(
+    py: Python<'_>,
+    prices: HashMap<String, Vec<f64>>,
+    lookbacks: Vec<usize>,
+    skip_recent: usize,
+) -> PyResult<PyObject> {
+    check_lookbacks(&lookbacks)?;
+    let (tickers, series): (Vec<String>, Vec<Vec<f64>>) = sorted_universe(prices).into_iter().unzip();
+    let columns = lookbacks.clone();
+    aio::spawn(
+        py,
+        move || momentum_ranks(&series, &lookbacks, skip_recent),
+        move |py, results| Ok(momentum_to_py(py, tickers, &columns, results)?.into_any().unbind()),
+    )
+}
+
+fn gaps_to_py<'py>(py: Python<'py>, candles: &Candles, gaps: &[PriceGap]) -> PyResult<Vec<Bound<'py, PyDict>>> {
+    gaps.iter()
+        .map(|g| {
+            let d = PyDict::new(py);
+            d.set_item("index", g.index)?;
+            d.set_item("type", if g.direction == 1 { "gap_up" } else { "gap_down" })?;
+            d.set_item("gap_pct", g.gap_pct)?;
+            d.set_item("prev_close", g.prev_close)?;
+            d.set_item("open", g.open)?;
+            d.set_item("filled", g.fill_index.is_some())?;
+            d.set_item("fill_index", g.fill_index)?;
+            d.set_item("bars_to_fill", g.bars_to_fill())?;
+            let ts = |i: usize| candles.timestamps.get(i).copied();
+            d.set_item("timestamp", ts(g.index))?;
+            d.set_item("fill_timestamp", g.fill_index.and_then(ts))?;
+            Ok(d)
+        })
+        .collect()
+}
+
+/// Detect opening price gaps and whether they filled.
+///
+/// Args:
+///     candles (Mapping[str, List[float]]): OHLC columns, optional `timestamp`.
+///     min_gap_pct (float): Minimum |open / prev_close - 1| in percent (default 2.0).
+///     max_fill_bars (int, optional): Only count fills within this many bars.
+///
+/// Returns:
+///     List[dict]: {"index", "type" ("gap_up" | "gap_down"), "gap_pct", "prev_close",
+///     "open", "filled", "fill_index", "bars_to_fill", "timestamp", "fill_timestamp"}.
+#[pyfunction]
+#[pyo3(name = "detect_price_gaps", signature = (candles, min_gap_pct=2.0, max_fill_bars=None))]
+pub fn py_detect_price_gaps<'py>(
+    py: Python<'py>,
+    candles: PyCandles,
+    min_gap_pct: f64,
+    max_fill_bars: Option<usize>,
+) -> PyResult<Vec<Bound<'py, PyDict>>> {
+    let gaps = py.allow_threads(|| detect_price_gaps(&candles, min_gap_pct, max_fill_bars));
+    gaps_to_py(py, &candles, &gaps)
+}
+
+/// Run `detect_price_gaps` across a universe in parallel.
+///
+/// Args:
+///     universe (Dict[str, Mapping[str, List[float]]]): ticker -> candles.
+///     min_gap_pct (float): Minimum gap in percent (default 2.0).
+///     max_fill_bars (int, optional): Only count fills within this many bars.
+///
+/// Returns:
+///     Dict[str, List[dict]]: ticker -> gaps, same layout as `detect_price_gaps`.
+#[pyfunction]
+#[pyo3(name = "detect_price_gaps_batch", signature = (universe, min_gap_pct=2.0, max_fill_bars=None))]
+pub fn py_detect_price_gaps_batch<'py>(
+    py: Python<'py>,
+    universe: HashMap<String, PyCandles>,
+    min_gap_pct: f64,
+    max_fill_bars: Option<usize>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let items = sorted_universe(universe);
+    let results = py.allow_threads(|| gaps_batch(&items, min_gap_pct, max_fill_bars));
+    gaps_batch_to_py(py, &items, &results)
+}
+
+fn gaps_batch(items: &[(String, PyCandles)], min_gap_pct: f64, max_fill_bars: Option<usize>) -> Vec<Vec<PriceGap>> {
+    items.par_iter().map(|(_, c)| detect_price_gaps(c, min_gap_pct, max_fill_bars)).collect()
+}
+
+fn gaps_batch_to_py<'py>(py: Python<'py>, items: &[(String, PyCandles)], results: &[Vec<PriceGap>]) -> PyResult<Bound<'py, PyDict>> {
+    let out = PyDict::new(py);
+    for ((ticker, candles), gaps) in items.iter().zip(results) {
+        out.set_item(ticker, gaps_to_py(py, candles, gaps)?)?;
+    }
+    Ok(out)
+}
+
+/// Awaitable `detect_price_gaps_batch`; same arguments and result.
+#[pyfunction]
+#[pyo3(name = "detect_price_gaps_batch_async", signature = (universe, min_gap_pct=2.0, max_fill_bars=None))]
+pub fn py_detect_price_gaps_batch_async(
+    py: Python<'_>,
+    universe: HashMap<String, PyCandles>,
+    min_gap_pct: f64,
+    max_fill_bars: Option<usize>,
+) -> PyResult<PyObject> {
+    let items = sorted_universe(universe);
+    aio::spawn(
+        py,
+        move || {
+            let results = gaps_batch(&items, min_gap_pct, max_fill_bars);
+            (items, results)
+        },
+        |py, (items, results)| Ok(gaps_batch_to_py(py, &items, &results)?.into_any().unbind()),
+    )
+}
+
+/// Parallel universe screener.
+///
+/// Load a universe once, register filters, then `run()` to get the tickers
+/// that pass every filter along with the values that matched:
+///
+/// ```python
+/// s = Screener(universe, fundamentals={"AAPL": {"pe": 28.0}})
+/// s.add_indicator_filter("rsi(14)", "<", 30).add_fundamental_filter("pe", "<", 35)
+/// s.add_liquidity_floor(min_dollar_volume=5e6)
+/// hits = s.run()  # {"AAPL": {"rsi(14)": 27.1, "pe": 28.0, "avg_dollar_volume": 8.2e6}}
+/// ```
+#[pyclass(module = "growin_core")]
+pub struct Screener {
+    // Shared so that `run_async` can screen a snapshot without the GIL.
+    universe: Arc<Vec<(String, Candles)>>,
+    fundamentals: Arc<Fundamentals>,
+    filters: Vec<ScreenFilter>,
+}
+
+type Fundamentals = HashMap<String, HashMap<String, f64>>;
+
+impl Screener {
+    /// Adds or replaces tickers, keeping the universe sorted by ticker.
+    fn merge(&mut self, universe: impl IntoIterator<Item = (String, Candles)>) {
+        let current = Arc::unwrap_or_clone(std::mem::take(&mut self.universe));
+        let mut merged: HashMap<String, Candles> = current.into_iter().collect();
+        merged.extend(universe);
+        self.universe = Arc::new(sorted_universe(merged));
+    }
+}
+
+fn screen(
+    universe: &[(String, Candles)],
+    fundamentals: &Fundamentals,
+    filters: &[ScreenFilter],
+) -> Vec<Result<Option<ScreenMatch>, String>> {
+    universe
+        .par_iter()
+        .map(|(ticker, candles)| {
+            screen_ticker(candles, fundamentals.get(ticker), filters).map_err(|e| format!("{}: {}", ticker, e))
+        })
+        .collect()
+}
+
+fn screen_to_py<'py>(
+    py: Python<'py>,
+    universe: &[(String, Candles)],
+    results: Vec<Result<Option<ScreenMatch>, String>>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let out = PyDict::new(py);
+    for ((ticker, _), result) in universe.iter().zip(results) {
+        if let Some(matched) = result.map_err(PyValueError::new_err)? {
+            let values = PyDict::new(py);
+            for (name, value) in matched {
+                values.set_item(name, value)?;
+            }
+            out.set_item(ticker, values)?;
+        }
+    }
+    Ok(out)
+}
+
+#[pymethods]
+impl Screener {
+    #[new]
+    #[pyo3(signature = (universe=None, fundamentals=None))]
+    fn new(universe: Option<HashMap<String, PyCandles>>, fundamentals: Option<HashMap<String, HashMap<String, f64>>>) -> Self {
+        let mut screener =
+            Screener { universe: Arc::default(), fundamentals: Arc::new(fundamentals.unwrap_or_default()), filters: Vec::new() };
+        if let Some(universe) = universe {
+            screener.load(universe);
+        }
+        screener
+    }
+
+    /// Add or replace tickers in the universe.
+    fn load(&mut self, universe: HashMap<String, PyCandles>) {
+        self.merge(universe.into_iter().map(|(t, c)| (t, c.candles)));
+    }
+
+    /// Add or replace tickers with the bars currently held in a
+    /// `MarketDataStore` (default: every ticker in it).
+    #[pyo3(signature = (store, tickers=None))]
+    fn load_store(&mut self, py: Python<'_>, store: &MarketDataStore, tickers: Option<Vec<String>>) {
+        let snapshot = py.allow_threads(|| store.snapshot(tickers.as_deref()));
+        self.merge(snapshot.into_iter().map(|(t, bars)| (t, (*bars).clone())));
+    }
+
+    /// Add or replace per-ticker fundamentals ({ticker: {field: value}}).
+    fn set_fundamentals(&mut self, fundamentals: HashMap<String, HashMap<String, f64>>) {
+        Arc::make_mut(&mut self.fundamentals).extend(fundamentals);
+    }
+
+    /// Require the latest value of a rule-language expression (e.g. "rsi(14)",
+    /// "close / sma(200) - 1") to satisfy `op threshold`.
+    #[pyo3(signature = (expression, op, threshold, name=None))]
+    fn add_indicator_filter<'py>(
+        mut slf: PyRefMut<'py, Self>,
+        expression: &str,
+        op: &str,
+        threshold: f64,
+        name: Option<String>,
+    ) -> PyResult<PyRefMut<'py, Self>> {
+        let expr = rules::parse_rule(expression)
+            .map_err(|e| PyValueError::new_err(format!("invalid expression: {}", e)))?;
+        let op = op.parse().map_err(PyValueError::new_err)?;
+        let name = name.unwrap_or_else(|| expression.trim().to_string());
+        slf.filters.push(ScreenFilter::Indicator { name, expr, op, threshold });
+        Ok(slf)
+    }
+
+    /// Require a fundamentals field to satisfy `op threshold`. Tickers
+    /// without the field never match.
+    fn add_fundamental_filter<'py>(
+        mut slf: PyRefMut<'py, Self>,
+        field: String,
+        op: &str,
+        threshold: f64,
+    ) -> PyResult<PyRefMut<'py, Self>> {
+        let op = op.parse().map_err(PyValueError::new_err)?;
+        slf.filters.push(ScreenFilter::Fundamental { field, op, threshold });
+        Ok(slf)
+    }
+
+    /// Require minimum average share and/or dollar volume over `window` bars.
+    #[pyo3(signature = (min_volume=None, min_dollar_volume=None, window=20))]
+    fn add_liquidity_floor<'py>(
+        mut slf: PyRefMut<'py, Self>,
+        min_volume: Option<f64>,
+        min_dollar_volume: Option<f64>,
+        window: usize,
+    ) -> PyResult<PyRefMut<'py, Self>> {
+        errors::check_period("window", window)?;
+        if let Some(floor) = min_volume {
+            slf.filters.push(ScreenFilter::MinVolume { window, floor });
+        }
+        if let Some(floor) = min_dollar_volume {
+            slf.filters.push(ScreenFilter::MinDollarVolume { window, floor });
+        }
+        Ok(slf)
+    }
+
+    /// Remove every registered filter.
+    fn clear_filters(&mut self) {
+        self.filters.clear();
+    }
+
+    /// Screen the universe in parallel.
+    ///
+    /// Returns:
+    ///     Dict[str, Dict[str, float]]: matching ticker -> {filter name: value}.
+    fn run<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let results = py.allow_threads(|| screen(&self.universe, &self.fundamentals, &self.filters));
+        screen_to_py(py, &self.universe, results)
+    }
+
+    /// Awaitable `run()`. Screens the universe and filters as they are when
+    /// called; later changes do not affect the pending result.
+    fn run_async(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let (universe, fundamentals, filters) = (self.universe.clone(), self.fundamentals.clone(), self.filters.clone());
+        aio::spawn(
+            py,
+            move || {
+                let results = screen(&universe, &fundamentals, &filters);
+                (universe, results)
+            },
+            |py, (universe, results)| Ok(screen_to_py(py, &universe, results)?.into_any().unbind()),
+        )
+    }
+
+    fn __len__(&self) -> usize {
+        self.universe.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Screener(tickers={}, filters={})", self.universe.len(), self.filters.len())
+    }
+}
+
+/// Rolling 52-week (by default) high/low distance metrics.
+///
+/// Args:
+///     prices (List[float]): Closing prices.
+///     window (int | None): Lookback in bars (default: the configured
+///         `annualization`, 252 bars or one trading year).
+///
+/// Returns:
+///     dict: {"high", "low", "pct_from_high", "pct_from_low": List[float],
+///     "new_high", "new_low": List[bool]}. Values are NaN (flags False) until
+///     `window` bars are available.
+#[pyfunction]
+#[pyo3(name = "high_low_metrics", signature = (prices, window=None))]
+pub fn py_high_low_metrics<'py>(py: Python<'py>, prices: Vec<f64>, window: Option<usize>) -> PyResult<Bound<'py, PyDict>> {
+    let window = window.unwrap_or_else(|| config::current().annualization);
+    errors::check_period("window", window)?;
+    let m = py.allow_threads(|| high_low_metrics(&prices, window));
+    let out = PyDict::new(py);
+    out.set_item("high", m.high)?;
+    out.set_item("low", m.low)?;
+    out.set_item("pct_from_high", m.pct_from_high)?;
+    out.set_item("pct_from_low", m.pct_from_low)?;
+    out.set_item("new_high", m.new_high)?;
+    out.set_item("new_low", m.new_low)?;
+    Ok(out)
+}
+
+/// Golden/death cross screen across a universe.
+///
+/// Args:
+///     prices (Dict[str, List[float]]): ticker -> closing prices.
+///     fast (int): Fast SMA period (default 50).
+///     slow (int): Slow SMA period (default 200).
+///     lookback (int): Report crosses within this many most recent bars (default 5).
+///
+/// Returns:
+///     Dict[str, dict]: ticker -> {"cross": "golden" | "death" | None,
+///     "bars_ago": int | None, "separation_pct", "fast", "slow"}.
+#[pyfunction]
+#[pyo3(name = "ma_cross_screen", signature = (prices, fast=50, slow=200, lookback=5))]
+pub fn py_ma_cross_screen<'py>(
+    py: Python<'py>,
+    prices: HashMap<String, Vec<f64>>,
+    fast: usize,
+    slow: usize,
+    lookback: usize,
+) -> PyResult<Bound<'py, PyDict>> {
+    check_ma_periods(fast, slow)?;
+    let (tickers, series): (Vec<String>, Vec<Vec<f64>>) = sorted_universe(prices).into_iter().unzip();
+    let results = py.allow_threads(|| ma_crosses(&series, fast, slow, lookback));
+    ma_crosses_to_py(py, tickers, results)
+}
+
+fn check_ma_periods(fast: usize, slow: usize) -> PyResult<()> {
+    if fast == 0 || slow == 0 || fast >= slow {
+        return Err(InvalidPeriodError::new_err("periods must satisfy 0 < fast < slow"));
+    }
+    Ok(())
+}
+
+fn ma_crosses(series: &[Vec<f64>], fast: usize, slow: usize, lookback: usize) -> Vec<MaCross> {
+    series.par_iter().map(|p| ma_cross(p, fast, slow, lookback)).collect()
+}
+
+fn ma_crosses_to_py(py: Python<'_>, tickers: Vec<String>, results: Vec<MaCross>) -> PyResult<Bound<'_, PyDict>> {
+    let out = PyDict::new(py);
+    for (ticker, r) in tickers.into_iter().zip(results) {
+        let entry = PyDict::new(py);
+        let cross = match r.cross {
+            1 => Some("golden"),
+            -1 => Some("death"),
+            _ => None,
+        };
+        entry.set_item("cross", cross)?;
+        entry.set_item("bars_ago", r.bars_ago)?;
+        entry.set_item("separation_pct", r.separation_pct)?;
+        entry.set_item("fast", r.fast)?;
+        entry.set_item("slow", r.slow)?;
+        out.set_item(ticker, entry)?;
+    }
+    Ok(out)
+}
+
+/// Awaitable `ma_cross_screen`; same arguments and result.
+#[pyfunction]
+#[pyo3(name = "ma_cross_screen_async", signature = (prices, fast=50, slow=200, lookback=5))]
+pub fn py_ma_cross_screen_async(
+    py: Python<'_>,
+    prices: HashMap<String, Vec<f64>>,
+    fast: usize,
+    slow: usize,
+    lookback: usize,
+) -> PyResult<PyObject> {
+    check_ma_periods(fast, slow)?;
+    let (tickers, series): (Vec<String>, Vec<Vec<f64>>) = sorted_universe(prices).into_iter().unzip();
+    aio::spawn(
+        py,
+        move || ma_crosses(&series, fast, slow, lookback),
+        |py, results| Ok(ma_crosses_to_py(py, tickers, results)?.into_any().unbind()),
+    )
+}
+
+/// Relative strength versus a benchmark, ranked across the universe.
+///
+/// The rating follows the IBD convention: a 1–99 percentile of the weighted
+/// 3/6/9/12-month return (40/20/20/20), so 99 marks the top 1% of leaders.
+///
+/// Args:
+///     prices (Dict[str, List[float]]): ticker -> closing prices.
+///     benchmark_prices (List[float]): Index closes, aligned on the last bar.
+///     window (int): Bars used for the RS-line slope (default 63).
+///
+/// Returns:
+///     Dict[str, dict]: ticker -> {"rs_line": List[float], "slope": float
+///     (% per bar), "score": float, "rating": int | None}.
+#[pyfunction]
+#[pyo3(name = "relative_strength", signature = (prices, benchmark_prices, window=63))]
+pub fn py_relative_strength<'py>(
+    py: Python<'py>,
+    prices: HashMap<String, Vec<f64>>,
+    benchmark_prices: Vec<f64>,
+    window: usize,
+) -> PyResult<Bound<'py, PyDict>> {
+    errors::check_min_period("window", window, 2)?;
+    let (tickers, series): (Vec<String>, Vec<Vec<f64>>) = sorted_universe(prices).into_iter().unzip();
+    let ranked = py.allow_threads(|| ranked_relative_strength(&series, &benchmark_prices, window));
+    relative_strength_to_py(py, tickers, ranked)
+}
+
+/// Relative strength per series and its percentile rank across them.
+fn ranked_relative_strength(series: &[Vec<f64>], benchmark: &[f64], window: usize) -> (Vec<RelativeStrength>, Vec<f64>) {
+    let results: Vec<RelativeStrength> = series.par_iter().map(|p| relative_strength(p, benchmark, window)).collect();
+    let ranks = scoring::percentile_ranks(&results.iter().map(|r| r.score).collect::<Vec<_>>());
+    (results, ranks)
+}
+
+fn relative_strength_to_py(
+    py: Python<'_>,
+    tickers: Vec<String>,
+    (results, ranks): (Vec<RelativeStrength>, Vec<f64>),
+) -> PyResult<Bound<'_, PyDict>> {
+    let out = PyDict::new(py);
+    for ((ticker, r), pct) in tickers.into_iter().zip(results).zip(ranks) {
+        let entry = PyDict::new(py);
+        let rating = if pct.is_nan() { None } else { Some(1 + (98.0 * pct).round() as u8) };
+        entry.set_item("rs_line", r.rs_line)?;
+        entry.set_item("slope", r.slope)?;
+        entry.set_item("score", r.score)?;
+        entry.set_item("rating", rating)?;
+        out.set_item(ticker, entry)?;
+    }
+    Ok(out)
+}
+
+/// Awaitable `relative_strength`; same arguments and result.
+#[pyfunction]
+#[pyo3(name = "relative_strength_async", signature = (prices, benchmark_prices, window=63))]
+pub fn py_relative_strength_async(
+    py: Python<'_>,
+    prices: HashMap<String, Vec<f64>>,
+    benchmark_prices: Vec<f64>,
+    window: usize,
+) -> PyResult<PyObject> {
+    errors::check_min_period("window", window, 2)?;
+    let (tickers, series): (Vec<String>, Vec<Vec<f64>>) = sorted_universe(prices).into_iter().unzip();
+    aio::spawn(
+        py,
+        move || ranked_relative_strength(&series, &benchmark_prices, window),
+        |py, ranked| Ok(relative_strength_to_py(py, tickers, ranked)?.into_any().unbind()),
+    )
+}
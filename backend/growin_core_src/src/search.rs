@@ -0,0 +1,114 @@
+//! Python bindings of the symbol search index.
+
+use pyo3::exceptions::PyTypeError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use growin_core::search::{self, SymbolEntry};
+
+use crate::instrument::Instrument;
+
+/// Entries from a {symbol: name} dict or an iterable of (symbol, name)
+/// pairs or `Instrument`s.
+fn extract_entries(symbols: &Bound<'_, PyAny>) -> PyResult<Vec<SymbolEntry>> {
+    if let Ok(d) = symbols.downcast::<PyDict>() {
+        return d
+            .iter()
+            .map(|(symbol, name)| {
+                let name: Option<String> = name.extract()?;
+                Ok(SymbolEntry { symbol: symbol.extract()?, name: name.unwrap_or_default() })
+            })
+            .collect();
+    }
+    symbols
+        .try_iter()?
+        .map(|item| {
+            let item = item?;
+            if let Ok(instrument) = item.downcast::<Instrument>() {
+                let inner = &instrument.get().inner;
+                return Ok(SymbolEntry { symbol: inner.symbol.clone(), name: inner.name.clone().unwrap_or_default() });
+            }
+            let (symbol, name): (String, Option<String>) = item
+                .extract()
+                .map_err(|_| PyTypeError::new_err("symbols must be (symbol, name) pairs or Instruments"))?;
+            Ok(SymbolEntry { symbol, name: name.unwrap_or_default() })
+        })
+        .collect()
+}
+
+/// Fast prefix search over tickers and company names, for search boxes.
+///
+/// A query matches tickers and names starting with it and names with a
+/// word starting with it, case-insensitively. Lookups are two binary
+/// searches, so they stay in the microseconds with tens of thousands of
+/// instruments loaded.
+///
+/// ```python
+/// index = SymbolIndex({"AAPL": "Apple Inc.", "VOD.L": "Vodafone Group"})
+/// index.search("vod")    # [{"symbol": "VOD.L", "name": "Vodafone Group", "match": "symbol"}]
+/// index.search("group")  # matched on a word of the name
+/// ```
+///
+/// Args:
+///     symbols (dict | Iterable, optional): {symbol: name}, or (symbol,
+///         name) pairs or `Instrument`s.
+#[pyclass(module = "growin_core")]
+#[derive(Default)]
+pub struct SymbolIndex {
+    index: search::SymbolIndex,
+}
+
+#[pymethods]
+impl SymbolIndex {
+    #[new]
+    #[pyo3(signature = (symbols=None))]
+    fn new(symbols: Option<&Bound<'_, PyAny>>) -> PyResult<Self> {
+        let mut out = SymbolIndex::default();
+        if let Some(symbols) = symbols {
+            out.extend(symbols)?;
+        }
+        Ok(out)
+    }
+
+    /// Add instruments, in the forms the constructor takes. Add the whole
+    /// universe in one call where possible; each call re-sorts the index.
+    fn extend(&mut self, symbols: &Bound<'_, PyAny>) -> PyResult<()> {
+        let entries = extract_entries(symbols)?;
+        symbols.py().allow_threads(|| self.index.extend(entries));
+        Ok(())
+    }
+
+    /// Ranked matches for a prefix.
+    ///
+    /// Args:
+    ///     prefix (str): What the user has typed so far.
+    ///     limit (int): Most matches to return (default 10).
+    ///
+    /// Returns:
+    ///     List[dict]: {"symbol", "name", "match"}, best first. `match` is
+    ///     "exact" (the whole ticker), "symbol" (a ticker prefix), "name" (a
+    ///     name prefix) or "word" (a later word of the name); ties go to
+    ///     the shorter ticker.
+    #[pyo3(signature = (prefix, limit=10))]
+    fn search<'py>(&self, py: Python<'py>, prefix: &str, limit: usize) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        self.index
+            .search(prefix, limit)
+            .into_iter()
+            .map(|hit| {
+                let d = PyDict::new(py);
+                d.set_item("symbol", &hit.entry.symbol)?;
+                d.set_item("name", &hit.entry.name)?;
+                d.set_item("match", hit.kind.name())?;
+                Ok(d)
+            })
+            .collect()
+    }
+
+    fn __len__(&self) -> usize {
+        self.index.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("SymbolIndex(symbols={})", self.index.len())
+    }
+}
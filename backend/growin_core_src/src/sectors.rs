@@ -0,0 +1,145 @@
+//! Python bindings of the sector classification.
+
+use pyo3::exceptions::{PyOSError, PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::collections::HashMap;
+
+use growin_core::sectors::{self, Classification};
+
+use crate::errors::optional_field;
+
+/// A classification from Python: a sector name, or {"sector", "industry"}.
+fn extract_classification(value: &Bound<'_, PyAny>) -> PyResult<Classification> {
+    if let Ok(sector) = value.extract::<String>() {
+        return Ok(Classification { sector, industry: None });
+    }
+    let Ok(d) = value.downcast::<PyDict>() else {
+        return Err(PyTypeError::new_err("a classification must be a sector name or a dict with 'sector'"));
+    };
+    let sector = optional_field(d, "sector")?.ok_or_else(|| PyValueError::new_err("a classification dict needs a 'sector'"))?;
+    Ok(Classification { sector, industry: optional_field(d, "industry")? })
+}
+
+/// Ticker -> GICS-style sector and industry, with inference for tickers
+/// that are not in the map.
+///
+/// Tickers are normalized on the way in and out, so "VOD", "VOD.L" and
+/// "VOD_EQ" share an entry.
+///
+/// ```python
+/// sectors = SectorMap.from_csv("sectors.csv")    # ticker,sector,industry
+/// sectors.update({"AAPL": {"sector": "Information Technology", "industry": "Hardware"}})
+/// sectors.classify(["AAPL", "VUSA.L"], metadata={"VUSA.L": {"type": "ETF"}})
+/// ```
+#[pyclass(module = "growin_core")]
+#[derive(Clone, Default)]
+pub struct SectorMap {
+    pub map: sectors::SectorMap,
+}
+
+impl SectorMap {
+    /// Classification for metadata as ingested from the broker ("name" and
+    /// "type" or "instrument_type").
+    pub fn classify_with(&self, ticker: &str, metadata: Option<&Bound<'_, PyDict>>) -> PyResult<(Option<Classification>, sectors::Source)> {
+        let (mut name, mut kind): (Option<String>, Option<String>) = (None, None);
+        if let Some(m) = metadata {
+            name = optional_field(m, "name")?;
+            kind = match optional_field(m, "type")? {
+                Some(kind) => Some(kind),
+                None => optional_field(m, "instrument_type")?,
+            };
+        }
+        Ok(self.map.classify(ticker, name.as_deref(), kind.as_deref()))
+    }
+}
+
+#[pymethods]
+impl SectorMap {
+    /// Args:
+    ///     mapping (Dict[str, str | dict], optional): ticker -> sector, or
+    ///         {"sector", "industry"}.
+    #[new]
+    #[pyo3(signature = (mapping=None))]
+    fn new(mapping: Option<HashMap<String, Bound<'_, PyAny>>>) -> PyResult<Self> {
+        let mut out = SectorMap::default();
+        if let Some(mapping) = mapping {
+            out.update(mapping)?;
+        }
+        Ok(out)
+    }
+
+    /// Load `ticker,sector[,industry]` rows from a CSV file (a header row
+    /// starting with "ticker" is skipped).
+    #[staticmethod]
+    fn from_csv(path: &str) -> PyResult<Self> {
+        let text = std::fs::read_to_string(path).map_err(|e| PyOSError::new_err(format!("cannot read '{}': {}", path, e)))?;
+        let mut out = SectorMap::default();
+        out.map.load_csv(&text).map_err(|e| PyValueError::new_err(format!("{}: {}", path, e)))?;
+        Ok(out)
+    }
+
+    /// Add or replace classifications.
+    fn update(&mut self, mapping: HashMap<String, Bound<'_, PyAny>>) -> PyResult<()> {
+        for (ticker, value) in mapping {
+            self.map.insert(&ticker, extract_classification(&value)?);
+        }
+        Ok(())
+    }
+
+    /// The curated classification of a ticker, or None.
+    fn get<'py>(&self, py: Python<'py>, ticker: &str) -> PyResult<Option<Bound<'py, PyDict>>> {
+        self.map.get(ticker).map(|c| classification_to_dict(py, Some(c), sectors::Source::Map)).transpose()
+    }
+
+    /// Classify many tickers at once.
+    ///
+    /// Args:
+    ///     tickers (List[str]): Tickers to classify.
+    ///     metadata (Dict[str, dict], optional): ticker -> instrument
+    ///         metadata with "name" and "type" (e.g. "ETF"), used to infer
+    ///         the sector of tickers missing from the map.
+    ///
+    /// Returns:
+    ///     Dict[str, dict]: ticker -> {"sector", "industry", "source"} with
+    ///     source "map", "inferred" or "unknown" (sector None).
+    #[pyo3(signature = (tickers, metadata=None))]
+    fn classify<'py>(
+        &self,
+        py: Python<'py>,
+        tickers: Vec<String>,
+        metadata: Option<HashMap<String, Bound<'py, PyDict>>>,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let out = PyDict::new(py);
+        for ticker in tickers {
+            let meta = metadata.as_ref().and_then(|m| m.get(&ticker));
+            let (classification, source) = self.classify_with(&ticker, meta)?;
+            out.set_item(&ticker, classification_to_dict(py, classification.as_ref(), source)?)?;
+        }
+        Ok(out)
+    }
+
+    fn __len__(&self) -> usize {
+        self.map.len()
+    }
+
+    fn __contains__(&self, ticker: &str) -> bool {
+        self.map.get(ticker).is_some()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("SectorMap(tickers={})", self.map.len())
+    }
+}
+
+fn classification_to_dict<'py>(
+    py: Python<'py>,
+    classification: Option<&Classification>,
+    source: sectors::Source,
+) -> PyResult<Bound<'py, PyDict>> {
+    let d = PyDict::new(py);
+    d.set_item("sector", classification.map(|c| c.sector.as_str()))?;
+    d.set_item("industry", classification.and_then(|c| c.industry.as_deref()))?;
+    d.set_item("source", source.name())?;
+    Ok(d)
+}
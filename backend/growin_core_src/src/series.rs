@@ -0,0 +1,348 @@
+//! Numeric inputs for the indicator functions.
+//!
+//! Indicators accept a Python sequence or a 1-D numpy array. Sequences are
+//! computed in f64 and returned as lists, as before. Arrays are computed in
+//! their own dtype (float64 or float32) and returned as arrays of that dtype,
+//! so float32 universe scans never widen to f64. Columns a `CandleSeries`
+//! holds on behalf of arrays are borrowed in place and also come back as
+//! arrays.
+
+use numpy::{
+    dtype, Element, PyArray1, PyArrayDescrMethods, PyArrayMethods, PyReadonlyArray1, PyReadwriteArray1, PyUntypedArray,
+    PyUntypedArrayMethods,
+};
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyList, PyTuple};
+use std::borrow::Cow;
+
+use crate::errors::{self, LengthMismatchError};
+
+/// A 1-D input series.
+pub enum Series<'py> {
+    F64(PyReadonlyArray1<'py, f64>),
+    F32(PyReadonlyArray1<'py, f32>),
+    /// A sequence's values, or a column of bars built from sequences.
+    List(Cow<'py, [f64]>),
+    /// Values already in Rust that came in as a numpy array (a
+    /// `CandleSeries` column, say): computed in f64, returned as arrays.
+    Buffer(Cow<'py, [f64]>),
+}
+
+/// Whether numpy has been imported. Touching the array API without it
+/// panics, and nothing can be an ndarray before numpy is loaded anyway.
+fn numpy_loaded(py: Python<'_>) -> PyResult<bool> {
+    py.import("sys")?.getattr("modules")?.contains("numpy")
+}
+
+impl<'py> FromPyObject<'py> for Series<'py> {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        let _span = crate::profile::span("series.from_python");
+        let py = ob.py();
+        if ob.is_instance_of::<PyList>() || ob.is_instance_of::<PyTuple>() || !numpy_loaded(py)? {
+            return Ok(Series::List(Cow::Owned(ob.extract()?)));
+        }
+        let Ok(array) = ob.downcast::<PyUntypedArray>() else {
+            return Ok(Series::List(Cow::Owned(ob.extract()?)));
+        };
+        let dt = array.dtype();
+        if dt.is_equiv_to(&dtype::<f32>(py)) {
+            Ok(Series::F32(ob.extract()?))
+        } else if dt.is_equiv_to(&dtype::<f64>(py)) {
+            Ok(Series::F64(ob.extract()?))
+        } else {
+            // Integer and other float arrays are computed in f64.
+            Ok(Series::F64(ob.call_method1("astype", ("float64",))?.extract()?))
+        }
+    }
+}
+
+impl<'py> Series<'py> {
+    /// Borrows f64 values held in Rust, to be returned as arrays when
+    /// `as_array` (the values came in as one) and as lists otherwise.
+    pub fn borrowed(values: &'py [f64], as_array: bool) -> Self {
+        if as_array {
+            Series::Buffer(Cow::Borrowed(values))
+        } else {
+            Series::List(Cow::Borrowed(values))
+        }
+    }
+
+    /// Whether outputs computed from this series go back as numpy arrays.
+    pub fn is_array(&self) -> bool {
+        !matches!(self, Series::List(_))
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            Series::F64(array) => array.len(),
+            Series::F32(array) => array.len(),
+            Series::List(values) | Series::Buffer(values) => values.len(),
+        }
+    }
+
+    /// Raises `InsufficientDataError` if the series is non-empty but all NaN,
+    /// and logs a warning if only some values are NaN.
+    pub fn check_has_data(&self, name: &str) -> PyResult<()> {
+        let nans = match self {
+            Series::F64(array) => errors::check_has_data(name, as_slice(array, &mut Vec::new()))?,
+            Series::F32(array) => errors::check_has_data(name, as_slice(array, &mut Vec::new()))?,
+            Series::List(values) | Series::Buffer(values) => errors::check_has_data(name, values)?,
+        };
+        if nans > 0 {
+            log::warn!("'{}' has {} NaN values out of {}; they propagate through the indicator", name, nans, self.len());
+        }
+        Ok(())
+    }
+
+    /// The values as f64, borrowed when they already are.
+    pub fn f64_slice<'a>(&'a self, owned: &'a mut Vec<f64>) -> &'a [f64] {
+        match self {
+            Series::F64(array) => as_slice(array, owned),
+            Series::F32(array) => {
+                *owned = array.as_array().iter().map(|&x| f64::from(x)).collect();
+                owned
+            }
+            Series::List(values) | Series::Buffer(values) => values,
+        }
+    }
+
+    /// The values of a float32 array; None for any other input.
+    pub fn f32_slice<'a>(&'a self, owned: &'a mut Vec<f32>) -> Option<&'a [f32]> {
+        match self {
+            Series::F32(array) => Some(as_slice(array, owned)),
+            _ => None,
+        }
+    }
+}
+
+/// Checks that aligned inputs have one length and real data; returns it.
+pub fn check_columns(columns: &[(&str, &Series<'_>)]) -> PyResult<usize> {
+    let lengths: Vec<(&str, usize)> = columns.iter().map(|(name, s)| (*name, s.len())).collect();
+    errors::check_lengths(&lengths)?;
+    for (name, s) in columns {
+        s.check_has_data(name)?;
+    }
+    Ok(lengths.first().map_or(0, |l| l.1))
+}
+
+/// Converts kernel output into the Python type matching the input: a numpy
+/// array for array inputs, a list otherwise.
+pub trait IntoSeriesOutput {
+    fn into_output(self, py: Python<'_>, as_array: bool) -> PyResult<PyObject>;
+}
+
+impl<T> IntoSeriesOutput for Vec<T>
+where
+    T: Element + for<'py> IntoPyObject<'py>,
+{
+    fn into_output(self, py: Python<'_>, as_array: bool) -> PyResult<PyObject> {
+        if as_array {
+            Ok(PyArray1::from_vec(py, self).into_any().unbind())
+        } else {
+            Ok(self.into_pyobject(py)?.into_any().unbind())
+        }
+    }
+}
+
+impl<A: IntoSeriesOutput, const N: usize> IntoSeriesOutput for [A; N] {
+    fn into_output(self, py: Python<'_>, as_array: bool) -> PyResult<PyObject> {
+        let items = self.into_iter().map(|a| a.into_output(py, as_array)).collect::<PyResult<Vec<_>>>()?;
+        Ok(PyTuple::new(py, items)?.into_any().unbind())
+    }
+}
+
+impl<A: IntoSeriesOutput, B: IntoSeriesOutput> IntoSeriesOutput for (A, B) {
+    fn into_output(self, py: Python<'_>, as_array: bool) -> PyResult<PyObject> {
+        let items = [self.0.into_output(py, as_array)?, self.1.into_output(py, as_array)?];
+        Ok(PyTuple::new(py, items)?.into_any().unbind())
+    }
+}
+
+impl<A: IntoSeriesOutput, B: IntoSeriesOutput, C: IntoSeriesOutput> IntoSeriesOutput for (A, B, C) {
+    fn into_output(self, py: Python<'_>, as_array: bool) -> PyResult<PyObject> {
+        let items = [
+            self.0.into_output(py, as_array)?,
+            self.1.into_output(py, as_array)?,
+            self.2.into_output(py, as_array)?,
+        ];
+        Ok(PyTuple::new(py, items)?.into_any().unbind())
+    }
+}
+
+/// A variable number of aligned series, returned to Python as a list.
+pub struct Columns<T>(pub Vec<Vec<T>>);
+
+impl<T> IntoSeriesOutput for Columns<T>
+where
+    Vec<T>: IntoSeriesOutput,
+{
+    fn into_output(self, py: Python<'_>, as_array: bool) -> PyResult<PyObject> {
+        let items = self.0.into_iter().map(|c| c.into_output(py, as_array)).collect::<PyResult<Vec<_>>>()?;
+        Ok(PyList::new(py, items)?.into_any().unbind())
+    }
+}
+
+/// Borrows a contiguous array as a slice, copying only when it is strided.
+pub fn as_slice<'a, T: Element + Copy>(array: &'a PyReadonlyArray1<'_, T>, owned: &'a mut Vec<T>) -> &'a [T] {
+    match array.as_slice() {
+        Ok(slice) => slice,
+        Err(_) => {
+            *owned = array.as_array().to_vec();
+            owned
+        }
+    }
+}
+
+/// Borrows `out` as a writable 1-D array of `T` with exactly `len` elements.
+/// Fails if `out` is the input array itself, which is still borrowed.
+pub fn writable<'py, T: Element>(out: &Bound<'py, PyAny>, len: usize, dtype_name: &str) -> PyResult<PyReadwriteArray1<'py, T>> {
+    let wrong_type = || PyTypeError::new_err(format!("out must be a 1-D {} numpy array", dtype_name));
+    if !numpy_loaded(out.py())? {
+        return Err(wrong_type());
+    }
+    let array = out.downcast::<PyArray1<T>>().map_err(|_| wrong_type())?;
+    if array.len() != len {
+        return Err(LengthMismatchError::new_err(format!("out has length {}, expected {}", array.len(), len)));
+    }
+    array
+        .try_readwrite()
+        .map_err(|_| PyValueError::new_err("out is already borrowed; it must not alias the input"))
+}
+
+/// Mutable slice view of a writable array, which must be contiguous.
+pub fn slice_mut<'a, T: Element>(array: &'a mut PyReadwriteArray1<'_, T>) -> PyResult<&'a mut [T]> {
+    array
+        .as_slice_mut()
+        .map_err(|_| PyValueError::new_err("out must be a contiguous array"))
+}
+
+/// Runs a generic kernel on a [`Series`] with the GIL released, once per
+/// element type, and converts the result back to the input's Python type.
+///
+/// ```ignore
+/// dispatch_series!(py, data, |d| indicators::sma(d, period))
+/// ```
+///
+/// With `cached(name, [params...])` the result is memoized through
+/// [`crate::cache`] when the cache is enabled.
+///
+/// Several aligned inputs (high, low, close...) go in brackets, each bound
+/// to a slice of the same name. They are computed in float32 when every one
+/// is a float32 array and in f64 otherwise, and returned as arrays if any
+/// input is one:
+///
+/// ```ignore
+/// dispatch_series!(py, [high, low, close], cached("atr", [period]), |high, low, close| {
+///     indicators::atr(high, low, close, period)
+/// })
+/// ```
+macro_rules! dispatch_series {
+    ($py:expr, [$($series:ident),+], cached($name:expr, [$($param:expr),*]), |$($data:ident),+| $body:expr) => {
+        dispatch_series!($py, [$($series),+], |$($data),+| {
+            $crate::cache::memoize_columns($name, &[$($param as f64),*], &[$($data),+], || $body)
+        })
+    };
+    ($py:expr, [$($series:ident),+], |$($data:ident),+| $body:expr) => {{
+        use $crate::series::{IntoSeriesOutput, Series};
+        let as_array = [$(&$series),+].iter().any(|s| s.is_array());
+        if [$(&$series),+].iter().all(|s| matches!(s, Series::F32(_))) {
+            $(
+                // Keeps each input's buffer alive even when named like its slice.
+                let mut $data = (&$series, Vec::new());
+                let $data: &[f32] = $data.0.f32_slice(&mut $data.1).unwrap();
+            )+
+            let out = $crate::profile::timed("indicators.compute", || $py.allow_threads(|| $body));
+            let _span = $crate::profile::span("series.to_python");
+            out.into_output($py, as_array)
+        } else {
+            $(
+                let mut $data = (&$series, Vec::new());
+                let $data: &[f64] = $data.0.f64_slice(&mut $data.1);
+            )+
+            let out = $crate::profile::timed("indicators.compute", || $py.allow_threads(|| $body));
+            let _span = $crate::profile::span("series.to_python");
+            out.into_output($py, as_array)
+        }
+    }};
+    ($py:expr, $series:expr, cached($name:expr, [$($param:expr),*]), |$data:ident| $body:expr) => {
+        dispatch_series!($py, $series, |$data| {
+            $crate::cache::memoize($name, &[$($param as f64),*], $data, || $body)
+        })
+    };
+    ($py:expr, $series:expr, |$data:ident| $body:expr) => {{
+        use $crate::series::{as_slice, IntoSeriesOutput, Series};
+        match $series {
+            Series::F64(array) => {
+                let mut owned = Vec::new();
+                let $data: &[f64] = as_slice(&array, &mut owned);
+                let out = $crate::profile::timed("indicators.compute", || $py.allow_threads(|| $body));
+                let _span = $crate::profile::span("series.to_python");
+                out.into_output($py, true)
+            }
+            Series::F32(array) => {
+                let mut owned = Vec::new();
+                let $data: &[f32] = as_slice(&array, &mut owned);
+                let out = $crate::profile::timed("indicators.compute", || $py.allow_threads(|| $body));
+                let _span = $crate::profile::span("series.to_python");
+                out.into_output($py, true)
+            }
+            Series::List(values) => {
+                let $data: &[f64] = &values;
+                let out = $crate::profile::timed("indicators.compute", || $py.allow_threads(|| $body));
+                let _span = $crate::profile::span("series.to_python");
+                out.into_output($py, false)
+            }
+            Series::Buffer(values) => {
+                let $data: &[f64] = &values;
+                let out = $crate::profile::timed("indicators.compute", || $py.allow_threads(|| $body));
+                let _span = $crate::profile::span("series.to_python");
+                out.into_output($py, true)
+            }
+        }
+    }};
+}
+
+/// Like [`dispatch_series!`] for `*_into` kernels: each named `out` argument
+/// is rebound to a mutable slice of the caller's buffer, which must match the
+/// input's dtype (float64 for sequences) and length.
+///
+/// ```ignore
+/// dispatch_series_into!(py, data, [out], |d| indicators::sma_into(d, period, out))
+/// ```
+macro_rules! dispatch_series_into {
+    ($py:expr, $series:expr, [$($out:ident),+], |$data:ident| $body:expr) => {{
+        use $crate::series::{as_slice, slice_mut, writable, Series};
+        match $series {
+            Series::F64(array) => {
+                let mut owned = Vec::new();
+                let $data: &[f64] = as_slice(&array, &mut owned);
+                $(
+                    let mut $out = writable::<f64>(&$out, $data.len(), "float64")?;
+                    let $out = slice_mut(&mut $out)?;
+                )+
+                $crate::profile::timed("indicators.compute", || $py.allow_threads(|| $body));
+            }
+            Series::F32(array) => {
+                let mut owned = Vec::new();
+                let $data: &[f32] = as_slice(&array, &mut owned);
+                $(
+                    let mut $out = writable::<f32>(&$out, $data.len(), "float32")?;
+                    let $out = slice_mut(&mut $out)?;
+                )+
+                $crate::profile::timed("indicators.compute", || $py.allow_threads(|| $body));
+            }
+            Series::List(values) | Series::Buffer(values) => {
+                let $data: &[f64] = &values;
+                $(
+                    let mut $out = writable::<f64>(&$out, $data.len(), "float64")?;
+                    let $out = slice_mut(&mut $out)?;
+                )+
+                $crate::profile::timed("indicators.compute", || $py.allow_threads(|| $body));
+            }
+        }
+        Ok(())
+    }};
+}
+
+pub(crate) use {dispatch_series, dispatch_series_into};
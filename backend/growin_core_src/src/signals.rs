@@ -0,0 +1,331 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::collections::HashMap;
+
+use growin_core::signals::{
+    align_asof, breakout_signals, combine_signals, pair_spread, signal_stats, spread_zscore, volume_ratios,
+    zscore_band_signals, ConfirmRule, SignalSet,
+};
+use growin_core::stats;
+
+use crate::candles::PyCandles;
+use crate::errors::{self, InvalidPeriodError, LengthMismatchError};
+use crate::{config, money};
+
+/// Entry/exit arrays as a dict of lists, the layout vectorized backtesters
+/// consume.
+fn signal_set_to_dict<'py>(py: Python<'py>, set: &SignalSet) -> PyResult<Bound<'py, PyDict>> {
+    let d = PyDict::new(py);
+    d.set_item("long_entries", &set.long_entries)?;
+    d.set_item("long_exits", &set.long_exits)?;
+    d.set_item("short_entries", &set.short_entries)?;
+    d.set_item("short_exits", &set.short_exits)?;
+    d.set_item("position", &set.position)?;
+    Ok(d)
+}
+
+/// Validates band thresholds shared by the z-score signal generators.
+pub fn check_bands(entry_z: f64, exit_z: f64) -> PyResult<()> {
+    if entry_z.is_nan() || entry_z <= 0.0 || exit_z < 0.0 || exit_z >= entry_z {
+        return Err(PyValueError::new_err("bands must satisfy 0 <= exit_z < entry_z"));
+    }
+    Ok(())
+}
+
+/// Mean-reversion entries/exits from a rolling z-score of price.
+///
+/// Args:
+///     prices (List[float]): Closing prices.
+///     window (int): Rolling window for mean/std (default 20).
+///     entry_z (float): Enter when |z| exceeds this (default 2.0).
+///     exit_z (float): Exit when z reverts inside this band (default 0.5).
+///     allow_short (bool): Also trade the upper band (default True).
+///
+/// Returns:
+///     dict: "long_entries", "long_exits", "short_entries", "short_exits"
+///     (List[bool]), "position" (List[int] in {-1, 0, 1}) and "zscore" (List[float]).
+#[pyfunction]
+#[pyo3(name = "mean_reversion_signals", signature = (prices, window=20, entry_z=2.0, exit_z=0.5, allow_short=true))]
+pub fn py_mean_reversion_signals<'py>(
+    py: Python<'py>,
+    prices: Vec<f64>,
+    window: usize,
+    entry_z: f64,
+    exit_z: f64,
+    allow_short: bool,
+) -> PyResult<Bound<'py, PyDict>> {
+    errors::check_min_period("window", window, 2)?;
+    check_bands(entry_z, exit_z)?;
+    let ddof = config::current().ddof();
+    let (z, set) = py.allow_threads(|| {
+        let z = stats::rolling_zscore_ddof(&prices, window, ddof);
+        let set = zscore_band_signals(&z, entry_z, exit_z, None, allow_short);
+        (z, set)
+    });
+    let d = signal_set_to_dict(py, &set)?;
+    d.set_item("zscore", z)?;
+    Ok(d)
+}
+
+/// Build the spread between two aligned price series.
+///
+/// Args:
+///     a (List[float]): First leg prices (dependent).
+///     b (List[float]): Second leg prices.
+///     hedge_ratio (float, optional): Units of `b` per unit of `a`. Estimated by
+///         OLS when omitted.
+///
+/// Returns:
+///     Tuple[List[float], float]: (spread, hedge_ratio used).
+#[pyfunction]
+#[pyo3(name = "pair_spread", signature = (a, b, hedge_ratio=None))]
+pub fn py_pair_spread(py: Python<'_>, a: Vec<f64>, b: Vec<f64>, hedge_ratio: Option<f64>) -> PyResult<(Vec<f64>, f64)> {
+    errors::check_lengths(&[("a", a.len()), ("b", b.len())])?;
+    py.allow_threads(|| pair_spread(&a, &b, hedge_ratio))
+        .ok_or_else(|| PyValueError::new_err("cannot estimate a hedge ratio: second leg has no variance"))
+}
+
+/// Entry/exit/stop signals for a pairs spread.
+///
+/// Long spread (buy `a`, sell `b`) when z < -entry_z, short spread when
+/// z > entry_z; positions close when z reverts inside ±exit_z or diverges
+/// beyond ±stop_z.
+///
+/// Args:
+///     spread (List[float]): Spread series from `pair_spread`.
+///     entry_z (float): Entry band (default 2.0).
+///     exit_z (float): Exit band (default 0.5).
+///     stop_z (float, optional): Stop-out band (default 4.0); None disables.
+///     window (int, optional): Rolling z-score window. None uses the full sample.
+///
+/// Returns:
+///     dict: Same layout as `mean_reversion_signals` plus "zscore".
+#[pyfunction]
+#[pyo3(name = "pair_signals", signature = (spread, entry_z=2.0, exit_z=0.5, stop_z=Some(4.0), window=None))]
+pub fn py_pair_signals<'py>(
+    py: Python<'py>,
+    spread: Vec<f64>,
+    entry_z: f64,
+    exit_z: f64,
+    stop_z: Option<f64>,
+    window: Option<usize>,
+) -> PyResult<Bound<'py, PyDict>> {
+    check_bands(entry_z, exit_z)?;
+    if stop_z.is_some_and(|s| s <= entry_z) {
+        return Err(PyValueError::new_err("stop_z must be greater than entry_z"));
+    }
+    if let Some(window) = window {
+        errors::check_min_period("window", window, 2)?;
+    }
+    let ddof = config::current().ddof();
+    let (z, set) = py.allow_threads(|| {
+        let z = spread_zscore(&spread, window, ddof);
+        let set = zscore_band_signals(&z, entry_z, exit_z, stop_z, true);
+        (z, set)
+    });
+    let d = signal_set_to_dict(py, &set)?;
+    d.set_item("zscore", z)?;
+    Ok(d)
+}
+
+/// Detect channel breakouts.
+///
+/// Args:
+///     candles (Mapping[str, List[float]]): OHLCV columns.
+///     channel_period (int): Donchian lookback (default 20).
+///     vol_filter (bool): Require volume confirmation (default True; needs `volume`).
+///     volume_mult (float): Confirmation threshold vs average volume (default 1.5).
+///     atr_mult (float): Widen the channel by this many ATRs (default 0.0).
+///
+/// Returns:
+///     dict: "up"/"down" (List[bool]), "upper"/"lower" channel levels and
+///     "breakouts": list of {"index", "direction", "close", "level", "strength",
+///     "volume_ratio"}.
+#[pyfunction]
+#[pyo3(name = "breakout_signals", signature = (candles, channel_period=20, vol_filter=true, volume_mult=1.5, atr_mult=0.0))]
+pub fn py_breakout_signals<'py>(
+    py: Python<'py>,
+    candles: PyCandles,
+    channel_period: usize,
+    vol_filter: bool,
+    volume_mult: f64,
+    atr_mult: f64,
+) -> PyResult<Bound<'py, PyDict>> {
+    errors::check_period("channel_period", channel_period)?;
+    if vol_filter && !candles.has_volume() {
+        return Err(PyValueError::new_err("vol_filter requires a 'volume' column"));
+    }
+    let r = py.allow_threads(|| breakout_signals(&candles, channel_period, atr_mult, vol_filter.then_some(volume_mult)));
+
+    let d = PyDict::new(py);
+    d.set_item("up", &r.up)?;
+    d.set_item("down", &r.down)?;
+    d.set_item("upper", &r.upper)?;
+    d.set_item("lower", &r.lower)?;
+    let events = r
+        .breakouts
+        .iter()
+        .map(|b| {
+            let e = PyDict::new(py);
+            e.set_item("index", b.index)?;
+            e.set_item("direction", b.direction)?;
+            e.set_item("close", b.close)?;
+            e.set_item("level", b.level)?;
+            e.set_item("strength", b.strength)?;
+            e.set_item("volume_ratio", b.volume_ratio)?;
+            Ok(e)
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    d.set_item("breakouts", events)?;
+    Ok(d)
+}
+
+/// Flag bars with unusually high volume.
+///
+/// Args:
+///     volume (List[float]): Volume per bar.
+///     window (int): Baseline length in bars, or in sessions when `timestamps`
+///         are given (default 20).
+///     threshold (float): Flag when volume exceeds this multiple of the baseline
+///         (default 3.0).
+///     timestamps (List[int], optional): Bar open times in epoch seconds. Enables
+///         the time-of-day adjustment for intraday data.
+///     utc_offset_minutes (int): Exchange offset from UTC used to bucket
+///         time-of-day slots (default 0).
+///
+/// Returns:
+///     Tuple[List[bool], List[float]]: (spike flags, volume / baseline ratios).
+#[pyfunction]
+#[pyo3(name = "volume_spikes", signature = (volume, window=20, threshold=3.0, timestamps=None, utc_offset_minutes=0))]
+pub fn py_volume_spikes(
+    py: Python<'_>,
+    volume: Vec<f64>,
+    window: usize,
+    threshold: f64,
+    timestamps: Option<Vec<i64>>,
+    utc_offset_minutes: i64,
+) -> PyResult<(Vec<bool>, Vec<f64>)> {
+    errors::check_period("window", window)?;
+    if let Some(timestamps) = &timestamps {
+        errors::check_lengths(&[("volume", volume.len()), ("timestamps", timestamps.len())])?;
+    }
+    let ratios = py.allow_threads(|| volume_ratios(&volume, window, timestamps.as_deref(), utc_offset_minutes * 60));
+    let flags = ratios.iter().map(|&r| r > threshold).collect();
+    Ok((flags, ratios))
+}
+
+/// Combine signals computed on several timeframes onto one base timeline.
+///
+/// Timestamps should mark when each signal became known (the bar close), so
+/// a higher-timeframe signal only applies to base bars at or after its close.
+///
+/// Args:
+///     signals_by_timeframe (Dict[str, Tuple[List[int], List[int]]]):
+///         timeframe -> (timestamps, signals) with signals in {-1, 0, 1}.
+///     rule (str): "all" or "majority" (default "all").
+///     base_timestamps (List[int], optional): Target timeline. Defaults to the
+///         timeframe with the most bars.
+///
+/// Returns:
+///     dict: {"timestamps": List[int], "signal": List[int],
+///     "aligned": Dict[str, List[int]]}.
+#[pyfunction]
+#[pyo3(name = "mtf_confirm", signature = (signals_by_timeframe, rule="all", base_timestamps=None))]
+pub fn py_mtf_confirm<'py>(
+    py: Python<'py>,
+    signals_by_timeframe: HashMap<String, (Vec<i64>, Vec<i64>)>,
+    rule: &str,
+    base_timestamps: Option<Vec<i64>>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let rule: ConfirmRule = rule.parse().map_err(PyValueError::new_err)?;
+    let mut frames: Vec<(String, Vec<i64>, Vec<i8>)> = signals_by_timeframe
+        .into_iter()
+        .map(|(name, (ts, s))| (name, ts, s.into_iter().map(|v| v.signum() as i8).collect()))
+        .collect();
+    frames.sort_by(|a, b| a.0.cmp(&b.0));
+    if frames.is_empty() {
+        return Err(PyValueError::new_err("signals_by_timeframe must not be empty"));
+    }
+    for (name, ts, s) in &frames {
+        if ts.len() != s.len() {
+            return Err(LengthMismatchError::new_err(format!("'{}': timestamps and signals differ in length", name)));
+        }
+        if ts.windows(2).any(|w| w[1] < w[0]) {
+            return Err(PyValueError::new_err(format!("'{}': timestamps must be ascending", name)));
+        }
+    }
+
+    let base = match base_timestamps {
+        Some(base) => {
+            if base.windows(2).any(|w| w[1] < w[0]) {
+                return Err(PyValueError::new_err("base_timestamps must be ascending"));
+            }
+            base
+        }
+        None => frames.iter().max_by_key(|(_, ts, _)| ts.len()).map(|(_, ts, _)| ts.clone()).unwrap_or_default(),
+    };
+    let (aligned, combined) = py.allow_threads(|| {
+        let aligned: Vec<Vec<i8>> = frames.iter().map(|(_, ts, s)| align_asof(ts, s, &base)).collect();
+        let combined = combine_signals(&aligned, rule);
+        (aligned, combined)
+    });
+
+    let per_frame = PyDict::new(py);
+    for ((name, _, _), a) in frames.iter().zip(&aligned) {
+        per_frame.set_item(name, a.clone())?;
+    }
+    let out = PyDict::new(py);
+    out.set_item("timestamps", base)?;
+    out.set_item("signal", combined)?;
+    out.set_item("aligned", per_frame)?;
+    Ok(out)
+}
+
+/// Hit rate and forward returns of a signal series, a quick sanity check
+/// before running a full backtest.
+///
+/// Args:
+///     prices (List[float]): Closing prices.
+///     signals (List[int]): 1 (long), -1 (short) or 0 (no signal) per bar.
+///     horizons (List[int]): Forward horizons in bars (default [1, 5, 20]).
+///     currency (str, optional): Currency the instrument trades in. When it
+///         differs from the configured account currency, each trade pays
+///         `fx_fee_bps` on entry and on exit.
+///     fx_fee_bps (float): FX fee in basis points (default 15).
+///
+/// Returns:
+///     Dict[int, dict]: horizon -> {"count", "hit_rate", "avg_return",
+///     "avg_win", "avg_loss", "expectancy"}. Returns are fractions; all but
+///     the raw `avg_return` are net of FX costs. Statistics without data are
+///     NaN.
+#[pyfunction]
+#[pyo3(name = "signal_stats", signature = (prices, signals, horizons=vec![1, 5, 20], currency=None, fx_fee_bps=15.0))]
+pub fn py_signal_stats<'py>(
+    py: Python<'py>,
+    prices: Vec<f64>,
+    signals: Vec<i64>,
+    horizons: Vec<usize>,
+    currency: Option<&str>,
+    fx_fee_bps: f64,
+) -> PyResult<Bound<'py, PyDict>> {
+    errors::check_lengths(&[("prices", prices.len()), ("signals", signals.len())])?;
+    if horizons.contains(&0) {
+        return Err(InvalidPeriodError::new_err("horizons must be positive integers"));
+    }
+    let signals: Vec<i8> = signals.into_iter().map(|s| s.signum() as i8).collect();
+
+    let out = PyDict::new(py);
+    let cost = money::round_trip_fx(currency, fx_fee_bps);
+    for st in py.allow_threads(|| signal_stats(&prices, &signals, &horizons, cost)) {
+        let d = PyDict::new(py);
+        d.set_item("count", st.count)?;
+        d.set_item("hit_rate", st.hit_rate)?;
+        d.set_item("avg_return", st.avg_return)?;
+        d.set_item("avg_win", st.avg_win)?;
+        d.set_item("avg_loss", st.avg_loss)?;
+        d.set_item("expectancy", st.expectancy)?;
+        out.set_item(st.horizon, d)?;
+    }
+    Ok(out)
+}
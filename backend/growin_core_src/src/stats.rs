@@ -0,0 +1,113 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use growin_core::stats::{self, detect_return_anomalies, permutation_entropy, sample_entropy, std_dev, AnomalyMethod};
+
+use crate::series::{dispatch_series, Series};
+use crate::{config, errors, profile};
+
+/// Sample Entropy (SampEn) of a series, a regularity/noise measure.
+///
+/// Args:
+///     series (List[float]): Input series (e.g. returns).
+///     m (int): Embedding dimension (default 2).
+///     r (float, optional): Absolute match tolerance. Defaults to 0.2 * std(series).
+///
+/// Returns:
+///     float: SampEn value (NaN when undefined). Higher means less regular.
+#[pyfunction]
+#[pyo3(name = "sample_entropy", signature = (series, m=2, r=None))]
+pub fn py_sample_entropy(py: Python<'_>, series: Vec<f64>, m: usize, r: Option<f64>) -> PyResult<f64> {
+    Ok(py.allow_threads(|| {
+        let tolerance = r.unwrap_or_else(|| 0.2 * std_dev(&series));
+        sample_entropy(&series, m, tolerance)
+    }))
+}
+
+/// Permutation Entropy of a series based on ordinal patterns.
+///
+/// Args:
+///     series (List[float]): Input series.
+///     order (int): Pattern length / embedding dimension (default 3).
+///     delay (int): Time delay between pattern elements (default 1).
+///     normalize (bool): Scale to [0, 1] by ln(order!) (default True).
+///
+/// Returns:
+///     float: Permutation entropy (NaN when the series is too short).
+#[pyfunction]
+#[pyo3(name = "permutation_entropy", signature = (series, order=3, delay=1, normalize=true))]
+pub fn py_permutation_entropy(py: Python<'_>, series: Vec<f64>, order: usize, delay: usize, normalize: bool) -> PyResult<f64> {
+    Ok(py.allow_threads(|| permutation_entropy(&series, order, delay, normalize)))
+}
+
+/// Detect statistically extreme returns.
+///
+/// Args:
+///     returns (List[float]): Periodic returns for a single ticker.
+///     method (str): "zscore" (std devs from mean) or "iqr" (IQRs from median).
+///     window (int, optional): Trailing baseline length. None uses the full series.
+///     threshold (float): Absolute score above which a return is flagged (default 3.0).
+///
+/// Returns:
+///     Tuple[List[bool], List[float]]: (anomaly flags, scores), aligned with input.
+#[pyfunction]
+#[pyo3(name = "detect_return_anomalies", signature = (returns, method="zscore", window=None, threshold=3.0))]
+pub fn py_detect_return_anomalies(
+    py: Python<'_>,
+    returns: Vec<f64>,
+    method: &str,
+    window: Option<usize>,
+    threshold: f64,
+) -> PyResult<(Vec<bool>, Vec<f64>)> {
+    let method: AnomalyMethod = method.parse().map_err(PyValueError::new_err)?;
+    if let Some(window) = window {
+        errors::check_period("window", window)?;
+    }
+    let ddof = config::current().ddof();
+    Ok(py.allow_threads(|| detect_return_anomalies(&returns, method, window, threshold, ddof)))
+}
+
+/// Rolling standard deviation over `period` values: the population std, or
+/// the sample std under `Config(compat="pandas")`.
+///
+/// Runs on the same running moments as `calculate_bbands` and
+/// `rolling_stats_chunked`, so the three agree bit for bit.
+///
+/// Args:
+///     data (List[float] | np.ndarray): Input series.
+///     period (int): Window length.
+///
+/// Returns:
+///     List[float] | np.ndarray: NaN until the first full window and for any
+///     window containing a NaN or ±Inf.
+#[pyfunction]
+#[pyo3(name = "rolling_std", signature = (data, period))]
+pub fn py_rolling_std(py: Python<'_>, data: Series<'_>, period: usize) -> PyResult<PyObject> {
+    let _span = profile::span("rolling_std");
+    errors::check_period("period", period)?;
+    let cfg = config::current();
+    dispatch_series!(py, data, cached("rolling_std", [period, cfg.cache_key()]), |d| {
+        stats::rolling_std_ddof(d, period, cfg.ddof())
+    })
+}
+
+/// Rolling z-score `(x - mean) / std` over `period` values, with the std
+/// of `rolling_std`.
+///
+/// Args:
+///     data (List[float] | np.ndarray): Input series.
+///     period (int): Window length.
+///
+/// Returns:
+///     List[float] | np.ndarray: NaN until the first full window and for any
+///     window containing a NaN or ±Inf; 0 for a flat window.
+#[pyfunction]
+#[pyo3(name = "rolling_zscore", signature = (data, period))]
+pub fn py_rolling_zscore(py: Python<'_>, data: Series<'_>, period: usize) -> PyResult<PyObject> {
+    let _span = profile::span("rolling_zscore");
+    errors::check_period("period", period)?;
+    let cfg = config::current();
+    dispatch_series!(py, data, cached("rolling_zscore", [period, cfg.cache_key()]), |d| {
+        stats::rolling_zscore_ddof(d, period, cfg.ddof())
+    })
+}
@@ -0,0 +1,268 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use growin_core::candles::Candles;
+use growin_core::rules;
+
+use crate::candles::PyCandles;
+
+/// Latest price and bar history of one ticker.
+#[derive(Clone, Debug, Default)]
+struct Entry {
+    price: f64,
+    price_ts: i64,
+    /// Shared with in-flight readers; writers copy on write only while a
+    /// reader still holds the previous version.
+    bars: Arc<Candles>,
+}
+
+impl Entry {
+    fn set_price(&mut self, price: f64, timestamp: i64) -> bool {
+        if timestamp < self.price_ts {
+            return false;
+        }
+        self.price = price;
+        self.price_ts = timestamp;
+        true
+    }
+}
+
+/// Appends a bar, or replaces the last one when it has the same timestamp
+/// (an in-progress bar). Bars older than the last one are rejected.
+fn push_bar(bars: &mut Candles, timestamp: i64, ohlcv: [f64; 5], max_bars: usize) -> bool {
+    let [open, high, low, close, volume] = ohlcv;
+    match bars.timestamps.last() {
+        Some(&last) if timestamp < last => return false,
+        Some(&last) if timestamp == last => {
+            let i = bars.len() - 1;
+            (bars.open[i], bars.high[i], bars.low[i], bars.close[i], bars.volume[i]) = (open, high, low, close, volume);
+            return true;
+        }
+        _ => {}
+    }
+    bars.open.push(open);
+    bars.high.push(high);
+    bars.low.push(low);
+    bars.close.push(close);
+    bars.volume.push(volume);
+    bars.timestamps.push(timestamp);
+    if bars.len() > max_bars {
+        let excess = bars.len() - max_bars;
+        for column in [&mut bars.open, &mut bars.high, &mut bars.low, &mut bars.close, &mut bars.volume] {
+            column.drain(..excess);
+        }
+        bars.timestamps.drain(..excess);
+    }
+    true
+}
+
+/// Thread-safe store of live market data shared between the feed and the
+/// analysis code.
+///
+/// A websocket thread pushes prices and bars while analysis threads read
+/// them, all without holding the GIL. Updates stamped older than the stored
+/// data are dropped, so a late message can never overwrite a newer price.
+/// Readers take a reference to each ticker's bars under a brief read lock and
+/// compute outside it, so long evaluations never stall the feed.
+///
+/// ```python
+/// store = MarketDataStore(max_bars=5000)
+/// store.push_bar("AAPL", ts, 189.1, 189.6, 188.9, 189.4, 1.2e6)   # feed thread
+/// store.evaluate("rsi(14)")                                        # analysis thread
+/// ```
+#[pyclass(module = "growin_core", frozen)]
+pub struct MarketDataStore {
+    entries: RwLock<HashMap<String, Entry>>,
+    max_bars: usize,
+}
+
+impl MarketDataStore {
+    /// Bar histories of the requested (default: all) tickers, sorted by ticker.
+    pub fn snapshot(&self, tickers: Option<&[String]>) -> Vec<(String, Arc<Candles>)> {
+        let entries = self.entries.read().unwrap();
+        let mut out: Vec<(String, Arc<Candles>)> = match tickers {
+            Some(tickers) => tickers
+                .iter()
+                .filter_map(|t| entries.get(t).map(|e| (t.clone(), e.bars.clone())))
+                .collect(),
+            None => entries.iter().map(|(t, e)| (t.clone(), e.bars.clone())).collect(),
+        };
+        out.sort_by(|a, b| a.0.cmp(&b.0));
+        out
+    }
+}
+
+#[pymethods]
+impl MarketDataStore {
+    #[new]
+    #[pyo3(signature = (max_bars=10_000))]
+    fn new(max_bars: usize) -> PyResult<Self> {
+        crate::errors::check_period("max_bars", max_bars)?;
+        Ok(MarketDataStore { entries: RwLock::new(HashMap::new()), max_bars })
+    }
+
+    /// Record the latest trade/quote price.
+    ///
+    /// Returns:
+    ///     bool: False if `timestamp` is older than the stored price (ignored).
+    fn update_price(&self, py: Python<'_>, ticker: String, price: f64, timestamp: i64) -> bool {
+        py.allow_threads(|| self.entries.write().unwrap().entry(ticker).or_default().set_price(price, timestamp))
+    }
+
+    /// Record latest prices for several tickers sharing one timestamp.
+    ///
+    /// Returns:
+    ///     int: Number of prices applied (stale ones are ignored).
+    fn update_prices(&self, py: Python<'_>, prices: HashMap<String, f64>, timestamp: i64) -> usize {
+        py.allow_threads(|| {
+            let mut entries = self.entries.write().unwrap();
+            prices
+                .into_iter()
+                .filter(|(ticker, price)| entries.entry(ticker.clone()).or_default().set_price(*price, timestamp))
+                .count()
+        })
+    }
+
+    /// Append a bar, or update the last one if `timestamp` matches it. The
+    /// bar's close also becomes the latest price unless a newer one is stored.
+    ///
+    /// Returns:
+    ///     bool: False if the bar is older than the last stored bar (ignored).
+    #[pyo3(signature = (ticker, timestamp, open, high, low, close, volume=None))]
+    #[allow(clippy::too_many_arguments)]
+    fn push_bar(
+        &self,
+        py: Python<'_>,
+        ticker: String,
+        timestamp: i64,
+        open: f64,
+        high: f64,
+        low: f64,
+        close: f64,
+        volume: Option<f64>,
+    ) -> bool {
+        let ohlcv = [open, high, low, close, volume.unwrap_or(f64::NAN)];
+        py.allow_threads(|| {
+            let mut entries = self.entries.write().unwrap();
+            let entry = entries.entry(ticker).or_default();
+            if !push_bar(Arc::make_mut(&mut entry.bars), timestamp, ohlcv, self.max_bars) {
+                return false;
+            }
+            entry.set_price(close, timestamp);
+            true
+        })
+    }
+
+    /// Replace a ticker's bar history, e.g. after backfilling from REST.
+    /// `candles` must include timestamps; only the last `max_bars` are kept.
+    fn set_bars(&self, py: Python<'_>, ticker: String, candles: PyCandles) -> PyResult<()> {
+        let mut candles = candles.candles;
+        if candles.timestamps.len() != candles.len() {
+            return Err(PyValueError::new_err("candles must include a 'timestamp' column"));
+        }
+        if candles.timestamps.windows(2).any(|w| w[1] <= w[0]) {
+            return Err(PyValueError::new_err("candle timestamps must be strictly ascending"));
+        }
+        if !candles.has_volume() {
+            candles.volume = vec![f64::NAN; candles.len()];
+        }
+        let excess = candles.len().saturating_sub(self.max_bars);
+        for column in [&mut candles.open, &mut candles.high, &mut candles.low, &mut candles.close, &mut candles.volume] {
+            column.drain(..excess);
+        }
+        candles.timestamps.drain(..excess);
+
+        py.allow_threads(|| {
+            let mut entries = self.entries.write().unwrap();
+            let entry = entries.entry(ticker).or_default();
+            if let (Some(&ts), Some(&close)) = (candles.timestamps.last(), candles.close.last()) {
+                entry.set_price(close, ts);
+            }
+            entry.bars = Arc::new(candles);
+        });
+        Ok(())
+    }
+
+    /// Latest price of `ticker`, or None if unknown.
+    fn latest_price(&self, ticker: &str) -> Option<f64> {
+        self.entries.read().unwrap().get(ticker).map(|e| e.price).filter(|p| !p.is_nan())
+    }
+
+    /// Latest prices as {ticker: price} for `tickers` (default: all known).
+    #[pyo3(signature = (tickers=None))]
+    fn latest_prices(&self, tickers: Option<Vec<String>>) -> HashMap<String, f64> {
+        let entries = self.entries.read().unwrap();
+        let priced = |(t, e): (&String, &Entry)| (!e.price.is_nan()).then(|| (t.clone(), e.price));
+        match tickers {
+            Some(tickers) => tickers.iter().filter_map(|t| entries.get_key_value(t)).filter_map(priced).collect(),
+            None => entries.iter().filter_map(priced).collect(),
+        }
+    }
+
+    /// Copy of a ticker's bars as a dict of columns (open, high, low, close,
+    /// volume, timestamp), optionally only the `last` n. None if unknown.
+    #[pyo3(signature = (ticker, last=None))]
+    fn bars<'py>(&self, py: Python<'py>, ticker: &str, last: Option<usize>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        let Some(bars) = self.entries.read().unwrap().get(ticker).map(|e| e.bars.clone()) else {
+            return Ok(None);
+        };
+        let start = last.map_or(0, |n| bars.len().saturating_sub(n));
+        let out = PyDict::new(py);
+        out.set_item("open", &bars.open[start..])?;
+        out.set_item("high", &bars.high[start..])?;
+        out.set_item("low", &bars.low[start..])?;
+        out.set_item("close", &bars.close[start..])?;
+        out.set_item("volume", &bars.volume[start..])?;
+        out.set_item("timestamp", &bars.timestamps[start..])?;
+        Ok(Some(out))
+    }
+
+    /// Latest value of a rule-language expression (e.g. "rsi(14)") for each
+    /// ticker with bars, evaluated in parallel on the stored data.
+    ///
+    /// Returns:
+    ///     Dict[str, float]: ticker -> latest value (NaN during warm-up).
+    #[pyo3(signature = (expression, tickers=None))]
+    fn evaluate(&self, py: Python<'_>, expression: &str, tickers: Option<Vec<String>>) -> PyResult<HashMap<String, f64>> {
+        let expr = rules::parse_rule(expression).map_err(PyValueError::new_err)?;
+        py.allow_threads(|| {
+            self.snapshot(tickers.as_deref())
+                .par_iter()
+                .filter(|(_, bars)| !bars.is_empty())
+                .map(|(ticker, bars)| {
+                    let values = rules::evaluate_numeric(&expr, bars).map_err(|e| format!("{}: {}", ticker, e))?;
+                    Ok((ticker.clone(), values.last().copied().unwrap_or(f64::NAN)))
+                })
+                .collect::<Result<HashMap<_, _>, String>>()
+        })
+        .map_err(PyValueError::new_err)
+    }
+
+    /// Drop a ticker. Returns whether it was present.
+    fn remove(&self, ticker: &str) -> bool {
+        self.entries.write().unwrap().remove(ticker).is_some()
+    }
+
+    /// Known tickers, sorted.
+    fn tickers(&self) -> Vec<String> {
+        let mut tickers: Vec<String> = self.entries.read().unwrap().keys().cloned().collect();
+        tickers.sort();
+        tickers
+    }
+
+    fn __len__(&self) -> usize {
+        self.entries.read().unwrap().len()
+    }
+
+    fn __contains__(&self, ticker: &str) -> bool {
+        self.entries.read().unwrap().contains_key(ticker)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("MarketDataStore(tickers={}, max_bars={})", self.__len__(), self.max_bars)
+    }
+}
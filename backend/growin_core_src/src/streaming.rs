@@ -0,0 +1,419 @@
+//! Live indicator states advanced one tick at a time.
+//!
+//! Each class wraps one of the chunk-at-a-time states, so `update` costs
+//! O(1) whatever the history length and returns exactly the last value the
+//! matching `calculate_*` function gives over every value fed so far,
+//! warm-up padding included.
+
+use pyo3::prelude::*;
+
+use growin_core::chunked::{AtrState, BbandsState, EmaSeed, EmaState, MacdState, RsiState, SmaState, Streaming};
+
+use crate::chunked::Warmup;
+use crate::config::{self, Padding};
+use crate::errors;
+
+/// A state plus what the padding policy needs: how many values it has seen
+/// and where each output's warm-up ends.
+struct Live<S: Streaming> {
+    state: S,
+    valid_from: Vec<usize>,
+    pad: bool,
+    seen: usize,
+    last: Option<S::Output>,
+}
+
+impl<S: Streaming> Live<S>
+where
+    S::Output: Warmup + Copy,
+{
+    fn new(state: S, valid_from: Vec<usize>) -> Self {
+        let pad = config::current().padding == Padding::Nan;
+        Live { state, valid_from, pad, seen: 0, last: None }
+    }
+
+    fn update(&mut self, x: f64) -> S::Output {
+        let mut value = self.state.push(x);
+        self.finish(&mut value);
+        value
+    }
+
+    fn finish(&mut self, value: &mut S::Output) {
+        if self.pad {
+            value.blank(self.seen, &self.valid_from);
+        }
+        self.seen += 1;
+        self.last = Some(*value);
+    }
+
+    /// Whether every output is past its warm-up.
+    fn ready(&self) -> bool {
+        self.valid_from.iter().all(|&from| self.seen > from)
+    }
+}
+
+/// Streaming RSI; see `calculate_rsi`.
+///
+/// ```python
+/// rsi = RsiState(14)
+/// rsi.update_many(history)   # prime from the backfill
+/// rsi.update(tick_price)     # O(1) per tick
+/// ```
+#[pyclass(module = "growin_core", name = "RsiState")]
+pub struct PyRsiState {
+    period: usize,
+    live: Live<RsiState>,
+}
+
+#[pymethods]
+impl PyRsiState {
+    #[new]
+    #[pyo3(signature = (period=14))]
+    fn new(period: usize) -> PyResult<Self> {
+        errors::check_period("period", period)?;
+        Ok(PyRsiState { period, live: Live::new(RsiState::new(period), vec![period]) })
+    }
+
+    /// Feed one price and return the latest RSI.
+    fn update(&mut self, price: f64) -> f64 {
+        self.live.update(price)
+    }
+
+    /// Feed prices in order and return the RSI after each.
+    fn update_many(&mut self, prices: Vec<f64>) -> Vec<f64> {
+        prices.into_iter().map(|p| self.live.update(p)).collect()
+    }
+
+    /// Forget every value fed so far.
+    fn reset(&mut self) {
+        self.live = Live::new(RsiState::new(self.period), vec![self.period]);
+    }
+
+    /// The latest RSI; None before the first update.
+    #[getter]
+    fn value(&self) -> Option<f64> {
+        self.live.last
+    }
+
+    /// Number of values fed.
+    #[getter]
+    fn count(&self) -> usize {
+        self.live.seen
+    }
+
+    /// Whether the warm-up is over.
+    #[getter]
+    fn ready(&self) -> bool {
+        self.live.ready()
+    }
+
+    #[getter]
+    fn period(&self) -> usize {
+        self.period
+    }
+
+    fn __repr__(&self) -> String {
+        format!("RsiState(period={}, count={})", self.period, self.live.seen)
+    }
+}
+
+/// Streaming SMA; see `calculate_sma` and `RsiState`.
+#[pyclass(module = "growin_core", name = "SmaState")]
+pub struct PySmaState {
+    period: usize,
+    live: Live<SmaState>,
+}
+
+#[pymethods]
+impl PySmaState {
+    #[new]
+    #[pyo3(signature = (period=20))]
+    fn new(period: usize) -> PyResult<Self> {
+        errors::check_period("period", period)?;
+        Ok(PySmaState { period, live: Live::new(SmaState::new(period), vec![period - 1]) })
+    }
+
+    fn update(&mut self, price: f64) -> f64 {
+        self.live.update(price)
+    }
+
+    fn update_many(&mut self, prices: Vec<f64>) -> Vec<f64> {
+        prices.into_iter().map(|p| self.live.update(p)).collect()
+    }
+
+    fn reset(&mut self) {
+        self.live = Live::new(SmaState::new(self.period), vec![self.period - 1]);
+    }
+
+    #[getter]
+    fn value(&self) -> Option<f64> {
+        self.live.last
+    }
+
+    #[getter]
+    fn count(&self) -> usize {
+        self.live.seen
+    }
+
+    #[getter]
+    fn ready(&self) -> bool {
+        self.live.ready()
+    }
+
+    #[getter]
+    fn period(&self) -> usize {
+        self.period
+    }
+
+    fn __repr__(&self) -> String {
+        format!("SmaState(period={}, count={})", self.period, self.live.seen)
+    }
+}
+
+/// Streaming EMA; see `calculate_ema` and `RsiState`. Matches
+/// `calculate_ema` once at least `period` values have been fed.
+#[pyclass(module = "growin_core", name = "EmaState")]
+pub struct PyEmaState {
+    period: usize,
+    live: Live<EmaState>,
+}
+
+#[pymethods]
+impl PyEmaState {
+    #[new]
+    #[pyo3(signature = (period=14))]
+    fn new(period: usize) -> PyResult<Self> {
+        errors::check_period("period", period)?;
+        Ok(PyEmaState { period, live: Live::new(EmaState::new(period, EmaSeed::Lanes), vec![period - 1]) })
+    }
+
+    fn update(&mut self, price: f64) -> f64 {
+        self.live.update(price)
+    }
+
+    fn update_many(&mut self, prices: Vec<f64>) -> Vec<f64> {
+        prices.into_iter().map(|p| self.live.update(p)).collect()
+    }
+
+    fn reset(&mut self) {
+        self.live = Live::new(EmaState::new(self.period, EmaSeed::Lanes), vec![self.period - 1]);
+    }
+
+    #[getter]
+    fn value(&self) -> Option<f64> {
+        self.live.last
+    }
+
+    #[getter]
+    fn count(&self) -> usize {
+        self.live.seen
+    }
+
+    #[getter]
+    fn ready(&self) -> bool {
+        self.live.ready()
+    }
+
+    #[getter]
+    fn period(&self) -> usize {
+        self.period
+    }
+
+    fn __repr__(&self) -> String {
+        format!("EmaState(period={}, count={})", self.period, self.live.seen)
+    }
+}
+
+/// Streaming MACD; see `calculate_macd` and `RsiState`. Values are
+/// (macd, signal, histogram) tuples.
+#[pyclass(module = "growin_core", name = "MacdState")]
+pub struct PyMacdState {
+    fast: usize,
+    slow: usize,
+    signal: usize,
+    live: Live<MacdState>,
+}
+
+impl PyMacdState {
+    fn live(fast: usize, slow: usize, signal: usize) -> Live<MacdState> {
+        let seeded = fast.max(slow) - 1;
+        Live::new(MacdState::new(fast, slow, signal), vec![seeded, seeded + signal - 1, seeded + signal - 1])
+    }
+}
+
+#[pymethods]
+impl PyMacdState {
+    #[new]
+    #[pyo3(signature = (fast=12, slow=26, signal=9))]
+    fn new(fast: usize, slow: usize, signal: usize) -> PyResult<Self> {
+        errors::check_period("fast", fast)?;
+        errors::check_period("slow", slow)?;
+        errors::check_period("signal", signal)?;
+        Ok(PyMacdState { fast, slow, signal, live: Self::live(fast, slow, signal) })
+    }
+
+    fn update(&mut self, price: f64) -> (f64, f64, f64) {
+        self.live.update(price)
+    }
+
+    fn update_many(&mut self, prices: Vec<f64>) -> Vec<(f64, f64, f64)> {
+        prices.into_iter().map(|p| self.live.update(p)).collect()
+    }
+
+    fn reset(&mut self) {
+        self.live = Self::live(self.fast, self.slow, self.signal);
+    }
+
+    #[getter]
+    fn value(&self) -> Option<(f64, f64, f64)> {
+        self.live.last
+    }
+
+    #[getter]
+    fn count(&self) -> usize {
+        self.live.seen
+    }
+
+    #[getter]
+    fn ready(&self) -> bool {
+        self.live.ready()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("MacdState(fast={}, slow={}, signal={}, count={})", self.fast, self.slow, self.signal, self.live.seen)
+    }
+}
+
+/// Streaming Bollinger Bands; see `calculate_bbands` and `RsiState`.
+/// Values are (upper, middle, lower) tuples.
+#[pyclass(module = "growin_core", name = "BbandsState")]
+pub struct PyBbandsState {
+    period: usize,
+    std_dev: f64,
+    live: Live<BbandsState>,
+}
+
+impl PyBbandsState {
+    fn live(period: usize, std_dev: f64) -> Live<BbandsState> {
+        Live::new(BbandsState::with_ddof(period, std_dev, config::current().ddof()), vec![period - 1; 3])
+    }
+}
+
+#[pymethods]
+impl PyBbandsState {
+    #[new]
+    #[pyo3(signature = (period=20, std_dev=2.0))]
+    fn new(period: usize, std_dev: f64) -> PyResult<Self> {
+        errors::check_period("period", period)?;
+        Ok(PyBbandsState { period, std_dev, live: Self::live(period, std_dev) })
+    }
+
+    fn update(&mut self, price: f64) -> (f64, f64, f64) {
+        self.live.update(price)
+    }
+
+    fn update_many(&mut self, prices: Vec<f64>) -> Vec<(f64, f64, f64)> {
+        prices.into_iter().map(|p| self.live.update(p)).collect()
+    }
+
+    fn reset(&mut self) {
+        self.live = Self::live(self.period, self.std_dev);
+    }
+
+    #[getter]
+    fn value(&self) -> Option<(f64, f64, f64)> {
+        self.live.last
+    }
+
+    #[getter]
+    fn count(&self) -> usize {
+        self.live.seen
+    }
+
+    #[getter]
+    fn ready(&self) -> bool {
+        self.live.ready()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("BbandsState(period={}, std_dev={:?}, count={})", self.period, self.std_dev, self.live.seen)
+    }
+}
+
+/// Streaming ATR; see `calculate_atr` and `RsiState`. Bars without a high
+/// and low count as the close alone.
+#[pyclass(module = "growin_core", name = "AtrState")]
+pub struct PyAtrState {
+    period: usize,
+    state: AtrState,
+    seen: usize,
+    pad: bool,
+    last: Option<f64>,
+}
+
+impl PyAtrState {
+    fn push(&mut self, close: f64, high: Option<f64>, low: Option<f64>) -> f64 {
+        let mut value = self.state.push_bar(high.unwrap_or(close), low.unwrap_or(close), close);
+        if self.pad {
+            value.blank(self.seen, &[self.period - 1]);
+        }
+        self.seen += 1;
+        self.last = Some(value);
+        value
+    }
+}
+
+#[pymethods]
+impl PyAtrState {
+    #[new]
+    #[pyo3(signature = (period=14))]
+    fn new(period: usize) -> PyResult<Self> {
+        errors::check_period("period", period)?;
+        let pad = config::current().padding == Padding::Nan;
+        Ok(PyAtrState { period, state: AtrState::new(period), seen: 0, pad, last: None })
+    }
+
+    /// Feed one bar and return the latest ATR.
+    #[pyo3(signature = (close, high=None, low=None))]
+    fn update(&mut self, close: f64, high: Option<f64>, low: Option<f64>) -> f64 {
+        self.push(close, high, low)
+    }
+
+    /// Feed bars in order and return the ATR after each; `high` and `low`
+    /// default to the closes.
+    #[pyo3(signature = (close, high=None, low=None))]
+    fn update_many(&mut self, close: Vec<f64>, high: Option<Vec<f64>>, low: Option<Vec<f64>>) -> PyResult<Vec<f64>> {
+        let (high, low) = (high.unwrap_or_else(|| close.clone()), low.unwrap_or_else(|| close.clone()));
+        errors::check_lengths(&[("close", close.len()), ("high", high.len()), ("low", low.len())])?;
+        Ok((0..close.len()).map(|i| self.push(close[i], Some(high[i]), Some(low[i]))).collect())
+    }
+
+    fn reset(&mut self) {
+        let pad = config::current().padding == Padding::Nan;
+        *self = PyAtrState { period: self.period, state: AtrState::new(self.period), seen: 0, pad, last: None };
+    }
+
+    #[getter]
+    fn value(&self) -> Option<f64> {
+        self.last
+    }
+
+    #[getter]
+    fn count(&self) -> usize {
+        self.seen
+    }
+
+    #[getter]
+    fn ready(&self) -> bool {
+        self.seen >= self.period
+    }
+
+    #[getter]
+    fn period(&self) -> usize {
+        self.period
+    }
+
+    fn __repr__(&self) -> String {
+        format!("AtrState(period={}, count={})", self.period, self.seen)
+    }
+}
@@ -0,0 +1,1489 @@
+//! Price-based indicators beyond the RSI/SMA/EMA/MACD/Bollinger core set.
+//!
+//! Same conventions as the functions in `lib.rs`: lists or float32/float64
+//! arrays in, the same type out, warm-ups padded per `Config(padding=...)`
+//! and results memoized when the cache is enabled. Inputs such as high, low
+//! and close must have equal lengths.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{IntoPyDict, PyDict};
+
+use growin_core::candles;
+use growin_core::indicators::{self, PivotMethod, Swing};
+
+use crate::candles::PyCandles;
+use crate::results::IndicatorResult;
+use crate::series::{check_columns, dispatch_series, Columns, IntoSeriesOutput, Series};
+use crate::{config, errors, profile};
+
+/// Calculate Average True Range (ATR) with Wilder's smoothing.
+///
+/// Args:
+///     high (List[float] | np.ndarray): Bar highs.
+///     low (List[float] | np.ndarray): Bar lows.
+///     close (List[float] | np.ndarray): Bar closes.
+///     period (int): Lookback period (default 14).
+///
+/// Returns:
+///     List[float] | np.ndarray: ATR per bar, seeded with the mean true range
+///     of the first `period` bars (the first `period - 1` are 0, or NaN under
+///     `Config(padding="nan")`).
+///
+/// Raises:
+///     InvalidPeriodError: If `period` is 0.
+///     LengthMismatchError: If the inputs differ in length.
+#[pyfunction]
+#[pyo3(name = "calculate_atr", signature = (high, low, close, period=14))]
+pub fn py_calculate_atr(py: Python<'_>, high: Series<'_>, low: Series<'_>, close: Series<'_>, period: usize) -> PyResult<PyObject> {
+    let _span = profile::span("calculate_atr");
+    errors::check_period("period", period)?;
+    check_columns(&[("high", &high), ("low", &low), ("close", &close)])?;
+    let cfg = config::current();
+    dispatch_series!(py, [high, low, close], cached("atr", [period, cfg.cache_key()]), |high, low, close| {
+        let mut out = indicators::atr(high, low, close, period);
+        cfg.pad(&mut out, period - 1);
+        out
+    })
+}
+
+/// Calculate the Stochastic Oscillator.
+///
+/// Args:
+///     high (List[float] | np.ndarray): Bar highs.
+///     low (List[float] | np.ndarray): Bar lows.
+///     close (List[float] | np.ndarray): Bar closes.
+///     k_period (int): High-low range lookback (default 14).
+///     d_period (int): SMA period of %D over %K (default 3).
+///     smooth_k (int): SMA period smoothing raw %K (default 3, the "slow"
+///         stochastic; 1 for the fast one).
+///
+/// Returns:
+///     IndicatorResult: .k and .d in 0-100 (50 while the range is flat),
+///     unpacking as (k, d). Warm-ups are 0 (NaN under
+///     `Config(padding="nan")`).
+#[pyfunction]
+#[pyo3(name = "calculate_stochastic", signature = (high, low, close, k_period=14, d_period=3, smooth_k=3))]
+pub fn py_calculate_stochastic(
+    py: Python<'_>,
+    high: Series<'_>,
+    low: Series<'_>,
+    close: Series<'_>,
+    k_period: usize,
+    d_period: usize,
+    smooth_k: usize,
+) -> PyResult<IndicatorResult> {
+    let _span = profile::span("calculate_stochastic");
+    errors::check_period("k_period", k_period)?;
+    errors::check_period("d_period", d_period)?;
+    errors::check_period("smooth_k", smooth_k)?;
+    let len = check_columns(&[("high", &high), ("low", &low), ("close", &close)])?;
+    let cfg = config::current();
+    let k_from = k_period + smooth_k - 2;
+    let out = dispatch_series!(
+        py,
+        [high, low, close],
+        cached("stochastic", [k_period, d_period, smooth_k, cfg.cache_key()]),
+        |high, low, close| {
+            let (mut k, mut d) = indicators::stochastic(high, low, close, k_period, d_period, smooth_k);
+            cfg.pad(&mut k, k_from);
+            cfg.pad(&mut d, k_from + d_period - 1);
+            (k, d)
+        }
+    )?;
+    IndicatorResult::new(py, "stochastic", out, &["k", "d"], (k_from + d_period - 1).min(len))?
+        .param(py, "k_period", k_period)?
+        .param(py, "d_period", d_period)?
+        .param(py, "smooth_k", smooth_k)
+}
+
+/// Calculate the Average Directional Index with +DI and -DI (Wilder).
+///
+/// Args:
+///     high (List[float] | np.ndarray): Bar highs.
+///     low (List[float] | np.ndarray): Bar lows.
+///     close (List[float] | np.ndarray): Bar closes.
+///     period (int): Smoothing period (default 14).
+///
+/// Returns:
+///     IndicatorResult: .adx, .plus_di and .minus_di in 0-100, unpacking as
+///     (adx, plus_di, minus_di). The DI lines warm up over `period` bars and
+///     ADX over `2 * period - 1`; warm-ups are 0 (NaN under
+///     `Config(padding="nan")`).
+#[pyfunction]
+#[pyo3(name = "calculate_adx", signature = (high, low, close, period=14))]
+pub fn py_calculate_adx(py: Python<'_>, high: Series<'_>, low: Series<'_>, close: Series<'_>, period: usize) -> PyResult<IndicatorResult> {
+    let _span = profile::span("calculate_adx");
+    errors::check_period("period", period)?;
+    let len = check_columns(&[("high", &high), ("low", &low), ("close", &close)])?;
+    let cfg = config::current();
+    let out = dispatch_series!(py, [high, low, close], cached("adx", [period, cfg.cache_key()]), |high, low, close| {
+        let (mut adx, mut plus_di, mut minus_di) = indicators::adx(high, low, close, period);
+        cfg.pad(&mut adx, 2 * period - 1);
+        cfg.pad(&mut plus_di, period);
+        cfg.pad(&mut minus_di, period);
+        (adx, plus_di, minus_di)
+    })?;
+    IndicatorResult::new(py, "adx", out, &["adx", "plus_di", "minus_di"], (2 * period - 1).min(len))?.param(py, "period", period)
+}
+
+/// Calculate On-Balance Volume (OBV).
+///
+/// Args:
+///     close (List[float] | np.ndarray): Bar closes.
+///     volume (List[float] | np.ndarray): Bar volumes.
+///
+/// Returns:
+///     List[float] | np.ndarray: Cumulative OBV, 0 on the first bar; a bar
+///     whose close is unchanged (or NaN) leaves it flat.
+#[pyfunction]
+#[pyo3(name = "calculate_obv", signature = (close, volume))]
+pub fn py_calculate_obv(py: Python<'_>, close: Series<'_>, volume: Series<'_>) -> PyResult<PyObject> {
+    let _span = profile::span("calculate_obv");
+    check_columns(&[("close", &close), ("volume", &volume)])?;
+    dispatch_series!(py, [close, volume], cached("obv", []), |close, volume| indicators::obv(close, volume))
+}
+
+/// Calculate the Volume-Weighted Average Price (VWAP) of the typical price
+/// `(high + low + close) / 3`.
+///
+/// By default the VWAP accumulates from the first bar. With `timestamps` it
+/// restarts at each new day (session VWAP, days taken in local time via
+/// `utc_offset_minutes`); with `window` it covers the trailing `window` bars.
+///
+/// Args:
+///     high (List[float] | np.ndarray): Bar highs.
+///     low (List[float] | np.ndarray): Bar lows.
+///     close (List[float] | np.ndarray): Bar closes.
+///     volume (List[float] | np.ndarray): Bar volumes.
+///     window (int, optional): Rolling window in bars.
+///     timestamps (List[int], optional): Bar times in epoch seconds, for
+///         daily sessions.
+///     utc_offset_minutes (int): Offset of the exchange's local time from
+///         UTC (default 0).
+///
+/// Returns:
+///     List[float] | np.ndarray: VWAP per bar (the typical price until
+///     volume trades). A rolling VWAP's first `window - 1` values are 0, or
+///     NaN under `Config(padding="nan")`.
+///
+/// Raises:
+///     ValueError: If both `window` and `timestamps` are given.
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(
+    name = "calculate_vwap",
+    signature = (high, low, close, volume, window=None, timestamps=None, utc_offset_minutes=0)
+)]
+pub fn py_calculate_vwap(
+    py: Python<'_>,
+    high: Series<'_>,
+    low: Series<'_>,
+    close: Series<'_>,
+    volume: Series<'_>,
+    window: Option<usize>,
+    timestamps: Option<Vec<i64>>,
+    utc_offset_minutes: i64,
+) -> PyResult<PyObject> {
+    let _span = profile::span("calculate_vwap");
+    let len = check_columns(&[("high", &high), ("low", &low), ("close", &close), ("volume", &volume)])?;
+    if let Some(window) = window {
+        if timestamps.is_some() {
+            return Err(PyValueError::new_err("pass either window or timestamps, not both"));
+        }
+        errors::check_period("window", window)?;
+        let cfg = config::current();
+        return dispatch_series!(
+            py,
+            [high, low, close, volume],
+            cached("rolling_vwap", [window, cfg.cache_key()]),
+            |high, low, close, volume| {
+                let mut out = indicators::rolling_vwap(high, low, close, volume, window);
+                cfg.pad(&mut out, window - 1);
+                out
+            }
+        );
+    }
+    let sessions = match timestamps {
+        Some(ts) => {
+            errors::check_lengths(&[("high", len), ("timestamps", ts.len())])?;
+            let offset = utc_offset_minutes * 60;
+            Some(ts.iter().map(|t| (t + offset).div_euclid(86_400)).collect::<Vec<i64>>())
+        }
+        None => None,
+    };
+    dispatch_series!(py, [high, low, close, volume], |high, low, close, volume| {
+        indicators::vwap(high, low, close, volume, sessions.as_deref())
+    })
+}
+
+/// Calculate the Commodity Channel Index (CCI).
+///
+/// Args:
+///     high (List[float] | np.ndarray): Bar highs.
+///     low (List[float] | np.ndarray): Bar lows.
+///     close (List[float] | np.ndarray): Bar closes.
+///     period (int): Lookback period (default 20).
+///     constant (float): Lambert's scaling constant (default 0.015, which puts
+///         roughly three quarters of values within ±100).
+///
+/// Returns:
+///     List[float] | np.ndarray: CCI per bar (0 over a flat window); the first
+///     `period - 1` are 0, or NaN under `Config(padding="nan")`.
+#[pyfunction]
+#[pyo3(name = "calculate_cci", signature = (high, low, close, period=20, constant=0.015))]
+pub fn py_calculate_cci(
+    py: Python<'_>,
+    high: Series<'_>,
+    low: Series<'_>,
+    close: Series<'_>,
+    period: usize,
+    constant: f64,
+) -> PyResult<PyObject> {
+    let _span = profile::span("calculate_cci");
+    errors::check_period("period", period)?;
+    if !constant.is_finite() || constant <= 0.0 {
+        return Err(PyValueError::new_err(format!("constant must be positive, got {}", constant)));
+    }
+    check_columns(&[("high", &high), ("low", &low), ("close", &close)])?;
+    let cfg = config::current();
+    dispatch_series!(py, [high, low, close], cached("cci", [period, constant, cfg.cache_key()]), |high, low, close| {
+        let mut out = indicators::cci(high, low, close, period, constant);
+        cfg.pad(&mut out, period - 1);
+        out
+    })
+}
+
+/// Calculate Williams %R.
+///
+/// Args:
+///     high (List[float] | np.ndarray): Bar highs.
+///     low (List[float] | np.ndarray): Bar lows.
+///     close (List[float] | np.ndarray): Bar closes.
+///     period (int): Lookback period (default 14).
+///
+/// Returns:
+///     List[float] | np.ndarray: %R in [-100, 0] (above -20 is overbought,
+///     below -80 oversold; -50 over a flat range). The first `period - 1`
+///     are 0, or NaN under `Config(padding="nan")`.
+#[pyfunction]
+#[pyo3(name = "calculate_williams_r", signature = (high, low, close, period=14))]
+pub fn py_calculate_williams_r(py: Python<'_>, high: Series<'_>, low: Series<'_>, close: Series<'_>, period: usize) -> PyResult<PyObject> {
+    let _span = profile::span("calculate_williams_r");
+    errors::check_period("period", period)?;
+    check_columns(&[("high", &high), ("low", &low), ("close", &close)])?;
+    let cfg = config::current();
+    dispatch_series!(py, [high, low, close], cached("williams_r", [period, cfg.cache_key()]), |high, low, close| {
+        let mut out = indicators::williams_r(high, low, close, period);
+        cfg.pad(&mut out, period - 1);
+        out
+    })
+}
+
+/// Calculate the Money Flow Index (MFI), a volume-weighted RSI.
+///
+/// Args:
+///     high (List[float] | np.ndarray): Bar highs.
+///     low (List[float] | np.ndarray): Bar lows.
+///     close (List[float] | np.ndarray): Bar closes.
+///     volume (List[float] | np.ndarray): Bar volumes.
+///     period (int): Lookback period (default 14).
+///
+/// Returns:
+///     List[float] | np.ndarray: MFI in 0-100 (above 80 is overbought, below
+///     20 oversold). The first `period` are 0, or NaN under
+///     `Config(padding="nan")`.
+#[pyfunction]
+#[pyo3(name = "calculate_mfi", signature = (high, low, close, volume, period=14))]
+pub fn py_calculate_mfi(
+    py: Python<'_>,
+    high: Series<'_>,
+    low: Series<'_>,
+    close: Series<'_>,
+    volume: Series<'_>,
+    period: usize,
+) -> PyResult<PyObject> {
+    let _span = profile::span("calculate_mfi");
+    errors::check_period("period", period)?;
+    check_columns(&[("high", &high), ("low", &low), ("close", &close), ("volume", &volume)])?;
+    let cfg = config::current();
+    dispatch_series!(
+        py,
+        [high, low, close, volume],
+        cached("mfi", [period, cfg.cache_key()]),
+        |high, low, close, volume| {
+            let mut out = indicators::mfi(high, low, close, volume, period);
+            cfg.pad(&mut out, period);
+            out
+        }
+    )
+}
+
+/// Calculate SuperTrend.
+///
+/// The trend flips as in the original SuperTrend script, when the close
+/// crosses the previous bar's final band; Pine's `ta.supertrend` uses the
+/// current bar's band, and can flip a bar earlier where the band ratchets.
+///
+/// Args:
+///     high (List[float] | np.ndarray): Bar highs.
+///     low (List[float] | np.ndarray): Bar lows.
+///     close (List[float] | np.ndarray): Bar closes.
+///     period (int): ATR period (default 10).
+///     multiplier (float): Band distance in ATRs (default 3.0).
+///
+/// Returns:
+///     IndicatorResult: .supertrend (the trailing band, below price in an
+///     uptrend and above it in a downtrend) and .direction (1.0 up, -1.0
+///     down), unpacking as (supertrend, direction). The first `period - 1`
+///     are 0, or NaN under `Config(padding="nan")`.
+#[pyfunction]
+#[pyo3(name = "calculate_supertrend", signature = (high, low, close, period=10, multiplier=3.0))]
+pub fn py_calculate_supertrend(
+    py: Python<'_>,
+    high: Series<'_>,
+    low: Series<'_>,
+    close: Series<'_>,
+    period: usize,
+    multiplier: f64,
+) -> PyResult<IndicatorResult> {
+    let _span = profile::span("calculate_supertrend");
+    errors::check_period("period", period)?;
+    if !multiplier.is_finite() || multiplier <= 0.0 {
+        return Err(PyValueError::new_err(format!("multiplier must be positive, got {}", multiplier)));
+    }
+    let len = check_columns(&[("high", &high), ("low", &low), ("close", &close)])?;
+    let cfg = config::current();
+    let out = dispatch_series!(
+        py,
+        [high, low, close],
+        cached("supertrend", [period, multiplier, cfg.cache_key()]),
+        |high, low, close| {
+            let (mut line, mut direction) = indicators::supertrend(high, low, close, period, multiplier);
+            cfg.pad(&mut line, period - 1);
+            cfg.pad(&mut direction, period - 1);
+            (line, direction)
+        }
+    )?;
+    IndicatorResult::new(py, "supertrend", out, &["supertrend", "direction"], (period - 1).min(len))?
+        .param(py, "period", period)?
+        .param(py, "multiplier", multiplier)
+}
+
+/// Calculate the Rate of Change (ROC).
+///
+/// Args:
+///     data (List[float] | np.ndarray): Prices.
+///     period (int): Lookback period (default 12).
+///
+/// Returns:
+///     List[float] | np.ndarray: Percent change from `period` bars earlier
+///     (NaN where that price is 0). The first `period` are 0, or NaN under
+///     `Config(padding="nan")`.
+#[pyfunction]
+#[pyo3(name = "calculate_roc", signature = (data, period=12))]
+pub fn py_calculate_roc(py: Python<'_>, data: Series<'_>, period: usize) -> PyResult<PyObject> {
+    let _span = profile::span("calculate_roc");
+    errors::check_period("period", period)?;
+    data.check_has_data("data")?;
+    let cfg = config::current();
+    dispatch_series!(py, data, cached("roc", [period, cfg.cache_key()]), |d| {
+        let mut out = indicators::roc(d, period);
+        cfg.pad(&mut out, period);
+        out
+    })
+}
+
+/// Calculate Momentum, the price change over `period` bars.
+///
+/// Args:
+///     data (List[float] | np.ndarray): Prices.
+///     period (int): Lookback period (default 10).
+///
+/// Returns:
+///     List[float] | np.ndarray: `data[i] - data[i - period]`. The first
+///     `period` are 0, or NaN under `Config(padding="nan")`.
+#[pyfunction]
+#[pyo3(name = "calculate_momentum", signature = (data, period=10))]
+pub fn py_calculate_momentum(py: Python<'_>, data: Series<'_>, period: usize) -> PyResult<PyObject> {
+    let _span = profile::span("calculate_momentum");
+    errors::check_period("period", period)?;
+    data.check_has_data("data")?;
+    let cfg = config::current();
+    dispatch_series!(py, data, cached("momentum", [period, cfg.cache_key()]), |d| {
+        let mut out = indicators::momentum(d, period);
+        cfg.pad(&mut out, period);
+        out
+    })
+}
+
+/// Calculate TRIX, the rate of change of a triple-smoothed EMA.
+///
+/// Args:
+///     data (List[float] | np.ndarray): Prices.
+///     period (int): Period of each of the three EMAs (default 15).
+///     signal (int): EMA period of the signal line (default 9).
+///
+/// Returns:
+///     IndicatorResult: .trix (percent per bar) and .signal, unpacking as
+///     (trix, signal). TRIX warms up over `3 * (period - 1) + 1` bars and
+///     the signal over `signal - 1` more; warm-ups are 0 (NaN under
+///     `Config(padding="nan")`).
+#[pyfunction]
+#[pyo3(name = "calculate_trix", signature = (data, period=15, signal=9))]
+pub fn py_calculate_trix(py: Python<'_>, data: Series<'_>, period: usize, signal: usize) -> PyResult<IndicatorResult> {
+    let _span = profile::span("calculate_trix");
+    errors::check_period("period", period)?;
+    errors::check_period("signal", signal)?;
+    data.check_has_data("data")?;
+    let len = data.len();
+    let cfg = config::current();
+    let trix_from = 3 * (period - 1) + 1;
+    let out = dispatch_series!(py, data, cached("trix", [period, signal, cfg.cache_key()]), |d| {
+        let (mut trix, mut signal_line) = indicators::trix(d, period, signal);
+        cfg.pad(&mut trix, trix_from);
+        cfg.pad(&mut signal_line, trix_from + signal - 1);
+        (trix, signal_line)
+    })?;
+    IndicatorResult::new(py, "trix", out, &["trix", "signal"], (trix_from + signal - 1).min(len))?
+        .param(py, "period", period)?
+        .param(py, "signal", signal)
+}
+
+/// Calculate Aroon Up/Down and the Aroon Oscillator.
+///
+/// Args:
+///     high (List[float] | np.ndarray): Bar highs.
+///     low (List[float] | np.ndarray): Bar lows.
+///     period (int): Lookback period (default 25).
+///
+/// Returns:
+///     IndicatorResult: .aroon_up and .aroon_down in 0-100 (100 on the bar
+///     of a new `period` high or low, falling as it ages) and .oscillator
+///     (up minus down), unpacking as (aroon_up, aroon_down, oscillator).
+///     The first `period` are 0, or NaN under `Config(padding="nan")`.
+#[pyfunction]
+#[pyo3(name = "calculate_aroon", signature = (high, low, period=25))]
+pub fn py_calculate_aroon(py: Python<'_>, high: Series<'_>, low: Series<'_>, period: usize) -> PyResult<IndicatorResult> {
+    let _span = profile::span("calculate_aroon");
+    errors::check_period("period", period)?;
+    let len = check_columns(&[("high", &high), ("low", &low)])?;
+    let cfg = config::current();
+    let out = dispatch_series!(py, [high, low], cached("aroon", [period, cfg.cache_key()]), |high, low| {
+        let (mut up, mut down, mut oscillator) = indicators::aroon(high, low, period);
+        for line in [&mut up, &mut down, &mut oscillator] {
+            cfg.pad(line, period);
+        }
+        (up, down, oscillator)
+    })?;
+    IndicatorResult::new(py, "aroon", out, &["aroon_up", "aroon_down", "oscillator"], period.min(len))?.param(py, "period", period)
+}
+
+/// Calculate the Accumulation/Distribution (A/D) line.
+///
+/// Args:
+///     high (List[float] | np.ndarray): Bar highs.
+///     low (List[float] | np.ndarray): Bar lows.
+///     close (List[float] | np.ndarray): Bar closes.
+///     volume (List[float] | np.ndarray): Bar volumes.
+///
+/// Returns:
+///     List[float] | np.ndarray: Running total of each bar's volume weighted
+///     by where it closed in its range (+1 at the high, -1 at the low).
+#[pyfunction]
+#[pyo3(name = "calculate_ad", signature = (high, low, close, volume))]
+pub fn py_calculate_ad(py: Python<'_>, high: Series<'_>, low: Series<'_>, close: Series<'_>, volume: Series<'_>) -> PyResult<PyObject> {
+    let _span = profile::span("calculate_ad");
+    check_columns(&[("high", &high), ("low", &low), ("close", &close), ("volume", &volume)])?;
+    dispatch_series!(py, [high, low, close, volume], cached("ad", []), |high, low, close, volume| {
+        indicators::ad_line(high, low, close, volume)
+    })
+}
+
+/// Calculate Chaikin Money Flow (CMF).
+///
+/// Args:
+///     high (List[float] | np.ndarray): Bar highs.
+///     low (List[float] | np.ndarray): Bar lows.
+///     close (List[float] | np.ndarray): Bar closes.
+///     volume (List[float] | np.ndarray): Bar volumes.
+///     period (int): Lookback period (default 20).
+///
+/// Returns:
+///     List[float] | np.ndarray: CMF in [-1, 1] (positive under buying
+///     pressure). The first `period - 1` are 0, or NaN under
+///     `Config(padding="nan")`.
+#[pyfunction]
+#[pyo3(name = "calculate_cmf", signature = (high, low, close, volume, period=20))]
+pub fn py_calculate_cmf(
+    py: Python<'_>,
+    high: Series<'_>,
+    low: Series<'_>,
+    close: Series<'_>,
+    volume: Series<'_>,
+    period: usize,
+) -> PyResult<PyObject> {
+    let _span = profile::span("calculate_cmf");
+    errors::check_period("period", period)?;
+    check_columns(&[("high", &high), ("low", &low), ("close", &close), ("volume", &volume)])?;
+    let cfg = config::current();
+    dispatch_series!(
+        py,
+        [high, low, close, volume],
+        cached("cmf", [period, cfg.cache_key()]),
+        |high, low, close, volume| {
+            let mut out = indicators::cmf(high, low, close, volume, period);
+            cfg.pad(&mut out, period - 1);
+            out
+        }
+    )
+}
+
+/// Calculate the Chaikin Oscillator, MACD-style momentum of the A/D line.
+///
+/// Args:
+///     high (List[float] | np.ndarray): Bar highs.
+///     low (List[float] | np.ndarray): Bar lows.
+///     close (List[float] | np.ndarray): Bar closes.
+///     volume (List[float] | np.ndarray): Bar volumes.
+///     fast (int): Fast EMA period (default 3).
+///     slow (int): Slow EMA period (default 10).
+///
+/// Returns:
+///     List[float] | np.ndarray: EMA(fast) - EMA(slow) of `calculate_ad`.
+///     The first `max(fast, slow) - 1` are 0, or NaN under
+///     `Config(padding="nan")`.
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(name = "calculate_chaikin_oscillator", signature = (high, low, close, volume, fast=3, slow=10))]
+pub fn py_calculate_chaikin_oscillator(
+    py: Python<'_>,
+    high: Series<'_>,
+    low: Series<'_>,
+    close: Series<'_>,
+    volume: Series<'_>,
+    fast: usize,
+    slow: usize,
+) -> PyResult<PyObject> {
+    let _span = profile::span("calculate_chaikin_oscillator");
+    errors::check_period("fast", fast)?;
+    errors::check_period("slow", slow)?;
+    check_columns(&[("high", &high), ("low", &low), ("close", &close), ("volume", &volume)])?;
+    let cfg = config::current();
+    dispatch_series!(
+        py,
+        [high, low, close, volume],
+        cached("chaikin_oscillator", [fast, slow, cfg.cache_key()]),
+        |high, low, close, volume| {
+            let mut out = indicators::chaikin_oscillator(high, low, close, volume, fast, slow);
+            cfg.pad(&mut out, fast.max(slow) - 1);
+            out
+        }
+    )
+}
+
+/// Calculate the Double Exponential Moving Average (DEMA).
+///
+/// Args:
+///     data (List[float] | np.ndarray): Prices.
+///     period (int): EMA period (default 20).
+///
+/// Returns:
+///     List[float] | np.ndarray: `2 * EMA - EMA(EMA)`. The first
+///     `2 * (period - 1)` are 0, or NaN under `Config(padding="nan")`.
+#[pyfunction]
+#[pyo3(name = "calculate_dema", signature = (data, period=20))]
+pub fn py_calculate_dema(py: Python<'_>, data: Series<'_>, period: usize) -> PyResult<PyObject> {
+    let _span = profile::span("calculate_dema");
+    errors::check_period("period", period)?;
+    data.check_has_data("data")?;
+    let cfg = config::current();
+    dispatch_series!(py, data, cached("dema", [period, cfg.cache_key()]), |d| {
+        let mut out = indicators::dema(d, period);
+        cfg.pad(&mut out, 2 * (period - 1));
+        out
+    })
+}
+
+/// Calculate the Triple Exponential Moving Average (TEMA).
+///
+/// Args:
+///     data (List[float] | np.ndarray): Prices.
+///     period (int): EMA period (default 20).
+///
+/// Returns:
+///     List[float] | np.ndarray: `3 * EMA - 3 * EMA(EMA) + EMA(EMA(EMA))`.
+///     The first `3 * (period - 1)` are 0, or NaN under
+///     `Config(padding="nan")`.
+#[pyfunction]
+#[pyo3(name = "calculate_tema", signature = (data, period=20))]
+pub fn py_calculate_tema(py: Python<'_>, data: Series<'_>, period: usize) -> PyResult<PyObject> {
+    let _span = profile::span("calculate_tema");
+    errors::check_period("period", period)?;
+    data.check_has_data("data")?;
+    let cfg = config::current();
+    dispatch_series!(py, data, cached("tema", [period, cfg.cache_key()]), |d| {
+        let mut out = indicators::tema(d, period);
+        cfg.pad(&mut out, 3 * (period - 1));
+        out
+    })
+}
+
+/// Calculate the Weighted Moving Average (WMA).
+///
+/// Args:
+///     data (List[float] | np.ndarray): Prices.
+///     period (int): Lookback period (default 20).
+///
+/// Returns:
+///     List[float] | np.ndarray: Linearly weighted mean of the last `period`
+///     values (the newest weighs `period`, the oldest 1). The first
+///     `period - 1` are 0, or NaN under `Config(padding="nan")`.
+#[pyfunction]
+#[pyo3(name = "calculate_wma", signature = (data, period=20))]
+pub fn py_calculate_wma(py: Python<'_>, data: Series<'_>, period: usize) -> PyResult<PyObject> {
+    let _span = profile::span("calculate_wma");
+    errors::check_period("period", period)?;
+    data.check_has_data("data")?;
+    let cfg = config::current();
+    dispatch_series!(py, data, cached("wma", [period, cfg.cache_key()]), |d| {
+        let mut out = indicators::wma(d, period);
+        cfg.pad(&mut out, period - 1);
+        out
+    })
+}
+
+/// Calculate the Volume-Weighted Moving Average (VWMA).
+///
+/// Args:
+///     close (List[float] | np.ndarray): Bar closes.
+///     volume (List[float] | np.ndarray): Bar volumes.
+///     period (int): Lookback period (default 20).
+///
+/// Returns:
+///     List[float] | np.ndarray: Mean close of the last `period` bars
+///     weighted by volume (the close itself where nothing traded). The first
+///     `period - 1` are 0, or NaN under `Config(padding="nan")`.
+#[pyfunction]
+#[pyo3(name = "calculate_vwma", signature = (close, volume, period=20))]
+pub fn py_calculate_vwma(py: Python<'_>, close: Series<'_>, volume: Series<'_>, period: usize) -> PyResult<PyObject> {
+    let _span = profile::span("calculate_vwma");
+    errors::check_period("period", period)?;
+    check_columns(&[("close", &close), ("volume", &volume)])?;
+    let cfg = config::current();
+    dispatch_series!(py, [close, volume], cached("vwma", [period, cfg.cache_key()]), |close, volume| {
+        let mut out = indicators::vwma(close, volume, period);
+        cfg.pad(&mut out, period - 1);
+        out
+    })
+}
+
+/// Calculate the Zero-Lag Exponential Moving Average (ZLEMA).
+///
+/// Args:
+///     data (List[float] | np.ndarray): Prices.
+///     period (int): EMA period (default 14).
+///
+/// Returns:
+///     List[float] | np.ndarray: EMA of `2 * data[i] - data[i - lag]` with
+///     `lag = (period - 1) // 2`. The first `lag + period - 1` are 0, or NaN
+///     under `Config(padding="nan")`.
+#[pyfunction]
+#[pyo3(name = "calculate_zlema", signature = (data, period=14))]
+pub fn py_calculate_zlema(py: Python<'_>, data: Series<'_>, period: usize) -> PyResult<PyObject> {
+    let _span = profile::span("calculate_zlema");
+    errors::check_period("period", period)?;
+    data.check_has_data("data")?;
+    let cfg = config::current();
+    dispatch_series!(py, data, cached("zlema", [period, cfg.cache_key()]), |d| {
+        let mut out = indicators::zlema(d, period);
+        cfg.pad(&mut out, (period - 1) / 2 + period - 1);
+        out
+    })
+}
+
+/// Calculate pivot point levels.
+///
+/// Each bar is one period (typically a day, week or month); its levels
+/// are the support and resistance for the period that follows.
+///
+/// Args:
+///     high (List[float] | np.ndarray): Period highs.
+///     low (List[float] | np.ndarray): Period lows.
+///     close (List[float] | np.ndarray): Period closes.
+///     method (str): "classic" (default), "fibonacci" or "camarilla".
+///
+/// Returns:
+///     IndicatorResult: .pp, .r1, .r2, .r3, .s1, .s2 and .s3, unpacking in
+///     that order. The pivot is `(high + low + close) / 3` for every method.
+#[pyfunction]
+#[pyo3(name = "calculate_pivots", signature = (high, low, close, method="classic"))]
+pub fn py_calculate_pivots(py: Python<'_>, high: Series<'_>, low: Series<'_>, close: Series<'_>, method: &str) -> PyResult<IndicatorResult> {
+    let _span = profile::span("calculate_pivots");
+    let method: PivotMethod = method.parse().map_err(PyValueError::new_err)?;
+    check_columns(&[("high", &high), ("low", &low), ("close", &close)])?;
+    let out = dispatch_series!(py, [high, low, close], cached("pivots", [method as u8]), |high, low, close| {
+        indicators::pivots(high, low, close, method)
+    })?;
+    IndicatorResult::new(py, "pivots", out, &["pp", "r1", "r2", "r3", "s1", "s2", "s3"], 0)?.param(py, "method", method.name())
+}
+
+/// Fibonacci retracement and extension levels of a swing.
+///
+/// Pass the swing as `swing_high`/`swing_low`, or pass `candles` to use the
+/// last confirmed swing high and low: bars that are the extreme of the
+/// `lookback` bars either side of them.
+///
+/// Args:
+///     swing_high (float, optional): Top of the swing.
+///     swing_low (float, optional): Bottom of the swing.
+///     candles (dict | DataFrame, optional): Bars with "high"/"low" (or just
+///         "close") columns, for auto-detection.
+///     lookback (int): Bars either side that a swing must dominate (default 5).
+///     trend (str, optional): "up" if the swing ran from the low to the high,
+///         "down" otherwise. Defaults to "up" for given levels and to the
+///         order of the detected swings.
+///
+/// Returns:
+///     Dict: {"swing_high", "swing_low", "trend", "high_index",
+///     "low_index", "retracements": {ratio: price}, "extensions": {ratio:
+///     price}}. Retracements (0.236 to 0.786) step back from the swing's end;
+///     extensions (1.272 to 2.618) project past it. The indices are None
+///     for given levels.
+///
+/// Raises:
+///     ValueError: If neither a swing nor candles are given, or no swing
+///         high and low can be detected.
+#[pyfunction]
+#[pyo3(
+    name = "fibonacci_levels",
+    signature = (swing_high=None, swing_low=None, candles=None, lookback=5, trend=None)
+)]
+pub fn py_fibonacci_levels<'py>(
+    py: Python<'py>,
+    swing_high: Option<f64>,
+    swing_low: Option<f64>,
+    candles: Option<PyCandles>,
+    lookback: usize,
+    trend: Option<&str>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let up = match trend {
+        None => None,
+        Some(t) => match t.to_lowercase().as_str() {
+            "up" => Some(true),
+            "down" => Some(false),
+            other => return Err(PyValueError::new_err(format!("Unknown trend '{}', expected 'up' or 'down'", other))),
+        },
+    };
+    let (swing, high_index, low_index) = match (swing_high, swing_low, candles) {
+        (Some(high), Some(low), _) => {
+            if !(high.is_finite() && low.is_finite() && high > low) {
+                return Err(PyValueError::new_err(format!("swing_high ({}) must be above swing_low ({})", high, low)));
+            }
+            (Swing { high, low, up: up.unwrap_or(true) }, None, None)
+        }
+        (None, None, Some(candles)) => {
+            errors::check_period("lookback", lookback)?;
+            let (hi, lo) = py.allow_threads(|| indicators::last_swing_points(&candles.high, &candles.low, lookback));
+            let (Some(hi), Some(lo)) = (hi, lo) else {
+                return Err(PyValueError::new_err(format!(
+                    "no confirmed swing high and low with lookback {} in {} bars",
+                    lookback,
+                    candles.len()
+                )));
+            };
+            let swing = Swing { high: candles.high[hi], low: candles.low[lo], up: up.unwrap_or(lo < hi) };
+            (swing, Some(hi), Some(lo))
+        }
+        _ => return Err(PyValueError::new_err("pass swing_high and swing_low, or candles")),
+    };
+    let levels = indicators::fibonacci_levels(swing);
+
+    let out = PyDict::new(py);
+    out.set_item("swing_high", swing.high)?;
+    out.set_item("swing_low", swing.low)?;
+    out.set_item("trend", if swing.up { "up" } else { "down" })?;
+    out.set_item("high_index", high_index)?;
+    out.set_item("low_index", low_index)?;
+    out.set_item("retracements", levels.retracements.into_py_dict(py)?)?;
+    out.set_item("extensions", levels.extensions.into_py_dict(py)?)?;
+    Ok(out)
+}
+
+/// Transform OHLC bars into Heikin-Ashi candles.
+///
+/// Args:
+///     open (List[float] | np.ndarray): Bar opens.
+///     high (List[float] | np.ndarray): Bar highs.
+///     low (List[float] | np.ndarray): Bar lows.
+///     close (List[float] | np.ndarray): Bar closes.
+///
+/// Returns:
+///     IndicatorResult: .open, .high, .low and .close of the Heikin-Ashi
+///     candles, unpacking in that order. The close is the bar's OHLC mean
+///     and the open the midpoint of the previous candle's body.
+#[pyfunction]
+#[pyo3(name = "to_heikin_ashi", signature = (open, high, low, close))]
+pub fn py_to_heikin_ashi(py: Python<'_>, open: Series<'_>, high: Series<'_>, low: Series<'_>, close: Series<'_>) -> PyResult<IndicatorResult> {
+    let _span = profile::span("to_heikin_ashi");
+    check_columns(&[("open", &open), ("high", &high), ("low", &low), ("close", &close)])?;
+    let out = dispatch_series!(py, [open, high, low, close], cached("heikin_ashi", []), |open, high, low, close| {
+        candles::heikin_ashi(open, high, low, close)
+    })?;
+    IndicatorResult::new(py, "heikin_ashi", out, &["open", "high", "low", "close"], 0)
+}
+
+/// Build Renko bricks from closing prices.
+///
+/// Args:
+///     close (List[float] | np.ndarray): Closing prices.
+///     brick_size (float, optional): Price height of a brick. Defaults to the
+///         latest ATR(`atr_period`).
+///     atr_period (int): ATR period for the automatic brick size (default 14).
+///     high (List[float] | np.ndarray, optional): Bar highs for that ATR;
+///         without `high` and `low` the ATR is taken over close-to-close
+///         moves.
+///     low (List[float] | np.ndarray, optional): Bar lows for that ATR.
+///
+/// Returns:
+///     Dict: {"brick_size", and per brick "open", "close", "direction" (1 up,
+///     -1 down) and "index" (the bar that completed it)}, oldest brick first,
+///     as arrays when `close` is an array. A reversal takes two bricks of
+///     movement.
+///
+/// Raises:
+///     ValueError: If `brick_size` is not positive, only one of `high` and
+///         `low` is given, the history is too short for the ATR, or
+///         `brick_size` is too small for the prices (over a million bricks,
+///         or below their floating-point resolution).
+#[pyfunction]
+#[pyo3(name = "to_renko", signature = (close, brick_size=None, atr_period=14, high=None, low=None))]
+pub fn py_to_renko<'py>(
+    py: Python<'py>,
+    close: Series<'_>,
+    brick_size: Option<f64>,
+    atr_period: usize,
+    high: Option<Series<'_>>,
+    low: Option<Series<'_>>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let _span = profile::span("to_renko");
+    close.check_has_data("close")?;
+    if high.is_some() != low.is_some() {
+        return Err(PyValueError::new_err("high and low must be given together"));
+    }
+    let as_array = close.is_array();
+    let mut owned = Vec::new();
+    let close = close.f64_slice(&mut owned);
+    let brick_size = match brick_size {
+        Some(size) => size,
+        None => {
+            errors::check_period("atr_period", atr_period)?;
+            let (mut high_owned, mut low_owned) = (Vec::new(), Vec::new());
+            let high = high.as_ref().map_or(close, |s| s.f64_slice(&mut high_owned));
+            let low = low.as_ref().map_or(close, |s| s.f64_slice(&mut low_owned));
+            errors::check_lengths(&[("close", close.len()), ("high", high.len()), ("low", low.len())])?;
+            if close.len() < atr_period {
+                return Err(PyValueError::new_err(format!(
+                    "need at least {} prices for the ATR brick size, got {}",
+                    atr_period,
+                    close.len()
+                )));
+            }
+            indicators::atr(high, low, close, atr_period)[close.len() - 1]
+        }
+    };
+    let bricks = py.allow_threads(|| candles::renko(close, brick_size)).map_err(PyValueError::new_err)?;
+
+    let out = PyDict::new(py);
+    out.set_item("brick_size", brick_size)?;
+    out.set_item("open", bricks.iter().map(|b| b.open).collect::<Vec<_>>().into_output(py, as_array)?)?;
+    out.set_item("close", bricks.iter().map(|b| b.close).collect::<Vec<_>>().into_output(py, as_array)?)?;
+    out.set_item("direction", bricks.iter().map(|b| b.direction).collect::<Vec<_>>().into_output(py, as_array)?)?;
+    out.set_item("index", bricks.iter().map(|b| b.index as i64).collect::<Vec<_>>().into_output(py, as_array)?)?;
+    Ok(out)
+}
+
+/// Calculate a rolling linear regression channel.
+///
+/// Args:
+///     data (List[float] | np.ndarray): Prices.
+///     period (int): Bars per fit, at least 3 (default 100).
+///     k (float): Channel half-width in standard errors (default 2.0).
+///
+/// Returns:
+///     IndicatorResult: .slope (price change per bar, a trend score),
+///     .intercept (the fit at the window's oldest bar), .midline (the fit at
+///     the current bar) and .upper/.lower (`midline ± k` standard errors),
+///     unpacking in that order. The first `period - 1` are 0, or NaN under
+///     `Config(padding="nan")`.
+#[pyfunction]
+#[pyo3(name = "calculate_linreg", signature = (data, period=100, k=2.0))]
+pub fn py_calculate_linreg(py: Python<'_>, data: Series<'_>, period: usize, k: f64) -> PyResult<IndicatorResult> {
+    let _span = profile::span("calculate_linreg");
+    errors::check_min_period("period", period, 3)?;
+    data.check_has_data("data")?;
+    let len = data.len();
+    let cfg = config::current();
+    let out = dispatch_series!(py, data, cached("linreg", [period, k, cfg.cache_key()]), |d| {
+        let mut lines = indicators::linreg(d, period, k);
+        for line in &mut lines {
+            cfg.pad(line, period - 1);
+        }
+        lines
+    })?;
+    IndicatorResult::new(py, "linreg", out, &["slope", "intercept", "midline", "upper", "lower"], (period - 1).min(len))?
+        .param(py, "period", period)?
+        .param(py, "k", k)
+}
+
+/// Calculate the Arnaud Legoux Moving Average (ALMA).
+///
+/// Args:
+///     data (List[float] | np.ndarray): Prices.
+///     period (int): Window length (default 9).
+///     offset (float): Where the Gaussian peaks across the window, from 0
+///         (oldest bar, smoother) to 1 (newest, more responsive); default 0.85.
+///     sigma (float): Window length over the Gaussian's width (default 6.0);
+///         larger values weight fewer bars around the peak.
+///
+/// Returns:
+///     List[float] | np.ndarray: The weighted averages. The first
+///     `period - 1` are 0, or NaN under `Config(padding="nan")`.
+///
+/// Raises:
+///     ValueError: If `offset` is outside [0, 1] or `sigma` is not positive.
+#[pyfunction]
+#[pyo3(name = "calculate_alma", signature = (data, period=9, offset=0.85, sigma=6.0))]
+pub fn py_calculate_alma(py: Python<'_>, data: Series<'_>, period: usize, offset: f64, sigma: f64) -> PyResult<PyObject> {
+    let _span = profile::span("calculate_alma");
+    errors::check_period("period", period)?;
+    if !(0.0..=1.0).contains(&offset) {
+        return Err(PyValueError::new_err(format!("offset must be between 0 and 1, got {}", offset)));
+    }
+    if !sigma.is_finite() || sigma <= 0.0 {
+        return Err(PyValueError::new_err(format!("sigma must be positive, got {}", sigma)));
+    }
+    data.check_has_data("data")?;
+    let cfg = config::current();
+    dispatch_series!(py, data, cached("alma", [period, offset, sigma, cfg.cache_key()]), |d| {
+        let mut out = indicators::alma(d, period, offset, sigma);
+        cfg.pad(&mut out, period - 1);
+        out
+    })
+}
+
+/// Calculate the Vortex Indicator (VI+ and VI-).
+///
+/// Args:
+///     high (List[float] | np.ndarray): Bar highs.
+///     low (List[float] | np.ndarray): Bar lows.
+///     close (List[float] | np.ndarray): Bar closes.
+///     period (int): Summing period (default 14).
+///
+/// Returns:
+///     IndicatorResult: .plus and .minus, unpacking as (plus, minus); VI+
+///     crossing above VI- signals a new uptrend. Both are 1 over a window
+///     without range. The first `period` are 0, or NaN under
+///     `Config(padding="nan")`.
+#[pyfunction]
+#[pyo3(name = "calculate_vortex", signature = (high, low, close, period=14))]
+pub fn py_calculate_vortex(py: Python<'_>, high: Series<'_>, low: Series<'_>, close: Series<'_>, period: usize) -> PyResult<IndicatorResult> {
+    let _span = profile::span("calculate_vortex");
+    errors::check_period("period", period)?;
+    let len = check_columns(&[("high", &high), ("low", &low), ("close", &close)])?;
+    let cfg = config::current();
+    let out = dispatch_series!(py, [high, low, close], cached("vortex", [period, cfg.cache_key()]), |high, low, close| {
+        let (mut plus, mut minus) = indicators::vortex(high, low, close, period);
+        cfg.pad(&mut plus, period);
+        cfg.pad(&mut minus, period);
+        (plus, minus)
+    })?;
+    IndicatorResult::new(py, "vortex", out, &["plus", "minus"], period.min(len))?.param(py, "period", period)
+}
+
+/// Calculate the Coppock Curve, a long-horizon momentum oscillator.
+///
+/// Args:
+///     data (List[float] | np.ndarray): Prices, classically monthly closes.
+///     roc1 (int): Longer rate-of-change period (default 14).
+///     roc2 (int): Shorter rate-of-change period (default 11).
+///     wma (int): Weighted moving average period (default 10).
+///
+/// Returns:
+///     List[float] | np.ndarray: WMA of `roc(roc1) + roc(roc2)`, in
+///     percent; a turn up from below zero is the classic buy signal. The
+///     first `max(roc1, roc2) + wma - 1` are 0, or NaN under
+///     `Config(padding="nan")`.
+#[pyfunction]
+#[pyo3(name = "calculate_coppock", signature = (data, roc1=14, roc2=11, wma=10))]
+pub fn py_calculate_coppock(py: Python<'_>, data: Series<'_>, roc1: usize, roc2: usize, wma: usize) -> PyResult<PyObject> {
+    let _span = profile::span("calculate_coppock");
+    errors::check_period("roc1", roc1)?;
+    errors::check_period("roc2", roc2)?;
+    errors::check_period("wma", wma)?;
+    data.check_has_data("data")?;
+    let cfg = config::current();
+    dispatch_series!(py, data, cached("coppock", [roc1, roc2, wma, cfg.cache_key()]), |d| {
+        let mut out = indicators::coppock(d, roc1, roc2, wma);
+        cfg.pad(&mut out, roc1.max(roc2) + wma - 1);
+        out
+    })
+}
+
+/// Calculate the Chande Momentum Oscillator (CMO).
+///
+/// Args:
+///     data (List[float] | np.ndarray): Prices.
+///     period (int): Number of price changes summed (default 14).
+///
+/// Returns:
+///     List[float] | np.ndarray: `100 * (ups - downs) / (ups + downs)`,
+///     ranging from -100 (every change down) to 100 (every change up); 0
+///     over a flat window. The first `period` are 0, or NaN under
+///     `Config(padding="nan")`.
+#[pyfunction]
+#[pyo3(name = "calculate_cmo", signature = (data, period=14))]
+pub fn py_calculate_cmo(py: Python<'_>, data: Series<'_>, period: usize) -> PyResult<PyObject> {
+    let _span = profile::span("calculate_cmo");
+    errors::check_period("period", period)?;
+    data.check_has_data("data")?;
+    let cfg = config::current();
+    dispatch_series!(py, data, cached("cmo", [period, cfg.cache_key()]), |d| {
+        let mut out = indicators::cmo(d, period);
+        cfg.pad(&mut out, period);
+        out
+    })
+}
+
+/// Calculate Bill Williams' Awesome Oscillator (AO).
+///
+/// Args:
+///     high (List[float] | np.ndarray): Bar highs.
+///     low (List[float] | np.ndarray): Bar lows.
+///     fast (int): Fast SMA period of the median price (default 5).
+///     slow (int): Slow SMA period, longer than `fast` (default 34).
+///     signals (bool): Also return the zero-cross and saucer flags
+///         (default False).
+///
+/// Returns:
+///     List[float] | np.ndarray: `sma(median, fast) - sma(median, slow)`
+///     with `median = (high + low) / 2`. The first `slow - 1` are 0, or NaN
+///     under `Config(padding="nan")`.
+///
+///     With `signals=True`, an IndicatorResult: .ao plus .zero_cross (the
+///     bar AO turns positive, 1, or negative, -1) and .saucer (1 for two
+///     falling bars then a rising one above zero, -1 for the mirror below
+///     zero), unpacking as (ao, zero_cross, saucer). Flags are 0 elsewhere,
+///     and first fire at `slow` (zero cross) and `slow + 2` (saucer), which
+///     is `.valid_from`.
+///
+/// Raises:
+///     ValueError: If `fast` is not shorter than `slow`.
+#[pyfunction]
+#[pyo3(name = "calculate_ao", signature = (high, low, fast=5, slow=34, signals=false))]
+pub fn py_calculate_ao(py: Python<'_>, high: Series<'_>, low: Series<'_>, fast: usize, slow: usize, signals: bool) -> PyResult<PyObject> {
+    let _span = profile::span("calculate_ao");
+    errors::check_period("fast", fast)?;
+    errors::check_period("slow", slow)?;
+    if fast >= slow {
+        return Err(PyValueError::new_err(format!("fast ({}) must be shorter than slow ({})", fast, slow)));
+    }
+    let len = check_columns(&[("high", &high), ("low", &low)])?;
+    let cfg = config::current();
+    if !signals {
+        return dispatch_series!(py, [high, low], cached("ao", [fast, slow, cfg.cache_key()]), |high, low| {
+            let mut ao = indicators::awesome_oscillator(high, low, fast, slow);
+            cfg.pad(&mut ao, slow - 1);
+            ao
+        });
+    }
+    let out = dispatch_series!(py, [high, low], cached("ao_signals", [fast, slow, cfg.cache_key()]), |high, low| {
+        let mut ao = indicators::awesome_oscillator(high, low, fast, slow);
+        let (zero_cross, saucer) = indicators::ao_signals(&ao, slow - 1);
+        cfg.pad(&mut ao, slow - 1);
+        (ao, zero_cross, saucer)
+    })?;
+    // A saucer reads four valid bars, so it is the last output to warm up.
+    let result = IndicatorResult::new(py, "ao", out, &["ao", "zero_cross", "saucer"], (slow + 2).min(len))?
+        .param(py, "fast", fast)?
+        .param(py, "slow", slow)?;
+    Ok(result.into_pyobject(py)?.into_any().unbind())
+}
+
+/// Calculate the Percentage Volume Oscillator (PVO).
+///
+/// MACD's construction on volume, in percent of the slow EMA so that
+/// thinly and heavily traded tickers compare.
+///
+/// Args:
+///     volume (List[float] | np.ndarray): Bar volumes.
+///     fast (int): Fast EMA period (default 12).
+///     slow (int): Slow EMA period, longer than `fast` (default 26).
+///     signal (int): Signal EMA period (default 9).
+///
+/// Returns:
+///     IndicatorResult: .pvo (`100 * (ema_fast - ema_slow) / ema_slow`, 0
+///     where the slow EMA is), .signal and .histogram (`pvo - signal`),
+///     unpacking in that order; a positive PVO means volume is expanding.
+///     PVO warms up over `slow - 1` bars and the others over
+///     `slow + signal - 2`; warm-ups are 0 (NaN under
+///     `Config(padding="nan")`).
+///
+/// Raises:
+///     ValueError: If `fast` is not shorter than `slow`.
+#[pyfunction]
+#[pyo3(name = "calculate_pvo", signature = (volume, fast=12, slow=26, signal=9))]
+pub fn py_calculate_pvo(py: Python<'_>, volume: Series<'_>, fast: usize, slow: usize, signal: usize) -> PyResult<IndicatorResult> {
+    let _span = profile::span("calculate_pvo");
+    errors::check_period("fast", fast)?;
+    errors::check_period("slow", slow)?;
+    errors::check_period("signal", signal)?;
+    if fast >= slow {
+        return Err(PyValueError::new_err(format!("fast ({}) must be shorter than slow ({})", fast, slow)));
+    }
+    volume.check_has_data("volume")?;
+    let len = volume.len();
+    let cfg = config::current();
+    let out = dispatch_series!(py, volume, cached("pvo", [fast, slow, signal, cfg.cache_key()]), |v| {
+        let (mut pvo, mut signal_line, mut histogram) = indicators::ppo(v, fast, slow, signal);
+        cfg.pad(&mut pvo, slow - 1);
+        cfg.pad(&mut signal_line, slow + signal - 2);
+        cfg.pad(&mut histogram, slow + signal - 2);
+        (pvo, signal_line, histogram)
+    })?;
+    IndicatorResult::new(py, "pvo", out, &["pvo", "signal", "histogram"], (slow + signal - 2).min(len))?
+        .param(py, "fast", fast)?
+        .param(py, "slow", slow)?
+        .param(py, "signal", signal)
+}
+
+/// Calculate Ease of Movement (EOM).
+///
+/// Args:
+///     high (List[float] | np.ndarray): Bar highs.
+///     low (List[float] | np.ndarray): Bar lows.
+///     volume (List[float] | np.ndarray): Bar volumes.
+///     period (int): SMA period (default 14).
+///     divisor (float): Volume scale of the box ratio (default 1e8, as
+///         StockCharts; TradingView uses 10000).
+///
+/// Returns:
+///     List[float] | np.ndarray: SMA of `(mid - prev_mid) * (high - low) *
+///     divisor / volume` with `mid = (high + low) / 2`; positive when price
+///     rises easily on light volume. Bars without range or volume count as
+///     0. The first `period` are 0, or NaN under `Config(padding="nan")`.
+#[pyfunction]
+#[pyo3(name = "calculate_eom", signature = (high, low, volume, period=14, divisor=1e8))]
+pub fn py_calculate_eom(py: Python<'_>, high: Series<'_>, low: Series<'_>, volume: Series<'_>, period: usize, divisor: f64) -> PyResult<PyObject> {
+    let _span = profile::span("calculate_eom");
+    errors::check_period("period", period)?;
+    if !divisor.is_finite() || divisor <= 0.0 {
+        return Err(PyValueError::new_err(format!("divisor must be positive, got {}", divisor)));
+    }
+    check_columns(&[("high", &high), ("low", &low), ("volume", &volume)])?;
+    let cfg = config::current();
+    dispatch_series!(py, [high, low, volume], cached("eom", [period, divisor, cfg.cache_key()]), |high, low, volume| {
+        let mut out = indicators::eom(high, low, volume, period, divisor);
+        cfg.pad(&mut out, period);
+        out
+    })
+}
+
+/// Bar indices of anchors given either as indices or, with `timestamps`, as
+/// times resolved to the first bar at or after each.
+fn resolve_anchors(len: usize, indices: Option<Vec<usize>>, times: Option<Vec<i64>>, timestamps: Option<Vec<i64>>) -> PyResult<Vec<usize>> {
+    let anchors = match (indices, times) {
+        (Some(indices), None) => indices,
+        (None, Some(times)) => {
+            let Some(timestamps) = timestamps else {
+                return Err(PyValueError::new_err("anchor times need the bar timestamps"));
+            };
+            errors::check_lengths(&[("high", len), ("timestamps", timestamps.len())])?;
+            times.iter().map(|&t| timestamps.partition_point(|&ts| ts < t)).collect()
+        }
+        _ => return Err(PyValueError::new_err("pass either anchor indices or anchor times")),
+    };
+    if let Some(&anchor) = anchors.iter().find(|&&a| a >= len) {
+        return Err(PyValueError::new_err(format!("anchor {} is past the last bar ({})", anchor, len.saturating_sub(1))));
+    }
+    Ok(anchors)
+}
+
+/// Calculate a VWAP anchored at one bar.
+///
+/// Args:
+///     high (List[float] | np.ndarray): Bar highs.
+///     low (List[float] | np.ndarray): Bar lows.
+///     close (List[float] | np.ndarray): Bar closes.
+///     volume (List[float] | np.ndarray): Bar volumes.
+///     anchor_index (int, optional): Bar the VWAP starts from.
+///     anchor_time (int, optional): Epoch seconds to start from instead; the
+///         anchor is the first bar at or after it.
+///     timestamps (List[int], optional): Bar times in epoch seconds, sorted,
+///         for `anchor_time`.
+///
+/// Returns:
+///     List[float] | np.ndarray: The volume-weighted typical price since the
+///     anchor. Bars before it are 0, or NaN under `Config(padding="nan")`.
+///
+/// Raises:
+///     ValueError: Unless exactly one of `anchor_index` and `anchor_time` is
+///         given, or if the anchor is past the last bar.
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(
+    name = "calculate_anchored_vwap",
+    signature = (high, low, close, volume, anchor_index=None, anchor_time=None, timestamps=None)
+)]
+pub fn py_calculate_anchored_vwap(
+    py: Python<'_>,
+    high: Series<'_>,
+    low: Series<'_>,
+    close: Series<'_>,
+    volume: Series<'_>,
+    anchor_index: Option<usize>,
+    anchor_time: Option<i64>,
+    timestamps: Option<Vec<i64>>,
+) -> PyResult<PyObject> {
+    let _span = profile::span("calculate_anchored_vwap");
+    let len = check_columns(&[("high", &high), ("low", &low), ("close", &close), ("volume", &volume)])?;
+    let anchor = resolve_anchors(len, anchor_index.map(|a| vec![a]), anchor_time.map(|t| vec![t]), timestamps)?[0];
+    let cfg = config::current();
+    dispatch_series!(
+        py,
+        [high, low, close, volume],
+        cached("anchored_vwap", [anchor, cfg.cache_key()]),
+        |high, low, close, volume| {
+            let mut out = indicators::anchored_vwap(high, low, close, volume, anchor);
+            cfg.pad(&mut out, anchor);
+            out
+        }
+    )
+}
+
+/// Calculate one anchored VWAP per anchor; see `calculate_anchored_vwap`.
+///
+/// Args:
+///     high (List[float] | np.ndarray): Bar highs.
+///     low (List[float] | np.ndarray): Bar lows.
+///     close (List[float] | np.ndarray): Bar closes.
+///     volume (List[float] | np.ndarray): Bar volumes.
+///     anchor_indices (List[int], optional): Bars the VWAPs start from.
+///     anchor_times (List[int], optional): Epoch seconds to start from
+///         instead, each resolved to the first bar at or after it.
+///     timestamps (List[int], optional): Bar times in epoch seconds, sorted,
+///         for `anchor_times`.
+///
+/// Returns:
+///     List[List[float] | np.ndarray]: One series per anchor, in the order
+///     given, each 0 (NaN under `Config(padding="nan")`) before its anchor.
+///
+/// Raises:
+///     ValueError: Unless exactly one of `anchor_indices` and `anchor_times`
+///         is given, or if an anchor is past the last bar.
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(
+    name = "calculate_anchored_vwaps",
+    signature = (high, low, close, volume, anchor_indices=None, anchor_times=None, timestamps=None)
+)]
+pub fn py_calculate_anchored_vwaps(
+    py: Python<'_>,
+    high: Series<'_>,
+    low: Series<'_>,
+    close: Series<'_>,
+    volume: Series<'_>,
+    anchor_indices: Option<Vec<usize>>,
+    anchor_times: Option<Vec<i64>>,
+    timestamps: Option<Vec<i64>>,
+) -> PyResult<PyObject> {
+    let _span = profile::span("calculate_anchored_vwaps");
+    let len = check_columns(&[("high", &high), ("low", &low), ("close", &close), ("volume", &volume)])?;
+    let anchors = resolve_anchors(len, anchor_indices, anchor_times, timestamps)?;
+    let cfg = config::current();
+    dispatch_series!(py, [high, low, close, volume], |high, low, close, volume| {
+        Columns(
+            anchors
+                .iter()
+                .map(|&anchor| {
+                    let mut out = indicators::anchored_vwap(high, low, close, volume, anchor);
+                    cfg.pad(&mut out, anchor);
+                    out
+                })
+                .collect(),
+        )
+    })
+}
+
+/// Calculate the McGinley Dynamic moving average.
+///
+/// Args:
+///     data (List[float] | np.ndarray): Prices.
+///     period (int): Nominal length (default 14).
+///
+/// Returns:
+///     List[float] | np.ndarray: The average, which slows as price runs
+///     away above it and catches up faster when price falls through it. The
+///     first `period - 1` are 0, or NaN under `Config(padding="nan")`.
+#[pyfunction]
+#[pyo3(name = "calculate_mcginley", signature = (data, period=14))]
+pub fn py_calculate_mcginley(py: Python<'_>, data: Series<'_>, period: usize) -> PyResult<PyObject> {
+    let _span = profile::span("calculate_mcginley");
+    errors::check_period("period", period)?;
+    data.check_has_data("data")?;
+    let cfg = config::current();
+    dispatch_series!(py, data, cached("mcginley", [period, cfg.cache_key()]), |d| {
+        let mut out = indicators::mcginley(d, period);
+        cfg.pad(&mut out, period - 1);
+        out
+    })
+}
+
+/// Calculate the KDJ indicator.
+///
+/// Args:
+///     high (List[float] | np.ndarray): Bar highs.
+///     low (List[float] | np.ndarray): Bar lows.
+///     close (List[float] | np.ndarray): Bar closes.
+///     period (int): Lookback of the raw stochastic (default 9).
+///     k_smooth (int): Smoothing of K (default 3).
+///     d_smooth (int): Smoothing of D (default 3).
+///
+/// Returns:
+///     IndicatorResult: .k and .d (0-100) and .j (`3K - 2D`, which overshoots
+///     0-100 at extremes), unpacking as (k, d, j). K and D start from 50 on
+///     the first full window; the first `period - 1` are 0, or NaN under
+///     `Config(padding="nan")`.
+#[pyfunction]
+#[pyo3(name = "calculate_kdj", signature = (high, low, close, period=9, k_smooth=3, d_smooth=3))]
+pub fn py_calculate_kdj(
+    py: Python<'_>,
+    high: Series<'_>,
+    low: Series<'_>,
+    close: Series<'_>,
+    period: usize,
+    k_smooth: usize,
+    d_smooth: usize,
+) -> PyResult<IndicatorResult> {
+    let _span = profile::span("calculate_kdj");
+    errors::check_period("period", period)?;
+    errors::check_period("k_smooth", k_smooth)?;
+    errors::check_period("d_smooth", d_smooth)?;
+    let len = check_columns(&[("high", &high), ("low", &low), ("close", &close)])?;
+    let cfg = config::current();
+    let out = dispatch_series!(
+        py,
+        [high, low, close],
+        cached("kdj", [period, k_smooth, d_smooth, cfg.cache_key()]),
+        |high, low, close| {
+            let mut lines = indicators::kdj(high, low, close, period, k_smooth, d_smooth);
+            for line in &mut lines {
+                cfg.pad(line, period - 1);
+            }
+            lines
+        }
+    )?;
+    IndicatorResult::new(py, "kdj", out, &["k", "d", "j"], (period - 1).min(len))?
+        .param(py, "period", period)?
+        .param(py, "k_smooth", k_smooth)?
+        .param(py, "d_smooth", d_smooth)
+}
+
+/// Calculate the Ulcer Index, a drawdown-based volatility measure.
+///
+/// Args:
+///     close (List[float] | np.ndarray): Closes.
+///     period (int): Lookback of the running high and of the average
+///         (default 14).
+///
+/// Returns:
+///     List[float] | np.ndarray: RMS of the percent drawdown from the
+///     `period`-bar high; 0 when price makes only new highs. The first
+///     `2 * (period - 1)` are 0, or NaN under `Config(padding="nan")`.
+#[pyfunction]
+#[pyo3(name = "calculate_ulcer_index", signature = (close, period=14))]
+pub fn py_calculate_ulcer_index(py: Python<'_>, close: Series<'_>, period: usize) -> PyResult<PyObject> {
+    let _span = profile::span("calculate_ulcer_index");
+    errors::check_period("period", period)?;
+    close.check_has_data("close")?;
+    let cfg = config::current();
+    dispatch_series!(py, close, cached("ulcer_index", [period, cfg.cache_key()]), |c| {
+        let mut out = indicators::ulcer_index(c, period);
+        cfg.pad(&mut out, 2 * (period - 1));
+        out
+    })
+}
+
+/// Calculate the Ulcer Performance Index (Martin ratio).
+///
+/// Args:
+///     close (List[float] | np.ndarray): Closes.
+///     period (int): Lookback of the return and the Ulcer Index (default 14).
+///     risk_free_rate (float): Annual risk-free rate as a decimal (default 0).
+///     annualization (int, optional): Bars per year; defaults to the
+///         configured `annualization` (252).
+///
+/// Returns:
+///     List[float] | np.ndarray: Annualized excess return over the last
+///     `period` bars divided by `calculate_ulcer_index`, both in percent;
+///     NaN where there was no drawdown. The first `max(2 * (period - 1),
+///     period)` are 0, or NaN under `Config(padding="nan")`.
+#[pyfunction]
+#[pyo3(name = "calculate_upi", signature = (close, period=14, risk_free_rate=0.0, annualization=None))]
+pub fn py_calculate_upi(py: Python<'_>, close: Series<'_>, period: usize, risk_free_rate: f64, annualization: Option<usize>) -> PyResult<PyObject> {
+    let _span = profile::span("calculate_upi");
+    errors::check_period("period", period)?;
+    if !risk_free_rate.is_finite() {
+        return Err(PyValueError::new_err(format!("risk_free_rate must be finite, got {}", risk_free_rate)));
+    }
+    close.check_has_data("close")?;
+    let cfg = config::current();
+    let annualization = annualization.unwrap_or(cfg.annualization);
+    errors::check_period("annualization", annualization)?;
+    dispatch_series!(py, close, cached("upi", [period, risk_free_rate, annualization, cfg.cache_key()]), |c| {
+        let mut out = indicators::ulcer_performance_index(c, period, risk_free_rate, annualization as f64);
+        cfg.pad(&mut out, (2 * (period - 1)).max(period));
+        out
+    })
+}
+
+/// Calculate the Schaff Trend Cycle (STC).
+///
+/// Args:
+///     data (List[float] | np.ndarray): Prices.
+///     fast (int): Fast EMA period of the MACD (default 23).
+///     slow (int): Slow EMA period of the MACD, longer than `fast`
+///         (default 50).
+///     cycle (int): Stochastic lookback of both stages (default 10).
+///     factor (float): Smoothing applied after each stage, in (0, 1]
+///         (default 0.5).
+///
+/// Returns:
+///     List[float] | np.ndarray: The cycle in 0-100; readings leaving 25
+///     upwards or 75 downwards are the usual signals. The first
+///     `slow + 2 * cycle - 3` are 0, or NaN under `Config(padding="nan")`.
+///
+/// Raises:
+///     ValueError: If `fast` is not shorter than `slow` or `factor` is out
+///         of range.
+#[pyfunction]
+#[pyo3(name = "calculate_stc", signature = (data, fast=23, slow=50, cycle=10, factor=0.5))]
+pub fn py_calculate_stc(py: Python<'_>, data: Series<'_>, fast: usize, slow: usize, cycle: usize, factor: f64) -> PyResult<PyObject> {
+    let _span = profile::span("calculate_stc");
+    errors::check_period("fast", fast)?;
+    errors::check_period("slow", slow)?;
+    errors::check_period("cycle", cycle)?;
+    if fast >= slow {
+        return Err(PyValueError::new_err(format!("fast ({}) must be shorter than slow ({})", fast, slow)));
+    }
+    if factor.is_nan() || factor <= 0.0 || factor > 1.0 {
+        return Err(PyValueError::new_err(format!("factor must be in (0, 1], got {}", factor)));
+    }
+    data.check_has_data("data")?;
+    let cfg = config::current();
+    dispatch_series!(py, data, cached("stc", [fast, slow, cycle, factor, cfg.cache_key()]), |d| {
+        let mut out = indicators::stc(d, fast, slow, cycle, factor);
+        cfg.pad(&mut out, slow + 2 * cycle - 3);
+        out
+    })
+}
@@ -107,17 +107,22 @@ class SimulationEngine:
         self.use_mlx = MLX_AVAILABLE
         self.xgb_model = None
         
-    def run_monte_carlo(self, spot: float, vol: float, drift: float, steps: int, paths: int) -> np.ndarray:
+    def run_monte_carlo(self, spot: float, vol: float, drift: float, steps: int, paths: int, seed: Optional[int] = None) -> np.ndarray:
         """
         Generates simulated price paths using Geometric Brownian Motion.
         Vectorized on GPU if MLX is available.
+
+        Pass `seed` for reproducible paths. The normals always come from
+        NumPy's PCG64 (`np.random.default_rng`), which is stable across
+        platforms, so MLX and NumPy draw the same paths for a seed (MLX
+        computes them in float32).
         """
         dt = 1.0 / 252.0
-        
+        z = np.random.default_rng(seed).standard_normal((paths, steps))
+
         if self.use_mlx:
             # MLX Vectorized Implementation
-            key = mx.random.key(int(datetime.now().timestamp()))
-            z = mx.random.normal((paths, steps), key=key)
+            z_mx = mx.array(z.astype(np.float32))
             
             # GBM: S(t+1) = S(t) * exp((drift - 0.5*vol^2)*dt + vol*sqrt(dt)*Z)
             # Use mx for all operations to avoid implicit conversion to numpy
@@ -125,7 +130,7 @@ class SimulationEngine:
             vol_mx = mx.array(vol)
             dt_mx = mx.array(dt)
             
-            periodic_returns = (drift_mx - 0.5 * vol_mx**2) * dt_mx + vol_mx * mx.sqrt(dt_mx) * z
+            periodic_returns = (drift_mx - 0.5 * vol_mx**2) * dt_mx + vol_mx * mx.sqrt(dt_mx) * z_mx
             
             # Cumulative returns
             log_price_paths = mx.cumsum(periodic_returns, axis=1)
@@ -133,7 +138,6 @@ class SimulationEngine:
             return np.array(price_paths)
         else:
             # NumPy Fallback
-            z = np.random.standard_normal((paths, steps))
             periodic_returns = (drift - 0.5 * vol**2) * dt + vol * np.sqrt(dt) * z
             log_price_paths = np.cumsum(periodic_returns, axis=1)
             price_paths = spot * np.exp(log_price_paths)
@@ -201,13 +205,13 @@ class PortfolioMarginManager:
             "pfe": total_addon
         }
 
-    def calculate_epe(self, ticker: str, spot: float, vol: float) -> Decimal:
+    def calculate_epe(self, ticker: str, spot: float, vol: float, seed: Optional[int] = None) -> Decimal:
         """
         Calculates Expected Positive Exposure (EPE) using Monte Carlo simulation.
-        EPE = average(max(S(t) - K, 0)) over paths.
+        EPE = average(max(S(t) - K, 0)) over paths. `seed` makes it reproducible.
         """
         # Run 1000 paths for a 10-day MPoR (Margin Period of Risk)
-        paths = self.sim.run_monte_carlo(spot, vol, drift=0.0, steps=10, paths=1000)
+        paths = self.sim.run_monte_carlo(spot, vol, drift=0.0, steps=10, paths=1000, seed=seed)
         final_prices = paths[:, -1]
         
         exposures = np.maximum(final_prices - spot, 0)
@@ -250,14 +254,15 @@ class QuantEngine:
             
         return self._margin_manager.calculate_sa_ccr_margin(positions)
 
-    def simulate_stress_test(self, spot: float, vol: float, drift: float, steps: int = 252, paths: int = 100000) -> Dict[str, Any]:
+    def simulate_stress_test(self, spot: float, vol: float, drift: float, steps: int = 252, paths: int = 100000, seed: Optional[int] = None) -> Dict[str, Any]:
         """
         Public interface for running portfolio stress tests using GPU-accelerated MC.
+        `seed` makes the simulated paths reproducible (see `run_monte_carlo`).
         """
         if self._simulation_engine is None:
             self._simulation_engine = SimulationEngine()
             
-        paths_data = self._simulation_engine.run_monte_carlo(spot, vol, drift, steps, paths)
+        paths_data = self._simulation_engine.run_monte_carlo(spot, vol, drift, steps, paths, seed=seed)
         risk_metrics = self._simulation_engine.predict_tail_loss_overlay(paths_data)
         
         return risk_metrics
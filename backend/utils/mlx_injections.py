@@ -7,12 +7,15 @@ from utils.mlx_loader import mx
 
 MLX_INJECTIONS = {
     "monte_carlo_sim": """
-def monte_carlo_sim(S0, mu, sigma, T, dt, num_sims):
-    '''Vectorized Monte Carlo simulation for price paths on NPU.'''
+def monte_carlo_sim(S0, mu, sigma, T, dt, num_sims, seed=None):
+    '''Vectorized Monte Carlo simulation for price paths on NPU.
+    Pass `seed` for reproducible paths; the normals come from NumPy's PCG64,
+    so a seed gives the same paths on every platform.'''
     import mlx.core as mx
+    import numpy as np
     num_steps = int(T / dt)
     # Batch generation of random normals
-    Z = mx.random.normal(shape=(num_sims, num_steps))
+    Z = mx.array(np.random.default_rng(seed).standard_normal((num_sims, num_steps)).astype(np.float32))
     
     drift = (mu - 0.5 * sigma**2) * dt
     diffusion = sigma * mx.sqrt(mx.array(dt)) * Z
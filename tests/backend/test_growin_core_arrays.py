@@ -0,0 +1,73 @@
+import pytest
+
+growin_core = pytest.importorskip("growin_core")
+
+
+def _bars(n=60):
+    close = [100.0 + (i % 13) * 0.7 + i * 0.05 for i in range(n)]
+    return {
+        "open": [c - 0.2 for c in close],
+        "high": [c + 0.5 for c in close],
+        "low": [c - 0.5 for c in close],
+        "close": close,
+        "volume": [1000.0 + (i % 5) * 100.0 for i in range(n)],
+    }
+
+
+def test_list_columns_give_lists():
+    bars = _bars()
+    series = growin_core.CandleSeries(**bars)
+    assert series.close == bars["close"]
+    assert series.open == bars["open"]
+    assert isinstance(series.rsi(14), list)
+    assert isinstance(series.atr(14), list)
+    assert series.rsi(14) == growin_core.calculate_rsi(bars["close"], 14)
+    renko = growin_core.to_renko(bars["close"], 1.0)
+    assert isinstance(renko["open"], list)
+
+
+def test_numpy_columns_give_arrays():
+    np = pytest.importorskip("numpy")
+    bars = _bars()
+    arrays = {k: np.array(v) for k, v in bars.items()}
+    series = growin_core.CandleSeries(**arrays)
+
+    rsi = series.rsi(14)
+    assert isinstance(rsi, np.ndarray) and rsi.dtype == np.float64
+    np.testing.assert_array_equal(rsi, growin_core.calculate_rsi(bars["close"], 14))
+    atr = series.atr(14)
+    assert isinstance(atr, np.ndarray)
+    np.testing.assert_array_equal(atr, growin_core.calculate_atr(bars["high"], bars["low"], bars["close"], 14))
+    assert isinstance(series.obv(), np.ndarray)
+
+    renko = growin_core.to_renko(arrays["close"], 1.0)
+    assert isinstance(renko["open"], np.ndarray)
+    assert renko["open"].tolist() == growin_core.to_renko(bars["close"], 1.0)["open"]
+
+
+def test_contiguous_float64_columns_are_held_by_reference():
+    np = pytest.importorskip("numpy")
+    close = np.array(_bars()["close"])
+    series = growin_core.CandleSeries(close)
+    assert series.close is close
+    # open/high/low default to the close column itself.
+    assert series.high is close
+    close[-1] = 500.0
+    assert series.sma(5)[-1] == pytest.approx(np.mean(close[-5:]))
+
+
+def test_other_arrays_are_copied_and_still_give_arrays():
+    np = pytest.importorskip("numpy")
+    bars = _bars()
+    strided = np.array(bars["close"] * 2)[::2]
+    series = growin_core.CandleSeries(strided, volume=np.array(bars["volume"], dtype=np.float32))
+    assert series.close is not strided
+    assert isinstance(series.close, np.ndarray)
+    np.testing.assert_array_equal(series.close, strided)
+    assert isinstance(series.volume, np.ndarray) and series.volume.dtype == np.float64
+
+
+def test_functions_taking_candles_accept_a_series():
+    bars = _bars()
+    series = growin_core.CandleSeries(**bars)
+    assert growin_core.classify_regime(series) == growin_core.classify_regime(bars)
@@ -0,0 +1,96 @@
+import asyncio
+import time
+
+import pytest
+
+growin_core = pytest.importorskip("growin_core")
+
+
+def _prices(n_tickers=3, n_bars=300):
+    return {f"T{t}": [100.0 + t + (i % 17) * 0.5 + i * 0.1 for i in range(n_bars)] for t in range(n_tickers)}
+
+
+@pytest.mark.asyncio
+async def test_async_result_matches_sync():
+    prices = _prices()
+    expected = growin_core.ma_cross_screen(prices, 5, 20, 5)
+    assert await growin_core.ma_cross_screen_async(prices, 5, 20, 5) == expected
+
+    scores = {"A": {"rsi": 30.0, "pe": 10.0}, "B": {"rsi": 70.0, "pe": 20.0}}
+    weights = {"rsi": -1.0, "pe": -1.0}
+    assert await growin_core.composite_score_batch_async(scores, weights) == growin_core.composite_score_batch(scores, weights)
+
+
+@pytest.mark.asyncio
+async def test_async_calls_run_concurrently():
+    prices = _prices()
+    results = await asyncio.gather(*(growin_core.relative_strength_async(prices, prices["T0"], 63) for _ in range(8)))
+    assert all(r == results[0] for r in results)
+
+
+@pytest.mark.asyncio
+async def test_invalid_arguments_raise_before_scheduling():
+    with pytest.raises(growin_core.errors.InvalidPeriodError):
+        growin_core.ma_cross_screen_async(_prices(), 20, 5, 5)
+
+
+@pytest.mark.asyncio
+async def test_job_errors_propagate_to_the_awaiting_coroutine():
+    # Screening a rule that needs volume fails inside the job.
+    screener = growin_core.Screener({"A": {"close": [1.0, 2.0, 3.0]}})
+    screener.add_indicator_filter("volume", ">", 0.0)
+    with pytest.raises(ValueError, match="volume"):
+        await screener.run_async()
+
+
+@pytest.mark.asyncio
+async def test_run_async_screens_a_snapshot():
+    screener = growin_core.Screener({"A": {"close": [float(i) for i in range(50)]}})
+    screener.add_indicator_filter("close", ">", 10.0)
+    pending = screener.run_async()
+    screener.clear_filters()
+    screener.add_indicator_filter("close", "<", 10.0)
+    assert await pending == {"A": {"close": 49.0}}
+    assert screener.run() == {}
+
+
+@pytest.mark.asyncio
+async def test_cancelled_future_discards_the_result():
+    loop = asyncio.get_running_loop()
+    errors = []
+    loop.set_exception_handler(lambda _loop, context: errors.append(context))
+    try:
+        future = growin_core.relative_strength_async(_prices(50, 2000), _prices(1, 2000)["T0"], 63)
+        future.cancel()
+        with pytest.raises(asyncio.CancelledError):
+            await future
+        # Let the job finish and its result reach the loop.
+        await asyncio.sleep(0.5)
+        assert future.cancelled()
+        assert errors == []
+    finally:
+        loop.set_exception_handler(None)
+
+
+def test_requires_a_running_loop():
+    with pytest.raises(RuntimeError):
+        growin_core.ma_cross_screen_async(_prices(), 5, 20, 5)
+
+
+def test_result_for_a_closed_loop_is_dropped():
+    loop = asyncio.new_event_loop()
+
+    async def start():
+        return growin_core.relative_strength_async(_prices(200, 5000), _prices(1, 5000)["T0"], 63)
+
+    future = loop.run_until_complete(start())
+    loop.close()
+    # The job finishes after the close and logs that it could not deliver.
+    time.sleep(1.0)
+    assert not future.done()
+
+    # The pool and the interpreter are still usable afterwards.
+    async def again():
+        return await growin_core.ma_cross_screen_async(_prices(), 5, 20, 5)
+
+    assert asyncio.run(again()) == growin_core.ma_cross_screen(_prices(), 5, 20, 5)
@@ -38,3 +38,20 @@ def test_quant_engine_stress_test():
     
     assert "cvar_95" in metrics
     assert metrics["cvar_95"] is not None
+
+def test_monte_carlo_seed_is_reproducible():
+    engine = SimulationEngine()
+    a = engine.run_monte_carlo(100.0, 0.2, 0.05, 10, 200, seed=42)
+    b = engine.run_monte_carlo(100.0, 0.2, 0.05, 10, 200, seed=42)
+    c = engine.run_monte_carlo(100.0, 0.2, 0.05, 10, 200, seed=7)
+    assert np.array_equal(a, b)
+    assert not np.array_equal(a, c)
+
+def test_monte_carlo_seed_matches_across_backends():
+    engine = SimulationEngine()
+    if not engine.use_mlx:
+        pytest.skip("MLX is not available")
+    mlx_paths = engine.run_monte_carlo(100.0, 0.2, 0.05, 10, 200, seed=42)
+    engine.use_mlx = False
+    numpy_paths = engine.run_monte_carlo(100.0, 0.2, 0.05, 10, 200, seed=42)
+    np.testing.assert_allclose(mlx_paths, numpy_paths, rtol=1e-4)